@@ -10,11 +10,15 @@ use rxing::{BarcodeFormat, MultiFormatWriter, Writer};
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    file_name: String,
+    /// The image to decode, or the file to save an encoded barcode to. Without it, encoded
+    /// barcodes are printed to the terminal.
+    file_name: Option<String>,
     #[command(subcommand)]
     command: Commands,
 }
 
+// parsed once from the command line, so the size of the encode options does not matter
+#[allow(clippy::large_enum_variant)]
 #[derive(Subcommand)]
 enum Commands {
     #[command(group(
@@ -71,6 +75,10 @@ enum Commands {
         #[arg(long)]
         assume_code_39_check_digit: Option<bool>,
 
+        /// Assume Code 39 codes use Full ASCII mode, and decode shift pairs like "+A" as "a".
+        #[arg(long, verbatim_doc_comment)]
+        assume_code_39_full_ascii: Option<bool>,
+
         /// Assume the barcode is being processed as a GS1 barcode, and modify behavior as needed.
         /// For example this affects FNC1 handling for Code 128 (aka GS1-128).
         #[arg(long, verbatim_doc_comment)]
@@ -94,6 +102,19 @@ enum Commands {
         /// second time with an inverted image.
         #[arg(long, verbatim_doc_comment)]
         also_inverted: Option<bool>,
+
+        /// Only look for barcodes within this rectangle of the image, in pixels.
+        #[arg(long, num_args = 4, value_names = ["LEFT", "TOP", "WIDTH", "HEIGHT"])]
+        region: Option<Vec<u32>>,
+
+        /// The smallest and largest size, in pixels, of the barcodes to look for.
+        #[arg(long, num_args = 2, value_names = ["MIN", "MAX"])]
+        symbol_size: Option<Vec<u32>>,
+
+        /// For 1D barcodes, scan lines at these angles, in degrees clockwise from horizontal,
+        /// rather than rows of the image.
+        #[arg(long, num_args = 1.., verbatim_doc_comment)]
+        scan_angles: Option<Vec<f32>>,
     },
     #[command(group(
         ArgGroup::new("code_set_rules")
@@ -231,6 +252,84 @@ enum Commands {
         */
         #[arg(long)]
         code_128_compact: Option<bool>,
+
+        /// Adds a GS1 Composite component above the linear symbol. The value is the GS1
+        /// element string of the component, with the AIs in parentheses.
+        #[arg(long, verbatim_doc_comment)]
+        gs1_composite: Option<String>,
+
+        /// Specifies the bearer bars printed around an ITF-14 symbol.
+        /// Valid values are "none", "horizontal" and "frame" (the default).
+        #[arg(long, verbatim_doc_comment)]
+        itf_bearer_bars: Option<rxing::oned::BearerBars>,
+
+        /// Specifies whether Code 39 and Code 93 may use Full ASCII mode.
+        /// By default it is used only when the contents need it. If false, contents outside
+        /// the basic character set are rejected. If true, Code 39 always uses it.
+        #[arg(long, verbatim_doc_comment)]
+        full_ascii: Option<bool>,
+
+        /// Colour of the dark modules in raster output, as #RRGGBB or #RRGGBBAA.
+        #[arg(long, verbatim_doc_comment)]
+        foreground: Option<rxing::render::Rgba>,
+
+        /// Colour of the light modules in raster output, as #RRGGBB, #RRGGBBAA or "transparent".
+        #[arg(long, verbatim_doc_comment)]
+        background: Option<rxing::render::Rgba>,
+
+        /// Size of each module in raster output, in pixels.
+        #[arg(long, verbatim_doc_comment)]
+        module_size: Option<u32>,
+
+        /// Quiet zone added around the symbol in raster output, in modules.
+        #[arg(long, verbatim_doc_comment)]
+        quiet_zone: Option<u32>,
+
+        /// Draws light modules on a dark background in raster output.
+        #[arg(long, verbatim_doc_comment)]
+        invert: bool,
+
+        /// Resolution recorded in PNG and TIFF output.
+        #[arg(long, verbatim_doc_comment)]
+        dpi: Option<u32>,
+
+        /// Prints the human readable interpretation under linear barcodes. The symbol is drawn at
+        /// one pixel per module and ignores width and height; use --module-size to scale it.
+        #[arg(long, verbatim_doc_comment)]
+        human_readable: bool,
+
+        /// Width of a module in PDF and EPS output, with a unit of mm, mil or pt, for example 0.33mm.
+        /// Encode with a width and height of 0 so that the matrix has one cell per module.
+        #[arg(long, verbatim_doc_comment)]
+        x_dimension: Option<rxing::render::PhysicalLength>,
+
+        /// Height of a module row in PDF and EPS output, if it differs from the x dimension.
+        #[arg(long, verbatim_doc_comment)]
+        y_dimension: Option<rxing::render::PhysicalLength>,
+
+        /// Prints the barcode with ANSI colours when no file name is given, so that it is black on
+        /// white whatever the colours of the terminal are.
+        #[arg(long, verbatim_doc_comment)]
+        ansi_colors: bool,
+
+        /// Prints the barcode for a terminal with dark text on a light background when no file name
+        /// is given and ANSI colours are not used.
+        #[arg(long, verbatim_doc_comment)]
+        light_terminal: bool,
+
+        /// Renders the barcode in the dots of a printer of this resolution, each module exactly
+        /// --x-dimension wide (0.33mm if not given). The resolution is recorded in PNG and TIFF output.
+        #[arg(long, verbatim_doc_comment)]
+        printer_dpi: Option<u32>,
+
+        /// Bar width reduction for printer output, with a unit of mm, mil or pt.
+        #[arg(long, verbatim_doc_comment)]
+        bar_width_reduction: Option<rxing::render::PhysicalLength>,
+
+        /// Bar height of linear barcodes in printer output, with a unit of mm, mil or pt. Defaults
+        /// to the GS1 minimum where there is one.
+        #[arg(long, verbatim_doc_comment)]
+        bar_height: Option<rxing::render::PhysicalLength>,
     },
 }
 
@@ -246,33 +345,47 @@ fn main() -> ExitCode {
             character_set,
             allowed_lengths,
             assume_code_39_check_digit,
+            assume_code_39_full_ascii,
             assume_gs1,
             return_codabar_start_end,
             allowed_ean_extensions,
             also_inverted,
+            region,
+            symbol_size,
+            scan_angles,
             detailed_results,
             detailed_results_json,
             parsed_results,
             raw_bytes,
-        } => decode_command(
-            &cli.file_name,
-            try_harder,
-            decode_multi,
-            barcode_types,
-            other,
-            pure_barcode,
-            character_set,
-            allowed_lengths,
-            assume_code_39_check_digit,
-            assume_gs1,
-            return_codabar_start_end,
-            allowed_ean_extensions,
-            also_inverted,
-            detailed_results,
-            detailed_results_json,
-            parsed_results,
-            raw_bytes,
-        ),
+        } => {
+            let Some(file_name) = &cli.file_name else {
+                eprintln!("A file to decode is required");
+                return ExitCode::FAILURE;
+            };
+            decode_command(
+                file_name,
+                try_harder,
+                decode_multi,
+                barcode_types,
+                other,
+                pure_barcode,
+                character_set,
+                allowed_lengths,
+                assume_code_39_check_digit,
+                assume_code_39_full_ascii,
+                assume_gs1,
+                return_codabar_start_end,
+                allowed_ean_extensions,
+                also_inverted,
+                region,
+                symbol_size,
+                scan_angles,
+                detailed_results,
+                detailed_results_json,
+                parsed_results,
+                raw_bytes,
+            )
+        }
         Commands::Encode {
             barcode_type,
             width,
@@ -294,8 +407,25 @@ fn main() -> ExitCode {
             force_code_set,
             force_c40,
             code_128_compact,
+            gs1_composite,
+            itf_bearer_bars,
+            full_ascii,
+            foreground,
+            background,
+            module_size,
+            quiet_zone,
+            invert,
+            dpi,
+            human_readable,
+            x_dimension,
+            y_dimension,
+            ansi_colors,
+            light_terminal,
+            printer_dpi,
+            bar_width_reduction,
+            bar_height,
         } => encode_command(
-            &cli.file_name,
+            cli.file_name.as_deref(),
             barcode_type,
             width,
             height,
@@ -316,6 +446,23 @@ fn main() -> ExitCode {
             force_code_set,
             force_c40,
             code_128_compact,
+            gs1_composite,
+            itf_bearer_bars,
+            full_ascii,
+            foreground,
+            background,
+            module_size,
+            quiet_zone,
+            invert,
+            dpi,
+            human_readable,
+            x_dimension,
+            y_dimension,
+            ansi_colors,
+            light_terminal,
+            printer_dpi,
+            bar_width_reduction,
+            bar_height,
         ),
     }
 }
@@ -331,10 +478,14 @@ fn decode_command(
     character_set: &Option<String>,
     allowed_lengths: &Option<Vec<u32>>,
     assume_code_39_check_digit: &Option<bool>,
+    assume_code_39_full_ascii: &Option<bool>,
     assume_gs1: &Option<bool>,
     return_codabar_start_end: &Option<bool>,
     allowed_ean_extensions: &Option<Vec<u32>>,
     also_inverted: &Option<bool>,
+    region: &Option<Vec<u32>>,
+    symbol_size: &Option<Vec<u32>>,
+    scan_angles: &Option<Vec<f32>>,
     detailed_result: &bool,
     detailed_results_json: &bool,
     parsed_bytes: &bool,
@@ -372,6 +523,12 @@ fn decode_command(
             rxing::DecodeHintValue::AssumeCode39CheckDigit(*assume_code_39_check_digit),
         );
     }
+    if let Some(assume_code_39_full_ascii) = assume_code_39_full_ascii {
+        hints.insert(
+            rxing::DecodeHintType::ASSUME_CODE_39_FULL_ASCII,
+            rxing::DecodeHintValue::AssumeCode39FullAscii(*assume_code_39_full_ascii),
+        );
+    }
     if let Some(assume_gs1) = assume_gs1 {
         hints.insert(
             rxing::DecodeHintType::ASSUME_GS1,
@@ -396,6 +553,32 @@ fn decode_command(
             rxing::DecodeHintValue::AlsoInverted(*also_inverted),
         );
     }
+    if let Some([left, top, width, height]) = region.as_deref() {
+        hints.insert(
+            rxing::DecodeHintType::REGION_OF_INTEREST,
+            rxing::DecodeHintValue::RegionOfInterest(
+                rxing::common::Quadrilateral::rectangle_from_xy(
+                    *left as f32,
+                    (left + width) as f32,
+                    *top as f32,
+                    (top + height) as f32,
+                    Some(0.0),
+                ),
+            ),
+        );
+    }
+    if let Some([min, max]) = symbol_size.as_deref() {
+        hints.insert(
+            rxing::DecodeHintType::SYMBOL_SIZE_RANGE,
+            rxing::DecodeHintValue::SymbolSizeRange(*min..=*max),
+        );
+    }
+    if let Some(scan_angles) = scan_angles {
+        hints.insert(
+            rxing::DecodeHintType::SCAN_ANGLES,
+            rxing::DecodeHintValue::ScanAngles(scan_angles.to_vec()),
+        );
+    }
 
     // println!(
     //     "Decode '{}' with: try_harder: {}, decode_multi: {}, barcode_types: {:?}",
@@ -492,7 +675,7 @@ fn decode_command(
 
 #[allow(clippy::too_many_arguments)]
 fn encode_command(
-    file_name: &str,
+    file_name: Option<&str>,
     barcode_type: &BarcodeFormat,
     width: &u32,
     height: &u32,
@@ -513,6 +696,23 @@ fn encode_command(
     force_code_set: &Option<String>,
     force_c40: &Option<bool>,
     code_128_compact: &Option<bool>,
+    gs1_composite: &Option<String>,
+    itf_bearer_bars: &Option<rxing::oned::BearerBars>,
+    full_ascii: &Option<bool>,
+    foreground: &Option<rxing::render::Rgba>,
+    background: &Option<rxing::render::Rgba>,
+    module_size: &Option<u32>,
+    quiet_zone: &Option<u32>,
+    invert: &bool,
+    dpi: &Option<u32>,
+    human_readable: &bool,
+    x_dimension: &Option<rxing::render::PhysicalLength>,
+    y_dimension: &Option<rxing::render::PhysicalLength>,
+    ansi_colors: &bool,
+    light_terminal: &bool,
+    printer_dpi: &Option<u32>,
+    bar_width_reduction: &Option<rxing::render::PhysicalLength>,
+    bar_height: &Option<rxing::render::PhysicalLength>,
 ) -> ExitCode {
     // if data.is_none() && data_file.is_none() {
     //     println!("must provide either data string or data file");
@@ -649,19 +849,108 @@ fn encode_command(
         );
     }
 
+    if let Some(gs1_composite) = gs1_composite {
+        hints.insert(
+            rxing::EncodeHintType::GS1_COMPOSITE,
+            rxing::EncodeHintValue::Gs1Composite(gs1_composite.to_owned()),
+        );
+    }
+
+    if let Some(itf_bearer_bars) = itf_bearer_bars {
+        hints.insert(
+            rxing::EncodeHintType::ITF_BEARER_BARS,
+            rxing::EncodeHintValue::ItfBearerBars(*itf_bearer_bars),
+        );
+    }
+
+    if let Some(full_ascii) = full_ascii {
+        hints.insert(
+            rxing::EncodeHintType::FULL_ASCII,
+            rxing::EncodeHintValue::FullAscii(*full_ascii),
+        );
+    }
+
     // println!("Encode: file_name: {}, barcode_type: {}, width: {:?}, height: {:?}, data: '{:?}', data_file: {:?}", file_name, barcode_type, width, height, data, data_file);
 
-    let writer = MultiFormatWriter;
-    match writer.encode_with_hints(
-        &input_data,
-        barcode_type,
-        *width as i32,
-        *height as i32,
-        &hints.into(),
-    ) {
+    let defaults = rxing::render::RenderOptions::default();
+    let render_options = rxing::render::RenderOptions {
+        foreground: foreground.unwrap_or(defaults.foreground),
+        background: background.unwrap_or(defaults.background),
+        module_size: module_size
+            .map(rxing::render::ModuleSize::Pixels)
+            .unwrap_or(defaults.module_size),
+        quiet_zone: quiet_zone.unwrap_or(defaults.quiet_zone),
+        inverted: *invert,
+        dpi: dpi.or(*printer_dpi),
+    };
+
+    let encoded = if *human_readable {
+        rxing::render::encode_with_human_readable(
+            &input_data,
+            barcode_type,
+            rxing::render::DEFAULT_BAR_HEIGHT,
+            &hints.into(),
+        )
+    } else if let Some(printer_dpi) = printer_dpi {
+        let printer_defaults = rxing::render::PrinterOptions::default();
+        rxing::render::encode_for_printer(
+            &input_data,
+            barcode_type,
+            &hints.into(),
+            &rxing::render::PrinterOptions {
+                dpi: *printer_dpi,
+                x_dimension: x_dimension.unwrap_or(printer_defaults.x_dimension),
+                bar_width_reduction: *bar_width_reduction,
+                bar_height: *bar_height,
+                ..printer_defaults
+            },
+        )
+        .map(|printed| printed.matrix)
+    } else if file_name.is_none() {
+        // one character per module in the terminal
+        MultiFormatWriter.encode_with_hints(&input_data, barcode_type, 0, 0, &hints.into())
+    } else {
+        MultiFormatWriter.encode_with_hints(
+            &input_data,
+            barcode_type,
+            *width as i32,
+            *height as i32,
+            &hints.into(),
+        )
+    };
+    match encoded {
         Ok(result) => {
+            let Some(file_name) = file_name else {
+                let terminal_options = rxing::render::TerminalOptions {
+                    quiet_zone: quiet_zone.unwrap_or_default(),
+                    ansi_colors: *ansi_colors,
+                    dark_background: !*light_terminal,
+                };
+                print!(
+                    "{}",
+                    rxing::render::render_terminal(&result, &terminal_options)
+                );
+                return ExitCode::SUCCESS;
+            };
             println!("Encode successful, saving...");
-            match rxing::helpers::save_file(file_name, &result) {
+            let saved = if printer_dpi.is_none() && (x_dimension.is_some() || y_dimension.is_some())
+            {
+                let print_defaults = rxing::render::PrintOptions::default();
+                rxing::render::save_vector(
+                    file_name,
+                    &result,
+                    &render_options,
+                    &rxing::render::PrintOptions {
+                        x_dimension: x_dimension.unwrap_or(print_defaults.x_dimension),
+                        y_dimension: *y_dimension,
+                    },
+                )
+            } else if render_options == defaults {
+                rxing::helpers::save_file(file_name, &result)
+            } else {
+                rxing::helpers::save_file_with_options(file_name, &result, &render_options)
+            };
+            match saved {
                 Ok(_) => {
                     println!("Saved to '{file_name}'");
                     ExitCode::SUCCESS
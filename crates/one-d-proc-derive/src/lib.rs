@@ -34,8 +34,9 @@ fn impl_one_d_reader_macro(ast: &syn::DeriveInput) -> TokenStream {
             if let Ok(res) = self._do_decode(image, hints) {
                 Ok(res)
              }else {
+               hints.check_cancelled()?;
                let tryHarder = hints.TryHarder.unwrap_or(false);
-               if tryHarder && image.is_rotate_supported() {
+               if tryHarder && image.is_rotate_supported() && !hints.has_scan_region() {
                  let mut rotated_image = image.rotate_counter_clockwise();
                  let mut result = self._do_decode(&mut rotated_image, hints)?;
                  // Record that we found it rotated 90 degrees CCW / 270 degrees CW
@@ -135,6 +136,19 @@ fn impl_one_d_writer_macro(ast: &syn::DeriveInput) -> TokenStream {
             height: i32,
             hints: &EncodeHints,
         ) -> Result<crate::common::BitMatrix, crate::Exceptions> {
+            Ok(self
+                .encode_with_metadata(contents, format, width, height, hints)?
+                .matrix)
+        }
+
+        fn encode_with_metadata(
+            &self,
+            contents: &str,
+            format: &crate::BarcodeFormat,
+            width: i32,
+            height: i32,
+            hints: &EncodeHints,
+        ) -> Result<crate::EncodedSymbol, crate::Exceptions> {
             if contents.is_empty() {
                 return Err(Exceptions::illegal_argument_with(
                     "Found empty contents"
@@ -163,7 +177,17 @@ fn impl_one_d_writer_macro(ast: &syn::DeriveInput) -> TokenStream {
 
             let code = self.encode_oned_with_hints(contents, hints)?;
 
-            Self::renderRXingResult(&code, width, height, sidesMargin)
+            if let Some(component) = &hints.Gs1Composite {
+                return crate::composite::encode_composite(
+                    format, contents, &code, component, width, height, sidesMargin,
+                );
+            }
+
+            Ok(crate::EncodedSymbol::new(
+                *format,
+                crate::oned::linear_modules(&code)?,
+                crate::oned::render_linear(&code, width, height, sidesMargin)?,
+            ))
         }
     }
     };
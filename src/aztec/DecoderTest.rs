@@ -72,6 +72,35 @@ fn test_high_level_decode() {
     );
 }
 
+#[test]
+fn test_structured_append() {
+    // M/L   U/L   ' '   'I'   'D'   ' '   'B'   'C'   'H'   'I'
+    let bits = toBooleanArray(&toBitArray(&stripSpace(
+        "XXX.X XXX.X ....X .X.X. ..X.X ....X ...XX ..X.. .X..X .X.X.",
+    )));
+    let (sai, header_bits) = decoder::parse_structured_append(&bits).expect("header");
+    assert_eq!((sai.index, sai.count, sai.id.as_str()), (1, 3, "ID"));
+    assert_eq!(header_bits, 40);
+    assert_eq!(
+        decoder::highLevelDecode(&bits[header_bits..]).expect("decode"),
+        "HI"
+    );
+
+    // without a message ID, symbol 'C' of 'C'
+    let bits = toBooleanArray(&toBitArray(&stripSpace(
+        "XXX.X XXX.X ..X.. ..X.. .X..X .X.X.",
+    )));
+    let (sai, header_bits) = decoder::parse_structured_append(&bits).expect("header");
+    assert_eq!((sai.index, sai.count, sai.id.as_str()), (2, 3, ""));
+    assert_eq!(header_bits, 20);
+
+    // data that does not start with M/L U/L
+    let bits = toBooleanArray(&toBitArray(&stripSpace(
+        "...X. ..... ...XX XXX.. ...XX XXXX. XX.X",
+    )));
+    assert!(decoder::parse_structured_append(&bits).is_none());
+}
+
 fn test_high_level_decode_string(expectedString: &str, b: &str) {
     let bits = toBitArray(&stripSpace(b));
     assert_eq!(
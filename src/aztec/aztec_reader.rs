@@ -16,9 +16,10 @@
 
 use crate::{
     common::{DecoderRXingResult, DetectorRXingResult, Result},
+    decode_region::decode_in_region,
     exceptions::Exceptions,
-    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, ImmutableReader, RXingResult,
-    RXingResultMetadataType, RXingResultMetadataValue, Reader,
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, EstimatedModuleSize, ImmutableReader,
+    Point, RXingResult, RXingResultMetadataType, RXingResultMetadataValue, Reader,
 };
 
 use super::{decoder, detector::Detector};
@@ -28,7 +29,7 @@ use super::{decoder, detector::Detector};
  *
  * @author David Olivier
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct AztecReader;
 
 impl Reader for AztecReader {
@@ -48,7 +49,9 @@ impl Reader for AztecReader {
         image: &mut BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -58,7 +61,9 @@ impl ImmutableReader for AztecReader {
         image: &mut BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -74,16 +79,26 @@ impl AztecReader {
 
         //  try {
 
-        let detectorRXingResult = if let Ok(det) = detector.detect(false) {
-            det
-        } else if let Ok(det) = detector.detect(true) {
-            det
-        } else {
+        let detected = detector
+            .detect(false)
+            .map(|det| (det, false))
+            .or_else(|_| detector.detect(true).map(|det| (det, true)));
+        let Ok((detectorRXingResult, mirrored)) =
+            hints.record_rejection(BarcodeFormat::AZTEC, None, detected)
+        else {
             return Err(Exceptions::NOT_FOUND);
         };
 
         let points = detectorRXingResult.getPoints();
-        let decoderRXingResult: DecoderRXingResult = decoder::decode(&detectorRXingResult)?;
+        if let Some(diagnostics) = &hints.Diagnostics {
+            diagnostics.quadrilateral(BarcodeFormat::AZTEC, points);
+            diagnostics.sampled_grid(BarcodeFormat::AZTEC, detectorRXingResult.getBits());
+        }
+        let decoderRXingResult: DecoderRXingResult = hints.record_rejection(
+            BarcodeFormat::AZTEC,
+            points.first().copied(),
+            decoder::decode_with_hints(&detectorRXingResult, &hints.located(points)),
+        )?;
         // } catch (NotFoundException e) {
         //   notFoundException = e;
         // } catch (FormatException e) {
@@ -113,6 +128,26 @@ impl AztecReader {
             // }
         }
 
+        let bits = detectorRXingResult.getBits();
+        let moduleSize =
+            EstimatedModuleSize::from_corners(points, bits.getWidth(), bits.getHeight());
+
+        Ok(Self::build_result(
+            &decoderRXingResult,
+            points,
+            mirrored,
+            moduleSize,
+        ))
+    }
+
+    /// Builds the result, with its metadata, of a decoded symbol found at `points`, `mirrored` if
+    /// it was read from a mirror image.
+    pub(crate) fn build_result(
+        decoderRXingResult: &DecoderRXingResult,
+        points: &[Point],
+        mirrored: bool,
+        moduleSize: Option<EstimatedModuleSize>,
+    ) -> RXingResult {
         let mut result = RXingResult::new_complex(
             decoderRXingResult.getText(),
             decoderRXingResult.getRawBytes().clone(),
@@ -136,6 +171,12 @@ impl AztecReader {
                 RXingResultMetadataValue::ErrorCorrectionLevel(ecLevel.to_owned()),
             );
         }
+        if let Some(sai) = decoderRXingResult.getStructuredAppendInfo() {
+            result.putMetadata(
+                RXingResultMetadataType::STRUCTURED_APPEND,
+                RXingResultMetadataValue::StructuredAppend(sai.clone()),
+            );
+        }
         result.putMetadata(
             RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
             RXingResultMetadataValue::SymbologyIdentifier(format!(
@@ -143,7 +184,23 @@ impl AztecReader {
                 decoderRXingResult.getSymbologyModifier()
             )),
         );
+        result.putMetadata(
+            RXingResultMetadataType::IS_MIRRORED,
+            RXingResultMetadataValue::IsMirrored(mirrored),
+        );
+        if let Some(errorCorrection) = decoderRXingResult.getErrorCorrectionStats() {
+            result.putMetadata(
+                RXingResultMetadataType::ERRORS_CORRECTED,
+                RXingResultMetadataValue::ErrorsCorrected(errorCorrection),
+            );
+        }
+        if let Some(moduleSize) = moduleSize {
+            result.putMetadata(
+                RXingResultMetadataType::MODULE_SIZE,
+                RXingResultMetadataValue::ModuleSize(moduleSize),
+            );
+        }
 
-        Ok(result)
+        result
     }
 }
@@ -16,8 +16,10 @@
 
 use crate::{
     common::{BitMatrix, CharacterSet, Result},
+    encoded_symbol::Placement,
     exceptions::Exceptions,
-    BarcodeFormat, EncodeHints, Writer,
+    BarcodeFormat, EncodeHints, EncodedSymbol, SymbolEcLevel, SymbolMetadata, SymbolVersion,
+    Writer,
 };
 
 use super::encoder::{aztec_encoder, AztecCode};
@@ -50,20 +52,20 @@ impl Writer for AztecWriter {
         height: i32,
         hints: &EncodeHints,
     ) -> Result<crate::common::BitMatrix> {
-        let mut charset = None; // Do not add any ECI code by default
-        let mut ecc_percent = aztec_encoder::DEFAULT_EC_PERCENT;
-        let mut layers = aztec_encoder::DEFAULT_AZTEC_LAYERS;
-        if let Some(cset_name) = &hints.CharacterSet {
-            if cset_name.to_lowercase() != "iso-8859-1" {
-                charset = CharacterSet::get_character_set_by_name(cset_name);
-            }
-        }
-        if let Some(ecc_level) = &hints.ErrorCorrection {
-            ecc_percent = ecc_level.parse().unwrap_or(23);
-        }
-        if let Some(az_layers) = hints.AztecLayers {
-            layers = az_layers;
-        }
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
+        let (charset, ecc_percent, layers) = encoding_options(hints);
 
         let margins = if let Some(margin) = &hints.Margin {
             margin
@@ -96,22 +98,94 @@ fn encode(
     charset: Option<CharacterSet>,
     ecc_percent: u32,
     layers: i32,
-) -> Result<BitMatrix> {
+) -> Result<EncodedSymbol> {
     if format != BarcodeFormat::AZTEC {
         return Err(Exceptions::illegal_argument_with(format!(
             "can only encode AZTEC, but got {format:?}"
         )));
     }
-    let aztec = if let Some(cset) = charset {
+    let aztec = encode_code(contents, charset, ecc_percent, layers)?;
+    Ok(EncodedSymbol::new(
+        format,
+        aztec.getMatrix().clone(),
+        renderRXingResult(&aztec, width, height, margins)?,
+    )
+    .with_metadata(AztecWriter::symbol_metadata(&aztec, ecc_percent)))
+}
+
+fn encoding_options(hints: &EncodeHints) -> (Option<CharacterSet>, u32, i32) {
+    let mut charset = None; // Do not add any ECI code by default
+    let mut ecc_percent = aztec_encoder::DEFAULT_EC_PERCENT;
+    let mut layers = aztec_encoder::DEFAULT_AZTEC_LAYERS;
+    if let Some(cset_name) = &hints.CharacterSet {
+        if cset_name.to_lowercase() != "iso-8859-1" {
+            charset = CharacterSet::get_character_set_by_name(cset_name);
+        }
+    }
+    if let Some(ecc_level) = &hints.ErrorCorrection {
+        ecc_percent = ecc_level.parse().unwrap_or(23);
+    }
+    if let Some(az_layers) = hints.AztecLayers {
+        layers = az_layers;
+    }
+    (charset, ecc_percent, layers)
+}
+
+fn encode_code(
+    contents: &str,
+    charset: Option<CharacterSet>,
+    ecc_percent: u32,
+    layers: i32,
+) -> Result<AztecCode> {
+    if let Some(cset) = charset {
         // dbg!(cset.name(), cset.whatwg_name());
-        aztec_encoder::encode_with_charset(contents, ecc_percent, layers, cset)?
+        aztec_encoder::encode_with_charset(contents, ecc_percent, layers, cset)
     } else {
-        aztec_encoder::encode(contents, ecc_percent, layers)?
-    };
-    renderRXingResult(&aztec, width, height, margins)
+        aztec_encoder::encode(contents, ecc_percent, layers)
+    }
+}
+
+impl AztecWriter {
+    /**
+     * Describes the encoded `code`, which keeps room for `ecc_percent` of its data in check words.
+     */
+    fn symbol_metadata(code: &AztecCode, ecc_percent: u32) -> SymbolMetadata {
+        let word_size = aztec_encoder::WORD_SIZE[code.getLayers() as usize];
+        let total_bits = aztec_encoder::total_bits_in_layer(code.getLayers(), code.isCompact());
+        let total_codewords = total_bits / word_size;
+        let data_codewords = code.getCodeWords();
+        let ec_codewords = total_codewords - data_codewords;
+
+        // the encoder keeps room for check words of ecc_percent of the data bits plus 11 bits
+        let usable_bits = total_codewords * word_size;
+        let mut capacity_bits = usable_bits.saturating_sub(11) * 100 / (100 + ecc_percent);
+        if code.isCompact() {
+            // compact symbols hold at most 64 data words
+            capacity_bits = capacity_bits.min(64 * word_size);
+        }
+
+        SymbolMetadata {
+            version: SymbolVersion::Aztec {
+                compact: code.isCompact(),
+                layers: code.getLayers(),
+            },
+            ec_level: SymbolEcLevel::Aztec(ec_codewords * 100 / total_codewords),
+            data_codewords,
+            used_data_codewords: data_codewords,
+            ec_codewords,
+            mask: None,
+            data_bits: Some(code.getDataBits()),
+            capacity_bits: Some(capacity_bits),
+        }
+    }
 }
 
-fn renderRXingResult(code: &AztecCode, width: u32, height: u32, margins: u32) -> Result<BitMatrix> {
+fn renderRXingResult(
+    code: &AztecCode,
+    width: u32,
+    height: u32,
+    margins: u32,
+) -> Result<(BitMatrix, Placement)> {
     let input = code.getMatrix();
 
     let input_width = input.getWidth();
@@ -144,5 +218,8 @@ fn renderRXingResult(code: &AztecCode, width: u32, height: u32, margins: u32) ->
         input_y += 1;
         output_y += multiple
     }
-    Ok(output)
+    Ok((
+        output,
+        Placement::square(multiple, left_padding, top_padding),
+    ))
 }
@@ -16,12 +16,14 @@
 
 use crate::{
     common::{
+        cpp_essentials::StructuredAppendInfo,
         reedsolomon::{
             get_predefined_genericgf, GenericGFRef, PredefinedGenericGF, ReedSolomonDecoder,
         },
         BitMatrix, CharacterSet, DecoderRXingResult, DetectorRXingResult, Eci, Result,
     },
     exceptions::Exceptions,
+    BarcodeFormat, CodewordRecorder, DecodeHints,
 };
 
 use super::aztec_detector_result::AztecDetectorRXingResult;
@@ -74,12 +76,24 @@ const DIGIT_TABLE: [&str; 16] = [
 //   private AztecDetectorRXingResult ddata;
 
 pub fn decode(detectorRXingResult: &AztecDetectorRXingResult) -> Result<DecoderRXingResult> {
+    decode_with_hints(detectorRXingResult, &DecodeHints::default())
+}
+
+/// Like [`decode`], recording the codewords before and after error correction with the
+/// diagnostics of the `hints`, if any, and collecting them with their best effort collector, if
+/// any, if error correction fails.
+pub fn decode_with_hints(
+    detectorRXingResult: &AztecDetectorRXingResult,
+    hints: &DecodeHints,
+) -> Result<DecoderRXingResult> {
     //let mut detectorRXingResult = detectorRXingResult.clone();
     let matrix = detectorRXingResult.getBits();
     let rawbits = extract_bits(detectorRXingResult, matrix);
-    let corrected_bits = correct_bits(detectorRXingResult, &rawbits)?;
+    let corrected_bits = correct_bits(detectorRXingResult, &rawbits, hints)?;
     let raw_bytes = convertBoolArrayToByteArray(&corrected_bits.correct_bits);
-    let result = get_encoded_data(&corrected_bits.correct_bits);
+    let structured_append = parse_structured_append(&corrected_bits.correct_bits);
+    let header_bits = structured_append.as_ref().map_or(0, |(_, bits)| *bits);
+    let result = get_encoded_data(&corrected_bits.correct_bits[header_bits..]);
     let mut decoder_rxing_result = DecoderRXingResult::new(
         raw_bytes,
         result?,
@@ -87,6 +101,11 @@ pub fn decode(detectorRXingResult: &AztecDetectorRXingResult) -> Result<DecoderR
         format!("{}%", corrected_bits.ec_level),
     );
     decoder_rxing_result.setNumBits(corrected_bits.correct_bits.len());
+    decoder_rxing_result.setErrorsCorrected(corrected_bits.errors_corrected);
+    decoder_rxing_result.setErrorCorrectionCapacity(corrected_bits.ec_codewords);
+    if let Some((sai, _)) = structured_append {
+        decoder_rxing_result.setStructuredAppendInfo(sai);
+    }
 
     Ok(decoder_rxing_result)
 }
@@ -96,6 +115,57 @@ pub fn highLevelDecode(correctedBits: &[bool]) -> Result<String> {
     get_encoded_data(correctedBits)
 }
 
+/**
+ * Reads the structured append header the data may start with, ISO/IEC 24778:2008 8: M/L U/L, an
+ * optional message ID between spaces, then the position of the symbol and the number of symbols,
+ * as the letters A to Z for 1 to 26. The data that follows starts again in the Upper table.
+ *
+ * @return the header and the number of bits it takes, or None if there is no header
+ */
+pub(crate) fn parse_structured_append(bits: &[bool]) -> Option<(StructuredAppendInfo, usize)> {
+    // M/L then U/L, a pair no encoder emits for data
+    if bits.len() <= 20 || read_code(bits, 0, 5) != 29 || read_code(bits, 5, 5) != 29 {
+        return None;
+    }
+    let mut index = 10;
+    let mut id = String::new();
+    if read_code(bits, index, 5) == 1 {
+        index += 5;
+        loop {
+            if index + 5 > bits.len() {
+                return None;
+            }
+            let code = read_code(bits, index, 5);
+            index += 5;
+            if code == 1 {
+                break;
+            }
+            id.push_str(UPPER_TABLE.get(code as usize).filter(|c| c.len() == 1)?);
+        }
+    }
+    if index + 10 > bits.len() {
+        return None;
+    }
+    // 'A' is code 2 in the Upper table
+    let position = read_code(bits, index, 5) as i32 - 2;
+    let mut count = read_code(bits, index + 5, 5) as i32 - 1;
+    if !(0..26).contains(&position) {
+        return None;
+    }
+    if count <= position || count > 26 {
+        // mark the count unknown
+        count = 0;
+    }
+    Some((
+        StructuredAppendInfo {
+            index: position,
+            count,
+            id,
+        },
+        index + 10,
+    ))
+}
+
 /**
  * Gets the string encoded in the aztec code bits
  *
@@ -301,12 +371,21 @@ fn get_character(table: Table, code: u32) -> Result<&'static str> {
 struct CorrectedBitsRXingResult {
     correct_bits: Vec<bool>,
     ec_level: u32,
+    errors_corrected: usize,
+    ec_codewords: usize,
 }
 impl CorrectedBitsRXingResult {
-    pub const fn new(correct_bits: Vec<bool>, ec_level: u32) -> Self {
+    pub const fn new(
+        correct_bits: Vec<bool>,
+        ec_level: u32,
+        errors_corrected: usize,
+        ec_codewords: usize,
+    ) -> Self {
         Self {
             correct_bits,
             ec_level,
+            errors_corrected,
+            ec_codewords,
         }
     }
 }
@@ -320,6 +399,7 @@ impl CorrectedBitsRXingResult {
 fn correct_bits(
     ddata: &AztecDetectorRXingResult,
     rawbits: &[bool],
+    hints: &DecodeHints,
 ) -> Result<CorrectedBitsRXingResult> {
     let gf: GenericGFRef;
     let codeword_size;
@@ -356,15 +436,52 @@ fn correct_bits(
     }
 
     //try {
+    let mut recorder = CodewordRecorder::new(hints, BarcodeFormat::AZTEC);
+    recorder.received(num_data_codewords as usize, &data_words);
     let rs_decoder = ReedSolomonDecoder::new(gf);
-    rs_decoder.decode(
+    // error correction may have changed some of the codewords when it fails
+    let received = recorder
+        .best_effort()
+        .then(|| data_words[..num_data_codewords as usize].to_vec());
+    let errors_corrected = match rs_decoder.decode(
         &mut data_words,
         (num_codewords - num_data_codewords as usize) as i32,
-    )?;
+    ) {
+        Ok(errors_corrected) => errors_corrected,
+        Err(e) => {
+            if let Some(received) = received {
+                recorder.failed(received, move |words| {
+                    let bits = unstuff(words, codeword_size)?;
+                    let header_bits = parse_structured_append(&bits).map_or(0, |(_, bits)| bits);
+                    get_encoded_data(&bits[header_bits..])
+                });
+            }
+            return Err(e);
+        }
+    };
+    recorder.corrected(&data_words);
     //} catch (ReedSolomonException ex) {
     //throw FormatException.getFormatInstance(ex);
     //}
 
+    let corrected_bits = unstuff(&data_words[..num_data_codewords as usize], codeword_size)?;
+
+    Ok(CorrectedBitsRXingResult::new(
+        corrected_bits,
+        (100 * (num_codewords - num_data_codewords as usize) / num_codewords) as u32,
+        errors_corrected,
+        num_codewords - num_data_codewords as usize,
+    ))
+}
+
+/**
+ * Unpacks the bits of the data codewords, removing the bits stuffed into the codewords that would
+ * otherwise be all zeros or all ones.
+ *
+ * @throws FormatException if a codeword is all zeros or all ones
+ */
+fn unstuff(data_words: &[i32], codeword_size: usize) -> Result<Vec<bool>> {
+    let num_data_codewords = data_words.len() as u32;
     // Now perform the unstuffing operation.
     // First, count how many bits are going to be thrown out as stuffing
     let mask = (1 << codeword_size) - 1;
@@ -405,10 +522,7 @@ fn correct_bits(
         }
     }
 
-    Ok(CorrectedBitsRXingResult::new(
-        corrected_bits,
-        (100 * (num_codewords - num_data_codewords as usize) / num_codewords) as u32,
-    ))
+    Ok(corrected_bits)
 }
 
 /**
@@ -78,6 +78,27 @@ impl<'a> Detector<'_> {
         // 1. Get the center of the aztec matrix
         let p_center = self.get_matrix_center();
 
+        self.detect_at(p_center, is_mirror)
+    }
+
+    /**
+     * Detects every Aztec Code in an image, from the bull's eyes found by scanning all of its
+     * rows, rather than from the single center {@link #detect} looks for.
+     *
+     * @param isMirror if true, image is a mirror-image of original
+     * @return the symbols whose bull's eye and mode message could be read, in row order
+     */
+    pub fn detect_multiple(&mut self, is_mirror: bool) -> Vec<AztecDetectorRXingResult> {
+        self.find_bulls_eyes()
+            .into_iter()
+            .filter_map(|center| self.detect_at(center, is_mirror).ok())
+            .collect()
+    }
+
+    /**
+     * Detects the Aztec Code whose bull's eye is centered on `p_center`.
+     */
+    fn detect_at(&mut self, p_center: Point, is_mirror: bool) -> Result<AztecDetectorRXingResult> {
         // 2. Get the center points of the four diagonal points just outside the bull's eye
         //  [topRight, bottomRight, bottomLeft, topLeft]
         let mut bulls_eye_corners = self.get_bulls_eye_corners(p_center)?;
@@ -436,6 +457,128 @@ impl<'a> Detector<'_> {
         Point::from((cx, cy))
     }
 
+    /**
+     * Finds the centers of all bull's eyes in the image. A row crossing the center of a bull's eye
+     * has nine alternating runs of about one module each, the middle one black, and so does the
+     * column through the middle of that run. Hits on neighbouring rows are merged into one center.
+     */
+    fn find_bulls_eyes(&self) -> Vec<Point> {
+        // (sum of x, sum of y, number of hits, module size)
+        let mut clusters: Vec<(f32, f32, u32, f32)> = Vec::new();
+        let mut runs: Vec<(u32, u32)> = Vec::new();
+
+        for y in 0..self.image.getHeight() {
+            runs.clear();
+            let mut start = 0;
+            let mut color = false;
+            for x in 0..=self.image.getWidth() {
+                let here = x < self.image.getWidth() && self.image.get(x, y);
+                if x == self.image.getWidth() || here != color {
+                    if x > start {
+                        runs.push((start, x - start));
+                    }
+                    start = x;
+                    color = here;
+                }
+            }
+            if runs.len() < 9 {
+                continue;
+            }
+
+            // the first run is white unless the row starts with a black pixel
+            let first_black = usize::from(!self.image.get(0, y));
+            for middle in (4..runs.len() - 4).filter(|i| i % 2 == first_black) {
+                let lengths: Vec<u32> = runs[middle - 4..=middle + 4]
+                    .iter()
+                    .map(|run| run.1)
+                    .collect();
+                let Some(module_size) = Self::bulls_eye_module_size(&lengths) else {
+                    continue;
+                };
+                let cx = runs[middle].0 as f32 + runs[middle].1 as f32 / 2.0;
+                let Some(vertical) = self.bulls_eye_runs(cx as u32, y) else {
+                    continue;
+                };
+                if !Self::bulls_eye_module_size(&vertical)
+                    .is_some_and(|size| (size - module_size).abs() <= module_size / 2.0)
+                {
+                    continue;
+                }
+
+                match clusters.iter_mut().find(|(sx, sy, n, size)| {
+                    Self::distance(point(sx / *n as f32, sy / *n as f32), point(cx, y as f32))
+                        < 2.0 * size.max(module_size)
+                }) {
+                    Some(cluster) => {
+                        cluster.0 += cx;
+                        cluster.1 += y as f32;
+                        cluster.2 += 1;
+                    }
+                    None => clusters.push((cx, y as f32, 1, module_size)),
+                }
+            }
+        }
+
+        clusters
+            .into_iter()
+            .map(|(sx, sy, n, _)| point(sx / n as f32, sy / n as f32).floor())
+            .collect()
+    }
+
+    /**
+     * Gets the lengths of the nine runs of the column through `(x, y)`, centered on the black run
+     * `y` is in.
+     */
+    fn bulls_eye_runs(&self, x: u32, y: u32) -> Option<Vec<u32>> {
+        if x >= self.image.getWidth() || !self.image.get(x, y) {
+            return None;
+        }
+        let height = self.image.getHeight() as i64;
+        let run_from = |from: i64, dy: i64| -> (u32, i64) {
+            let color = self.image.get(x, from as u32);
+            let mut to = from;
+            while to >= 0 && to < height && self.image.get(x, to as u32) == color {
+                to += dy;
+            }
+            ((to - from).unsigned_abs() as u32, to)
+        };
+
+        let (up, mut top) = run_from(y as i64, -1);
+        let (down, mut bottom) = run_from(y as i64, 1);
+        let mut lengths = vec![up + down - 1];
+        for _ in 0..4 {
+            if top < 0 || bottom >= height {
+                return None;
+            }
+            let (above, next_top) = run_from(top, -1);
+            let (below, next_bottom) = run_from(bottom, 1);
+            lengths.insert(0, above);
+            lengths.push(below);
+            top = next_top;
+            bottom = next_bottom;
+        }
+        Some(lengths)
+    }
+
+    /**
+     * Gets the module size of nine runs crossing a bull's eye, or None if the seven inner runs are
+     * not of about the same length. The outer runs may merge with black modules of the mode
+     * message and the data layers around it, so only need to be long enough.
+     */
+    fn bulls_eye_module_size(lengths: &[u32]) -> Option<f32> {
+        let module_size = lengths[1..8].iter().sum::<u32>() as f32 / 7.0;
+        if module_size < 1.0 {
+            return None;
+        }
+        let inner = lengths[1..8]
+            .iter()
+            .all(|&length| (length as f32 - module_size).abs() <= module_size / 2.0 + 0.5);
+        let outer = [lengths[0], lengths[8]]
+            .iter()
+            .all(|&length| length as f32 >= module_size / 2.0);
+        (inner && outer).then_some(module_size)
+    }
+
     /**
      * Gets the Aztec code corners from the bull's eye corners and the parameters.
      *
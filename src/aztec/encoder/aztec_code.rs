@@ -26,6 +26,7 @@ pub struct AztecCode {
     size: u32,
     layers: u32,
     code_words: u32,
+    data_bits: u32,
     matrix: BitMatrix,
 }
 
@@ -36,6 +37,7 @@ impl AztecCode {
             size,
             layers,
             code_words,
+            data_bits: 0,
             matrix,
         }
     }
@@ -84,6 +86,17 @@ impl AztecCode {
         self.code_words = code_words;
     }
 
+    /**
+     * @return number of bits of the encoded data, before stuffing and padding
+     */
+    pub fn getDataBits(&self) -> u32 {
+        self.data_bits
+    }
+
+    pub fn setDataBits(&mut self, data_bits: u32) {
+        self.data_bits = data_bits;
+    }
+
     /**
      * @return the symbol image
      */
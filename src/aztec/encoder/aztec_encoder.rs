@@ -339,7 +339,8 @@ pub fn encode_bytes_with_charset(
 
     // dbg!(matrix.to_string());
 
-    let aztec = AztecCode::new(compact, matrixSize, layers, messageSizeInWords, matrix);
+    let mut aztec = AztecCode::new(compact, matrixSize, layers, messageSizeInWords, matrix);
+    aztec.setDataBits(bits.get_size() as u32);
     // aztec.setCompact(compact);
     // aztec.setSize(matrixSize);
     // aztec.setLayers(layers);
@@ -509,6 +510,6 @@ pub fn stuffBits(bits: &BitArray, word_size: usize) -> Result<BitArray> {
     Ok(out)
 }
 
-fn total_bits_in_layer(layers: u32, compact: bool) -> u32 {
+pub(crate) fn total_bits_in_layer(layers: u32, compact: bool) -> u32 {
     ((if compact { 88 } else { 112 }) + 16 * layers) * layers
 }
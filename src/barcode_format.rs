@@ -32,6 +32,9 @@ pub enum BarcodeFormat {
     /** Aztec 2D barcode format. */
     AZTEC,
 
+    /** Australia Post 4-state customer barcode format. */
+    AUSTRALIA_POST,
+
     /** CODABAR 1D format. */
     CODABAR,
 
@@ -47,6 +50,12 @@ pub enum BarcodeFormat {
     /** Data Matrix 2D barcode format. */
     DATA_MATRIX,
 
+    /** Deutsche Post Identcode, a 12 digit ITF symbol with a 4/9 weighted check digit. */
+    DEUTSCHE_POST_IDENTCODE,
+
+    /** Deutsche Post Leitcode, a 14 digit ITF symbol with a 4/9 weighted check digit. */
+    DEUTSCHE_POST_LEITCODE,
+
     /** EAN-8 1D format. */
     EAN_8,
 
@@ -56,12 +65,21 @@ pub enum BarcodeFormat {
     /** ITF (Interleaved Two of Five) 1D format. */
     ITF,
 
+    /** ITF-14, the GTIN-14 in an ITF symbol with a mod 10 check digit. */
+    ITF_14,
+
     /** MaxiCode 2D barcode format. */
     MAXICODE,
 
     /** PDF417 format. */
     PDF_417,
 
+    /** PLANET height modulated postal format. */
+    PLANET,
+
+    /** POSTNET height modulated postal format. */
+    POSTNET,
+
     /** QR Code 2D barcode format. */
     QR_CODE,
 
@@ -100,16 +118,22 @@ impl Display for BarcodeFormat {
             "{}",
             match self {
                 BarcodeFormat::AZTEC => "aztec",
+                BarcodeFormat::AUSTRALIA_POST => "australia post",
                 BarcodeFormat::CODABAR => "codabar",
                 BarcodeFormat::CODE_39 => "code 39",
                 BarcodeFormat::CODE_93 => "code 93",
                 BarcodeFormat::CODE_128 => "code 128",
                 BarcodeFormat::DATA_MATRIX => "datamatrix",
+                BarcodeFormat::DEUTSCHE_POST_IDENTCODE => "deutsche post identcode",
+                BarcodeFormat::DEUTSCHE_POST_LEITCODE => "deutsche post leitcode",
                 BarcodeFormat::EAN_8 => "ean 8",
                 BarcodeFormat::EAN_13 => "ean 13",
                 BarcodeFormat::ITF => "itf",
+                BarcodeFormat::ITF_14 => "itf 14",
                 BarcodeFormat::MAXICODE => "maxicode",
                 BarcodeFormat::PDF_417 => "pdf 417",
+                BarcodeFormat::PLANET => "planet",
+                BarcodeFormat::POSTNET => "postnet",
                 BarcodeFormat::QR_CODE => "qrcode",
                 BarcodeFormat::MICRO_QR_CODE => "mqr",
                 BarcodeFormat::RECTANGULAR_MICRO_QR_CODE => "rmqr",
@@ -136,6 +160,9 @@ impl From<&str> for BarcodeFormat {
     fn from(value: &str) -> Self {
         match value.to_lowercase().as_str() {
             "aztec" | "aztec code" | "aztec_code" => BarcodeFormat::AZTEC,
+            "australia post" | "australia_post" | "auspost" | "aus_post" => {
+                BarcodeFormat::AUSTRALIA_POST
+            }
             "codabar" | "coda" | "coda_bar" | "cod_a_bar" | "cod_a" => BarcodeFormat::CODABAR,
             "code 39" | "code_39" | "code39" | "alpha39" | "code_3_of_9" | "uss_39" | "usd-3" => {
                 BarcodeFormat::CODE_39
@@ -145,13 +172,20 @@ impl From<&str> for BarcodeFormat {
                 BarcodeFormat::CODE_128
             }
             "datamatrix" | "data matrix" | "data_matrix" => BarcodeFormat::DATA_MATRIX,
+            "deutsche post identcode" | "deutsche_post_identcode" | "identcode" => {
+                BarcodeFormat::DEUTSCHE_POST_IDENTCODE
+            }
+            "deutsche post leitcode" | "deutsche_post_leitcode" | "leitcode" => {
+                BarcodeFormat::DEUTSCHE_POST_LEITCODE
+            }
             "ean 8" | "ean_8" | "ean8" => BarcodeFormat::EAN_8,
             "ean 13" | "ean_13" | "ean13" => BarcodeFormat::EAN_13,
-            "itf" | "itf_code" | "itf14" | "itf 14" | "itf_14" | "interleaved 2 of 5" => {
-                BarcodeFormat::ITF
-            }
+            "itf" | "itf_code" | "interleaved 2 of 5" => BarcodeFormat::ITF,
+            "itf 14" | "itf_14" | "itf14" | "itf-14" => BarcodeFormat::ITF_14,
             "maxicode" | "maxi_code" => BarcodeFormat::MAXICODE,
             "pdf 417" | "pdf_417" | "pdf417" | "iso 15438" | "iso_15438" => BarcodeFormat::PDF_417,
+            "planet" => BarcodeFormat::PLANET,
+            "postnet" => BarcodeFormat::POSTNET,
             "qrcode" | "qr_code" | "qr code" => BarcodeFormat::QR_CODE,
             "mqr" | "microqr" | "micro_qr" | "micro_qrcode" | "micro_qr_code" | "mqr_code" => {
                 BarcodeFormat::MICRO_QR_CODE
@@ -0,0 +1,246 @@
+use std::{
+    borrow::Cow,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{common::Result, BarcodeFormat, CodewordBlock, DecodeHints, Point};
+
+/// Collects what could be read of the symbols whose error correction failed, for looking into
+/// damaged labels.
+///
+/// The decode still fails with [`Exceptions::ChecksumException`](crate::Exceptions) for such a
+/// symbol: what is collected is never a result, only an [`UnverifiedRead`] kept aside when the
+/// collector is given to the decode in the
+/// [`DecodeHintValue::BestEffort`](crate::DecodeHintValue::BestEffort) hint. Clones share the
+/// reads, so the one kept by the caller sees every read the decode collected, in the coordinates
+/// of the image given to it.
+#[derive(Clone, Debug, Default)]
+pub struct BestEffort {
+    reads: Arc<Mutex<Vec<UnverifiedRead>>>,
+    /// Where the image the readers see is in the image given to the decode.
+    offset: Point,
+    /// The points locating the symbol being decoded, in the image the readers see.
+    location: Vec<Point>,
+}
+
+impl BestEffort {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A copy of the reads collected so far.
+    pub fn reads(&self) -> Vec<UnverifiedRead> {
+        self.lock().clone()
+    }
+
+    /// Forgets the reads collected so far, so that the collector can be used for another decode.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<UnverifiedRead>> {
+        self.reads.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// This collector for the image cropped at `offset`.
+    pub(crate) fn translated(&self, offset: Point) -> Self {
+        Self {
+            reads: self.reads.clone(),
+            offset: self.offset + offset,
+            location: Vec::new(),
+        }
+    }
+
+    /// Collects the read of a symbol, unless the same codewords were read already, by another
+    /// reader or from another candidate of the same symbol.
+    pub(crate) fn collect(
+        &self,
+        format: BarcodeFormat,
+        blocks: Vec<CodewordBlock>,
+        partial_text: String,
+    ) {
+        let mut reads = self.lock();
+        if reads
+            .iter()
+            .any(|read| read.format == format && read.blocks == blocks)
+        {
+            return;
+        }
+        reads.push(UnverifiedRead {
+            format,
+            points: self.location.iter().map(|p| *p + self.offset).collect(),
+            blocks,
+            partial_text,
+        });
+    }
+}
+
+impl DecodeHints {
+    /// These hints for decoding the symbol located by `points`, so that a read collected by their
+    /// best effort collector, if any, is located.
+    pub(crate) fn located(&self, points: &[Point]) -> Cow<'_, DecodeHints> {
+        let Some(best_effort) = &self.BestEffort else {
+            return Cow::Borrowed(self);
+        };
+        let mut hints = self.clone();
+        hints.BestEffort = Some(BestEffort {
+            location: points.to_vec(),
+            ..best_effort.clone()
+        });
+        Cow::Owned(hints)
+    }
+}
+
+/// What was read of a symbol whose error correction failed.
+///
+/// None of it is verified: the codewords of the failed blocks are as they were read, with any
+/// number of errors, so the partial text may be wrong anywhere, not only where it is garbled.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnverifiedRead {
+    pub format: BarcodeFormat,
+    /// The points locating the symbol, as the reader would give them with a result, or nothing if
+    /// it was decoded from a pure image without locating it.
+    pub points: Vec<Point>,
+    /// The codewords read from the symbol, one block for each error correction block, with the
+    /// blocks that could not be corrected left as they were read.
+    pub blocks: Vec<CodewordBlock>,
+    /// The text of the data codewords, corrected or not, as far as they could be decoded.
+    pub partial_text: String,
+}
+
+impl UnverifiedRead {
+    /// The indexes of the blocks that could not be corrected.
+    pub fn failed_blocks(&self) -> Vec<usize> {
+        self.blocks
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| block.corrected.is_none())
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// The codewords as they were read from the symbol, block after block.
+    pub fn raw_codewords(&self) -> Vec<u32> {
+        self.blocks
+            .iter()
+            .flat_map(|block| block.received.iter().copied())
+            .collect()
+    }
+}
+
+/// The text of the longest prefix of `data` that `parse` decodes, so that the text before a
+/// codeword garbled beyond decoding is still read.
+pub(crate) fn partial_text<T>(data: &[T], parse: impl Fn(&[T]) -> Result<String>) -> String {
+    (0..=data.len())
+        .rev()
+        .find_map(|len| parse(&data[..len]).ok())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod BestEffortTestCase {
+    use crate::{
+        common::{test_image::image, BitMatrix, Result},
+        qrcode::QRCodeWriter,
+        BarcodeFormat, DecodeHintValue, DecodeHints, EncodeHintValue, EncodeHints, Exceptions,
+        MultiFormatReader, MultiFormatWriter, Reader, Writer,
+    };
+
+    use super::{partial_text, BestEffort};
+
+    const SCALE: u32 = 4;
+    /// The quiet zone around the symbol, in pixels.
+    const MARGIN: u32 = 10 * SCALE;
+
+    fn decode(matrix: &BitMatrix, format: BarcodeFormat, best_effort: &BestEffort) -> Result<()> {
+        let hints = DecodeHints::default()
+            .with(DecodeHintValue::BestEffort(best_effort.clone()))
+            .with(DecodeHintValue::PossibleFormats([format].into()));
+        MultiFormatReader::default()
+            .decode_with_hints(&mut image(matrix, SCALE, MARGIN), &hints)
+            .map(|_| ())
+    }
+
+    #[test]
+    fn testDamagedErrorCorrection() {
+        // a version 1 symbol with error correction level H has its 9 data codewords in the 6
+        // columns on the right and its 17 error correction codewords in the others
+        let hints = EncodeHints::default().with(EncodeHintValue::ErrorCorrection("H".to_owned()));
+        let mut matrix = QRCodeWriter
+            .encode_with_hints("DAMAGED", &BarcodeFormat::QR_CODE, 0, 0, &hints)
+            .expect("encode");
+        // the writer adds a quiet zone of 4 modules
+        assert_eq!(matrix.getWidth(), 21 + 8);
+        // columns 9 to 12 hold 10 error correction codewords, more than the 8 that can be
+        // corrected, around the timing pattern
+        for y in (4..4 + 21).filter(|y| *y != 4 + 6) {
+            for x in 4 + 9..=4 + 12 {
+                matrix.flip_coords(x, y);
+            }
+        }
+
+        let best_effort = BestEffort::new();
+        assert!(decode(&matrix, BarcodeFormat::QR_CODE, &best_effort).is_err());
+        let reads = best_effort.reads();
+        // both QR Code readers read the same codewords
+        assert_eq!(reads.len(), 1);
+        let symbol = MARGIN as f32..=(MARGIN + matrix.getWidth() * SCALE) as f32;
+        let read = &reads[0];
+        assert_eq!(read.format, BarcodeFormat::QR_CODE);
+        assert_eq!(read.failed_blocks(), vec![0]);
+        assert_eq!(read.raw_codewords().len(), 26);
+        assert_eq!(read.blocks[0].data_codewords, 9);
+        // the data codewords are intact
+        assert_eq!(read.partial_text, "DAMAGED");
+        assert!(!read.points.is_empty());
+        assert!(read
+            .points
+            .iter()
+            .all(|p| symbol.contains(&p.x) && symbol.contains(&p.y)));
+
+        best_effort.clear();
+        assert!(best_effort.reads().is_empty());
+        // nothing is collected for a symbol that decodes
+        let matrix = QRCodeWriter
+            .encode_with_hints("DAMAGED", &BarcodeFormat::QR_CODE, 0, 0, &hints)
+            .expect("encode");
+        decode(&matrix, BarcodeFormat::QR_CODE, &best_effort).expect("decode");
+        assert!(best_effort.reads().is_empty());
+    }
+
+    #[test]
+    fn testDamagedDataMatrix() {
+        let mut matrix = MultiFormatWriter
+            .encode("BEST EFFORT", &BarcodeFormat::DATA_MATRIX, 0, 0)
+            .expect("encode");
+        // more damage to the data region than error correction can repair
+        for y in 2..matrix.getHeight() - 2 {
+            for x in (2..matrix.getWidth() - 2).step_by(2) {
+                matrix.flip_coords(x, y);
+            }
+        }
+        let best_effort = BestEffort::new();
+        assert!(decode(&matrix, BarcodeFormat::DATA_MATRIX, &best_effort).is_err());
+        let read = best_effort
+            .reads()
+            .into_iter()
+            .find(|read| read.format == BarcodeFormat::DATA_MATRIX)
+            .expect("read");
+        assert!(!read.failed_blocks().is_empty());
+        assert!(!read.raw_codewords().is_empty());
+        assert_eq!(read.points.len(), 4);
+    }
+
+    #[test]
+    fn testPartialText() {
+        let parse = |data: &[u8]| -> Result<String> {
+            if data.contains(&0) {
+                return Err(Exceptions::FORMAT);
+            }
+            Ok(data.iter().map(|b| char::from(b'A' + b)).collect())
+        };
+        assert_eq!(partial_text(&[1, 2, 3], parse), "BCD");
+        assert_eq!(partial_text(&[1, 2, 0, 3], parse), "BC");
+        assert_eq!(partial_text(&[0, 1], parse), "");
+    }
+}
@@ -224,6 +224,18 @@ impl<B: Binarizer> BinaryBitmap<B> {
         )
     }
 
+    /// A copy of the bitmap, with its black matrix as it is now, for decoding on another thread.
+    #[cfg(feature = "parallel")]
+    pub(crate) fn fork(&self) -> Self
+    where
+        B::Source: Clone,
+    {
+        Self {
+            binarizer: self.binarizer.create_binarizer(self.get_source().clone()),
+            matrix: self.matrix.clone(),
+        }
+    }
+
     pub fn get_source(&self) -> &B::Source {
         self.binarizer.get_luminance_source()
     }
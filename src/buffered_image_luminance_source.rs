@@ -32,6 +32,7 @@ const MINUS_45_IN_RADIANS: f32 = std::f32::consts::FRAC_PI_4;
  * @author Sean Owen
  * @author code@elektrowolle.de (Wolfgang Jung)
  */
+#[derive(Clone)]
 pub struct BufferedImageLuminanceSource {
     // extends LuminanceSource {
     image: DynamicImage,
@@ -0,0 +1,243 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{common::Result, DecodeHints, Exceptions};
+
+/// Stops a decode that takes too long. Long running readers check the token between steps and
+/// return [`Exceptions::TimeoutException`], with any results found so far, once it has been
+/// cancelled or its deadline has passed.
+///
+/// Clones share the cancellation, so one can be given to the decode in the
+/// [`DecodeHintValue::CancellationToken`](crate::DecodeHintValue::CancellationToken) hint and the
+/// other kept to cancel it from another thread.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+}
+
+impl CancellationToken {
+    /// A token that is only cancelled by [`CancellationToken::cancel`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A token that is also cancelled at `deadline`.
+    pub fn with_deadline(deadline: Instant) -> Self {
+        Self {
+            cancelled: Arc::default(),
+            deadline: Some(deadline),
+        }
+    }
+
+    /// A token that is also cancelled once `timeout` has passed from now.
+    pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_deadline(Instant::now() + timeout)
+    }
+
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Cancels the decodes using this token or any of its clones.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the token has been cancelled or its deadline has passed.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Fails with [`Exceptions::TIMEOUT`] if the token is cancelled.
+    pub fn check(&self) -> Result<()> {
+        if self.is_cancelled() {
+            Err(Exceptions::TIMEOUT)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl DecodeHints {
+    /// Whether the cancellation token of the hints, if any, is cancelled.
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.CancellationToken
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    /// Fails with [`Exceptions::TIMEOUT`] if the cancellation token of the hints is cancelled.
+    pub(crate) fn check_cancelled(&self) -> Result<()> {
+        match &self.CancellationToken {
+            Some(token) => token.check(),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod CancellationTokenTestCase {
+    use std::time::Duration;
+
+    use crate::{
+        common::{test_image::TestImage, HybridBinarizer, Result},
+        multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+        oned::Code128Reader,
+        qrcode::cpp_port::QrReader,
+        BarcodeFormat, Binarizer, BinaryBitmap, DecodeHintValue, DecodeHints, Exceptions,
+        Luma8LuminanceSource, MultiFormatReader, MultiFormatWriter, RXingResult, Reader, Writer,
+    };
+
+    use super::CancellationToken;
+
+    /// A 400 by 300 image with QR Codes of `contents` at the given top left corners.
+    fn image(symbols: &[(&str, u32, u32)]) -> BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>> {
+        let mut image = TestImage::new(400, 300);
+        for (contents, left, top) in symbols {
+            let matrix = MultiFormatWriter
+                .encode(contents, &BarcodeFormat::QR_CODE, 0, 0)
+                .expect("encode");
+            image.draw(&matrix, 3, *left, *top);
+        }
+        image.bitmap()
+    }
+
+    fn hints(token: &CancellationToken) -> DecodeHints {
+        DecodeHints::default().with(DecodeHintValue::CancellationToken(token.clone()))
+    }
+
+    /// Cancels the token once it has found a symbol.
+    #[derive(Clone)]
+    struct CancelAfterFirst(CancellationToken);
+
+    impl Reader for CancelAfterFirst {
+        fn decode<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
+            self.decode_with_hints(image, &DecodeHints::default())
+        }
+
+        fn decode_with_hints<B: Binarizer>(
+            &mut self,
+            image: &mut BinaryBitmap<B>,
+            hints: &DecodeHints,
+        ) -> Result<RXingResult> {
+            let result = QrReader.decode_with_hints(image, hints)?;
+            self.0.cancel();
+            Ok(result)
+        }
+    }
+
+    /// Runs out of time after its first symbol, with the symbol it was decoding.
+    #[derive(Clone, Default)]
+    struct TimeoutAfterFirst(bool);
+
+    impl Reader for TimeoutAfterFirst {
+        fn decode<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
+            self.decode_with_hints(image, &DecodeHints::default())
+        }
+
+        fn decode_with_hints<B: Binarizer>(
+            &mut self,
+            image: &mut BinaryBitmap<B>,
+            hints: &DecodeHints,
+        ) -> Result<RXingResult> {
+            let result = QrReader.decode_with_hints(image, hints)?;
+            if self.0 {
+                return Err(Exceptions::timeout_with(vec![result]));
+            }
+            self.0 = true;
+            Ok(result)
+        }
+    }
+
+    #[test]
+    fn testToken() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!token.is_cancelled());
+        clone.cancel();
+        assert!(token.is_cancelled());
+        assert_eq!(token.check(), Err(Exceptions::TIMEOUT));
+
+        assert!(CancellationToken::with_timeout(Duration::ZERO).is_cancelled());
+        let token = CancellationToken::with_timeout(Duration::from_secs(3600));
+        assert!(!token.is_cancelled());
+        assert!(token.deadline().is_some());
+    }
+
+    #[test]
+    fn testCancelledDecode() {
+        let symbols = [("ONE", 20, 20)];
+        let token = CancellationToken::new();
+        assert_eq!(
+            MultiFormatReader::default()
+                .decode_with_hints(&mut image(&symbols), &hints(&token))
+                .expect("decode")
+                .getText(),
+            "ONE"
+        );
+
+        token.cancel();
+        assert_eq!(
+            MultiFormatReader::default().decode_with_hints(&mut image(&symbols), &hints(&token)),
+            Err(Exceptions::TIMEOUT)
+        );
+        // 1D row scanning tells a timeout from finding nothing
+        let hints = DecodeHints {
+            TryHarder: Some(true),
+            ..hints(&token)
+        };
+        assert_eq!(
+            Code128Reader.decode_with_hints(&mut image(&[]), &hints),
+            Err(Exceptions::TIMEOUT)
+        );
+    }
+
+    #[test]
+    fn testPartialResults() {
+        let symbols = [("ONE", 20, 20), ("TWO", 280, 180)];
+        let token = CancellationToken::new();
+        let mut reader = GenericMultipleBarcodeReader::new(CancelAfterFirst(token.clone()));
+        match reader.decode_multiple_with_hints(&mut image(&symbols), &hints(&token)) {
+            Err(Exceptions::TimeoutException(partial)) => {
+                assert_eq!(partial.len(), 1);
+                assert!(["ONE", "TWO"].contains(&partial[0].getText()));
+            }
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+
+        // the partial results of the reader are kept, in the coordinates of the whole image
+        let mut reader = GenericMultipleBarcodeReader::new(TimeoutAfterFirst::default());
+        match reader.decode_multiple_with_hints(&mut image(&symbols), &DecodeHints::default()) {
+            Err(Exceptions::TimeoutException(partial)) => {
+                assert_eq!(partial.len(), 2);
+                for result in &partial {
+                    let (_, left, top) = symbols
+                        .iter()
+                        .find(|(text, _, _)| *text == result.getText())
+                        .expect("symbol");
+                    // the symbols are 29 modules of 3 pixels with the quiet zone
+                    for p in result.getPoints() {
+                        assert!(p.x >= *left as f32 && p.x <= (left + 87) as f32);
+                        assert!(p.y >= *top as f32 && p.y <= (top + 87) as f32);
+                    }
+                }
+            }
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+
+        // without cancelling, both are found
+        let results = GenericMultipleBarcodeReader::new(QrReader)
+            .decode_multiple(&mut image(&symbols))
+            .expect("decode");
+        assert_eq!(results.len(), 2);
+    }
+}
@@ -806,23 +806,10 @@ impl From<&BitMatrix> for image::DynamicImage {
 #[cfg(feature = "svg_write")]
 impl From<&BitMatrix> for svg::Document {
     fn from(value: &BitMatrix) -> Self {
-        let block_size = 1;
-        let mut document = svg::Document::new().set(
-            "viewBox",
-            (0, 0, value.width * block_size, value.height * block_size),
-        );
-        for x in 0..value.width {
-            for y in 0..value.height {
-                if value.get(x, y) {
-                    let block = svg::node::element::Rectangle::new()
-                        .set("x", x * block_size)
-                        .set("y", y * block_size)
-                        .set("width", block_size)
-                        .set("height", block_size);
-                    document = document.add(block);
-                }
-            }
-        }
-        document
+        crate::render::render_svg(
+            value,
+            &crate::render::RenderOptions::default(),
+            &crate::render::SvgOptions::default(),
+        )
     }
 }
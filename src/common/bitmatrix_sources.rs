@@ -4,6 +4,7 @@ use crate::{Binarizer, LuminanceSource};
 
 use super::{BitArray, BitMatrix, LineOrientation};
 
+#[derive(Clone)]
 pub struct BitMatrixSource {
     base_bitmatrix: BitMatrix,
     byte_array: Box<[u8]>,
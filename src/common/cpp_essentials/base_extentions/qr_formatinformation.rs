@@ -115,7 +115,8 @@ impl FormatInformation {
             ErrorCorrectionLevel::ECLevelFromBits(((fi.data >> 5) as u8 & 1) << 1, false); // Shift to match QRCode M/H
         fi.data_mask = 4; // ((y / 2) + (x / 3)) % 2 == 0
         fi.microVersion = (fi.data & 0x1F) + 1;
-        fi.isMirrored = false; // TODO: implement mirrored format bit reading
+        // the bits of a mirrored symbol are read as those of its transposed matrix, see ReadFormatInformation
+        fi.isMirrored = false;
 
         fi
     }
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use crate::{common::ECIStringBuilder, Exceptions};
+use crate::{common::ECIStringBuilder, ErrorCorrectionStats, Exceptions};
 
 use super::StructuredAppendInfo;
 
@@ -16,6 +16,7 @@ where
     structuredAppend: StructuredAppendInfo,
     isMirrored: bool, // = false;
     readerInit: bool, // = false;
+    errorCorrection: Option<ErrorCorrectionStats>,
     //Error _error;
     //std::shared_ptr<CustomData> _extra;
     error: Option<Exceptions>,
@@ -35,6 +36,7 @@ where
             structuredAppend: Default::default(),
             isMirrored: false,
             readerInit: false,
+            errorCorrection: None,
             error: None,
             extra: Default::default(),
         }
@@ -135,6 +137,20 @@ where
         self
     }
 
+    pub fn errorCorrection(&self) -> Option<ErrorCorrectionStats> {
+        self.errorCorrection
+    }
+    pub fn setErrorCorrection(&mut self, error_correction: ErrorCorrectionStats) {
+        self.errorCorrection = Some(error_correction)
+    }
+    pub fn withErrorCorrection(
+        mut self,
+        error_correction: ErrorCorrectionStats,
+    ) -> DecoderResult<T> {
+        self.setErrorCorrection(error_correction);
+        self
+    }
+
     pub fn extra(&self) -> Arc<T> {
         self.extra.clone()
     }
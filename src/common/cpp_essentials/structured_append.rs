@@ -1,7 +1,15 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// The place of a symbol in a structured append sequence.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct StructuredAppendInfo {
+    /// The position of the symbol in the sequence, from 0, or -1.
     pub index: i32, // -1;
+    /// The number of symbols in the sequence, 0 if unknown, or -1.
     pub count: i32, // = -1;
+    /// The identifier shared by the symbols of the sequence, if the format has one.
     pub id: String,
 }
 
@@ -20,6 +20,10 @@
 
 use std::{any::Any, sync::Arc};
 
+use crate::ErrorCorrectionStats;
+
+use super::{cpp_essentials::StructuredAppendInfo, Eci};
+
 /**
  * <p>Encapsulates the result of decoding a matrix of bits. This typically
  * applies to 2D barcode formats. For now it contains the raw bytes obtained,
@@ -35,12 +39,15 @@ pub struct DecoderRXingResult {
     ecLevel: String,
     errorsCorrected: usize,
     erasures: usize,
+    errorCorrectionCapacity: usize,
     other: Option<Arc<dyn Any + Send + Sync>>,
     structuredAppendParity: i32,
     structuredAppendSequenceNumber: i32,
+    structuredAppendInfo: Option<StructuredAppendInfo>,
     symbologyModifier: u32,
     contentType: String,
     isMirrored: bool,
+    eci: Option<Eci>,
 }
 
 impl DecoderRXingResult {
@@ -125,12 +132,15 @@ impl DecoderRXingResult {
             ecLevel,
             errorsCorrected: 0,
             erasures: 0,
+            errorCorrectionCapacity: 0,
             other: None,
             structuredAppendParity: saParity,
             structuredAppendSequenceNumber: saSequence,
+            structuredAppendInfo: None,
             symbologyModifier,
             contentType,
             isMirrored,
+            eci: None,
         }
     }
 
@@ -200,6 +210,29 @@ impl DecoderRXingResult {
         self.erasures = erasures
     }
 
+    /**
+     * @return number of error correction codewords in the symbol, or 0 if not applicable
+     */
+    pub const fn getErrorCorrectionCapacity(&self) -> usize {
+        self.errorCorrectionCapacity
+    }
+
+    pub const fn setErrorCorrectionCapacity(&mut self, capacity: usize) {
+        self.errorCorrectionCapacity = capacity
+    }
+
+    /**
+     * @return the errors and erasures corrected against the capacity, or `None` if the symbol
+     *  has no Reed-Solomon error correction
+     */
+    pub fn getErrorCorrectionStats(&self) -> Option<ErrorCorrectionStats> {
+        (self.errorCorrectionCapacity > 0).then_some(ErrorCorrectionStats {
+            errors: self.errorsCorrected as u32,
+            erasures: self.erasures as u32,
+            capacity: self.errorCorrectionCapacity as u32,
+        })
+    }
+
     /**
      * @return arbitrary additional metadata
      */
@@ -223,6 +256,18 @@ impl DecoderRXingResult {
         self.structuredAppendSequenceNumber
     }
 
+    /**
+     * @return the place of the symbol in a structured append sequence, for the formats that
+     *  identify the sequence rather than give a QR Code parity
+     */
+    pub fn getStructuredAppendInfo(&self) -> Option<&StructuredAppendInfo> {
+        self.structuredAppendInfo.as_ref()
+    }
+
+    pub fn setStructuredAppendInfo(&mut self, info: StructuredAppendInfo) {
+        self.structuredAppendInfo = Some(info)
+    }
+
     pub const fn getSymbologyModifier(&self) -> u32 {
         self.symbologyModifier
     }
@@ -242,4 +287,15 @@ impl DecoderRXingResult {
     pub const fn setIsMirrored(&mut self, is_mirrored: bool) {
         self.isMirrored = is_mirrored
     }
+
+    /**
+     * @return the first character set ECI of the symbol, if it has one
+     */
+    pub const fn getEci(&self) -> Option<Eci> {
+        self.eci
+    }
+
+    pub const fn setEci(&mut self, eci: Option<Eci>) {
+        self.eci = eci
+    }
 }
@@ -137,6 +137,17 @@ impl ECIStringBuilder {
         }
     }
 
+    /// The first ECI appended, if the data has any ECI other than the default ISO-8859-1.
+    pub fn first_eci(&self) -> Option<Eci> {
+        if !self.has_eci {
+            return None;
+        }
+        self.eci_positions
+            .first()
+            .map(|(eci, _, _)| *eci)
+            .filter(|eci| *eci != Eci::Unknown)
+    }
+
     /// Change the current encoding characterset, finding an eci to do so
     pub fn switch_encoding(&mut self, charset: CharacterSet, is_eci: bool) {
         //self.append_eci(Eci::from(charset))
@@ -18,6 +18,9 @@ mod BitSourceTestCase;
 #[cfg(test)]
 mod PerspectiveTransformTestCase;
 
+#[cfg(test)]
+pub(crate) mod test_image;
+
 pub mod string_utils;
 
 mod bit_array;
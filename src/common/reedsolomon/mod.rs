@@ -49,6 +49,7 @@ pub enum PredefinedGenericGF {
     DataMatrixField256,
     AztecData8,
     MaxicodeField64,
+    AustraliaPostField64,
     // PDF417,
 }
 
@@ -57,13 +58,15 @@ pub fn get_predefined_genericgf(request: PredefinedGenericGF) -> GenericGFRef {
     match request {
         PredefinedGenericGF::AztecData12 => &AZTEC_DATA_12, // x^12 + x^6 + x^5 + x^3 + 1,
         PredefinedGenericGF::AztecData10 => &AZTEC_DATA_10, // x^10 + x^3 + 1
-        PredefinedGenericGF::AztecData6 | PredefinedGenericGF::MaxicodeField64 => &AZTEC_DATA_6, // x^6 + x + 1
-        PredefinedGenericGF::AztecParam => &AZTEC_PARAM, // x^4 + x + 1
+        PredefinedGenericGF::AztecData6
+        | PredefinedGenericGF::MaxicodeField64
+        | PredefinedGenericGF::AustraliaPostField64 => &AZTEC_DATA_6, // x^6 + x + 1
+        PredefinedGenericGF::AztecParam => &AZTEC_PARAM,    // x^4 + x + 1
         PredefinedGenericGF::QrCodeField256 => &QR_CODE_FIELD_256, // x^8 + x^4 + x^3 + x^2 + 1
         PredefinedGenericGF::DataMatrixField256 | PredefinedGenericGF::AztecData8 => {
             &DATA_MATRIX_FIELD_256
         } // x^8 + x^5 + x^3 + x^2 + 1
-                                                          // PredefinedGenericGF::PDF417 => &PDF_417_FIELD,
+                                                             // PredefinedGenericGF::PDF417 => &PDF_417_FIELD,
     }
 }
 
@@ -0,0 +1,85 @@
+use crate::{
+    common::{BitMatrix, HybridBinarizer},
+    BinaryBitmap, Luma8LuminanceSource,
+};
+
+/// A light image to draw symbols on, for the tests of the readers.
+pub(crate) struct TestImage {
+    luma: Vec<u8>,
+    width: u32,
+    height: u32,
+}
+
+impl TestImage {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            luma: vec![255; (width * height) as usize],
+            width,
+            height,
+        }
+    }
+
+    /// Draws the dark modules of `matrix`, `scale` pixels wide, from the top left corner at
+    /// (`left`, `top`).
+    pub fn draw(&mut self, matrix: &BitMatrix, scale: u32, left: u32, top: u32) -> &mut Self {
+        for y in 0..matrix.getHeight() * scale {
+            for x in 0..matrix.getWidth() * scale {
+                if matrix.get(x / scale, y / scale) {
+                    self.set(left + x, top + y, 0);
+                }
+            }
+        }
+        self
+    }
+
+    /// Draws `matrix` light on dark, as [`TestImage::draw`] does, in a dark quiet zone of
+    /// `quiet_zone` pixels.
+    pub fn draw_inverted(
+        &mut self,
+        matrix: &BitMatrix,
+        scale: u32,
+        left: u32,
+        top: u32,
+        quiet_zone: u32,
+    ) -> &mut Self {
+        for y in top - quiet_zone..top + matrix.getHeight() * scale + quiet_zone {
+            for x in left - quiet_zone..left + matrix.getWidth() * scale + quiet_zone {
+                self.set(x, y, 0);
+            }
+        }
+        for y in 0..matrix.getHeight() * scale {
+            for x in 0..matrix.getWidth() * scale {
+                if matrix.get(x / scale, y / scale) {
+                    self.set(left + x, top + y, 255);
+                }
+            }
+        }
+        self
+    }
+
+    pub fn set(&mut self, x: u32, y: u32, luma: u8) {
+        self.luma[(y * self.width + x) as usize] = luma;
+    }
+
+    pub fn source(&self) -> Luma8LuminanceSource {
+        Luma8LuminanceSource::new(self.luma.clone(), self.width, self.height)
+    }
+
+    pub fn bitmap(&self) -> BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>> {
+        BinaryBitmap::new(HybridBinarizer::new(self.source()))
+    }
+}
+
+/// The image of `matrix`, with modules `scale` pixels wide and a quiet zone of `margin` pixels.
+pub(crate) fn image(
+    matrix: &BitMatrix,
+    scale: u32,
+    margin: u32,
+) -> BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>> {
+    TestImage::new(
+        matrix.getWidth() * scale + 2 * margin,
+        matrix.getHeight() * scale + 2 * margin,
+    )
+    .draw(matrix, scale, margin, margin)
+    .bitmap()
+}
@@ -0,0 +1,402 @@
+use crate::{
+    composite::micro_pdf417,
+    multi::MultipleBarcodeReader,
+    oned::{convertUPCEtoUPCA, rss::expanded::decoders::field_parser, MultiFormatOneDReader},
+    pdf417::PDF417Reader,
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHintValue, DecodeHints, Point, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader,
+};
+
+/** The linear symbols that can carry a composite component. */
+pub const COMPOSITE_LINEAR_FORMATS: [BarcodeFormat; 7] = [
+    BarcodeFormat::EAN_13,
+    BarcodeFormat::EAN_8,
+    BarcodeFormat::UPC_A,
+    BarcodeFormat::UPC_E,
+    BarcodeFormat::CODE_128,
+    BarcodeFormat::RSS_14,
+    BarcodeFormat::RSS_EXPANDED,
+];
+
+/**
+ * Completes a result that may be one half of a GS1 Composite symbol.
+ *
+ * For a linear GS1 symbol, the image is searched for a composite component printed above it; for
+ * a composite component, for the linear symbol below it. When both halves are found, they are
+ * merged into one result of the linear format whose text is the complete GS1 element string, with
+ * the AIs in parentheses. The data of the 2D component is also reported in the
+ * `GS1_COMPOSITE` metadata.
+ *
+ * The component of a linear symbol is only searched for when the symbol flags one, see the
+ * `GS1_COMPOSITE_LINKAGE` metadata, or with the `AssumeGs1` hint, as EAN/UPC symbols have no flag.
+ *
+ * Any other result, or a half whose partner is not found, is returned unchanged.
+ */
+pub fn complete_composite<B: Binarizer>(
+    result: RXingResult,
+    image: &mut BinaryBitmap<B>,
+    hints: &DecodeHints,
+) -> RXingResult {
+    let allowed = |format: &BarcodeFormat| {
+        hints
+            .PossibleFormats
+            .as_ref()
+            .is_none_or(|possible| possible.contains(format))
+    };
+
+    if is_composite_component(&result) {
+        let formats = COMPOSITE_LINEAR_FORMATS
+            .iter()
+            .filter(|f| allowed(f))
+            .copied()
+            .collect::<std::collections::HashSet<_>>();
+        if formats.is_empty() {
+            return result;
+        }
+        let linear_hints = hints
+            .clone()
+            .with(DecodeHintValue::PossibleFormats(formats));
+        let Ok(linear) =
+            MultiFormatOneDReader::new(&linear_hints).decode_with_hints(image, &linear_hints)
+        else {
+            return result;
+        };
+        return merge(&linear, result.getText(), result.getPoints()).unwrap_or(result);
+    }
+
+    if COMPOSITE_LINEAR_FORMATS.contains(result.getBarcodeFormat())
+        && allowed(&BarcodeFormat::PDF_417)
+        && (has_linkage(&result) || hints.AssumeGs1 == Some(true))
+        && linear_element_string(&result).is_some()
+    {
+        if let Some((text, points)) = read_cc_a_b(&result, image) {
+            return merge(&result, &text, &points).unwrap_or(result);
+        }
+
+        let Ok(candidates) = PDF417Reader.decode_multiple_with_hints(image, hints) else {
+            return result;
+        };
+        // the nearest component above the linear symbol
+        let component = candidates
+            .iter()
+            .filter(|c| is_composite_component(c) && is_above(c, &result))
+            .min_by(|a, b| {
+                let (_, a_bottom) = vertical_extent(a.getPoints());
+                let (_, b_bottom) = vertical_extent(b.getPoints());
+                b_bottom.total_cmp(&a_bottom)
+            });
+        if let Some(merged) = component.and_then(|c| merge(&result, c.getText(), c.getPoints())) {
+            return merged;
+        }
+    }
+
+    result
+}
+
+fn is_composite_component(result: &RXingResult) -> bool {
+    result.getBarcodeFormat() == &BarcodeFormat::PDF_417
+        && matches!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::PDF417_EXTRA_METADATA),
+            Some(RXingResultMetadataValue::Pdf417ExtraMetadata(metadata))
+                if metadata.isCompositeComponent()
+        )
+}
+
+fn has_linkage(linear: &RXingResult) -> bool {
+    matches!(
+        linear
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::GS1_COMPOSITE_LINKAGE),
+        Some(RXingResultMetadataValue::Gs1CompositeLinkage(true))
+    )
+}
+
+/**
+ * Reads a CC-A or CC-B component directly above an upright linear symbol, in a window reaching
+ * half the width of the symbol beyond it on either side.
+ */
+fn read_cc_a_b<B: Binarizer>(
+    linear: &RXingResult,
+    image: &BinaryBitmap<B>,
+) -> Option<(String, Vec<Point>)> {
+    let upright = matches!(
+        linear
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::ORIENTATION),
+        None | Some(RXingResultMetadataValue::Orientation(0))
+    );
+    if !upright {
+        return None;
+    }
+
+    let (top, _) = vertical_extent(linear.getPoints());
+    let (left, right) = horizontal_extent(linear.getPoints());
+    let margin = (right - left) / 2.0;
+    micro_pdf417::read_component(
+        image,
+        (left - margin).max(0.0) as usize,
+        (right + margin).max(0.0) as usize,
+        top.max(0.0) as usize,
+    )
+}
+
+fn vertical_extent(points: &[Point]) -> (f32, f32) {
+    points
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(top, bottom), p| {
+            (top.min(p.y), bottom.max(p.y))
+        })
+}
+
+fn horizontal_extent(points: &[Point]) -> (f32, f32) {
+    points
+        .iter()
+        .fold((f32::MAX, f32::MIN), |(left, right), p| {
+            (left.min(p.x), right.max(p.x))
+        })
+}
+
+/**
+ * Whether `component` sits above `linear` and overlaps it horizontally.
+ */
+fn is_above(component: &RXingResult, linear: &RXingResult) -> bool {
+    let (_, component_bottom) = vertical_extent(component.getPoints());
+    let (linear_top, _) = vertical_extent(linear.getPoints());
+    let (component_left, component_right) = horizontal_extent(component.getPoints());
+    let (linear_left, linear_right) = horizontal_extent(linear.getPoints());
+
+    component_bottom <= linear_top && component_left < linear_right && linear_left < component_right
+}
+
+/**
+ * The GS1 element string carried by a linear result, with the AIs in parentheses, or `None` if
+ * the symbol does not carry GS1 data.
+ */
+fn linear_element_string(linear: &RXingResult) -> Option<String> {
+    let text = linear.getText();
+    let gtin = |digits: &str| {
+        (digits.len() <= 14 && digits.chars().all(|c| c.is_ascii_digit()))
+            .then(|| format!("(01){digits:0>14}"))
+    };
+
+    match linear.getBarcodeFormat() {
+        BarcodeFormat::EAN_13 | BarcodeFormat::EAN_8 | BarcodeFormat::UPC_A => gtin(text),
+        BarcodeFormat::UPC_E => convertUPCEtoUPCA(text).and_then(|upca| gtin(&upca)),
+        BarcodeFormat::RSS_14 => gtin(text),
+        BarcodeFormat::RSS_EXPANDED => Some(text.to_owned()),
+        BarcodeFormat::CODE_128 => {
+            let gs1 = matches!(
+                linear
+                    .getRXingResultMetadata()
+                    .get(&RXingResultMetadataType::SYMBOLOGY_IDENTIFIER),
+                Some(RXingResultMetadataValue::SymbologyIdentifier(id)) if id == "]C1"
+            );
+            if !gs1 {
+                return None;
+            }
+            // with `AssumeGs1` the text carries the identifier and GS separators
+            let data = text.strip_prefix("]C1").unwrap_or(text);
+            data.split('\u{1d}')
+                .map(field_parser::parseFieldsInGeneralPurpose)
+                .collect::<crate::common::Result<String>>()
+                .ok()
+        }
+        _ => None,
+    }
+}
+
+/**
+ * Merges a linear result with the element string and points of its composite component.
+ */
+fn merge(linear: &RXingResult, component: &str, component_points: &[Point]) -> Option<RXingResult> {
+    let linear_data = linear_element_string(linear)?;
+
+    let mut points = linear.getPoints().to_vec();
+    points.extend_from_slice(component_points);
+
+    let mut merged = RXingResult::new(
+        &format!("{linear_data}{component}"),
+        linear.getRawBytes().to_vec(),
+        points,
+        *linear.getBarcodeFormat(),
+    );
+    merged.putAllMetadata(linear.getRXingResultMetadata().clone());
+    merged.putMetadata(
+        RXingResultMetadataType::GS1_COMPOSITE,
+        RXingResultMetadataValue::Gs1Composite(component.to_owned()),
+    );
+
+    Some(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::{test_image::image, BitMatrix},
+        oned::{rss::RSS14Writer, Code128Writer, EAN13Writer},
+        BarcodeFormat, DecodeHintValue, DecodeHints, EncodeHintValue, EncodeHints,
+        MultiFormatReader, MultiFormatWriter, RXingResult, RXingResultMetadataType,
+        RXingResultMetadataValue, Reader, Writer,
+    };
+
+    fn gs1_128_composite() -> BitMatrix {
+        let hints = EncodeHints::default().with(EncodeHintValue::Gs1Composite(
+            "(17)261231(10)LOT-42/a".to_owned(),
+        ));
+        Code128Writer
+            .encode_with_hints(
+                "\u{f1}0109501101530003",
+                &BarcodeFormat::CODE_128,
+                0,
+                0,
+                &hints,
+            )
+            .expect("encode")
+    }
+
+    fn assert_composite(hints: &DecodeHints) {
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut image(&gs1_128_composite(), 1, 0), hints)
+            .expect("decode");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::CODE_128);
+        assert_eq!(result.getText(), "(01)09501101530003(17)261231(10)LOT-42/a");
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::GS1_COMPOSITE),
+            Some(&RXingResultMetadataValue::Gs1Composite(
+                "(17)261231(10)LOT-42/a".to_owned()
+            ))
+        );
+    }
+
+    #[test]
+    fn gs1_128_with_cc_c() {
+        // the linear symbol is found first
+        assert_composite(&DecodeHints::default());
+        // the component is found first
+        assert_composite(&DecodeHints::default().with(DecodeHintValue::TryHarder(true)));
+    }
+
+    #[test]
+    fn component_needs_pdf417() {
+        let hints = DecodeHints::default().with(DecodeHintValue::PossibleFormats(
+            [BarcodeFormat::CODE_128].into(),
+        ));
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut image(&gs1_128_composite(), 1, 0), &hints)
+            .expect("decode");
+        assert_eq!(result.getText(), "0109501101530003");
+        assert!(!result
+            .getRXingResultMetadata()
+            .contains_key(&RXingResultMetadataType::GS1_COMPOSITE));
+    }
+
+    fn decode_composite(matrix: &BitMatrix, hints: &DecodeHints) -> RXingResult {
+        MultiFormatReader::default()
+            .decode_with_hints(&mut image(matrix, 1, 0), hints)
+            .expect("decode")
+    }
+
+    fn composite_data(result: &RXingResult) -> Option<&RXingResultMetadataValue> {
+        result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::GS1_COMPOSITE)
+    }
+
+    #[test]
+    fn ean_with_cc_a() {
+        let matrix = EAN13Writer
+            .encode_with_hints(
+                "5901234123457",
+                &BarcodeFormat::EAN_13,
+                0,
+                0,
+                &EncodeHints::default().with(EncodeHintValue::Gs1Composite(
+                    "(17)261231(10)LOT-42".to_owned(),
+                )),
+            )
+            .expect("encode");
+
+        let result = decode_composite(
+            &matrix,
+            &DecodeHints::default().with(DecodeHintValue::AssumeGs1(true)),
+        );
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::EAN_13);
+        assert_eq!(result.getText(), "(01)05901234123457(17)261231(10)LOT-42");
+
+        // EAN/UPC symbols do not flag their component, it is only looked for with the hint
+        let result = decode_composite(&matrix, &DecodeHints::default());
+        assert_eq!(result.getText(), "5901234123457");
+        assert_eq!(composite_data(&result), None);
+    }
+
+    #[test]
+    fn ean_8_and_upc_e_with_cc_a() {
+        let hints =
+            EncodeHints::default().with(EncodeHintValue::Gs1Composite("(10)ABC123".to_owned()));
+        let decode_hints = DecodeHints::default().with(DecodeHintValue::AssumeGs1(true));
+        for (contents, format, text) in [
+            (
+                "96385074",
+                BarcodeFormat::EAN_8,
+                "(01)00000096385074(10)ABC123",
+            ),
+            (
+                "01234565",
+                BarcodeFormat::UPC_E,
+                "(01)00012345000065(10)ABC123",
+            ),
+        ] {
+            let matrix = MultiFormatWriter
+                .encode_with_hints(contents, &format, 0, 0, &hints)
+                .expect("encode");
+            let result = decode_composite(&matrix, &decode_hints);
+            assert_eq!(result.getBarcodeFormat(), &format);
+            assert_eq!(result.getText(), text);
+        }
+    }
+
+    #[test]
+    fn databar_with_cc_a_and_cc_b() {
+        for component in [
+            "(17)261231(10)LOT-42",
+            "(21)ABCDEFGHIJKLMNOPQRST(240)abcdefghijklmnopqrstuvwxyz0123",
+        ] {
+            let matrix = RSS14Writer
+                .encode_with_hints(
+                    "0950110153001",
+                    &BarcodeFormat::RSS_14,
+                    0,
+                    0,
+                    &EncodeHints::default()
+                        .with(EncodeHintValue::Gs1Composite(component.to_owned())),
+                )
+                .expect("encode");
+            // the linkage flag of the DataBar symbol makes the hint unnecessary
+            let result = decode_composite(&matrix, &DecodeHints::default());
+            assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::RSS_14);
+            assert_eq!(result.getText(), format!("(01)09501101530010{component}"));
+            assert_eq!(
+                composite_data(&result),
+                Some(&RXingResultMetadataValue::Gs1Composite(
+                    component.to_owned()
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn unlinked_gs1_128_is_not_searched() {
+        let matrix = Code128Writer
+            .encode("\u{f1}0109501101530003", &BarcodeFormat::CODE_128, 0, 0)
+            .expect("encode");
+        let result = decode_composite(&matrix, &DecodeHints::default());
+        assert!(!result
+            .getRXingResultMetadata()
+            .contains_key(&RXingResultMetadataType::GS1_COMPOSITE_LINKAGE));
+        assert_eq!(composite_data(&result), None);
+    }
+}
@@ -0,0 +1,212 @@
+use crate::{
+    common::{BitMatrix, Result},
+    encoded_symbol::Placement,
+    pdf417::encoder::{pdf_417_error_correction, PDF417},
+    BarcodeFormat, EncodedSymbol, Exceptions,
+};
+
+use super::{encode_data, micro_pdf417};
+
+/** The linkage flag which starts the data of a CC-C component. */
+const LINKAGE_FLAG_CC_C: u32 = 920;
+const BYTE_COMPACTION_MODE_LATCH: u32 = 901;
+const BYTE_COMPACTION_MODE_LATCH_6: u32 = 924;
+
+/** Height of a CC-C row, in modules. */
+const CC_C_ROW_HEIGHT: u32 = 3;
+
+/** Height of a CC-A or CC-B row, in modules. */
+const CC_A_B_ROW_HEIGHT: u32 = 2;
+
+/** Space between the composite component and the linear symbol, in modules. */
+const SEPARATOR_HEIGHT: u32 = 1;
+
+/** Minimum height of the linear symbol of a composite, in modules. */
+const MIN_LINEAR_HEIGHT: u32 = 30;
+
+/**
+ * The codewords of a CC-C component carrying `data`: the linkage flag, followed by the data bit
+ * stream in Byte Compaction mode.
+ */
+pub fn cc_c_codewords(data: &str) -> Result<Vec<u32>> {
+    let bytes = encode_data(data)?;
+
+    let mut codewords = vec![
+        LINKAGE_FLAG_CC_C,
+        if bytes.len().is_multiple_of(6) {
+            BYTE_COMPACTION_MODE_LATCH_6
+        } else {
+            BYTE_COMPACTION_MODE_LATCH
+        },
+    ];
+    let mut chunks = bytes.chunks_exact(6);
+    for chunk in chunks.by_ref() {
+        let mut value = chunk.iter().fold(0u64, |v, b| (v << 8) | *b as u64);
+        let mut group = [0u32; 5];
+        for cw in group.iter_mut().rev() {
+            *cw = (value % 900) as u32;
+            value /= 900;
+        }
+        codewords.extend_from_slice(&group);
+    }
+    codewords.extend(chunks.remainder().iter().map(|b| *b as u32));
+
+    Ok(codewords)
+}
+
+/**
+ * Renders a GS1 Composite symbol: the linear symbol `code` with a composite component carrying
+ * `component` (a GS1 element string such as `(17)010200(10)ABC`) printed above it.
+ *
+ * GS1-128 symbols, given as Code 128 contents starting with FNC1, are combined with a CC-C
+ * component as wide as the linear symbol allows. EAN/UPC and GS1 DataBar symbols are combined
+ * with the smallest CC-A of the width their format prescribes that fits the data, or a CC-B if
+ * none does.
+ */
+pub fn render_composite(
+    format: &BarcodeFormat,
+    contents: &str,
+    code: &[bool],
+    component: &str,
+    width: i32,
+    height: i32,
+    sides_margin: u32,
+) -> Result<BitMatrix> {
+    Ok(encode_composite(
+        format,
+        contents,
+        code,
+        component,
+        width,
+        height,
+        sides_margin,
+    )?
+    .matrix)
+}
+
+/**
+ * Renders a GS1 Composite symbol like [`render_composite`], and also returns its module matrix,
+ * in which the linear symbol is one module high, and where the modules are in the matrix.
+ */
+pub(crate) fn encode_composite(
+    format: &BarcodeFormat,
+    contents: &str,
+    code: &[bool],
+    component: &str,
+    width: i32,
+    height: i32,
+    sides_margin: u32,
+) -> Result<EncodedSymbol> {
+    let (rows, row_height) = match format {
+        BarcodeFormat::CODE_128 if contents.starts_with('\u{f1}') => {
+            (cc_c_rows(code, component)?, CC_C_ROW_HEIGHT)
+        }
+        BarcodeFormat::CODE_128 => {
+            return Err(Exceptions::illegal_argument_with(
+                "a composite component requires GS1-128 contents starting with FNC1",
+            ))
+        }
+        BarcodeFormat::EAN_13 | BarcodeFormat::UPC_A | BarcodeFormat::RSS_14 => (
+            micro_pdf417::encode_component(component, 4)?,
+            CC_A_B_ROW_HEIGHT,
+        ),
+        BarcodeFormat::EAN_8 => (
+            micro_pdf417::encode_component(component, 3)?,
+            CC_A_B_ROW_HEIGHT,
+        ),
+        BarcodeFormat::UPC_E => (
+            micro_pdf417::encode_component(component, 2)?,
+            CC_A_B_ROW_HEIGHT,
+        ),
+        _ => {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "{format} can not carry a composite component"
+            )))
+        }
+    };
+
+    let component_width = rows.first().map_or(0, |r| r.len()) as u32;
+    let content_width = component_width.max(code.len() as u32);
+    let full_width = content_width + sides_margin;
+    let output_width = (width.max(0) as u32).max(full_width);
+    let multiple = output_width / full_width;
+    let left_padding = (output_width - content_width * multiple) / 2;
+
+    let component_height = rows.len() as u32 * row_height;
+    let linear_height = (height.max(0) as u32)
+        .saturating_sub((component_height + SEPARATOR_HEIGHT) * multiple)
+        .max(MIN_LINEAR_HEIGHT * multiple);
+
+    // the component, the separator and the linear symbol, each module `multiple` pixels wide
+    let draw = |output_width: u32, multiple: u32, left_padding: u32, linear_height: u32| {
+        let component_height = component_height * multiple;
+        let separator_height = SEPARATOR_HEIGHT * multiple;
+        let output_height = component_height + separator_height + linear_height;
+
+        let mut output = BitMatrix::new(output_width, output_height)?;
+
+        let component_left = left_padding + (content_width - component_width) / 2 * multiple;
+        for (y, row) in rows.iter().enumerate() {
+            for (x, module) in row.iter().enumerate() {
+                if *module {
+                    output.setRegion(
+                        component_left + x as u32 * multiple,
+                        y as u32 * row_height * multiple,
+                        multiple,
+                        row_height * multiple,
+                    )?;
+                }
+            }
+        }
+
+        let linear_left = left_padding + (content_width - code.len() as u32) / 2 * multiple;
+        let linear_top = component_height + separator_height;
+        for (x, bar) in code.iter().enumerate() {
+            if *bar {
+                output.setRegion(
+                    linear_left + x as u32 * multiple,
+                    linear_top,
+                    multiple,
+                    linear_height,
+                )?;
+            }
+        }
+
+        Ok::<_, Exceptions>(output)
+    };
+
+    Ok(EncodedSymbol::new(
+        *format,
+        draw(content_width, 1, 0, 1)?,
+        (
+            draw(output_width, multiple, left_padding, linear_height)?,
+            Placement::square(multiple, left_padding, 0),
+        ),
+    ))
+}
+
+/** The rows, top first, of the widest CC-C component carrying `component` above `code`. */
+fn cc_c_rows(code: &[bool], component: &str) -> Result<Vec<Vec<bool>>> {
+    // the widest CC-C that does not exceed the linear symbol, 17 modules per data column
+    // plus 69 for the start, stop and row indicator patterns
+    let columns = (code.len().saturating_sub(69) / 17).clamp(1, 30) as u32;
+    let codewords = cc_c_codewords(component)?;
+    let ec_level = pdf_417_error_correction::getRecommendedMinimumErrorCorrectionLevel(
+        codewords.len() as u32,
+    )?;
+
+    let mut pdf417 = PDF417::new();
+    pdf417.setDimensions(columns, columns, 90, 3);
+    pdf417.generateBarcodeLogicFromCodewords(&codewords, ec_level)?;
+    let rows = pdf417
+        .getBarcodeMatrix()
+        .as_ref()
+        .ok_or(Exceptions::ILLEGAL_STATE)?
+        .getMatrix();
+    // the barcode matrix is stored bottom row first
+    Ok(rows
+        .iter()
+        .rev()
+        .map(|row| row.iter().map(|module| *module == 1).collect())
+        .collect())
+}
@@ -0,0 +1,418 @@
+use crate::{
+    common::{BitArray, Result},
+    oned::rss::expanded::decoders::{field_parser, DecodedObject, GeneralAppIdDecoder},
+    Exceptions,
+};
+
+/**
+ * The first two digits of the AIs with a predefined length, which are never followed by a FNC1
+ * separator (GS1 General Specifications, figure 7.8.5-2).
+ */
+const PREDEFINED_LENGTH_PREFIXES: [&str; 23] = [
+    "00", "01", "02", "03", "04", "11", "12", "13", "14", "15", "16", "17", "18", "19", "20", "23",
+    "31", "32", "33", "34", "35", "36", "41",
+];
+
+/** Value of a 16 bit date field in encodation method "10" which marks the date AI as absent. */
+const NO_DATE: u32 = 38400;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Digit(u8),
+    Fnc1,
+    Char(char),
+}
+
+impl Token {
+    fn is_numeric(&self) -> bool {
+        matches!(self, Token::Digit(_) | Token::Fnc1)
+    }
+
+    fn numeric_value(&self) -> u32 {
+        match self {
+            Token::Digit(d) => *d as u32,
+            _ => 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    Numeric,
+    Alpha,
+    IsoIec646,
+}
+
+/**
+ * Splits a GS1 element string written as `(AI)value(AI)value...` into its AIs and values.
+ */
+pub fn parse_element_string(data: &str) -> Result<Vec<(String, String)>> {
+    let mut fields = Vec::new();
+    let mut rest = data;
+    while !rest.is_empty() {
+        let Some(body) = rest.strip_prefix('(') else {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "expected an AI in parentheses at \"{rest}\""
+            )));
+        };
+        let close = body
+            .find(')')
+            .ok_or(Exceptions::illegal_argument_with("unterminated AI"))?;
+        let ai = &body[..close];
+        if !(2..=4).contains(&ai.len()) || !ai.chars().all(|c| c.is_ascii_digit()) {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "invalid AI \"{ai}\""
+            )));
+        }
+        let value_and_rest = &body[close + 1..];
+        let value_end = value_and_rest.find('(').unwrap_or(value_and_rest.len());
+        let value = &value_and_rest[..value_end];
+        if value.is_empty() {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "AI ({ai}) has no data"
+            )));
+        }
+        fields.push((ai.to_owned(), value.to_owned()));
+        rest = &value_and_rest[value_end..];
+    }
+
+    if fields.is_empty() {
+        return Err(Exceptions::illegal_argument_with("Found empty contents"));
+    }
+
+    Ok(fields)
+}
+
+/**
+ * Encodes a GS1 element string, written as `(AI)value(AI)value...`, into the data bit stream of a
+ * 2D composite component using the general-purpose encodation method "0".
+ *
+ * The bits are padded to a whole number of bytes with the pad pattern of ISO/IEC 24723.
+ */
+pub fn encode_data(data: &str) -> Result<Vec<u8>> {
+    let bits = encode_data_bits(data, |len| Some(len.next_multiple_of(8)))?;
+
+    let mut bytes = vec![0; bits.get_size() / 8];
+    let len = bytes.len();
+    bits.toBytes(0, &mut bytes, 0, len);
+    Ok(bytes)
+}
+
+/**
+ * Encodes a GS1 element string into the data bit stream of a 2D composite component, padded with
+ * the pad pattern of ISO/IEC 24723 to the length `padded_len` gives for the unpadded length.
+ *
+ * @throws IllegalArgumentException if `padded_len` gives no length, because the data does not
+ *  fit
+ */
+pub fn encode_data_bits(
+    data: &str,
+    padded_len: impl Fn(usize) -> Option<usize>,
+) -> Result<BitArray> {
+    let fields = parse_element_string(data)?;
+
+    let mut tokens = Vec::new();
+    for (i, (ai, value)) in fields.iter().enumerate() {
+        for c in ai.chars().chain(value.chars()) {
+            tokens.push(match c.to_digit(10) {
+                Some(d) => Token::Digit(d as u8),
+                None => Token::Char(c),
+            });
+        }
+        let predefined = PREDEFINED_LENGTH_PREFIXES.contains(&&ai[..2]);
+        if !predefined && i + 1 < fields.len() {
+            tokens.push(Token::Fnc1);
+        }
+    }
+
+    let mut bits = BitArray::new();
+    // encodation method "0": general purpose
+    bits.appendBit(false);
+    let mode = encode_general_purpose(&tokens, &mut bits)?;
+
+    let len = bits.get_size();
+    let padded_len = padded_len(len)
+        .filter(|padded_len| *padded_len >= len)
+        .ok_or_else(|| {
+            Exceptions::illegal_argument_with(format!(
+                "{len} bits of composite data do not fit the component"
+            ))
+        })?;
+
+    // pad, latching out of numeric mode first
+    let latch = if mode == Mode::Numeric { 4 } else { 0 };
+    for i in 0..padded_len - len {
+        bits.appendBit(i >= latch && (i - latch) % 5 == 2);
+    }
+
+    Ok(bits)
+}
+
+/**
+ * Decodes the data bit stream of a 2D composite component into a GS1 element string with the
+ * AIs in parentheses.
+ *
+ * Encodation methods "0" (general purpose) and "10" (AI 11/17 and AI 10) are supported.
+ */
+pub fn decode_data(bytes: &[u8]) -> Result<String> {
+    let mut bits = BitArray::with_capacity(bytes.len() * 8);
+    for byte in bytes {
+        bits.appendBits(*byte as _, 8)?;
+    }
+    decode_data_bits(&bits)
+}
+
+/**
+ * Decodes the data bit stream of a 2D composite component, of any number of bits, into a GS1
+ * element string with the AIs in parentheses.
+ */
+pub fn decode_data_bits(bits: &BitArray) -> Result<String> {
+    if bits.get_size() == 0 {
+        return Err(Exceptions::FORMAT);
+    }
+
+    if !bits.get(0) {
+        return GeneralAppIdDecoder::new(bits).decodeAllCodes(String::new(), 1);
+    }
+    if bits.get(1) {
+        return Err(Exceptions::unsupported_operation_with(
+            "composite encodation method 11 is not supported",
+        ));
+    }
+
+    // encodation method "10": an optional date with AI 11 or 17, followed by the lot number
+    if bits.get_size() < 19 {
+        return Err(Exceptions::FORMAT);
+    }
+    let date = GeneralAppIdDecoder::extractNumericValueFromBitArrayWithInformation(bits, 2, 16);
+    let mut buffer = String::new();
+    if date != NO_DATE {
+        if date > NO_DATE {
+            return Err(Exceptions::FORMAT);
+        }
+        let ai = if bits.get(18) { "17" } else { "11" };
+        let year = date / 384;
+        let month = date % 384 / 32 + 1;
+        let day = date % 32;
+        buffer.push_str(&format!("({ai}){year:02}{month:02}{day:02}"));
+    }
+
+    // the lot number has no AI of its own, a leading FNC1 means it is absent
+    let mut decoder = GeneralAppIdDecoder::new(bits);
+    let lot = decoder.decodeGeneralPurposeField(19, "")?;
+    if !lot.getNewString().is_empty() {
+        buffer.push_str("(10)");
+        buffer.push_str(lot.getNewString());
+    }
+
+    let mut position = lot.getNewPosition();
+    let mut remaining = if lot.isRemaining() {
+        lot.getRemainingValue().to_string()
+    } else {
+        String::new()
+    };
+    loop {
+        let info = decoder.decodeGeneralPurposeField(position, &remaining)?;
+        buffer.push_str(&field_parser::parseFieldsInGeneralPurpose(
+            info.getNewString(),
+        )?);
+        remaining = if info.isRemaining() {
+            info.getRemainingValue().to_string()
+        } else {
+            String::new()
+        };
+        if position == info.getNewPosition() {
+            break;
+        }
+        position = info.getNewPosition();
+    }
+
+    Ok(buffer)
+}
+
+fn encode_general_purpose(tokens: &[Token], bits: &mut BitArray) -> Result<Mode> {
+    let mut mode = Mode::Numeric;
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        match mode {
+            Mode::Numeric => {
+                let next = tokens.get(i + 1);
+                match next {
+                    Some(next)
+                        if token.is_numeric()
+                            && next.is_numeric()
+                            && !(token == Token::Fnc1 && *next == Token::Fnc1) =>
+                    {
+                        let value = 8 + token.numeric_value() * 11 + next.numeric_value();
+                        bits.appendBits(value as _, 7)?;
+                        i += 2;
+                    }
+                    None if matches!(token, Token::Digit(_)) => {
+                        // the final digit is paired with a FNC1, which ends the data
+                        let value = 8 + token.numeric_value() * 11 + 10;
+                        bits.appendBits(value as _, 7)?;
+                        i += 1;
+                    }
+                    _ => {
+                        bits.appendBits(0b0000, 4)?;
+                        mode = Mode::Alpha;
+                    }
+                }
+            }
+            Mode::Alpha | Mode::IsoIec646 => {
+                if numeric_run(&tokens[i..]) >= 4 {
+                    bits.appendBits(0b000, 3)?;
+                    mode = Mode::Numeric;
+                    continue;
+                }
+                match token {
+                    Token::Digit(d) => bits.appendBits((5 + d) as _, 5)?,
+                    Token::Fnc1 => bits.appendBits(15, 5)?,
+                    Token::Char(c) => {
+                        if mode == Mode::Alpha {
+                            match alpha_value(c) {
+                                Some(value) => bits.appendBits(value as _, 6)?,
+                                None => {
+                                    bits.appendBits(0b00100, 5)?;
+                                    mode = Mode::IsoIec646;
+                                    continue;
+                                }
+                            }
+                        } else if alpha_value(c).is_some()
+                            && tokens[i..].iter().take(8).all(|t| match t {
+                                Token::Char(c) => alpha_value(*c).is_some(),
+                                _ => true,
+                            })
+                        {
+                            bits.appendBits(0b00100, 5)?;
+                            mode = Mode::Alpha;
+                            continue;
+                        } else {
+                            let (value, len) = iso_iec_646_value(c).ok_or(
+                                Exceptions::illegal_argument_with(format!(
+                                    "character '{c}' can not be encoded in a composite component"
+                                )),
+                            )?;
+                            bits.appendBits(value as _, len)?;
+                        }
+                    }
+                }
+                i += 1;
+            }
+        }
+    }
+    Ok(mode)
+}
+
+/** Number of consecutive digits and FNC1s at the start of `tokens`. */
+fn numeric_run(tokens: &[Token]) -> usize {
+    tokens.iter().take_while(|t| t.is_numeric()).count()
+}
+
+/** The 6 bit value of a non-digit character in alphanumeric mode. */
+fn alpha_value(c: char) -> Option<u32> {
+    match c {
+        'A'..='Z' => Some(c as u32 - 33),
+        '*' => Some(58),
+        ',' => Some(59),
+        '-' => Some(60),
+        '.' => Some(61),
+        '/' => Some(62),
+        _ => None,
+    }
+}
+
+/** The value and bit length of a non-digit character in ISO/IEC 646 mode. */
+fn iso_iec_646_value(c: char) -> Option<(u32, usize)> {
+    let value = match c {
+        'A'..='Z' => return Some((c as u32 - 1, 7)),
+        'a'..='z' => return Some((c as u32 - 7, 7)),
+        '!' => 232,
+        '"' => 233,
+        '%' => 234,
+        '&' => 235,
+        '\'' => 236,
+        '(' => 237,
+        ')' => 238,
+        '*' => 239,
+        '+' => 240,
+        ',' => 241,
+        '-' => 242,
+        '.' => 243,
+        '/' => 244,
+        ':' => 245,
+        ';' => 246,
+        '<' => 247,
+        '=' => 248,
+        '>' => 249,
+        '?' => 250,
+        '_' => 251,
+        ' ' => 252,
+        _ => return None,
+    };
+    Some((value, 8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(data: &str) {
+        let bytes = encode_data(data).expect("encode");
+        assert_eq!(decode_data(&bytes).expect("decode"), data);
+    }
+
+    #[test]
+    fn numeric_data() {
+        round_trip("(17)010200(10)1234");
+        round_trip("(11)991231(21)12345");
+    }
+
+    #[test]
+    fn alphanumeric_data() {
+        round_trip("(10)ABC123(21)XYZ");
+        round_trip("(17)050101(10)LOT-42/A");
+    }
+
+    #[test]
+    fn iso_iec_646_data() {
+        round_trip("(21)abc(240)Hello world");
+        round_trip("(10)a1b2c3d4e5f6(400)order!1");
+    }
+
+    #[test]
+    fn variable_fields_are_separated() {
+        // a variable length AI followed by another one needs a FNC1 separator, the round trip
+        // would merge the fields otherwise
+        round_trip("(10)12(21)34");
+        round_trip("(21)A(10)B");
+    }
+
+    #[test]
+    fn date_and_lot_method() {
+        // "10", date 05-07-14 as 5 * 384 + 6 * 32 + 14 = 2126, AI 17, then lot "12" + FNC1
+        let mut bits = BitArray::new();
+        bits.appendBits(0b10, 2).unwrap();
+        bits.appendBits(2126, 16).unwrap();
+        bits.appendBit(true);
+        bits.appendBits(8 + 11 + 2, 7).unwrap(); // "12"
+        bits.appendBits(0b0000, 4).unwrap(); // pad
+        while !bits.get_size().is_multiple_of(8) {
+            bits.appendBit(false);
+        }
+        let mut bytes = vec![0; bits.get_size() / 8];
+        let len = bytes.len();
+        bits.toBytes(0, &mut bytes, 0, len);
+        assert_eq!(decode_data(&bytes).expect("decode"), "(17)050714(10)12");
+    }
+
+    #[test]
+    fn illegal_data() {
+        assert!(encode_data("").is_err());
+        assert!(encode_data("17010200").is_err());
+        assert!(encode_data("(1)0").is_err());
+        assert!(encode_data("(10)").is_err());
+        assert!(encode_data("(10)\u{e9}").is_err());
+    }
+}
@@ -0,0 +1,840 @@
+use std::collections::HashMap;
+
+use crate::{
+    common::{BitArray, Result},
+    pdf417::{
+        decoder::{decoded_bit_stream_parser, ec::error_correction, pdf_417_codeword_decoder},
+        encoder::CODEWORD_TABLE,
+        pdf_417_common,
+    },
+    point, Binarizer, BinaryBitmap, Exceptions, Point,
+};
+
+use super::{cc_c_codewords, decode_data_bits, encode_data_bits};
+
+/**
+ * A symbol size of the MicroPDF417 based composite components: CC-A, or the MicroPDF417 symbol
+ * of a CC-B. The row address patterns and the cluster are those of the first row, each row below
+ * continues with the next pattern and cluster.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Variant {
+    cc_a: bool,
+    columns: usize,
+    rows: usize,
+    ec: usize,
+    left: usize,
+    centre: usize,
+    right: usize,
+    cluster: usize,
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn variant(
+    cc_a: bool,
+    columns: usize,
+    rows: usize,
+    ec: usize,
+    left: usize,
+    centre: usize,
+    right: usize,
+    cluster: usize,
+) -> Variant {
+    Variant {
+        cc_a,
+        columns,
+        rows,
+        ec,
+        left,
+        centre,
+        right,
+        cluster,
+    }
+}
+
+/** The CC-A sizes of ISO/IEC 24723. */
+const CC_A_VARIANTS: [Variant; 17] = [
+    variant(true, 2, 5, 4, 39, 0, 19, 0),
+    variant(true, 2, 6, 4, 1, 0, 33, 0),
+    variant(true, 2, 7, 5, 32, 0, 12, 0),
+    variant(true, 2, 8, 5, 8, 0, 40, 0),
+    variant(true, 2, 9, 6, 14, 0, 46, 0),
+    variant(true, 2, 10, 6, 43, 0, 23, 0),
+    variant(true, 2, 12, 7, 20, 0, 52, 0),
+    variant(true, 3, 4, 4, 11, 43, 23, 0),
+    variant(true, 3, 5, 5, 1, 33, 13, 0),
+    variant(true, 3, 6, 6, 5, 37, 17, 0),
+    variant(true, 3, 7, 7, 15, 47, 27, 0),
+    variant(true, 3, 8, 7, 21, 1, 1, 0),
+    variant(true, 4, 3, 4, 40, 20, 20, 0),
+    variant(true, 4, 4, 5, 43, 23, 23, 0),
+    variant(true, 4, 5, 6, 46, 26, 26, 0),
+    variant(true, 4, 6, 7, 34, 14, 14, 0),
+    variant(true, 4, 7, 8, 29, 9, 9, 0),
+];
+
+/** The MicroPDF417 sizes of two to four columns, which CC-B uses (ISO/IEC 24728). */
+const CC_B_VARIANTS: [Variant; 28] = [
+    variant(false, 2, 8, 8, 1, 0, 1, 0),
+    variant(false, 2, 11, 9, 1, 0, 9, 0),
+    variant(false, 2, 14, 9, 8, 0, 8, 3),
+    variant(false, 2, 17, 10, 36, 0, 36, 6),
+    variant(false, 2, 20, 11, 19, 0, 19, 0),
+    variant(false, 2, 23, 13, 9, 0, 17, 6),
+    variant(false, 2, 26, 15, 27, 0, 35, 6),
+    variant(false, 3, 6, 12, 1, 1, 1, 0),
+    variant(false, 3, 8, 14, 7, 7, 7, 0),
+    variant(false, 3, 10, 16, 15, 15, 15, 6),
+    variant(false, 3, 12, 18, 25, 25, 25, 0),
+    variant(false, 3, 15, 21, 37, 37, 37, 0),
+    variant(false, 3, 20, 26, 1, 17, 33, 0),
+    variant(false, 3, 26, 32, 1, 9, 17, 0),
+    variant(false, 3, 32, 38, 21, 29, 37, 6),
+    variant(false, 3, 38, 44, 15, 31, 47, 6),
+    variant(false, 3, 44, 50, 1, 25, 49, 0),
+    variant(false, 4, 4, 8, 47, 19, 43, 3),
+    variant(false, 4, 6, 12, 1, 1, 1, 0),
+    variant(false, 4, 8, 14, 7, 7, 7, 0),
+    variant(false, 4, 10, 16, 15, 15, 15, 6),
+    variant(false, 4, 12, 18, 25, 25, 25, 0),
+    variant(false, 4, 15, 21, 37, 37, 37, 0),
+    variant(false, 4, 20, 26, 1, 17, 33, 0),
+    variant(false, 4, 26, 32, 1, 9, 17, 0),
+    variant(false, 4, 32, 38, 21, 29, 37, 6),
+    variant(false, 4, 38, 44, 15, 31, 47, 6),
+    variant(false, 4, 44, 50, 1, 25, 49, 0),
+];
+
+/** Element widths of the 52 left and right row address patterns, starting with a bar. */
+const RAP_SIDE: [[u32; 6]; 52] = [
+    [2, 2, 1, 3, 1, 1],
+    [3, 1, 1, 3, 1, 1],
+    [3, 1, 2, 2, 1, 1],
+    [2, 2, 2, 2, 1, 1],
+    [2, 1, 3, 2, 1, 1],
+    [2, 1, 4, 1, 1, 1],
+    [2, 2, 3, 1, 1, 1],
+    [3, 1, 3, 1, 1, 1],
+    [3, 2, 2, 1, 1, 1],
+    [4, 1, 2, 1, 1, 1],
+    [4, 2, 1, 1, 1, 1],
+    [3, 3, 1, 1, 1, 1],
+    [2, 4, 1, 1, 1, 1],
+    [2, 3, 2, 1, 1, 1],
+    [2, 3, 1, 2, 1, 1],
+    [3, 2, 1, 2, 1, 1],
+    [4, 1, 1, 2, 1, 1],
+    [4, 1, 1, 1, 2, 1],
+    [4, 1, 1, 1, 1, 2],
+    [3, 2, 1, 1, 1, 2],
+    [3, 1, 2, 1, 1, 2],
+    [3, 1, 1, 2, 1, 2],
+    [3, 1, 1, 2, 2, 1],
+    [3, 1, 1, 1, 3, 1],
+    [3, 1, 1, 1, 2, 2],
+    [3, 1, 1, 1, 1, 3],
+    [2, 2, 1, 1, 1, 3],
+    [2, 2, 1, 1, 2, 2],
+    [2, 2, 1, 1, 3, 1],
+    [2, 2, 1, 2, 2, 1],
+    [2, 2, 2, 1, 2, 1],
+    [3, 1, 2, 1, 2, 1],
+    [3, 2, 1, 1, 2, 1],
+    [2, 3, 1, 1, 2, 1],
+    [2, 3, 1, 1, 1, 2],
+    [2, 2, 2, 1, 1, 2],
+    [2, 1, 3, 1, 1, 2],
+    [2, 1, 2, 2, 1, 2],
+    [2, 1, 2, 2, 2, 1],
+    [2, 1, 2, 1, 3, 1],
+    [2, 1, 2, 1, 2, 2],
+    [2, 1, 2, 1, 1, 3],
+    [2, 1, 1, 2, 1, 3],
+    [2, 1, 1, 1, 2, 3],
+    [2, 1, 1, 1, 3, 2],
+    [2, 1, 1, 1, 4, 1],
+    [2, 1, 1, 2, 3, 1],
+    [2, 1, 1, 2, 2, 2],
+    [2, 1, 1, 3, 1, 2],
+    [2, 1, 1, 3, 2, 1],
+    [2, 1, 1, 4, 1, 1],
+    [2, 1, 2, 3, 1, 1],
+];
+
+/** Element widths of the 52 centre row address patterns, starting with a bar. */
+const RAP_CENTRE: [[u32; 6]; 52] = [
+    [1, 1, 2, 2, 3, 1],
+    [1, 2, 1, 2, 3, 1],
+    [1, 2, 2, 1, 3, 1],
+    [1, 3, 1, 1, 3, 1],
+    [1, 3, 1, 2, 2, 1],
+    [1, 3, 2, 1, 2, 1],
+    [1, 4, 1, 1, 2, 1],
+    [1, 4, 1, 2, 1, 1],
+    [1, 4, 2, 1, 1, 1],
+    [1, 3, 3, 1, 1, 1],
+    [1, 3, 2, 2, 1, 1],
+    [1, 3, 1, 3, 1, 1],
+    [1, 2, 2, 3, 1, 1],
+    [1, 2, 3, 2, 1, 1],
+    [1, 2, 4, 1, 1, 1],
+    [1, 1, 5, 1, 1, 1],
+    [1, 1, 4, 2, 1, 1],
+    [1, 1, 4, 1, 2, 1],
+    [1, 2, 3, 1, 2, 1],
+    [1, 2, 3, 1, 1, 2],
+    [1, 2, 2, 2, 1, 2],
+    [1, 2, 2, 2, 2, 1],
+    [1, 2, 1, 3, 2, 1],
+    [1, 2, 1, 4, 1, 1],
+    [1, 1, 2, 4, 1, 1],
+    [1, 1, 3, 3, 1, 1],
+    [1, 1, 3, 2, 2, 1],
+    [1, 1, 3, 2, 1, 2],
+    [1, 1, 3, 1, 2, 2],
+    [1, 2, 2, 1, 2, 2],
+    [1, 3, 1, 1, 2, 2],
+    [1, 3, 1, 1, 1, 3],
+    [1, 2, 2, 1, 1, 3],
+    [1, 1, 3, 1, 1, 3],
+    [1, 1, 2, 2, 1, 3],
+    [1, 1, 2, 2, 2, 2],
+    [1, 1, 2, 3, 1, 2],
+    [1, 1, 2, 3, 2, 1],
+    [1, 1, 1, 4, 2, 1],
+    [1, 1, 1, 3, 3, 1],
+    [1, 1, 1, 3, 2, 2],
+    [1, 1, 1, 2, 3, 2],
+    [1, 1, 1, 2, 2, 3],
+    [1, 1, 1, 1, 3, 3],
+    [1, 1, 1, 1, 2, 4],
+    [1, 1, 1, 2, 1, 4],
+    [1, 1, 2, 1, 1, 4],
+    [1, 2, 1, 1, 1, 4],
+    [1, 2, 1, 1, 2, 3],
+    [1, 2, 1, 1, 3, 2],
+    [1, 1, 2, 1, 3, 2],
+    [1, 1, 2, 1, 4, 1],
+];
+
+/**
+ * The number of data bits carried by a group of up to 7 CC-A codewords, indexed by the number of
+ * codewords.
+ */
+const CC_A_GROUP_BITS: [usize; 8] = [0, 9, 19, 29, 39, 49, 59, 69];
+
+const PAD_CODEWORD: u32 = 900;
+
+/** Width of the left and right row address patterns and the stop bar, in modules. */
+const ROW_OVERHEAD_MODULES: usize = 10 + 10 + 1;
+
+impl Variant {
+    fn data_codewords(&self) -> usize {
+        self.columns * self.rows - self.ec
+    }
+
+    /** How many data bits a CC-A of this size carries. */
+    fn cc_a_capacity(&self) -> usize {
+        let codewords = self.data_codewords();
+        69 * (codewords / 7) + CC_A_GROUP_BITS[codewords % 7]
+    }
+
+    /** The data column the centre row address pattern precedes, if there is one. */
+    fn centre_column(&self) -> Option<usize> {
+        match self.columns {
+            3 => Some(1),
+            4 => Some(2),
+            _ => None,
+        }
+    }
+
+    fn modules(&self) -> usize {
+        ROW_OVERHEAD_MODULES
+            + 17 * self.columns
+            + if self.centre_column().is_some() {
+                10
+            } else {
+                0
+            }
+    }
+
+    fn cluster(&self, row: usize) -> usize {
+        (self.cluster / 3 + row) % 3 * 3
+    }
+}
+
+/** The row address pattern number, 1 to 52, of `row` when the first row has `start`. */
+fn rap(start: usize, row: usize) -> usize {
+    (start - 1 + row) % 52 + 1
+}
+
+/**
+ * The rows, top first, of a CC-A or CC-B component carrying the GS1 element string `data`, as
+ * modules. The smallest CC-A of `columns` columns which fits the data is used, or the smallest
+ * CC-B if none does.
+ */
+pub(super) fn encode_component(data: &str, columns: usize) -> Result<Vec<Vec<bool>>> {
+    let cc_a = || CC_A_VARIANTS.iter().filter(|v| v.columns == columns);
+    if let Ok(bits) = encode_data_bits(data, |len| {
+        cc_a().map(Variant::cc_a_capacity).find(|c| *c >= len)
+    }) {
+        let variant = cc_a()
+            .find(|v| v.cc_a_capacity() == bits.get_size())
+            .ok_or(Exceptions::ILLEGAL_STATE)?;
+        return Ok(encode_rows(variant, &to_base_928(&bits)));
+    }
+
+    let mut codewords = cc_c_codewords(data)?;
+    let variant = CC_B_VARIANTS
+        .iter()
+        .find(|v| v.columns == columns && v.data_codewords() >= codewords.len())
+        .ok_or_else(|| {
+            Exceptions::illegal_argument_with(format!(
+                "{data} does not fit a composite component of {columns} columns"
+            ))
+        })?;
+    codewords.resize(variant.data_codewords(), PAD_CODEWORD);
+    Ok(encode_rows(variant, &codewords))
+}
+
+/** Converts the data bits of a CC-A into codewords, 7 codewords for every 69 bits. */
+fn to_base_928(bits: &BitArray) -> Vec<u32> {
+    let mut codewords = Vec::new();
+    let mut offset = 0;
+    while offset < bits.get_size() {
+        let len = (bits.get_size() - offset).min(69);
+        let value = (offset..offset + len).fold(0u128, |v, i| (v << 1) | bits.get(i) as u128);
+        let count = CC_A_GROUP_BITS.iter().position(|b| *b == len).unwrap_or(7);
+        codewords.extend(
+            (0..count)
+                .rev()
+                .map(|i| (value / 928u128.pow(i as u32) % 928) as u32),
+        );
+        offset += len;
+    }
+    codewords
+}
+
+fn from_base_928(codewords: &[u32]) -> Option<BitArray> {
+    let mut bits = BitArray::new();
+    for group in codewords.chunks(7) {
+        let len = CC_A_GROUP_BITS[group.len()];
+        let value = group.iter().fold(0u128, |v, cw| v * 928 + *cw as u128);
+        if value >> len != 0 {
+            return None;
+        }
+        for i in (0..len).rev() {
+            bits.appendBit((value >> i) & 1 == 1);
+        }
+    }
+    Some(bits)
+}
+
+/** The `count` error correction codewords of `data`, as for PDF417 but of any number. */
+fn error_correction_codewords(data: &[u32], count: usize) -> Vec<u32> {
+    // the generator polynomial (x - 3)(x - 3^2)...(x - 3^count), highest power first
+    let mut generator = vec![1u32];
+    let mut root = 1;
+    for _ in 0..count {
+        root = root * 3 % 929;
+        let mut next = generator.clone();
+        next.push(0);
+        for (i, coefficient) in generator.iter().enumerate() {
+            next[i + 1] = (next[i + 1] + 929 - coefficient * root % 929) % 929;
+        }
+        generator = next;
+    }
+
+    let mut remainder = vec![0u32; count];
+    for codeword in data {
+        let factor = (codeword + remainder[0]) % 929;
+        for i in 0..count {
+            let next = remainder.get(i + 1).copied().unwrap_or(0);
+            remainder[i] = (next + 929 - factor * generator[i + 1] % 929) % 929;
+        }
+    }
+    remainder.iter().map(|r| (929 - r) % 929).collect()
+}
+
+fn encode_rows(variant: &Variant, data: &[u32]) -> Vec<Vec<bool>> {
+    let mut codewords = data.to_vec();
+    codewords.extend(error_correction_codewords(data, variant.ec));
+
+    let push_widths = |row: &mut Vec<bool>, widths: &[u32]| {
+        for (i, width) in widths.iter().enumerate() {
+            row.extend(std::iter::repeat_n(i % 2 == 0, *width as usize));
+        }
+    };
+
+    codewords
+        .chunks(variant.columns)
+        .enumerate()
+        .map(|(r, row_codewords)| {
+            let mut row = Vec::with_capacity(variant.modules());
+            push_widths(&mut row, &RAP_SIDE[rap(variant.left, r) - 1]);
+            for (c, codeword) in row_codewords.iter().enumerate() {
+                if variant.centre_column() == Some(c) {
+                    push_widths(&mut row, &RAP_CENTRE[rap(variant.centre, r) - 1]);
+                }
+                let pattern = CODEWORD_TABLE[variant.cluster(r) / 3][*codeword as usize];
+                row.extend((0..17).rev().map(|bit| (pattern >> bit) & 1 == 1));
+            }
+            push_widths(&mut row, &RAP_SIDE[rap(variant.right, r) - 1]);
+            // stop bar
+            row.push(true);
+            row
+        })
+        .collect()
+}
+
+/** One scanline through a row of a component. */
+struct RowRead {
+    columns: usize,
+    left: usize,
+    centre: Option<usize>,
+    right: usize,
+    cluster: usize,
+    codewords: Vec<u32>,
+    start: usize,
+    end: usize,
+    module_size: f32,
+}
+
+/** The scanlines read through one row of a component. */
+struct SymbolRow {
+    left: usize,
+    centre: Option<usize>,
+    right: usize,
+    cluster: usize,
+    votes: Vec<HashMap<u32, u32>>,
+    scanlines: usize,
+    top: usize,
+    bottom: usize,
+    start: usize,
+    end: usize,
+}
+
+impl SymbolRow {
+    fn new(read: &RowRead, y: usize) -> Self {
+        let mut row = Self {
+            left: read.left,
+            centre: read.centre,
+            right: read.right,
+            cluster: read.cluster,
+            votes: vec![HashMap::new(); read.columns],
+            scanlines: 0,
+            top: y,
+            bottom: y,
+            start: read.start,
+            end: read.end,
+        };
+        row.add(read, y);
+        row
+    }
+
+    fn add(&mut self, read: &RowRead, y: usize) {
+        for (votes, codeword) in self.votes.iter_mut().zip(&read.codewords) {
+            *votes.entry(*codeword).or_default() += 1;
+        }
+        self.scanlines += 1;
+        self.top = self.top.min(y);
+        self.start = self.start.min(read.start);
+        self.end = self.end.max(read.end);
+    }
+
+    fn codewords(&self) -> impl Iterator<Item = u32> + '_ {
+        self.votes.iter().map(|votes| {
+            votes
+                .iter()
+                .max_by_key(|(codeword, count)| (**count, std::cmp::Reverse(**codeword)))
+                .map_or(0, |(codeword, _)| *codeword)
+        })
+    }
+}
+
+/**
+ * Reads the CC-A or CC-B component printed above a linear symbol, scanning upwards from the row
+ * `bottom` between the columns `left` and `right`. Returns the GS1 element string of the
+ * component and its corners.
+ *
+ * Only components printed upright, with the linear symbol below them, are found.
+ */
+pub(super) fn read_component<B: Binarizer>(
+    image: &BinaryBitmap<B>,
+    left: usize,
+    right: usize,
+    bottom: usize,
+) -> Option<(String, Vec<Point>)> {
+    let right = right.min(image.get_width());
+    let mut rows: Vec<SymbolRow> = Vec::new();
+    let mut columns = 0;
+    let mut gap = 0;
+    let mut max_gap = 0;
+
+    for y in (0..bottom.min(image.get_height())).rev() {
+        let read = image
+            .get_black_row(y)
+            .ok()
+            .and_then(|row| read_row(&row, left, right));
+        let single = rows.len() == 1;
+        let accepted = match (read, rows.last_mut()) {
+            (Some(read), None) => {
+                columns = read.columns;
+                rows.push(SymbolRow::new(&read, y));
+                Some(read)
+            }
+            (Some(read), Some(last)) if read.columns == columns => {
+                if read.left == last.left && read.cluster == last.cluster {
+                    last.add(&read, y);
+                    Some(read)
+                } else if rap(read.left, 1) == last.left && (read.cluster + 3) % 9 == last.cluster {
+                    // the row above
+                    rows.push(SymbolRow::new(&read, y));
+                    Some(read)
+                } else if single && last.scanlines == 1 {
+                    // the first read was not part of a component
+                    *last = SymbolRow::new(&read, y);
+                    Some(read)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        match accepted {
+            Some(read) => {
+                gap = 0;
+                // rows are 2 modules high, allow for a row missed entirely
+                max_gap = (read.module_size * 3.0).ceil() as usize + 2;
+            }
+            None if !rows.is_empty() => {
+                gap += 1;
+                if gap > max_gap {
+                    break;
+                }
+            }
+            None => {}
+        }
+    }
+
+    rows.reverse();
+    let top = rows.first()?;
+    let candidates = CC_A_VARIANTS.iter().chain(&CC_B_VARIANTS).filter(|v| {
+        v.columns == columns
+            && v.rows == rows.len()
+            && rows.iter().enumerate().all(|(r, row)| {
+                row.left == rap(v.left, r)
+                    && row.right == rap(v.right, r)
+                    && row.centre == v.centre_column().map(|_| rap(v.centre, r))
+                    && row.cluster == v.cluster(r)
+            })
+    });
+
+    let codewords = rows
+        .iter()
+        .flat_map(SymbolRow::codewords)
+        .collect::<Vec<_>>();
+    let text = candidates
+        .filter_map(|variant| decode_codewords(variant, codewords.clone()))
+        .next()?;
+
+    let bottom_row = rows.last()?;
+    let start = rows.iter().map(|r| r.start).min()? as f32;
+    let end = rows.iter().map(|r| r.end).max()? as f32;
+    let points = vec![
+        point(start, top.top as f32),
+        point(end, top.top as f32),
+        point(start, bottom_row.bottom as f32),
+        point(end, bottom_row.bottom as f32),
+    ];
+    Some((text, points))
+}
+
+fn decode_codewords(variant: &Variant, mut codewords: Vec<u32>) -> Option<String> {
+    error_correction::decode(&mut codewords, variant.ec as u32, &mut []).ok()?;
+    codewords.truncate(variant.data_codewords());
+
+    if variant.cc_a {
+        return decode_data_bits(&from_base_928(&codewords)?).ok();
+    }
+
+    // a CC-B carries the same linkage flag and byte compacted data as a CC-C, which the PDF417
+    // decoder reads after the symbol length descriptor
+    let mut with_length = vec![codewords.len() as u32 + 1];
+    with_length.extend(codewords);
+    decoded_bit_stream_parser::decode(&with_length, "")
+        .ok()
+        .map(|result| result.getText().to_owned())
+}
+
+/** Reads a row of a component from the first place between `from` and `to` it is found. */
+fn read_row(row: &BitArray, from: usize, to: usize) -> Option<RowRead> {
+    // element start positions and widths, starting with a bar
+    let mut elements = Vec::new();
+    let mut x = row.getNextSet(from);
+    let mut bar = true;
+    while x < to {
+        let end = if bar {
+            row.getNextUnset(x)
+        } else {
+            row.getNextSet(x)
+        }
+        .min(to);
+        elements.push((x, (end - x) as u32));
+        x = end;
+        bar = !bar;
+    }
+
+    (0..elements.len())
+        .step_by(2)
+        .find_map(|i| (2..=4).find_map(|columns| read_row_at(&elements[i..], columns)))
+}
+
+fn read_row_at(elements: &[(usize, u32)], columns: usize) -> Option<RowRead> {
+    let shape = variant(false, columns, 0, 0, 0, 0, 0, 0);
+    let count = 6
+        + 8 * columns
+        + if shape.centre_column().is_some() {
+            6
+        } else {
+            0
+        }
+        + 6
+        + 1;
+    let elements = elements.get(..count)?;
+    let widths = elements.iter().map(|(_, w)| *w).collect::<Vec<_>>();
+    let module_size = widths.iter().sum::<u32>() as f32 / shape.modules() as f32;
+    let fits = |widths: &[u32], modules: f32| {
+        (widths.iter().sum::<u32>() as f32 / module_size - modules).abs() < 1.5
+    };
+
+    let mut offset = 0;
+    let mut next = |len: usize| {
+        let group = &widths[offset..offset + len];
+        offset += len;
+        group
+    };
+
+    let left_widths = next(6);
+    let left = fits(left_widths, 10.0).then(|| decode_rap(left_widths, &RAP_SIDE))??;
+    let mut centre = None;
+    let mut codewords = Vec::with_capacity(columns);
+    let mut cluster = None;
+    for c in 0..columns {
+        if shape.centre_column() == Some(c) {
+            let centre_widths = next(6);
+            centre =
+                Some(fits(centre_widths, 10.0).then(|| decode_rap(centre_widths, &RAP_CENTRE))??);
+        }
+        let codeword_widths = next(8);
+        if !fits(codeword_widths, 17.0) {
+            return None;
+        }
+        let symbol = pdf_417_codeword_decoder::getDecodedValue(codeword_widths);
+        let codeword = u32::try_from(pdf_417_common::getCodeword(symbol)).ok()?;
+        let symbol_cluster = symbol_cluster(symbol);
+        if *cluster.get_or_insert(symbol_cluster) != symbol_cluster {
+            return None;
+        }
+        codewords.push(codeword);
+    }
+    let right_widths = next(6);
+    let right = fits(right_widths, 10.0).then(|| decode_rap(right_widths, &RAP_SIDE))??;
+    let stop = next(1);
+    if !fits(stop, 1.0) {
+        return None;
+    }
+
+    let (end_start, end_width) = elements.last()?;
+    Some(RowRead {
+        columns,
+        left,
+        centre,
+        right,
+        cluster: cluster?,
+        codewords,
+        start: elements.first()?.0,
+        end: end_start + *end_width as usize,
+        module_size,
+    })
+}
+
+/** The number, 1 to 52, of the row address pattern closest to `widths`. */
+fn decode_rap(widths: &[u32], table: &[[u32; 6]; 52]) -> Option<usize> {
+    let total = widths.iter().sum::<u32>() as f32;
+    let (index, variance) = table
+        .iter()
+        .map(|pattern| {
+            pattern
+                .iter()
+                .zip(widths)
+                .map(|(p, w)| (*w as f32 * 10.0 / total - *p as f32).abs())
+                .sum::<f32>()
+        })
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))?;
+    (variance < 1.5).then_some(index + 1)
+}
+
+/** The cluster, 0, 3 or 6, of the 17 module pattern of a codeword. */
+fn symbol_cluster(symbol: u32) -> usize {
+    let mut widths = [0i32; 8];
+    let mut element = 0;
+    for bit in (0..17).rev() {
+        let bar = (symbol >> bit) & 1 == 1;
+        if bar != (element % 2 == 0) {
+            element += 1;
+        }
+        if let Some(width) = widths.get_mut(element) {
+            *width += 1;
+        }
+    }
+    ((widths[0] - widths[2] + widths[4] - widths[6] + 9) % 9) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variants_are_distinct() {
+        let variants = CC_A_VARIANTS
+            .iter()
+            .chain(&CC_B_VARIANTS)
+            .collect::<Vec<_>>();
+        for (i, a) in variants.iter().enumerate() {
+            for b in &variants[i + 1..] {
+                assert!(
+                    (a.columns, a.rows, a.left, a.centre, a.right, a.cluster)
+                        != (b.columns, b.rows, b.left, b.centre, b.right, b.cluster),
+                    "{a:?} {b:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn variant_sizes() {
+        for variant in CC_A_VARIANTS.iter().chain(&CC_B_VARIANTS) {
+            assert!(variant.data_codewords() > 0, "{variant:?}");
+            assert!([0, 3, 6].contains(&variant.cluster), "{variant:?}");
+            for start in [variant.left, variant.right] {
+                assert!((1..=52).contains(&start), "{variant:?}");
+            }
+            // only symbols of three and four columns have a centre pattern
+            assert_eq!(
+                variant.centre != 0,
+                variant.centre_column().is_some(),
+                "{variant:?}"
+            );
+        }
+        // within a number of columns, each size holds more than the one before
+        for variants in [&CC_A_VARIANTS[..], &CC_B_VARIANTS[..]] {
+            for pair in variants.windows(2) {
+                if pair[0].columns == pair[1].columns {
+                    assert!(pair[0].rows < pair[1].rows, "{pair:?}");
+                    assert!(
+                        pair[0].data_codewords() < pair[1].data_codewords(),
+                        "{pair:?}"
+                    );
+                }
+            }
+        }
+        assert_eq!(CC_A_VARIANTS[0].cc_a_capacity(), 59);
+        assert_eq!(CC_A_VARIANTS[16].cc_a_capacity(), 197);
+    }
+
+    #[test]
+    fn row_address_patterns() {
+        for table in [&RAP_SIDE, &RAP_CENTRE] {
+            for (i, pattern) in table.iter().enumerate() {
+                assert_eq!(pattern.iter().sum::<u32>(), 10, "{pattern:?}");
+                assert_eq!(decode_rap(pattern, table), Some(i + 1), "{pattern:?}");
+                // as printed three pixels a module
+                let wide = pattern.map(|width| width * 3);
+                assert_eq!(decode_rap(&wide, table), Some(i + 1), "{pattern:?}");
+                for other in &table[i + 1..] {
+                    assert_ne!(pattern, other);
+                }
+            }
+        }
+        // the left and right patterns start with a wide bar, the centre ones with a narrow one
+        assert!(RAP_SIDE.iter().all(|pattern| pattern[0] > 1));
+        assert!(RAP_CENTRE.iter().all(|pattern| pattern[0] == 1));
+
+        // the row below the 52nd pattern has the first one
+        assert_eq!(rap(1, 0), 1);
+        assert_eq!(rap(50, 2), 52);
+        assert_eq!(rap(50, 3), 1);
+    }
+
+    #[test]
+    fn clusters() {
+        for (cluster, patterns) in CODEWORD_TABLE.iter().enumerate() {
+            for pattern in patterns {
+                assert_eq!(symbol_cluster(*pattern), 3 * cluster, "{pattern:b}");
+            }
+        }
+        // each row continues with the next cluster
+        for variant in CC_A_VARIANTS.iter().chain(&CC_B_VARIANTS) {
+            for row in 0..variant.rows {
+                assert_eq!(variant.cluster(row), (variant.cluster + 3 * row) % 9);
+            }
+        }
+    }
+
+    #[test]
+    fn error_correction() {
+        let data = [920, 901, 1, 2, 3, 900, 900, 900];
+        let mut codewords = data.to_vec();
+        codewords.extend(error_correction_codewords(&data, 8));
+        let expected = codewords.clone();
+        codewords[1] = 17;
+        codewords[6] = 123;
+        codewords[12] = 0;
+        assert_eq!(
+            error_correction::decode(&mut codewords, 8, &mut []).ok(),
+            Some(3)
+        );
+        assert_eq!(codewords, expected);
+    }
+
+    #[test]
+    fn base_928() {
+        for len in [59, 78, 88, 167, 197] {
+            let mut bits = BitArray::new();
+            for i in 0..len {
+                bits.appendBit(i % 3 == 0 || i % 7 == 0);
+            }
+            let codewords = to_base_928(&bits);
+            assert!(codewords.iter().all(|cw| *cw < 928));
+            assert_eq!(from_base_928(&codewords), Some(bits));
+        }
+    }
+
+    #[test]
+    fn rows_read_back() {
+        for (data, columns) in [
+            ("(10)ABC", 2),
+            ("(17)261231(10)LOT-42/a", 3),
+            ("(21)12345678901234567890(10)ABCDEFGHIJKLMNOP", 4),
+            (
+                "(91)abcdefghijklmnopqrstuvwxyz0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+                4,
+            ),
+        ] {
+            let rows = encode_component(data, columns).expect("encode");
+            for row in &rows {
+                let mut bits = BitArray::with_size(row.len() + 10);
+                for (x, module) in row.iter().enumerate() {
+                    if *module {
+                        bits.set(x + 5);
+                    }
+                }
+                let read = read_row(&bits, 0, bits.get_size()).expect("read");
+                assert_eq!(read.columns, columns);
+            }
+        }
+    }
+}
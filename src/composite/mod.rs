@@ -0,0 +1,14 @@
+//! GS1 Composite symbols: a linear GS1 symbol with a 2D composite component printed above it.
+//!
+//! The composite component carries additional AI data, such as batch numbers and expiry dates,
+//! that does not fit in the linear symbol. GS1-128 symbols carry a CC-C component, a PDF417 symbol
+//! flagged with a linkage codeword. EAN/UPC and GS1 DataBar symbols carry the MicroPDF417 based
+//! CC-A, or CC-B when the data does not fit a CC-A.
+
+mod encodation;
+pub use encodation::*;
+mod composite_reader;
+pub use composite_reader::*;
+mod composite_writer;
+pub use composite_writer::*;
+mod micro_pdf417;
@@ -16,8 +16,9 @@
 
 use crate::{
     common::{BitMatrix, DecoderRXingResult, DetectorRXingResult, Result},
-    point, BarcodeFormat, Binarizer, DecodeHints, Exceptions, ImmutableReader, Point, RXingResult,
-    RXingResultMetadataType, RXingResultMetadataValue, Reader,
+    decode_region::decode_in_region,
+    point, BarcodeFormat, Binarizer, DecodeHints, EstimatedModuleSize, Exceptions, ImmutableReader,
+    Point, RXingResult, RXingResultMetadataType, RXingResultMetadataValue, Reader,
 };
 
 use super::{
@@ -34,7 +35,7 @@ static DECODER: Lazy<Decoder> = Lazy::new(Decoder::new);
  *
  * @author bbrown@google.com (Brian Brown)
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct DataMatrixReader;
 
 // private static final Point[] NO_POINTS = new Point[0];
@@ -70,7 +71,9 @@ impl Reader for DataMatrixReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<crate::RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -80,7 +83,9 @@ impl ImmutableReader for DataMatrixReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -165,19 +170,34 @@ impl DataMatrixReader {
         let try_harder = hints.TryHarder.unwrap_or(false);
         let decoderRXingResult;
         let mut points = Vec::new();
+        let mut moduleSize = None;
         if matches!(hints.PureBarcode, Some(true)) {
             let bits = self.extractPureBits(image.get_black_matrix())?;
-            decoderRXingResult = DECODER.decode(&bits)?;
+            decoderRXingResult = Self::decode_candidate(hints, &bits, &[])?;
             points.clear();
+            moduleSize =
+                EstimatedModuleSize::from_pure_symbol(image.get_black_matrix(), bits.getWidth());
         } else {
             //Result<DatamatrixDetectorResult, Exceptions>
             decoderRXingResult = if let Ok(fnd) = || -> Result<DecoderRXingResult> {
-                let detectorRXingResult =
-                    zxing_cpp_detector::detect(image.get_black_matrix(), try_harder, true)?;
+                let detectorRXingResult = hints.record_rejection(
+                    BarcodeFormat::DATA_MATRIX,
+                    None,
+                    zxing_cpp_detector::detect(image.get_black_matrix(), try_harder, true),
+                )?;
                 for symbol in detectorRXingResult {
-                    let decoded = DECODER.decode(symbol.getBits());
+                    if !hints.accepts_symbol(symbol.getPoints()) {
+                        continue;
+                    }
+                    let decoded =
+                        Self::decode_candidate(hints, symbol.getBits(), symbol.getPoints());
                     if decoded.is_ok() {
                         points = symbol.getPoints().to_vec();
+                        moduleSize = EstimatedModuleSize::from_corners(
+                            &points,
+                            symbol.getBits().getWidth(),
+                            symbol.getBits().getHeight(),
+                        );
                         return decoded;
                     } else {
                         continue;
@@ -188,15 +208,32 @@ impl DataMatrixReader {
                 fnd
             } else if try_harder {
                 if let Ok(fnd) = || -> Result<DecoderRXingResult> {
-                    let detectorRXingResult = Detector::new(image.get_black_matrix())?.detect()?;
-                    let decoded = DECODER.decode(detectorRXingResult.getBits())?;
+                    let detectorRXingResult = hints.record_rejection(
+                        BarcodeFormat::DATA_MATRIX,
+                        None,
+                        Detector::new(image.get_black_matrix()).and_then(|d| d.detect()),
+                    )?;
+                    let decoded = Self::decode_candidate(
+                        hints,
+                        detectorRXingResult.getBits(),
+                        detectorRXingResult.getPoints(),
+                    )?;
                     points = detectorRXingResult.getPoints().to_vec();
+                    moduleSize = EstimatedModuleSize::from_corners(
+                        &points,
+                        detectorRXingResult.getBits().getWidth(),
+                        detectorRXingResult.getBits().getHeight(),
+                    );
                     Ok(decoded)
                 }() {
                     fnd
                 } else {
                     let bits = self.extractPureBits(image.get_black_matrix())?;
-                    DECODER.decode(&bits)?
+                    moduleSize = EstimatedModuleSize::from_pure_symbol(
+                        image.get_black_matrix(),
+                        bits.getWidth(),
+                    );
+                    Self::decode_candidate(hints, &bits, &[])?
                 }
             } else {
                 return Err(Exceptions::NOT_FOUND);
@@ -205,10 +242,45 @@ impl DataMatrixReader {
             // decoderRXingResult = DECODER.decode(detectorRXingResult.getBits())?;
         }
 
+        Ok(Self::build_result(&decoderRXingResult, points, moduleSize))
+    }
+
+    /// Decodes the bits of a symbol found at `points`, recording its stages with the diagnostics of
+    /// the `hints`, if any, and why it was rejected if it does not decode, and collecting what was
+    /// read with their best effort collector, if any, if error correction fails.
+    fn decode_candidate(
+        hints: &DecodeHints,
+        bits: &BitMatrix,
+        points: &[Point],
+    ) -> Result<DecoderRXingResult> {
+        if let Some(diagnostics) = &hints.Diagnostics {
+            if !points.is_empty() {
+                diagnostics.quadrilateral(BarcodeFormat::DATA_MATRIX, points);
+            }
+            diagnostics.sampled_grid(BarcodeFormat::DATA_MATRIX, bits);
+        }
+        hints.record_rejection(
+            BarcodeFormat::DATA_MATRIX,
+            points.first().copied(),
+            DECODER.decode_with_hints(bits, &hints.located(points)),
+        )
+    }
+
+    /// Decodes the bits of a detected symbol with the shared decoder.
+    pub(crate) fn decode_bits(bits: &BitMatrix) -> Result<DecoderRXingResult> {
+        DECODER.decode(bits)
+    }
+
+    /// Builds the result, with its metadata, of a decoded symbol found at `points`.
+    pub(crate) fn build_result(
+        decoderRXingResult: &DecoderRXingResult,
+        points: Vec<Point>,
+        moduleSize: Option<EstimatedModuleSize>,
+    ) -> RXingResult {
         let mut result = RXingResult::new(
             decoderRXingResult.getText(),
             decoderRXingResult.getRawBytes().clone(),
-            points.clone(),
+            points,
             BarcodeFormat::DATA_MATRIX,
         );
         let byteSegments = decoderRXingResult.getByteSegments();
@@ -250,6 +322,13 @@ impl DataMatrixReader {
             );
         }
 
+        if let Some(sai) = decoderRXingResult.getStructuredAppendInfo() {
+            result.putMetadata(
+                RXingResultMetadataType::STRUCTURED_APPEND,
+                RXingResultMetadataValue::StructuredAppend(sai.clone()),
+            );
+        }
+
         result.putMetadata(
             RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
             RXingResultMetadataValue::SymbologyIdentifier(format!(
@@ -257,7 +336,25 @@ impl DataMatrixReader {
                 decoderRXingResult.getSymbologyModifier()
             )),
         );
+        if let Some(errorCorrection) = decoderRXingResult.getErrorCorrectionStats() {
+            result.putMetadata(
+                RXingResultMetadataType::ERRORS_CORRECTED,
+                RXingResultMetadataValue::ErrorsCorrected(errorCorrection),
+            );
+        }
+        if let Some(eci) = decoderRXingResult.getEci() {
+            result.putMetadata(
+                RXingResultMetadataType::CHARACTER_SET_ECI,
+                RXingResultMetadataValue::CharacterSetEci(eci as u32),
+            );
+        }
+        if let Some(moduleSize) = moduleSize {
+            result.putMetadata(
+                RXingResultMetadataType::MODULE_SIZE,
+                RXingResultMetadataValue::ModuleSize(moduleSize),
+            );
+        }
 
-        Ok(result)
+        result
     }
 }
@@ -17,8 +17,10 @@
 
 use crate::{
     common::{BitMatrix, CharacterSet, Result},
+    encoded_symbol::Placement,
     qrcode::encoder::ByteMatrix,
-    BarcodeFormat, EncodeHints, Exceptions, Writer,
+    BarcodeFormat, EncodeHints, EncodedSymbol, Exceptions, SymbolEcLevel, SymbolMetadata,
+    SymbolVersion, Writer,
 };
 
 use super::encoder::{
@@ -31,6 +33,20 @@ use super::encoder::error_correction;
 // make default 0 to match previous behavior
 const MARGINS_SIZE: u32 = 0;
 
+const PAD_CODEWORD: u32 = 129;
+
+/// The pad codeword at the one based `position`, after the first one, scrambled with the
+/// 253-state algorithm.
+fn randomized_pad(position: u32) -> u32 {
+    let pseudoRandom = ((149 * position) % 253) + 1;
+    let tempVariable = PAD_CODEWORD + pseudoRandom;
+    if tempVariable <= 254 {
+        tempVariable
+    } else {
+        tempVariable - 254
+    }
+}
+
 /**
  * This object renders a Data Matrix code as a BitMatrix 2D array of greyscale values.
  *
@@ -59,6 +75,19 @@ impl Writer for DataMatrixWriter {
         height: i32,
         hints: &EncodeHints,
     ) -> Result<crate::common::BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
         if contents.is_empty() {
             return Err(Exceptions::illegal_argument_with("Found empty contents"));
         }
@@ -75,6 +104,45 @@ impl Writer for DataMatrixWriter {
             )));
         }
 
+        let (encoded, symbolInfo) = Self::encodeHighLevel(contents, hints)?;
+
+        //2. step: ECC generation
+        let codewords = error_correction::encodeECC200(&encoded, symbolInfo)?;
+
+        //3. step: Module placement in Matrix
+        let mut placement = DefaultPlacement::new(
+            codewords,
+            symbolInfo.getSymbolDataWidth()? as usize,
+            symbolInfo.getSymbolDataHeight()? as usize,
+        );
+        placement.place()?;
+
+        let margins = if let Some(margin) = &hints.Margin {
+            margin
+                .parse::<u32>()
+                .map_err(|e| Exceptions::parse_with(format!("could not parse {margin}: {e}")))?
+        } else {
+            MARGINS_SIZE
+        };
+
+        //4. step: low-level encoding
+        let matrix = Self::encodeLowLevel(&placement, symbolInfo)?;
+        let drawn =
+            Self::convertByteMatrixToBitMatrix(&matrix, width as u32, height as u32, margins)?;
+
+        Ok(EncodedSymbol::new(*format, matrix.try_into()?, drawn)
+            .with_metadata(Self::symbol_metadata(&encoded, symbolInfo)?))
+    }
+}
+
+impl DataMatrixWriter {
+    /**
+     * Encodes the contents to data codewords, padded to the capacity of the symbol chosen for them.
+     */
+    fn encodeHighLevel(
+        contents: &str,
+        hints: &EncodeHints,
+    ) -> Result<(String, &'static SymbolInfo)> {
         // Try to get force shape & min / max size
         let shape = &hints.DataMatrixShape.unwrap_or(SymbolShapeHint::FORCE_NONE);
         let minSize = hints.MinSize;
@@ -124,45 +192,51 @@ impl Writer for DataMatrixWriter {
             return Err(Exceptions::not_found_with("symbol info is bad"));
         };
 
-        //2. step: ECC generation
-        let codewords = error_correction::encodeECC200(&encoded, symbolInfo)?;
-
-        //3. step: Module placement in Matrix
-        let mut placement = DefaultPlacement::new(
-            codewords,
-            symbolInfo.getSymbolDataWidth()? as usize,
-            symbolInfo.getSymbolDataHeight()? as usize,
-        );
-        placement.place()?;
+        Ok((encoded, symbolInfo))
+    }
 
-        let margins = if let Some(margin) = &hints.Margin {
-            margin
-                .parse::<u32>()
-                .map_err(|e| Exceptions::parse_with(format!("could not parse {margin}: {e}")))?
+    /**
+     * Describes the symbol of `symbolInfo` holding the `encoded` data codewords.
+     */
+    fn symbol_metadata(encoded: &str, symbolInfo: &SymbolInfo) -> Result<SymbolMetadata> {
+        let encoded: Vec<u32> = encoded.chars().map(|c| c as u32).collect();
+
+        // The padding is a 129 followed by pad codewords scrambled with their position, which
+        // never produces 129 again.
+        let mut used = encoded.len();
+        while used > 0 && encoded[used - 1] == randomized_pad(used as u32) {
+            used -= 1;
+        }
+        if used > 0 && encoded[used - 1] == PAD_CODEWORD {
+            used -= 1;
         } else {
-            MARGINS_SIZE
-        };
+            used = encoded.len();
+        }
 
-        //4. step: low-level encoding
-        Self::encodeLowLevel(&placement, symbolInfo, width as u32, height as u32, margins)
+        let data_codewords = symbolInfo.getDataCapacity();
+        Ok(SymbolMetadata {
+            version: SymbolVersion::DataMatrix {
+                rows: symbolInfo.getSymbolHeight()?,
+                columns: symbolInfo.getSymbolWidth()?,
+            },
+            ec_level: SymbolEcLevel::DataMatrixEcc200,
+            data_codewords,
+            used_data_codewords: used as u32,
+            ec_codewords: symbolInfo.getErrorCodewords(),
+            mask: None,
+            data_bits: Some(used as u32 * 8),
+            capacity_bits: Some(data_codewords * 8),
+        })
     }
-}
 
-impl DataMatrixWriter {
     /**
-     * Encode the given symbol info to a bit matrix.
+     * Encode the given symbol info to a matrix of modules.
      *
      * @param placement  The DataMatrix placement.
      * @param symbolInfo The symbol info to encode.
-     * @return The bit matrix generated.
+     * @return The byte matrix generated.
      */
-    fn encodeLowLevel(
-        placement: &DefaultPlacement,
-        symbolInfo: &SymbolInfo,
-        width: u32,
-        height: u32,
-        margins: u32,
-    ) -> Result<BitMatrix> {
+    fn encodeLowLevel(placement: &DefaultPlacement, symbolInfo: &SymbolInfo) -> Result<ByteMatrix> {
         let symbolWidth = symbolInfo.getSymbolDataWidth()?;
         let symbolHeight = symbolInfo.getSymbolDataHeight()?;
 
@@ -213,7 +287,7 @@ impl DataMatrixWriter {
             }
         }
 
-        Self::convertByteMatrixToBitMatrix(&matrix, width, height, margins)
+        Ok(matrix)
     }
 
     /**
@@ -222,14 +296,14 @@ impl DataMatrixWriter {
      * @param reqHeight The requested height of the image (in pixels) with the Datamatrix code
      * @param reqWidth The requested width of the image (in pixels) with the Datamatrix code
      * @param matrix The input matrix.
-     * @return The output matrix.
+     * @return The output matrix, and where the modules are in it.
      */
     fn convertByteMatrixToBitMatrix(
         matrix: &ByteMatrix,
         reqWidth: u32,
         reqHeight: u32,
         margins: u32,
-    ) -> Result<BitMatrix> {
+    ) -> Result<(BitMatrix, Placement)> {
         let matrixWidth = matrix.getWidth();
         let matrixHeight = matrix.getHeight();
         let paddedWidth = matrixWidth + (margins * 2);
@@ -274,7 +348,7 @@ impl DataMatrixWriter {
             outputY += multiple
         }
 
-        Ok(output)
+        Ok((output, Placement::square(multiple, leftPadding, topPadding)))
     }
 }
 
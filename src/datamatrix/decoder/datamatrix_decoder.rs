@@ -14,9 +14,12 @@
  * limitations under the License.
  */
 
-use crate::common::{
-    reedsolomon::{get_predefined_genericgf, PredefinedGenericGF, ReedSolomonDecoder},
-    BitMatrix, DecoderRXingResult, Result,
+use crate::{
+    common::{
+        reedsolomon::{get_predefined_genericgf, PredefinedGenericGF, ReedSolomonDecoder},
+        BitMatrix, DecoderRXingResult, Result,
+    },
+    BarcodeFormat, CodewordRecorder, DecodeHints,
 };
 
 use super::{decoded_bit_stream_parser, BitMatrixParser, DataBlock};
@@ -47,12 +50,23 @@ impl Decoder {
      * @throws ChecksumException if error correction fails
      */
     pub fn decode(&self, bits: &BitMatrix) -> Result<DecoderRXingResult> {
-        let decoded = self.perform_decode(bits, false, false);
+        self.decode_with_hints(bits, &DecodeHints::default())
+    }
+
+    /// Like [`Decoder::decode`], recording the codewords of each attempt before and after error
+    /// correction with the diagnostics of the `hints`, if any, and collecting them with their best
+    /// effort collector, if any, if error correction fails.
+    pub fn decode_with_hints(
+        &self,
+        bits: &BitMatrix,
+        hints: &DecodeHints,
+    ) -> Result<DecoderRXingResult> {
+        let decoded = self.perform_decode(bits, false, false, hints);
         if decoded.is_ok() {
             return decoded;
         }
 
-        self.perform_decode(&Self::flip_bitmatrix(bits)?, false, true)
+        self.perform_decode(&Self::flip_bitmatrix(bits)?, false, true, hints)
     }
 
     fn flip_bitmatrix(bits: &BitMatrix) -> Result<BitMatrix> {
@@ -82,7 +96,12 @@ impl Decoder {
      * @throws ChecksumException if error correction fails
      */
     pub fn decode_bools(&self, image: &[Vec<bool>]) -> Result<DecoderRXingResult> {
-        self.perform_decode(&BitMatrix::parse_bools(image), false, false)
+        self.perform_decode(
+            &BitMatrix::parse_bools(image),
+            false,
+            false,
+            &DecodeHints::default(),
+        )
     }
 
     /**
@@ -99,6 +118,7 @@ impl Decoder {
         bits: &BitMatrix,
         fix259: bool,
         is_flipped: bool,
+        hints: &DecodeHints,
     ) -> Result<DecoderRXingResult> {
         // Construct a parser and read version, error-correction level
         let mut parser = BitMatrixParser::new(bits)?;
@@ -119,17 +139,28 @@ impl Decoder {
         let mut resultBytes = vec![0u8; totalBytes as usize];
 
         let dataBlocksCount = dataBlocks.len();
+        let mut errorsCorrected = 0;
+        let mut recorder = CodewordRecorder::new(hints, BarcodeFormat::DATA_MATRIX);
+        let mut failure = None;
         // Error-correct and copy data blocks together into a stream of bytes
         for j in 0..dataBlocksCount {
             // for (int j = 0; j < dataBlocksCount; j++) {
             let dataBlock = &dataBlocks[j];
             let mut codewordBytes = dataBlock.getCodewords().to_vec();
             let numDataCodewords = dataBlock.getNumDataCodewords() as usize;
-            let errors_corrected = self.correctErrors(&mut codewordBytes, numDataCodewords as u32);
-            if errors_corrected.is_err() && !fix259 {
-                return self.perform_decode(bits, true, is_flipped);
-            } else if errors_corrected.is_err() {
-                return Err(errors_corrected.err().unwrap());
+            recorder.received(numDataCodewords, &codewordBytes);
+            match self.correctErrors(&mut codewordBytes, numDataCodewords as u32) {
+                Ok(errors_corrected) => {
+                    errorsCorrected += errors_corrected;
+                    recorder.corrected(&codewordBytes);
+                }
+                Err(_) if !fix259 => {
+                    drop(recorder);
+                    return self.perform_decode(bits, true, is_flipped, hints);
+                }
+                // go on with the codewords as they were read
+                Err(e) if recorder.best_effort() => failure = failure.or(Some(e)),
+                Err(e) => return Err(e),
             }
             for i in 0..numDataCodewords {
                 // for (int i = 0; i < numDataCodewords; i++) {
@@ -137,9 +168,23 @@ impl Decoder {
                 resultBytes[i * dataBlocksCount + j] = codewordBytes[i];
             }
         }
+        if let Some(e) = failure {
+            // a mirrored reading of a symbol that is not mirrored fails as well
+            if !is_flipped {
+                recorder.failed(resultBytes, move |bytes| {
+                    Ok(decoded_bit_stream_parser::decode(bytes, is_flipped)?
+                        .getText()
+                        .to_owned())
+                });
+            }
+            return Err(e);
+        }
 
         // Decode the contents of that stream of bytes
-        decoded_bit_stream_parser::decode(&resultBytes, is_flipped)
+        let mut result = decoded_bit_stream_parser::decode(&resultBytes, is_flipped)?;
+        result.setErrorsCorrected(errorsCorrected);
+        result.setErrorCorrectionCapacity(codewords.len() - totalBytes as usize);
+        Ok(result)
     }
 
     /**
@@ -148,9 +193,10 @@ impl Decoder {
      *
      * @param codewordBytes data and error correction codewords
      * @param numDataCodewords number of codewords that are data bytes
+     * @return the number of errors corrected
      * @throws ChecksumException if error correction fails
      */
-    fn correctErrors(&self, codewordBytes: &mut [u8], numDataCodewords: u32) -> Result<()> {
+    fn correctErrors(&self, codewordBytes: &mut [u8], numDataCodewords: u32) -> Result<usize> {
         let _numCodewords = codewordBytes.len();
         // First read into an array of ints
         // let codewordsInts = vec![0;numCodewords];
@@ -161,7 +207,7 @@ impl Decoder {
         let mut codewordsInts: Vec<i32> = codewordBytes.iter().map(|x| *x as i32).collect();
 
         //try {
-        self.0.decode(
+        let errorsCorrected = self.0.decode(
             &mut codewordsInts,
             codewordBytes.len() as i32 - numDataCodewords as i32,
         )?;
@@ -175,7 +221,7 @@ impl Decoder {
             codewordBytes[i] = codewordsInts[i] as u8;
         }
         // codewordsInts.into_iter().take(numDataCodewords as usize).map(|x| x as u8).collect::<Vec<u8>>()
-        Ok(())
+        Ok(errorsCorrected)
     }
 }
 
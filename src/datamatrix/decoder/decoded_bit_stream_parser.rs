@@ -15,7 +15,10 @@
  */
 
 use crate::{
-    common::{BitSource, CharacterSet, DecoderRXingResult, ECIStringBuilder, Eci, Result},
+    common::{
+        cpp_essentials::StructuredAppendInfo, BitSource, CharacterSet, DecoderRXingResult,
+        ECIStringBuilder, Eci, Result,
+    },
     Exceptions,
 };
 
@@ -120,6 +123,7 @@ pub fn decode(bytes: &[u8], is_flipped: bool) -> Result<DecoderRXingResult> {
     let mut isECIencoded = false;
     let mut known_eci = true;
     let mut is_gs1 = false;
+    let mut sai = StructuredAppendInfo::default();
     loop {
         match mode {
             Mode::ASCII_ENCODE => {
@@ -129,6 +133,7 @@ pub fn decode(bytes: &[u8], is_flipped: bool) -> Result<DecoderRXingResult> {
                     &mut resultTrailer,
                     &mut fnc1Positions,
                     &mut is_gs1,
+                    &mut sai,
                 )?
             }
             Mode::C40_ENCODE => {
@@ -184,6 +189,7 @@ pub fn decode(bytes: &[u8], is_flipped: bool) -> Result<DecoderRXingResult> {
         symbologyModifier = 1;
     }
 
+    let eci = result.first_eci();
     let mut result = DecoderRXingResult::with_symbology(
         bytes.to_vec(),
         result.build_result().to_string(),
@@ -203,6 +209,12 @@ pub fn decode(bytes: &[u8], is_flipped: bool) -> Result<DecoderRXingResult> {
         result.setIsMirrored(is_flipped);
     }
 
+    if sai.index >= 0 {
+        result.setStructuredAppendInfo(sai);
+    }
+
+    result.setEci(eci);
+
     Ok(result)
 }
 
@@ -215,11 +227,11 @@ fn decodeAsciiSegment(
     resultTrailer: &mut String,
     fnc1positions: &mut Vec<usize>,
     is_gs1: &mut bool,
+    sai: &mut StructuredAppendInfo,
 ) -> Result<Mode> {
     let mut upperShift = false;
     let mut firstFNC1Position = 1;
     let mut firstCodeword = true;
-    let mut sai = StructuredAppendInfo::default();
     loop {
         let mut oneByte = bits.readBits(8)?;
         match oneByte {
@@ -280,7 +292,7 @@ fn decodeAsciiSegment(
                         "structured append tag must be first code word",
                     ));
                 }
-                parse_structured_append(bits, &mut sai)?;
+                parse_structured_append(bits, sai)?;
                 firstFNC1Position = 5;
             }
             234 =>
@@ -780,22 +792,6 @@ fn parse_structured_append(bits: &mut BitSource, sai: &mut StructuredAppendInfo)
     sai.id = ((fileId1 << 8) | fileId2).to_string();
     Ok(())
 }
-struct StructuredAppendInfo {
-    index: i32, //= -1;
-    count: i32, // = -1;
-    id: String,
-}
-
-impl Default for StructuredAppendInfo {
-    fn default() -> Self {
-        Self {
-            index: -1,
-            count: -1,
-            id: Default::default(),
-        }
-    }
-}
-
 /**
  * See ISO 16022:2006, Annex B, B.2
  */
@@ -812,7 +808,7 @@ fn unrandomize255State(randomizedBase256Codeword: u32, base256CodewordPosition:
 
 #[cfg(test)]
 mod tests {
-    use crate::datamatrix::decoder::decoded_bit_stream_parser;
+    use crate::{common::Eci, datamatrix::decoder::decoded_bit_stream_parser};
 
     #[test]
     fn testAsciiStandardDecode() {
@@ -845,6 +841,31 @@ mod tests {
         assert_eq!("00019899", decodedString);
     }
 
+    #[test]
+    fn testStructuredAppendDecode() {
+        // symbol 2 of 3, file ID 1 2, then "AB"
+        let bytes = [233, (1 << 4) | (17 - 3), 1, 2, (b'A' + 1), (b'B' + 1)];
+        let result = decoded_bit_stream_parser::decode(&bytes, false).expect("decode");
+        assert_eq!(result.getText(), "AB");
+        let sai = result.getStructuredAppendInfo().expect("structured append");
+        assert_eq!((sai.index, sai.count, sai.id.as_str()), (1, 3, "258"));
+
+        let result = decoded_bit_stream_parser::decode(&bytes[4..], false).expect("decode");
+        assert!(result.getStructuredAppendInfo().is_none());
+    }
+
+    #[test]
+    fn testECIDecode() {
+        // ECI 26, UTF-8, then "A"
+        let result =
+            decoded_bit_stream_parser::decode(&[241, 26 + 1, b'A' + 1], false).expect("decode");
+        assert_eq!(result.getText(), "A");
+        assert_eq!(result.getEci(), Some(Eci::UTF8));
+
+        let result = decoded_bit_stream_parser::decode(&[b'A' + 1], false).expect("decode");
+        assert_eq!(result.getEci(), None);
+    }
+
     // TODO(bbrown): Add test cases for each encoding type
     // TODO(bbrown): Add test cases for switching encoding types
 }
@@ -246,6 +246,24 @@ pub fn detect(
     image: &BitMatrix,
     tryHarder: bool,
     tryRotate: bool,
+) -> Result<Vec<DatamatrixDetectorResult>> {
+    detect_symbols(image, tryHarder, tryRotate, false)
+}
+
+/**
+ * Detects every Data Matrix symbol in the image in one pass: all scan lines are followed in the
+ * four directions, each to its end, so that symbols beside each other on a line are all found.
+ * A symbol is usually found more than once, from different lines and directions.
+ */
+pub fn detect_multiple(image: &BitMatrix) -> Result<Vec<DatamatrixDetectorResult>> {
+    detect_symbols(image, true, true, true)
+}
+
+fn detect_symbols(
+    image: &BitMatrix,
+    tryHarder: bool,
+    tryRotate: bool,
+    multiple: bool,
 ) -> Result<Vec<DatamatrixDetectorResult>> {
     // #ifdef PRINT_DEBUG
     // 	LogMatrixWriter lmw(log, image, 1, "dm-log.pnm");
@@ -326,7 +344,12 @@ pub fn detect(
             // 			while (res = Scan(tracer, lines), res.isValid())
             // 				co_yield std::move(res);
             // #else
-            if let Ok(res) = Scan(&mut tracer, &mut lines) {
+            if multiple {
+                // keep tracing the same line past each symbol
+                while let Ok(res) = Scan(&mut tracer, &mut lines) {
+                    found_symbols.push(res);
+                }
+            } else if let Ok(res) = Scan(&mut tracer, &mut lines) {
                 // if res.isValid(){
                 //return Ok(res);
                 // i += res.getPoints()[2].distance( res.getPoints()[0] ) as i32;
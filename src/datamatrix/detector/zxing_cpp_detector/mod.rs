@@ -7,4 +7,4 @@ use crate::common::cpp_essentials::edge_tracer::*;
 
 use crate::common::cpp_essentials::util;
 
-pub use cpp_new_detector::detect;
+pub use cpp_new_detector::{detect, detect_multiple};
@@ -16,9 +16,15 @@
 
 //package com.google.zxing;
 
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
 
-use crate::{BarcodeFormat, PointCallback};
+use crate::{
+    common::Quadrilateral, BarcodeFormat, BestEffort, CancellationToken, Diagnostics, Point,
+    PointCallback,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -74,6 +80,12 @@ pub enum DecodeHintType {
      */
     ASSUME_CODE_39_CHECK_DIGIT,
 
+    /**
+     * Assume Code 39 codes use Full ASCII mode, and decode the shift pairs like "+A" into the
+     * characters they stand for. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    ASSUME_CODE_39_FULL_ASCII,
+
     /**
      * Assume the barcode is being processed as a GS1 barcode, and modify behavior as needed.
      * For example this affects FNC1 handling for Code 128 (aka GS1-128). Doesn't matter what it maps to;
@@ -126,6 +138,49 @@ pub enum DecodeHintType {
      * Will translate the ASCII values parsed by the Telepen reader into the Telepen Numeric form.
      */
     TELEPEN_AS_NUMERIC,
+
+    /**
+     * Only look for barcodes within this region of the image, given in pixels. A rectangle is
+     * given as {@link Quadrilateral#rectangle_from_xy}. Result points are still in the coordinates
+     * of the whole image. Maps to a {@link Quadrilateral}.
+     */
+    REGION_OF_INTEREST,
+
+    /**
+     * The smallest and the largest size, in pixels, of the barcodes to look for: the width or the
+     * height, whichever is larger, of the area their result points span. Maps to a range of pixels.
+     */
+    SYMBOL_SIZE_RANGE,
+
+    /**
+     * For 1D barcodes, scan these lines, from one point to the other, rather than rows of the image.
+     * Maps to a {@link List} of pairs of {@link Point}s.
+     */
+    SCAN_LINES,
+
+    /**
+     * For 1D barcodes, scan lines at these angles, in degrees clockwise from horizontal, across the
+     * image or its region of interest, rather than rows. Maps to a {@link List} of angles.
+     */
+    SCAN_ANGLES,
+
+    /**
+     * Stop decoding, with {@link Exceptions#TimeoutException}, once this token is cancelled or its
+     * deadline has passed. Maps to a {@link CancellationToken}.
+     */
+    CANCELLATION_TOKEN,
+
+    /**
+     * Record the intermediate stages of the decode, and why each candidate symbol was rejected,
+     * in this collector. Maps to a {@link Diagnostics}.
+     */
+    DIAGNOSTICS,
+
+    /**
+     * Collect what could be read of the symbols whose error correction failed in this collector.
+     * The decode still fails for them. Maps to a {@link BestEffort}.
+     */
+    BEST_EFFORT,
     /*
      * Data type the hint is expecting.
      * Among the possible values the {@link Void} stands out as being used for
@@ -188,6 +243,12 @@ pub enum DecodeHintValue {
      */
     AssumeCode39CheckDigit(bool),
 
+    /**
+     * Assume Code 39 codes use Full ASCII mode, and decode the shift pairs like "+A" into the
+     * characters they stand for. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    AssumeCode39FullAscii(bool),
+
     /**
      * Assume the barcode is being processed as a GS1 barcode, and modify behavior as needed.
      * For example this affects FNC1 handling for Code 128 (aka GS1-128). Doesn't matter what it maps to;
@@ -241,6 +302,52 @@ pub enum DecodeHintValue {
      * Translate the ASCII values parsed by the Telepen reader into the Telepen Numeric form; use {@link Boolean#TRUE}.
      */
     TelepenAsNumeric(bool),
+
+    /**
+     * Only look for barcodes within this region of the image, given in pixels. A rectangle is
+     * given as {@link Quadrilateral#rectangle_from_xy}. Result points are still in the coordinates
+     * of the whole image. Maps to a {@link Quadrilateral}.
+     */
+    RegionOfInterest(Quadrilateral),
+
+    /**
+     * The smallest and the largest size, in pixels, of the barcodes to look for: the width or the
+     * height, whichever is larger, of the area their result points span. Maps to a range of pixels.
+     */
+    SymbolSizeRange(RangeInclusive<u32>),
+
+    /**
+     * For 1D barcodes, scan these lines, from one point to the other, rather than rows of the image.
+     * Maps to a {@link List} of pairs of {@link Point}s.
+     */
+    ScanLines(Vec<(Point, Point)>),
+
+    /**
+     * For 1D barcodes, scan lines at these angles, in degrees clockwise from horizontal, across the
+     * image or its region of interest, rather than rows. Maps to a {@link List} of angles.
+     */
+    ScanAngles(Vec<f32>),
+
+    /**
+     * Stop decoding, with {@link Exceptions#TimeoutException}, once this token is cancelled or its
+     * deadline has passed. Maps to a {@link CancellationToken}.
+     */
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    CancellationToken(CancellationToken),
+
+    /**
+     * Record the intermediate stages of the decode, and why each candidate symbol was rejected,
+     * in this collector. Maps to a {@link Diagnostics}.
+     */
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    Diagnostics(Diagnostics),
+
+    /**
+     * Collect what could be read of the symbols whose error correction failed in this collector.
+     * The decode still fails for them. Maps to a {@link BestEffort}.
+     */
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    BestEffort(BestEffort),
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -285,6 +392,12 @@ pub struct DecodeHints {
      */
     pub AssumeCode39CheckDigit: Option<bool>,
 
+    /**
+     * Assume Code 39 codes use Full ASCII mode, and decode the shift pairs like "+A" into the
+     * characters they stand for. Doesn't matter what it maps to; use {@link Boolean#TRUE}.
+     */
+    pub AssumeCode39FullAscii: Option<bool>,
+
     /**
      * Assume the barcode is being processed as a GS1 barcode, and modify behavior as needed.
      * For example this affects FNC1 handling for Code 128 (aka GS1-128). Doesn't matter what it maps to;
@@ -338,6 +451,52 @@ pub struct DecodeHints {
      * Translate the ASCII values parsed by the Telepen reader into the Telepen Numeric form; use {@link Boolean#TRUE}.
      */
     pub TelepenAsNumeric: Option<bool>,
+
+    /**
+     * Only look for barcodes within this region of the image, given in pixels. A rectangle is
+     * given as {@link Quadrilateral#rectangle_from_xy}. Result points are still in the coordinates
+     * of the whole image. Maps to a {@link Quadrilateral}.
+     */
+    pub RegionOfInterest: Option<Quadrilateral>,
+
+    /**
+     * The smallest and the largest size, in pixels, of the barcodes to look for: the width or the
+     * height, whichever is larger, of the area their result points span. Maps to a range of pixels.
+     */
+    pub SymbolSizeRange: Option<RangeInclusive<u32>>,
+
+    /**
+     * For 1D barcodes, scan these lines, from one point to the other, rather than rows of the image.
+     * Maps to a {@link List} of pairs of {@link Point}s.
+     */
+    pub ScanLines: Option<Vec<(Point, Point)>>,
+
+    /**
+     * For 1D barcodes, scan lines at these angles, in degrees clockwise from horizontal, across the
+     * image or its region of interest, rather than rows. Maps to a {@link List} of angles.
+     */
+    pub ScanAngles: Option<Vec<f32>>,
+
+    /**
+     * Stop decoding, with {@link Exceptions#TimeoutException}, once this token is cancelled or its
+     * deadline has passed. Maps to a {@link CancellationToken}.
+     */
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    pub CancellationToken: Option<CancellationToken>,
+
+    /**
+     * Record the intermediate stages of the decode, and why each candidate symbol was rejected,
+     * in this collector. Maps to a {@link Diagnostics}.
+     */
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    pub Diagnostics: Option<Diagnostics>,
+
+    /**
+     * Collect what could be read of the symbols whose error correction failed in this collector.
+     * The decode still fails for them. Maps to a {@link BestEffort}.
+     */
+    #[cfg_attr(feature = "serde", serde(skip_serializing, skip_deserializing))]
+    pub BestEffort: Option<BestEffort>,
 }
 
 impl From<super::DecodingHintDictionary> for DecodeHints {
@@ -354,6 +513,9 @@ impl From<super::DecodingHintDictionary> for DecodeHints {
                 DecodeHintValue::AssumeCode39CheckDigit(v) => {
                     new_self.AssumeCode39CheckDigit = Some(v)
                 }
+                DecodeHintValue::AssumeCode39FullAscii(v) => {
+                    new_self.AssumeCode39FullAscii = Some(v)
+                }
                 DecodeHintValue::AssumeGs1(v) => new_self.AssumeGs1 = Some(v),
                 DecodeHintValue::ReturnCodabarStartEnd(v) => {
                     new_self.ReturnCodabarStartEnd = Some(v)
@@ -364,6 +526,13 @@ impl From<super::DecodingHintDictionary> for DecodeHints {
                 DecodeHintValue::AllowedEanExtensions(v) => new_self.AllowedEanExtensions = Some(v),
                 DecodeHintValue::AlsoInverted(v) => new_self.AlsoInverted = Some(v),
                 DecodeHintValue::TelepenAsNumeric(v) => new_self.TelepenAsNumeric = Some(v),
+                DecodeHintValue::RegionOfInterest(v) => new_self.RegionOfInterest = Some(v),
+                DecodeHintValue::SymbolSizeRange(v) => new_self.SymbolSizeRange = Some(v),
+                DecodeHintValue::ScanLines(v) => new_self.ScanLines = Some(v),
+                DecodeHintValue::ScanAngles(v) => new_self.ScanAngles = Some(v),
+                DecodeHintValue::CancellationToken(v) => new_self.CancellationToken = Some(v),
+                DecodeHintValue::Diagnostics(v) => new_self.Diagnostics = Some(v),
+                DecodeHintValue::BestEffort(v) => new_self.BestEffort = Some(v),
                 #[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
                 DecodeHintValue::QrAssumeSpecConformInput(v) => {
                     new_self.QrAssumeSpecConformInput = Some(v)
@@ -421,6 +590,13 @@ impl From<DecodeHints> for super::DecodingHintDictionary {
             );
         }
 
+        if let Some(v) = value.AssumeCode39FullAscii {
+            new_self.insert(
+                DecodeHintType::ASSUME_CODE_39_FULL_ASCII,
+                DecodeHintValue::AssumeCode39FullAscii(v),
+            );
+        }
+
         if let Some(v) = value.AssumeGs1 {
             new_self.insert(DecodeHintType::ASSUME_GS1, DecodeHintValue::AssumeGs1(v));
         }
@@ -460,6 +636,43 @@ impl From<DecodeHints> for super::DecodingHintDictionary {
             );
         }
 
+        if let Some(v) = value.RegionOfInterest {
+            new_self.insert(
+                DecodeHintType::REGION_OF_INTEREST,
+                DecodeHintValue::RegionOfInterest(v),
+            );
+        }
+
+        if let Some(v) = value.SymbolSizeRange {
+            new_self.insert(
+                DecodeHintType::SYMBOL_SIZE_RANGE,
+                DecodeHintValue::SymbolSizeRange(v),
+            );
+        }
+
+        if let Some(v) = value.ScanLines {
+            new_self.insert(DecodeHintType::SCAN_LINES, DecodeHintValue::ScanLines(v));
+        }
+
+        if let Some(v) = value.ScanAngles {
+            new_self.insert(DecodeHintType::SCAN_ANGLES, DecodeHintValue::ScanAngles(v));
+        }
+
+        if let Some(v) = value.CancellationToken {
+            new_self.insert(
+                DecodeHintType::CANCELLATION_TOKEN,
+                DecodeHintValue::CancellationToken(v),
+            );
+        }
+
+        if let Some(v) = value.Diagnostics {
+            new_self.insert(DecodeHintType::DIAGNOSTICS, DecodeHintValue::Diagnostics(v));
+        }
+
+        if let Some(v) = value.BestEffort {
+            new_self.insert(DecodeHintType::BEST_EFFORT, DecodeHintValue::BestEffort(v));
+        }
+
         #[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
         if let Some(v) = value.QrAssumeSpecConformInput {
             new_self.insert(
@@ -482,12 +695,20 @@ impl DecodeHints {
             DecodeHintValue::CharacterSet(v) => self.CharacterSet = Some(v),
             DecodeHintValue::AllowedLengths(v) => self.AllowedLengths = Some(v),
             DecodeHintValue::AssumeCode39CheckDigit(v) => self.AssumeCode39CheckDigit = Some(v),
+            DecodeHintValue::AssumeCode39FullAscii(v) => self.AssumeCode39FullAscii = Some(v),
             DecodeHintValue::AssumeGs1(v) => self.AssumeGs1 = Some(v),
             DecodeHintValue::ReturnCodabarStartEnd(v) => self.ReturnCodabarStartEnd = Some(v),
             DecodeHintValue::NeedResultPointCallback(v) => self.NeedResultPointCallback = Some(v),
             DecodeHintValue::AllowedEanExtensions(v) => self.AllowedEanExtensions = Some(v),
             DecodeHintValue::AlsoInverted(v) => self.AlsoInverted = Some(v),
             DecodeHintValue::TelepenAsNumeric(v) => self.TelepenAsNumeric = Some(v),
+            DecodeHintValue::RegionOfInterest(v) => self.RegionOfInterest = Some(v),
+            DecodeHintValue::SymbolSizeRange(v) => self.SymbolSizeRange = Some(v),
+            DecodeHintValue::ScanLines(v) => self.ScanLines = Some(v),
+            DecodeHintValue::ScanAngles(v) => self.ScanAngles = Some(v),
+            DecodeHintValue::CancellationToken(v) => self.CancellationToken = Some(v),
+            DecodeHintValue::Diagnostics(v) => self.Diagnostics = Some(v),
+            DecodeHintValue::BestEffort(v) => self.BestEffort = Some(v),
             #[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
             DecodeHintValue::QrAssumeSpecConformInput(v) => self.QrAssumeSpecConformInput = Some(v),
         }
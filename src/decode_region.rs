@@ -0,0 +1,387 @@
+use crate::{
+    common::Result, point, Binarizer, BinaryBitmap, DecodeHints, Exceptions, Point, RXingResult,
+};
+
+impl DecodeHints {
+    /// The left, top, width and height of the pixels around the region of interest that lie in an
+    /// image of `width` by `height`, or `None` without a region of interest.
+    pub(crate) fn region_bounds(
+        &self,
+        width: usize,
+        height: usize,
+    ) -> Option<(usize, usize, usize, usize)> {
+        let region = self.RegionOfInterest.as_ref()?;
+        let (left, top, right, bottom) = bounds(&region.0)?;
+        let left = (left.floor().max(0.0) as usize).min(width);
+        let top = (top.floor().max(0.0) as usize).min(height);
+        let right = (right.ceil().max(0.0) as usize).min(width);
+        let bottom = (bottom.ceil().max(0.0) as usize).min(height);
+        Some((
+            left,
+            top,
+            right.saturating_sub(left),
+            bottom.saturating_sub(top),
+        ))
+    }
+
+    /// Whether `p` lies in the region of interest, if there is one.
+    pub(crate) fn in_region(&self, p: Point) -> bool {
+        self.RegionOfInterest
+            .as_ref()
+            .is_none_or(|region| region.is_inside(p))
+    }
+
+    /// Whether a symbol found at `points` is centered in the region of interest and has a size in
+    /// the symbol size range. A symbol without points is always accepted.
+    pub(crate) fn accepts_symbol(&self, points: &[Point]) -> bool {
+        let Some((left, top, right, bottom)) = bounds(points) else {
+            return true;
+        };
+        if !self.in_region(point((left + right) / 2.0, (top + bottom) / 2.0)) {
+            return false;
+        }
+        self.SymbolSizeRange
+            .as_ref()
+            .is_none_or(|range| range.contains(&((right - left).max(bottom - top).round() as u32)))
+    }
+
+    /// Whether 1D readers should scan along lines given by a region of interest, scan lines or
+    /// scan angles rather than along the rows of the whole image.
+    pub(crate) fn has_scan_region(&self) -> bool {
+        self.RegionOfInterest.is_some() || self.ScanLines.is_some() || self.ScanAngles.is_some()
+    }
+
+    /// These hints for the image cropped at `offset`.
+    fn translated(&self, offset: Point) -> DecodeHints {
+        let mut hints = self.clone();
+        if let Some(region) = hints.RegionOfInterest.as_mut() {
+            for p in region.0.iter_mut() {
+                *p -= offset;
+            }
+        }
+        if let Some(lines) = hints.ScanLines.as_mut() {
+            for (start, end) in lines.iter_mut() {
+                *start -= offset;
+                *end -= offset;
+            }
+        }
+        if let Some(diagnostics) = hints.Diagnostics.as_mut() {
+            *diagnostics = diagnostics.translated(offset);
+        }
+        if let Some(best_effort) = hints.BestEffort.as_mut() {
+            *best_effort = best_effort.translated(offset);
+        }
+        hints
+    }
+}
+
+/// Runs `decode` on the region of interest of `image`, cropped from it when that is supported,
+/// and returns its result with the points in the coordinates of `image`, if the symbol is accepted
+/// by the region of interest and the symbol size range.
+pub(crate) fn decode_in_region<B: Binarizer>(
+    image: &mut BinaryBitmap<B>,
+    hints: &DecodeHints,
+    decode: impl FnOnce(&mut BinaryBitmap<B>, &DecodeHints) -> Result<RXingResult>,
+) -> Result<RXingResult> {
+    let result = match crop_region(image, hints)? {
+        Some((mut region, offset, region_hints)) => {
+            let mut result = decode(&mut region, &region_hints)?;
+            translate(&mut result, offset);
+            result
+        }
+        None => decode(image, hints)?,
+    };
+    if hints.accepts_symbol(result.getPoints()) {
+        Ok(result)
+    } else {
+        Err(Exceptions::NOT_FOUND)
+    }
+}
+
+/// Like [`decode_in_region`], for readers that find several symbols.
+pub(crate) fn decode_multiple_in_region<B: Binarizer>(
+    image: &mut BinaryBitmap<B>,
+    hints: &DecodeHints,
+    decode: impl FnOnce(&mut BinaryBitmap<B>, &DecodeHints) -> Result<Vec<RXingResult>>,
+) -> Result<Vec<RXingResult>> {
+    let mut results = match crop_region(image, hints)? {
+        Some((mut region, offset, region_hints)) => {
+            let mut results = decode(&mut region, &region_hints)?;
+            for result in results.iter_mut() {
+                translate(result, offset);
+            }
+            results
+        }
+        None => decode(image, hints)?,
+    };
+    results.retain(|result| hints.accepts_symbol(result.getPoints()));
+    Ok(results)
+}
+
+/// The region of interest cropped from `image`, where it is to the left and top, and the hints
+/// for it. `None` when the whole image is to be searched.
+fn crop_region<B: Binarizer>(
+    image: &mut BinaryBitmap<B>,
+    hints: &DecodeHints,
+) -> Result<Option<(BinaryBitmap<B>, Point, DecodeHints)>> {
+    if let Some(diagnostics) = &hints.Diagnostics {
+        diagnostics.binarized(image.get_black_matrix());
+    }
+    let (width, height) = (image.get_width(), image.get_height());
+    let Some((left, top, region_width, region_height)) = hints.region_bounds(width, height) else {
+        return Ok(None);
+    };
+    if region_width == 0 || region_height == 0 {
+        return Err(Exceptions::not_found_with(
+            "region of interest is outside the image",
+        ));
+    }
+    if !image.is_crop_supported() || (region_width == width && region_height == height) {
+        return Ok(None);
+    }
+    let offset = point(left as f32, top as f32);
+    Ok(Some((
+        image.crop(left, top, region_width, region_height),
+        offset,
+        hints.translated(offset),
+    )))
+}
+
+fn translate(result: &mut RXingResult, offset: Point) {
+    for p in result.getPointsMut() {
+        *p += offset;
+    }
+}
+
+/// The left, top, right and bottom of `points`.
+fn bounds(points: &[Point]) -> Option<(f32, f32, f32, f32)> {
+    let first = points.first()?;
+    Some(points.iter().fold(
+        (first.x, first.y, first.x, first.y),
+        |(left, top, right, bottom), p| {
+            (left.min(p.x), top.min(p.y), right.max(p.x), bottom.max(p.y))
+        },
+    ))
+}
+
+#[cfg(test)]
+mod DecodeRegionTestCase {
+    use crate::{
+        common::{test_image::TestImage, BitMatrix, HybridBinarizer, Quadrilateral},
+        multi::MultipleBarcodeReader,
+        oned::Code128Reader,
+        point,
+        qrcode::cpp_port::QrReader,
+        BarcodeFormat, BinaryBitmap, DecodeHintValue, DecodeHints, Luma8LuminanceSource,
+        MultiFormatReader, MultiFormatWriter, Point, RXingResult, RXingResultMetadataType,
+        RXingResultMetadataValue, Reader, Writer,
+    };
+
+    const WIDTH: u32 = 320;
+    const HEIGHT: u32 = 240;
+
+    /// Draws `matrix` with `scale` pixels per module, centered at `center` and rotated clockwise
+    /// by `angle` degrees. A matrix one module high is drawn as bars `bar_height` pixels high.
+    fn draw(
+        image: &mut TestImage,
+        matrix: &BitMatrix,
+        scale: f32,
+        center: Point,
+        angle: f32,
+        bar_height: f32,
+    ) {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let (width, height) = (matrix.getWidth() as f32, matrix.getHeight() as f32);
+        let half_height = if matrix.getHeight() == 1 {
+            bar_height / 2.0
+        } else {
+            height * scale / 2.0
+        };
+        for y in 0..HEIGHT {
+            for x in 0..WIDTH {
+                let d = point(x as f32 + 0.5, y as f32 + 0.5) - center;
+                let u = (d.x * cos + d.y * sin) / scale + width / 2.0;
+                let v = -d.x * sin + d.y * cos;
+                if u < 0.0 || u >= width || v.abs() >= half_height {
+                    continue;
+                }
+                let row = if matrix.getHeight() == 1 {
+                    0
+                } else {
+                    ((v + half_height) / scale) as u32
+                };
+                if matrix.get(u as u32, row) {
+                    image.set(x, y, 0);
+                }
+            }
+        }
+    }
+
+    fn image(
+        symbols: &[(&str, BarcodeFormat, f32, Point, f32)],
+    ) -> BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>> {
+        let mut image = TestImage::new(WIDTH, HEIGHT);
+        for (contents, format, scale, center, angle) in symbols {
+            let matrix = MultiFormatWriter
+                .encode(contents, format, 0, 0)
+                .expect("encode");
+            draw(&mut image, &matrix, *scale, *center, *angle, 60.0);
+        }
+        image.bitmap()
+    }
+
+    fn region(x0: f32, x1: f32, y0: f32, y1: f32) -> DecodeHints {
+        DecodeHints::default().with(DecodeHintValue::RegionOfInterest(
+            Quadrilateral::rectangle_from_xy(x0, x1, y0, y1, Some(0.0)),
+        ))
+    }
+
+    fn assert_points_within(result: &RXingResult, x0: f32, x1: f32, y0: f32, y1: f32) {
+        assert!(!result.getPoints().is_empty());
+        for p in result.getPoints() {
+            assert!(
+                p.x >= x0 && p.x <= x1 && p.y >= y0 && p.y <= y1,
+                "{p} not within ({x0}, {y0}) - ({x1}, {y1})"
+            );
+        }
+    }
+
+    #[test]
+    fn testRegionOfInterest() {
+        let symbols = [
+            ("LEFT", BarcodeFormat::QR_CODE, 3.0, point(80.0, 120.0), 0.0),
+            (
+                "RIGHT",
+                BarcodeFormat::QR_CODE,
+                3.0,
+                point(240.0, 120.0),
+                0.0,
+            ),
+        ];
+
+        let hints = region(160.0, 320.0, 0.0, 240.0);
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut image(&symbols), &hints)
+            .expect("decode");
+        assert_eq!(result.getText(), "RIGHT");
+        // in the coordinates of the whole image, not of the region
+        assert_points_within(&result, 200.0, 280.0, 80.0, 160.0);
+
+        let hints = region(0.0, 160.0, 0.0, 240.0);
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut image(&symbols), &hints)
+            .expect("decode");
+        assert_eq!(result.getText(), "LEFT");
+        assert_points_within(&result, 40.0, 120.0, 80.0, 160.0);
+
+        let results = QrReader
+            .decode_multiple_with_hints(&mut image(&symbols), &region(160.0, 320.0, 0.0, 240.0))
+            .expect("decode");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].getText(), "RIGHT");
+        assert_points_within(&results[0], 200.0, 280.0, 80.0, 160.0);
+
+        // nothing in the region
+        assert!(MultiFormatReader::default()
+            .decode_with_hints(&mut image(&symbols), &region(120.0, 200.0, 0.0, 240.0))
+            .is_err());
+    }
+
+    #[test]
+    fn testSymbolSizeRange() {
+        let symbols = [
+            (
+                "SMALL",
+                BarcodeFormat::QR_CODE,
+                2.0,
+                point(60.0, 120.0),
+                0.0,
+            ),
+            (
+                "LARGE",
+                BarcodeFormat::QR_CODE,
+                5.0,
+                point(220.0, 120.0),
+                0.0,
+            ),
+        ];
+        let decode = |range| {
+            let hints = DecodeHints::default().with(DecodeHintValue::SymbolSizeRange(range));
+            MultiFormatReader::default()
+                .decode_with_hints(&mut image(&symbols), &hints)
+                .map(|result| result.getText().to_owned())
+        };
+
+        // the corners of 21 modules of 5 and of 2 pixels
+        assert_eq!(decode(80..=120).expect("decode"), "LARGE");
+        assert_eq!(decode(0..=60).expect("decode"), "SMALL");
+        assert!(decode(200..=300).is_err());
+    }
+
+    #[test]
+    fn testScanAngles() {
+        let symbols = [(
+            "RXING-045",
+            BarcodeFormat::CODE_128,
+            2.0,
+            point(160.0, 120.0),
+            30.0,
+        )];
+        let hints = DecodeHints::default().with(DecodeHintValue::ScanAngles(vec![30.0]));
+        let result = Code128Reader
+            .decode_with_hints(&mut image(&symbols), &hints)
+            .expect("decode");
+        assert_eq!(result.getText(), "RXING-045");
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::ORIENTATION),
+            Some(&RXingResultMetadataValue::Orientation(330))
+        );
+        // the ends of the barcode, along the line through its center
+        let points = result.getPoints();
+        assert_eq!(points.len(), 2);
+        for p in points {
+            let d = *p - point(160.0, 120.0);
+            assert!((d.y - d.x * 30f32.to_radians().tan()).abs() < 3.0, "{p}");
+        }
+        assert!(points[0].x < points[1].x && points[0].y < points[1].y);
+    }
+
+    #[test]
+    fn testScanLines() {
+        // read from bottom to top
+        let symbols = [(
+            "RXING",
+            BarcodeFormat::CODE_128,
+            2.0,
+            point(100.0, 120.0),
+            270.0,
+        )];
+        let hints = DecodeHints::default().with(DecodeHintValue::ScanLines(vec![(
+            point(110.0, 0.0),
+            point(110.0, 240.0),
+        )]));
+        let result = Code128Reader
+            .decode_with_hints(&mut image(&symbols), &hints)
+            .expect("decode");
+        assert_eq!(result.getText(), "RXING");
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::ORIENTATION),
+            Some(&RXingResultMetadataValue::Orientation(90))
+        );
+        assert_points_within(&result, 110.0, 111.0, 0.0, 240.0);
+        assert!(result.getPoints()[0].y > result.getPoints()[1].y);
+
+        // a line that misses the barcode
+        let hints = DecodeHints::default().with(DecodeHintValue::ScanLines(vec![(
+            point(200.0, 0.0),
+            point(200.0, 240.0),
+        )]));
+        assert!(Code128Reader
+            .decode_with_hints(&mut image(&symbols), &hints)
+            .is_err());
+    }
+}
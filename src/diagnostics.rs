@@ -0,0 +1,707 @@
+use std::{
+    fmt::{self, Display, Write},
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    best_effort::partial_text,
+    common::{BitMatrix, Result},
+    BarcodeFormat, BestEffort, DecodeHints, Point,
+};
+
+/// Collects the intermediate stages of a decode: the binarized image, the finder pattern
+/// candidates, the quadrilaterals of the detected symbols, the grids sampled from them, their
+/// codewords before and after error correction, and why each candidate was rejected.
+///
+/// Recording is opt-in: readers only do the extra work when the collector is given to the decode
+/// in the [`DecodeHintValue::Diagnostics`](crate::DecodeHintValue::Diagnostics) hint. Clones share
+/// the record, so the one kept by the caller sees everything the decode recorded, in the
+/// coordinates of the image given to it. [`DiagnosticsRecord::to_html`] renders the record as an
+/// annotated debug image.
+#[derive(Clone, Debug, Default)]
+pub struct Diagnostics {
+    record: Arc<Mutex<DiagnosticsRecord>>,
+    /// Where the image the readers see is in the image given to the decode.
+    offset: Point,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A copy of everything recorded so far.
+    pub fn record(&self) -> DiagnosticsRecord {
+        self.lock().clone()
+    }
+
+    /// Forgets everything recorded so far, so that the collector can be used for another decode.
+    pub fn clear(&self) {
+        *self.lock() = DiagnosticsRecord::default();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, DiagnosticsRecord> {
+        // a reader that panicked leaves a record that is still worth looking at
+        self.record.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// This collector for the image cropped at `offset`.
+    pub(crate) fn translated(&self, offset: Point) -> Self {
+        Self {
+            record: self.record.clone(),
+            offset: self.offset + offset,
+        }
+    }
+
+    /// Records the binarized image, unless one has been recorded already.
+    pub(crate) fn binarized(&self, image: &BitMatrix) {
+        let mut record = self.lock();
+        if record.binarized.is_none() {
+            record.binarized = Some(image.clone());
+        }
+    }
+
+    pub(crate) fn finder_pattern(&self, kind: FinderPatternKind, center: Point, module_size: f32) {
+        let center = center + self.offset;
+        self.lock().finder_patterns.push(FinderPatternCandidate {
+            kind,
+            center,
+            module_size,
+        });
+    }
+
+    pub(crate) fn quadrilateral(&self, format: BarcodeFormat, corners: &[Point]) {
+        let corners = corners.iter().map(|p| *p + self.offset).collect();
+        self.lock()
+            .quadrilaterals
+            .push(DetectedQuadrilateral { format, corners });
+    }
+
+    pub(crate) fn sampled_grid(&self, format: BarcodeFormat, bits: &BitMatrix) {
+        self.lock().sampled_grids.push(SampledGrid {
+            format,
+            bits: bits.clone(),
+        });
+    }
+
+    pub(crate) fn codewords(&self, format: BarcodeFormat, blocks: Vec<CodewordBlock>) {
+        self.lock()
+            .codewords
+            .push(SymbolCodewords { format, blocks });
+    }
+
+    pub(crate) fn rejection(
+        &self,
+        format: BarcodeFormat,
+        location: Option<Point>,
+        reason: impl Display,
+    ) {
+        let location = location.map(|p| p + self.offset);
+        self.lock().rejections.push(Rejection {
+            format,
+            location,
+            reason: reason.to_string(),
+        });
+    }
+}
+
+impl DecodeHints {
+    /// Passes `result` on, recording with the diagnostics of the hints, if any, why the candidate
+    /// `format` symbol at `location` was rejected when it is an error.
+    pub(crate) fn record_rejection<T>(
+        &self,
+        format: BarcodeFormat,
+        location: Option<Point>,
+        result: Result<T>,
+    ) -> Result<T> {
+        if let (Some(diagnostics), Err(e)) = (&self.Diagnostics, &result) {
+            diagnostics.rejection(format, location, e);
+        }
+        result
+    }
+}
+
+/// Everything recorded by a [`Diagnostics`] collector, in the order it was recorded.
+#[derive(Clone, Debug, Default)]
+pub struct DiagnosticsRecord {
+    /// The binarized image the matrix readers searched.
+    pub binarized: Option<BitMatrix>,
+    pub finder_patterns: Vec<FinderPatternCandidate>,
+    pub quadrilaterals: Vec<DetectedQuadrilateral>,
+    pub sampled_grids: Vec<SampledGrid>,
+    pub codewords: Vec<SymbolCodewords>,
+    pub rejections: Vec<Rejection>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FinderPatternKind {
+    /// A finder pattern of the QR Code reader ported from ZXing,
+    /// [`qrcode::detector::FinderPattern`](crate::qrcode::detector::FinderPattern).
+    QrCode,
+    /// A pattern of concentric squares, found by the QR Code, Micro QR Code and rMQR readers
+    /// ported from zxing-cpp.
+    Concentric,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FinderPatternCandidate {
+    pub kind: FinderPatternKind,
+    pub center: Point,
+    pub module_size: f32,
+}
+
+/// The outline of a symbol that was found, whether or not it decoded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetectedQuadrilateral {
+    pub format: BarcodeFormat,
+    pub corners: Vec<Point>,
+}
+
+/// The modules sampled from a symbol, one bit per module.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SampledGrid {
+    pub format: BarcodeFormat,
+    pub bits: BitMatrix,
+}
+
+/// The codewords read from a symbol, one block for each error correction block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SymbolCodewords {
+    pub format: BarcodeFormat,
+    pub blocks: Vec<CodewordBlock>,
+}
+
+/// An error correction block of a symbol.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CodewordBlock {
+    /// How many of the codewords, at the start of the block, are data rather than error
+    /// correction codewords.
+    pub data_codewords: usize,
+    /// The codewords as they were read from the symbol.
+    pub received: Vec<u32>,
+    /// The codewords after error correction, or `None` if the block could not be corrected.
+    pub corrected: Option<Vec<u32>>,
+}
+
+impl CodewordBlock {
+    /// The positions of the codewords that error correction changed.
+    pub fn corrected_positions(&self) -> Vec<usize> {
+        let Some(corrected) = &self.corrected else {
+            return Vec::new();
+        };
+        (0..self.received.len())
+            .filter(|i| corrected.get(*i) != self.received.get(*i))
+            .collect()
+    }
+}
+
+/// A candidate symbol that did not give a result.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rejection {
+    pub format: BarcodeFormat,
+    /// Where the candidate was, if it got as far as being located.
+    pub location: Option<Point>,
+    pub reason: String,
+}
+
+/// Collects the codeword blocks of a symbol as a decoder corrects them, and records them with
+/// the diagnostics, if any, when dropped, so that a decoder can give up at the first block that
+/// cannot be corrected. With a best effort collector, the decoder goes on past such a block
+/// instead and the blocks are also collected as an [`UnverifiedRead`](crate::UnverifiedRead).
+pub(crate) struct CodewordRecorder<'a> {
+    diagnostics: Option<&'a Diagnostics>,
+    best_effort: Option<&'a BestEffort>,
+    format: BarcodeFormat,
+    blocks: Vec<CodewordBlock>,
+    /// Decodes the partial text of a failed attempt, only once it is known to be the last one.
+    partial_text: Option<Box<dyn FnOnce() -> String + 'a>>,
+}
+
+impl<'a> CodewordRecorder<'a> {
+    pub(crate) fn new(hints: &'a DecodeHints, format: BarcodeFormat) -> Self {
+        Self {
+            diagnostics: hints.Diagnostics.as_ref(),
+            best_effort: hints.BestEffort.as_ref(),
+            format,
+            blocks: Vec::new(),
+            partial_text: None,
+        }
+    }
+
+    /// Whether the decoder should go on past a block that cannot be corrected.
+    pub(crate) fn best_effort(&self) -> bool {
+        self.best_effort.is_some()
+    }
+
+    /// Starts a block with the codewords as they were read.
+    pub(crate) fn received<T: Copy + Into<i64>>(&mut self, data_codewords: usize, codewords: &[T]) {
+        if self.diagnostics.is_some() || self.best_effort.is_some() {
+            self.blocks.push(CodewordBlock {
+                data_codewords,
+                received: codewords.iter().map(|c| (*c).into() as u32).collect(),
+                corrected: None,
+            });
+        }
+    }
+
+    /// Forgets the blocks of an attempt that is being retried.
+    pub(crate) fn restart(&mut self) {
+        self.blocks.clear();
+        self.partial_text = None;
+    }
+
+    /// Completes the last block with its codewords after error correction.
+    pub(crate) fn corrected<T: Copy + Into<i64>>(&mut self, codewords: &[T]) {
+        if let Some(block) = self.blocks.last_mut() {
+            block.corrected = Some(codewords.iter().map(|c| (*c).into() as u32).collect());
+        }
+    }
+
+    /// Marks the attempt as failed, to be collected with the best effort collector with the text
+    /// `parse` decodes from as many of the `data` codewords as it can.
+    pub(crate) fn failed<T: 'a>(
+        &mut self,
+        data: Vec<T>,
+        parse: impl Fn(&[T]) -> Result<String> + 'a,
+    ) {
+        if self.best_effort.is_some() {
+            self.partial_text = Some(Box::new(move || partial_text(&data, parse)));
+        }
+    }
+}
+
+impl Drop for CodewordRecorder<'_> {
+    fn drop(&mut self) {
+        if let (Some(best_effort), Some(partial_text)) =
+            (self.best_effort, self.partial_text.take())
+        {
+            best_effort.collect(self.format, self.blocks.clone(), partial_text());
+        }
+        if let Some(diagnostics) = self.diagnostics {
+            if !self.blocks.is_empty() {
+                diagnostics.codewords(self.format, std::mem::take(&mut self.blocks));
+            }
+        }
+    }
+}
+
+impl DiagnosticsRecord {
+    /// Renders the binarized image, light grey on white, with the quadrilaterals of the detected
+    /// symbols in green, the finder pattern candidates circled in red (QR Code) or blue
+    /// (concentric), and the rejected candidates crossed out in orange with the reason.
+    pub fn to_svg(&self) -> String {
+        let mut svg = String::new();
+        self.write_svg(&mut svg)
+            .expect("writing to a String does not fail");
+        svg
+    }
+
+    /// Renders a page like `test_resources/matrix_visualizer.html`, with the image of
+    /// [`DiagnosticsRecord::to_svg`], a table of each sampled grid, the codewords of each symbol
+    /// with the ones changed by error correction marked, and the rejected candidates.
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        self.write_html(&mut html)
+            .expect("writing to a String does not fail");
+        html
+    }
+
+    /// The size of the image, or of the area covering everything recorded if there is none.
+    fn size(&self) -> (u32, u32) {
+        if let Some(image) = &self.binarized {
+            return (image.getWidth(), image.getHeight());
+        }
+        let points = self
+            .finder_patterns
+            .iter()
+            .map(|fp| fp.center)
+            .chain(
+                self.quadrilaterals
+                    .iter()
+                    .flat_map(|q| q.corners.iter().copied()),
+            )
+            .chain(self.rejections.iter().filter_map(|r| r.location));
+        let (width, height) = points.fold((0.0f32, 0.0f32), |(w, h), p| (w.max(p.x), h.max(p.y)));
+        (width as u32 + 10, height as u32 + 10)
+    }
+
+    fn write_svg(&self, out: &mut String) -> fmt::Result {
+        let (width, height) = self.size();
+        let stroke = (width.max(height) as f32 / 400.0).max(1.0);
+        writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+        )?;
+        writeln!(out, r#"<rect width="100%" height="100%" fill="white"/>"#)?;
+
+        if let Some(image) = &self.binarized {
+            // one rectangle per run of dark pixels
+            write!(out, r##"<path fill="#aaa" d=""##)?;
+            for y in 0..image.getHeight() {
+                let mut x = 0;
+                while x < image.getWidth() {
+                    if !image.get(x, y) {
+                        x += 1;
+                        continue;
+                    }
+                    let start = x;
+                    while x < image.getWidth() && image.get(x, y) {
+                        x += 1;
+                    }
+                    write!(out, "M{start} {y}h{}v1h-{}z", x - start, x - start)?;
+                }
+            }
+            writeln!(out, r#""/>"#)?;
+        }
+
+        for quad in &self.quadrilaterals {
+            let points = quad
+                .corners
+                .iter()
+                .map(|p| format!("{},{}", p.x, p.y))
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(
+                out,
+                r#"<polygon points="{points}" fill="none" stroke="green" stroke-width="{stroke}"/>"#
+            )?;
+            if let Some(first) = quad.corners.first() {
+                write_label(out, *first, stroke, "green", quad.format)?;
+            }
+        }
+
+        for fp in &self.finder_patterns {
+            let color = match fp.kind {
+                FinderPatternKind::QrCode => "red",
+                FinderPatternKind::Concentric => "blue",
+            };
+            // a finder pattern is 7 modules across
+            writeln!(
+                out,
+                r#"<circle cx="{}" cy="{}" r="{}" fill="none" stroke="{color}" stroke-width="{stroke}"/>"#,
+                fp.center.x,
+                fp.center.y,
+                (fp.module_size * 3.5).max(stroke)
+            )?;
+        }
+
+        for rejection in &self.rejections {
+            let Some(p) = rejection.location else {
+                continue;
+            };
+            let arm = stroke * 4.0;
+            writeln!(
+                out,
+                r#"<path d="M{} {}l{} {}M{} {}l{} {}" stroke="orangered" stroke-width="{stroke}"/>"#,
+                p.x - arm,
+                p.y - arm,
+                2.0 * arm,
+                2.0 * arm,
+                p.x - arm,
+                p.y + arm,
+                2.0 * arm,
+                -2.0 * arm
+            )?;
+            write_label(
+                out,
+                p,
+                stroke,
+                "orangered",
+                format_args!("{}: {}", rejection.format, rejection.reason),
+            )?;
+        }
+
+        writeln!(out, "</svg>")
+    }
+
+    fn write_html(&self, out: &mut String) -> fmt::Result {
+        writeln!(out, "<!DOCTYPE html>")?;
+        writeln!(
+            out,
+            r#"<html><head><meta charset="utf-8"><title>Decode diagnostics</title>"#
+        )?;
+        writeln!(
+            out,
+            "<style>table {{ font-family: monospace; border-collapse: collapse; }} \
+             td {{ min-width: 1em; text-align: center; }} \
+             tr:hover td, td:hover {{ background-color: lightblue; }} \
+             mark {{ background-color: orange; }}</style>"
+        )?;
+        writeln!(out, "</head><body>")?;
+
+        writeln!(out, "<h2>Image</h2>")?;
+        writeln!(
+            out,
+            "<p>Symbols found in green, QR Code finder patterns in red, concentric finder patterns \
+             in blue and rejected candidates in orange.</p>"
+        )?;
+        self.write_svg(out)?;
+
+        writeln!(out, "<h2>Sampled grids</h2>")?;
+        for grid in &self.sampled_grids {
+            let bits = &grid.bits;
+            writeln!(
+                out,
+                "<h3>{}, {} by {}</h3>",
+                grid.format,
+                bits.getWidth(),
+                bits.getHeight()
+            )?;
+            write!(out, "<table><tr><th></th>")?;
+            for x in 0..bits.getWidth() {
+                write!(out, "<th>{x}</th>")?;
+            }
+            writeln!(out, "</tr>")?;
+            for y in 0..bits.getHeight() {
+                write!(out, "<tr><th>{y}</th>")?;
+                for x in 0..bits.getWidth() {
+                    let module = if bits.get(x, y) { 'X' } else { ' ' };
+                    write!(out, r#"<td title="({y},{x})">{module}</td>"#)?;
+                }
+                writeln!(out, "</tr>")?;
+            }
+            writeln!(out, "</table>")?;
+        }
+
+        writeln!(out, "<h2>Codewords</h2>")?;
+        for symbol in &self.codewords {
+            writeln!(out, "<h3>{}</h3>", symbol.format)?;
+            writeln!(
+                out,
+                "<table><tr><th>block</th><th>data</th><th>received</th><th>corrected</th></tr>"
+            )?;
+            for (i, block) in symbol.blocks.iter().enumerate() {
+                let received = block
+                    .received
+                    .iter()
+                    .map(u32::to_string)
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let corrected = match &block.corrected {
+                    Some(corrected) => corrected
+                        .iter()
+                        .zip(&block.received)
+                        .map(|(c, r)| {
+                            if c == r {
+                                c.to_string()
+                            } else {
+                                format!("<mark>{c}</mark>")
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                    None => "<mark>failed</mark>".to_owned(),
+                };
+                writeln!(
+                    out,
+                    "<tr><td>{i}</td><td>{}</td><td>{received}</td><td>{corrected}</td></tr>",
+                    block.data_codewords
+                )?;
+            }
+            writeln!(out, "</table>")?;
+        }
+
+        writeln!(out, "<h2>Rejected candidates</h2><ul>")?;
+        for rejection in &self.rejections {
+            write!(out, "<li>{}", rejection.format)?;
+            if let Some(p) = rejection.location {
+                write!(out, " at ({}, {})", p.x, p.y)?;
+            }
+            writeln!(out, ": {}</li>", escape(&rejection.reason))?;
+        }
+        writeln!(out, "</ul></body></html>")
+    }
+}
+
+fn write_label(
+    out: &mut String,
+    at: Point,
+    stroke: f32,
+    color: &str,
+    text: impl Display,
+) -> fmt::Result {
+    writeln!(
+        out,
+        r#"<text x="{}" y="{}" font-size="{}" fill="{color}">{}</text>"#,
+        at.x + stroke * 5.0,
+        at.y - stroke * 2.0,
+        stroke * 8.0,
+        escape(&text.to_string())
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod DiagnosticsTestCase {
+    use crate::{
+        common::{test_image::image, BitMatrix, Quadrilateral},
+        point,
+        qrcode::{cpp_port::QrReader, QRCodeReader},
+        BarcodeFormat, DecodeHintValue, DecodeHints, MultiFormatReader, MultiFormatWriter, Reader,
+        Writer,
+    };
+
+    use super::{Diagnostics, FinderPatternKind};
+
+    const SCALE: u32 = 4;
+    /// The quiet zone around the symbol, in pixels.
+    const MARGIN: u32 = 10 * SCALE;
+
+    fn encode(format: BarcodeFormat, contents: &str) -> BitMatrix {
+        MultiFormatWriter
+            .encode(contents, &format, 0, 0)
+            .expect("encode")
+    }
+
+    fn hints(diagnostics: &Diagnostics) -> DecodeHints {
+        DecodeHints::default().with(DecodeHintValue::Diagnostics(diagnostics.clone()))
+    }
+
+    #[test]
+    fn testStages() {
+        let matrix = encode(BarcodeFormat::QR_CODE, "DIAGNOSTICS");
+        let mut image = image(&matrix, SCALE, MARGIN);
+        let diagnostics = Diagnostics::new();
+        // the stages of the cropped region are recorded in the coordinates of the image
+        let size = (matrix.getWidth() * SCALE) as f32;
+        let (near, far) = (MARGIN as f32 / 2.0, MARGIN as f32 * 1.5 + size);
+        let region = Quadrilateral::new(
+            point(near, near),
+            point(far, near),
+            point(far, far),
+            point(near, far),
+        );
+        let hints = hints(&diagnostics).with(DecodeHintValue::RegionOfInterest(region));
+        let result = QrReader
+            .decode_with_hints(&mut image, &hints)
+            .expect("decode");
+        assert_eq!(result.getText(), "DIAGNOSTICS");
+
+        let record = diagnostics.record();
+        let binarized = record.binarized.as_ref().expect("binarized");
+        assert_eq!(binarized.getWidth(), image.get_width() as u32);
+        let symbol = MARGIN as f32..=MARGIN as f32 + size;
+        let in_symbol = |p: crate::Point| symbol.contains(&p.x) && symbol.contains(&p.y);
+        let concentric = record
+            .finder_patterns
+            .iter()
+            .filter(|fp| fp.kind == FinderPatternKind::Concentric)
+            .collect::<Vec<_>>();
+        assert!(concentric.len() >= 3);
+        assert!(concentric.iter().all(|fp| in_symbol(fp.center)));
+        assert!(concentric
+            .iter()
+            .all(|fp| (fp.module_size - SCALE as f32).abs() < 1.0));
+        assert!(record.quadrilaterals.iter().any(|quad| {
+            quad.format == BarcodeFormat::QR_CODE && quad.corners.iter().all(|p| in_symbol(*p))
+        }));
+        // the writer adds a quiet zone of 4 modules
+        assert!(record
+            .sampled_grids
+            .iter()
+            .any(|grid| grid.bits.getWidth() == matrix.getWidth() - 8));
+        assert_eq!(record.codewords.len(), 1);
+        let symbol = &record.codewords[0];
+        assert_eq!(symbol.format, BarcodeFormat::QR_CODE);
+        assert!(!symbol.blocks.is_empty());
+        for block in &symbol.blocks {
+            assert_eq!(block.corrected.as_ref(), Some(&block.received));
+            assert!(block.corrected_positions().is_empty());
+        }
+
+        let html = record.to_html();
+        assert!(html.contains("<svg"));
+        assert!(html.contains("<polygon"));
+        assert!(html.contains("<circle"));
+        assert!(html.contains("<table"));
+
+        diagnostics.clear();
+        assert!(diagnostics.record().binarized.is_none());
+        // nothing is recorded without the hint
+        QrReader.decode(&mut image).expect("decode");
+        assert!(diagnostics.record().codewords.is_empty());
+    }
+
+    #[test]
+    fn testRejection() {
+        let mut matrix = encode(BarcodeFormat::DATA_MATRIX, "DIAGNOSTICS");
+        // more damage to the data region than error correction can repair
+        for y in 2..matrix.getHeight() - 2 {
+            for x in (2..matrix.getWidth() - 2).step_by(2) {
+                matrix.flip_coords(x, y);
+            }
+        }
+        let diagnostics = Diagnostics::new();
+        let hints = hints(&diagnostics).with(DecodeHintValue::PossibleFormats(
+            [BarcodeFormat::DATA_MATRIX].into(),
+        ));
+        assert!(MultiFormatReader::default()
+            .decode_with_hints(&mut image(&matrix, SCALE, MARGIN), &hints)
+            .is_err());
+
+        let record = diagnostics.record();
+        assert!(record
+            .quadrilaterals
+            .iter()
+            .any(|quad| quad.format == BarcodeFormat::DATA_MATRIX));
+        assert!(record
+            .codewords
+            .iter()
+            .flat_map(|symbol| &symbol.blocks)
+            .any(|block| block.corrected.is_none()));
+        let rejection = record
+            .rejections
+            .iter()
+            .find(|rejection| rejection.location.is_some())
+            .expect("rejection");
+        assert_eq!(rejection.format, BarcodeFormat::DATA_MATRIX);
+        assert!(!rejection.reason.is_empty());
+        assert!(record.to_html().contains("failed"));
+    }
+
+    #[test]
+    fn testOtherFormats() {
+        let diagnostics = Diagnostics::new();
+        QRCodeReader
+            .decode_with_hints(
+                &mut image(&encode(BarcodeFormat::QR_CODE, "LEGACY"), SCALE, MARGIN),
+                &hints(&diagnostics),
+            )
+            .expect("decode");
+        let record = diagnostics.record();
+        assert!(
+            record
+                .finder_patterns
+                .iter()
+                .filter(|fp| fp.kind == FinderPatternKind::QrCode)
+                .count()
+                >= 3
+        );
+        assert_eq!(record.quadrilaterals.len(), 1);
+        assert_eq!(record.sampled_grids.len(), 1);
+        assert_eq!(record.codewords.len(), 1);
+
+        for format in [BarcodeFormat::AZTEC, BarcodeFormat::PDF_417] {
+            let diagnostics = Diagnostics::new();
+            let hints = hints(&diagnostics).with(DecodeHintValue::PossibleFormats([format].into()));
+            MultiFormatReader::default()
+                .decode_with_hints(&mut image(&encode(format, "OTHER"), SCALE, MARGIN), &hints)
+                .expect("decode");
+            let record = diagnostics.record();
+            assert!(record
+                .quadrilaterals
+                .iter()
+                .any(|quad| quad.format == format));
+            assert!(record
+                .codewords
+                .iter()
+                .any(|symbol| symbol.format == format && !symbol.blocks.is_empty()));
+        }
+    }
+}
@@ -185,6 +185,27 @@ pub enum EncodeHintType {
      * Will translate the numeric values received by the Telepen writer into the Telepen Alphanumeric form.
      */
     TELEPEN_AS_NUMERIC,
+
+    /**
+     * Adds a GS1 Composite component above a linear symbol (type {@link String}). The value is
+     * the GS1 element string of the component with the AIs in parentheses, for example
+     * "(17)010200(10)ABC123".
+     */
+    GS1_COMPOSITE,
+
+    /**
+     * Specifies the bearer bars printed around an ITF-14 symbol (type
+     * {@link com.google.zxing.oned.BearerBars BearerBars}). Defaults to a frame.
+     */
+    ITF_BEARER_BARS,
+
+    /**
+     * Specifies whether Code 39 and Code 93 may use Full ASCII mode (type {@link Boolean}, or "true"
+     * or "false" {@link String} value). By default it is used only when the contents need it.
+     * If false, contents outside the basic character set are rejected. If true, Code 39 always
+     * uses it, so that Full ASCII readers read back text like "+A" as it was given.
+     */
+    FULL_ASCII,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -343,6 +364,25 @@ pub enum EncodeHintValue {
      * Translate the numeric values received by the Telepen reader into the Telepen Alphaumeric form; use {@link Boolean#TRUE}.
      */
     TelepenAsNumeric(bool),
+
+    /**
+     * Adds a GS1 Composite component above a linear symbol. The value is the GS1 element string
+     * of the component with the AIs in parentheses, for example "(17)010200(10)ABC123".
+     */
+    Gs1Composite(String),
+
+    /**
+     * Specifies the bearer bars printed around an ITF-14 symbol. Defaults to a frame.
+     */
+    ItfBearerBars(crate::oned::BearerBars),
+
+    /**
+     * Specifies whether Code 39 and Code 93 may use Full ASCII mode. By default it is used only
+     * when the contents need it. If false, contents outside the basic character set are rejected.
+     * If true, Code 39 always uses it, so that Full ASCII readers read back text like "+A" as it
+     * was given.
+     */
+    FullAscii(bool),
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -502,6 +542,25 @@ pub struct EncodeHints {
      * Translate the numeric values received by the Telepen reader into the Telepen Alphaumeric form; use {@link Boolean#TRUE}.
      */
     pub TelepenAsNumeric: Option<bool>,
+
+    /**
+     * Adds a GS1 Composite component above a linear symbol. The value is the GS1 element string
+     * of the component with the AIs in parentheses, for example "(17)010200(10)ABC123".
+     */
+    pub Gs1Composite: Option<String>,
+
+    /**
+     * Specifies the bearer bars printed around an ITF-14 symbol. Defaults to a frame.
+     */
+    pub ItfBearerBars: Option<crate::oned::BearerBars>,
+
+    /**
+     * Specifies whether Code 39 and Code 93 may use Full ASCII mode. By default it is used only
+     * when the contents need it. If false, contents outside the basic character set are rejected.
+     * If true, Code 39 always uses it, so that Full ASCII readers read back text like "+A" as it
+     * was given.
+     */
+    pub FullAscii: Option<bool>,
 }
 
 impl From<super::EncodingHintDictionary> for EncodeHints {
@@ -530,6 +589,9 @@ impl From<super::EncodingHintDictionary> for EncodeHints {
                 EncodeHintValue::ForceC40(v) => new_self.ForceC40 = Some(v),
                 EncodeHintValue::Code128Compact(v) => new_self.Code128Compact = Some(v),
                 EncodeHintValue::TelepenAsNumeric(v) => new_self.TelepenAsNumeric = Some(v),
+                EncodeHintValue::Gs1Composite(v) => new_self.Gs1Composite = Some(v),
+                EncodeHintValue::ItfBearerBars(v) => new_self.ItfBearerBars = Some(v),
+                EncodeHintValue::FullAscii(v) => new_self.FullAscii = Some(v),
             }
         }
 
@@ -560,6 +622,9 @@ impl EncodeHints {
             EncodeHintValue::ForceC40(v) => self.ForceC40 = Some(v),
             EncodeHintValue::Code128Compact(v) => self.Code128Compact = Some(v),
             EncodeHintValue::TelepenAsNumeric(v) => self.TelepenAsNumeric = Some(v),
+            EncodeHintValue::Gs1Composite(v) => self.Gs1Composite = Some(v),
+            EncodeHintValue::ItfBearerBars(v) => self.ItfBearerBars = Some(v),
+            EncodeHintValue::FullAscii(v) => self.FullAscii = Some(v),
         };
         self
     }
@@ -0,0 +1,288 @@
+use crate::{common::BitMatrix, qrcode::decoder::ErrorCorrectionLevel, BarcodeFormat};
+
+/// The version or size an encoder chose for a two-dimensional symbol.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SymbolVersion {
+    /// A QR Code version, 1 to 40.
+    QrCode(u32),
+    /// A Data Matrix symbol size, in modules, including the finder pattern.
+    DataMatrix { rows: u32, columns: u32 },
+    /// An Aztec symbol with its number of data layers.
+    Aztec { compact: bool, layers: u32 },
+    /// A PDF417 layout, in codeword rows and data columns.
+    Pdf417 { rows: u32, columns: u32 },
+}
+
+/// The error correction an encoder chose for a two-dimensional symbol.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SymbolEcLevel {
+    /// A QR Code error correction level.
+    QrCode(ErrorCorrectionLevel),
+    /// Data Matrix ECC 200, whose number of error correction codewords is set by the symbol size.
+    DataMatrixEcc200,
+    /// The share of the codewords of an Aztec symbol that are check words, in percent.
+    Aztec(u32),
+    /// A PDF417 error correction level, 0 to 8, which adds 2^(level + 1) codewords.
+    Pdf417(u32),
+}
+
+/// What the encoder of a two-dimensional format chose for a symbol, and how full it is.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct SymbolMetadata {
+    pub version: SymbolVersion,
+    pub ec_level: SymbolEcLevel,
+    /// The number of data codewords the symbol holds, including padding.
+    pub data_codewords: u32,
+    /// The number of data codewords taken by the contents, without padding.
+    pub used_data_codewords: u32,
+    /// The number of error correction codewords.
+    pub ec_codewords: u32,
+    /// The QR Code mask pattern.
+    pub mask: Option<u32>,
+    /// The number of bits taken by the encoded contents, without the padding and, for Aztec, the
+    /// stuffed bits. Not set for PDF417, whose codewords are not made of bits.
+    pub data_bits: Option<u32>,
+    /// The number of data bits the symbol could hold at the same version and error correction.
+    pub capacity_bits: Option<u32>,
+}
+
+/**
+ * A symbol encoded by [`Writer::encode_with_metadata`](crate::Writer::encode_with_metadata): the
+ * matrix the writer drew, the module matrix it was drawn from, and what the encoder chose.
+ */
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct EncodedSymbol {
+    pub format: BarcodeFormat,
+    /// The symbol at one pixel per module, without quiet zone. Linear symbols are one module
+    /// high, and each row of a PDF417 symbol is one module high.
+    pub modules: BitMatrix,
+    /// The matrix `encode_with_hints` returns for the same arguments.
+    pub matrix: BitMatrix,
+    /// The number of pixels of `matrix` per module, horizontally and vertically. The bars of
+    /// linear symbols, and the linear part of a composite symbol, are stretched vertically to the
+    /// height the writer draws them at.
+    pub scale_x: u32,
+    pub scale_y: u32,
+    /// The position in `matrix` of the top left module of `modules`.
+    pub offset_x: u32,
+    pub offset_y: u32,
+    /// Set for QR Code, Data Matrix, Aztec and PDF417.
+    pub metadata: Option<SymbolMetadata>,
+}
+
+impl EncodedSymbol {
+    /// A symbol drawn at one pixel per module, with no margin.
+    pub fn unscaled(format: BarcodeFormat, matrix: BitMatrix) -> Self {
+        Self::new(format, matrix.clone(), (matrix, Placement::UNSCALED))
+    }
+
+    /// The symbol `modules`, drawn into `matrix` at `placement`.
+    pub(crate) fn new(
+        format: BarcodeFormat,
+        modules: BitMatrix,
+        (matrix, placement): (BitMatrix, Placement),
+    ) -> Self {
+        Self {
+            format,
+            modules,
+            matrix,
+            scale_x: placement.scale_x,
+            scale_y: placement.scale_y,
+            offset_x: placement.offset_x,
+            offset_y: placement.offset_y,
+            metadata: None,
+        }
+    }
+
+    pub(crate) fn with_metadata(self, metadata: SymbolMetadata) -> Self {
+        Self {
+            metadata: Some(metadata),
+            ..self
+        }
+    }
+
+    /// The width of the symbol in modules.
+    pub fn width(&self) -> u32 {
+        self.modules.getWidth()
+    }
+
+    /// The height of the symbol in modules.
+    pub fn height(&self) -> u32 {
+        self.modules.getHeight()
+    }
+}
+
+/// Where a writer drew the modules of a symbol: the size of a module in pixels, and the position
+/// of the top left module.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct Placement {
+    pub scale_x: u32,
+    pub scale_y: u32,
+    pub offset_x: u32,
+    pub offset_y: u32,
+}
+
+impl Placement {
+    pub const UNSCALED: Self = Self::square(1, 0, 0);
+
+    /// Square modules of `scale` pixels, the first at `offset_x`, `offset_y`.
+    pub const fn square(scale: u32, offset_x: u32, offset_y: u32) -> Self {
+        Self {
+            scale_x: scale,
+            scale_y: scale,
+            offset_x,
+            offset_y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod EncodedSymbolTestCase {
+    use crate::{
+        qrcode::decoder::ErrorCorrectionLevel, BarcodeFormat, EncodeHintValue, EncodeHints,
+        MultiFormatWriter, Writer,
+    };
+
+    use super::{SymbolEcLevel, SymbolVersion};
+
+    #[test]
+    fn testQrCode() {
+        let hints = EncodeHints::default().with(EncodeHintValue::ErrorCorrection("M".to_owned()));
+        let symbol = MultiFormatWriter
+            .encode_with_metadata("HELLO WORLD", &BarcodeFormat::QR_CODE, 100, 100, &hints)
+            .expect("encode");
+        assert_eq!((symbol.width(), symbol.height()), (21, 21));
+        // 21 modules and the quiet zone of 4 on each side make 29, scaled 3 times into 100
+        assert_eq!((symbol.scale_x, symbol.scale_y), (3, 3));
+        assert_eq!((symbol.offset_x, symbol.offset_y), (18, 18));
+        assert!(symbol.matrix.get(18, 18) && symbol.matrix.get(20, 20));
+        assert!(!symbol.matrix.get(17, 17));
+
+        let metadata = symbol.metadata.expect("metadata");
+        assert_eq!(metadata.version, SymbolVersion::QrCode(1));
+        assert_eq!(
+            metadata.ec_level,
+            SymbolEcLevel::QrCode(ErrorCorrectionLevel::M)
+        );
+        assert_eq!(metadata.data_codewords, 16);
+        assert_eq!(metadata.ec_codewords, 10);
+        // mode, character count, five pairs of 11 bits and a last character of 6 bits
+        assert_eq!(metadata.data_bits, Some(4 + 9 + 61));
+        assert_eq!(metadata.used_data_codewords, 10);
+        assert_eq!(metadata.capacity_bits, Some(128));
+        assert!(metadata.mask.is_some_and(|mask| mask < 8));
+    }
+
+    #[test]
+    fn testDataMatrix() {
+        let symbol = MultiFormatWriter
+            .encode_with_metadata(
+                "123456",
+                &BarcodeFormat::DATA_MATRIX,
+                0,
+                0,
+                &EncodeHints::default(),
+            )
+            .expect("encode");
+        assert_eq!((symbol.width(), symbol.height()), (10, 10));
+        assert_eq!(symbol.modules, symbol.matrix);
+        assert_eq!((symbol.scale_x, symbol.offset_x), (1, 0));
+
+        let metadata = symbol.metadata.expect("metadata");
+        assert_eq!(
+            metadata.version,
+            SymbolVersion::DataMatrix {
+                rows: 10,
+                columns: 10
+            }
+        );
+        assert_eq!(metadata.data_codewords, 3);
+        assert_eq!(metadata.used_data_codewords, 3);
+        assert_eq!(metadata.ec_level, SymbolEcLevel::DataMatrixEcc200);
+        assert_eq!(metadata.ec_codewords, 5);
+        assert_eq!(metadata.data_bits, Some(24));
+        assert_eq!(metadata.mask, None);
+
+        // one digit pair, then the padding
+        let metadata = MultiFormatWriter
+            .encode_with_metadata(
+                "12",
+                &BarcodeFormat::DATA_MATRIX,
+                0,
+                0,
+                &EncodeHints::default(),
+            )
+            .expect("encode")
+            .metadata
+            .expect("metadata");
+        assert_eq!(metadata.data_codewords, 3);
+        assert_eq!(metadata.used_data_codewords, 1);
+        assert_eq!(metadata.capacity_bits, Some(24));
+    }
+
+    #[test]
+    fn testAztecAndPdf417() {
+        let metadata = MultiFormatWriter
+            .encode_with_metadata(
+                "Hello World",
+                &BarcodeFormat::AZTEC,
+                0,
+                0,
+                &EncodeHints::default(),
+            )
+            .expect("encode")
+            .metadata
+            .expect("metadata");
+        let SymbolVersion::Aztec { compact, layers } = metadata.version else {
+            panic!("not an Aztec version: {:?}", metadata.version);
+        };
+        assert!(compact);
+        assert!(layers >= 1);
+        // H, a lower case latch, ello, space, an upper case shift, W and orld
+        assert_eq!(metadata.data_bits, Some(5 + 5 + 4 * 5 + 5 + 5 + 5 + 4 * 5));
+        assert!(metadata.data_bits <= metadata.capacity_bits);
+        let SymbolEcLevel::Aztec(ecc_percent) = metadata.ec_level else {
+            panic!("not an Aztec EC level: {:?}", metadata.ec_level);
+        };
+        assert!(ecc_percent >= 23);
+        assert!(metadata.ec_codewords * 100 >= 23 * metadata.data_codewords);
+
+        let symbol = MultiFormatWriter
+            .encode_with_metadata(
+                "Hello World",
+                &BarcodeFormat::PDF_417,
+                0,
+                0,
+                &EncodeHints::default(),
+            )
+            .expect("encode");
+        let metadata = symbol.metadata.as_ref().expect("metadata");
+        let SymbolVersion::Pdf417 { rows, columns } = metadata.version else {
+            panic!("not a PDF417 version: {:?}", metadata.version);
+        };
+        assert_eq!(symbol.height(), rows);
+        assert_eq!(symbol.width(), (columns + 4) * 17 + 1);
+        assert_eq!((symbol.scale_x, symbol.scale_y), (1, 4));
+        assert_eq!(metadata.ec_level, SymbolEcLevel::Pdf417(2));
+        assert_eq!(metadata.ec_codewords, 8);
+        assert_eq!(metadata.data_codewords + 8, rows * columns);
+        assert_eq!(metadata.data_bits, None);
+    }
+
+    #[test]
+    fn testLinear() {
+        let symbol = MultiFormatWriter
+            .encode_with_metadata(
+                "12345670",
+                &BarcodeFormat::EAN_8,
+                200,
+                50,
+                &EncodeHints::default(),
+            )
+            .expect("encode");
+        assert_eq!((symbol.width(), symbol.height()), (67, 1));
+        assert_eq!((symbol.scale_x, symbol.scale_y), (2, 50));
+        assert_eq!(symbol.offset_y, 0);
+        assert_eq!(symbol.metadata, None);
+    }
+}
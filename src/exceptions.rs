@@ -2,6 +2,8 @@
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::RXingResult;
+
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Error, Debug, PartialEq, Eq, Clone)]
 pub enum Exceptions {
@@ -33,6 +35,10 @@ pub enum Exceptions {
     ParseException(String),
     #[error("ReaderDecodeException")]
     ReaderDecodeException(),
+    /// The decode was cancelled or ran past its deadline, see [`crate::CancellationToken`]. Holds
+    /// the results found before it stopped.
+    #[error("TimeoutException{}", if .0.is_empty() { String::new() } else { format!(" - {} partial results", .0.len()) })]
+    TimeoutException(Vec<RXingResult>),
 }
 
 impl Exceptions {
@@ -100,4 +106,9 @@ impl Exceptions {
     pub fn parse_with<I: Into<String>>(x: I) -> Self {
         Self::ParseException(x.into())
     }
+
+    pub const TIMEOUT: Self = Self::TimeoutException(Vec::new());
+    pub fn timeout_with(partial_results: Vec<RXingResult>) -> Self {
+        Self::TimeoutException(partial_results)
+    }
 }
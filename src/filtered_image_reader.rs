@@ -1,12 +1,16 @@
 use crate::common::{BitMatrix, HybridBinarizer, Result};
+#[cfg(feature = "parallel")]
+use crate::parallel::first_in_order;
 use crate::{
-    Binarizer, BinaryBitmap, DecodeHints, Exceptions, Luma8LuminanceSource, LuminanceSource, Reader,
+    Binarizer, BinaryBitmap, DecodeHints, Exceptions, Luma8LuminanceSource, LuminanceSource,
+    RXingResult, Reader,
 };
 
 pub const DEFAULT_DOWNSCALE_THRESHHOLD: usize = 500;
 pub const DEFAULT_DOWNSCALE_FACTOR: usize = 3;
 
 /// Passed image data is ignored, only the image data
+#[derive(Clone)]
 pub struct FilteredImageReader<R: Reader>(R);
 
 impl<R: Reader> FilteredImageReader<R> {
@@ -28,6 +32,40 @@ impl<R: Reader> Reader for FilteredImageReader<R> {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> crate::common::Result<crate::RXingResult> {
+        let result = Self::layers(image)?
+            .into_iter()
+            .map(|layer| Self::decode_layer(&mut self.0, layer, hints))
+            .find(Self::found);
+        Self::first_found(result, hints)
+    }
+}
+
+impl<R: Reader> FilteredImageReader<R> {
+    /// [`Reader::decode_with_hints`], with the layers of the pyramid decoded at the same time on
+    /// the rayon thread pool, each with its own copy of the reader. The result is the one
+    /// `decode_with_hints` would return.
+    #[cfg(feature = "parallel")]
+    pub fn decode_with_hints_parallel<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult>
+    where
+        R: Clone + Send + Sync,
+    {
+        let result = first_in_order(
+            Self::layers(image)?
+                .into_iter()
+                .map(|layer| (self.0.clone(), layer))
+                .collect(),
+            |(mut reader, layer)| Self::decode_layer(&mut reader, layer, hints),
+            Self::found,
+        );
+        Self::first_found(result, hints)
+    }
+
+    /// The layers of the pyramid of the image, from full resolution down.
+    fn layers<B: Binarizer>(image: &BinaryBitmap<B>) -> Result<Vec<Luma8LuminanceSource>> {
         let pyramids = LumImagePyramid::new(
             Luma8LuminanceSource::new(
                 image.get_source().get_matrix(),
@@ -38,28 +76,50 @@ impl<R: Reader> Reader for FilteredImageReader<R> {
             DEFAULT_DOWNSCALE_FACTOR,
         )
         .ok_or(Exceptions::ILLEGAL_ARGUMENT)?;
-        for layer in pyramids.layers {
-            let mut b = BinaryBitmap::new(HybridBinarizer::new(layer));
-            for close in [false, true] {
-                if close {
-                    let Ok(_) = b.close() else {
-                        continue;
-                    };
-                }
-                if let Ok(mut res) = self.0.decode_with_hints(&mut b, hints) {
-                    res.putMetadata(
-                        crate::RXingResultMetadataType::FILTERED_CLOSED,
-                        crate::RXingResultMetadataValue::FilteredClosed(close),
-                    );
-                    let resolution = (b.get_width(), b.get_height());
-                    res.putMetadata(
-                        crate::RXingResultMetadataType::FILTERED_RESOLUTION,
-                        crate::RXingResultMetadataValue::FilteredResolution(resolution),
-                    );
-                    return Ok(res);
-                } else {
+        Ok(pyramids.layers)
+    }
+
+    /// A timeout ends the search, as a result does
+    fn found(result: &Result<RXingResult>) -> bool {
+        !matches!(result, Err(Exceptions::NotFoundException(_)))
+    }
+
+    fn first_found(
+        result: Option<Result<RXingResult>>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        if let Some(result) = result {
+            return result;
+        }
+        hints.check_cancelled()?;
+        Err(Exceptions::NOT_FOUND)
+    }
+
+    /// Decodes a layer of the pyramid, then again with its black matrix closed.
+    fn decode_layer(
+        reader: &mut R,
+        layer: Luma8LuminanceSource,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let mut b = BinaryBitmap::new(HybridBinarizer::new(layer));
+        for close in [false, true] {
+            hints.check_cancelled()?;
+            if close {
+                let Ok(_) = b.close() else {
                     continue;
-                }
+                };
+            }
+            if let Ok(mut res) = reader.decode_with_hints(&mut b, hints) {
+                res.putMetadata(
+                    crate::RXingResultMetadataType::FILTERED_CLOSED,
+                    crate::RXingResultMetadataValue::FilteredClosed(close),
+                );
+                let resolution = (b.get_width(), b.get_height());
+                res.putMetadata(
+                    crate::RXingResultMetadataType::FILTERED_RESOLUTION,
+                    crate::RXingResultMetadataValue::FilteredResolution(resolution),
+                );
+                return Ok(res);
             }
         }
         Err(Exceptions::NOT_FOUND)
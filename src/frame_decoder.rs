@@ -0,0 +1,424 @@
+use std::{collections::HashSet, time::Duration};
+
+use crate::{
+    common::HybridBinarizer,
+    multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+    point, BinaryBitmap, DecodeHints, LuminanceSource, MultiFormatReader,
+    MultiUseMultiFormatReader, Point, RXingResult, Reader,
+};
+
+/// How a [`FrameDecoder`] searches frames and decides that a symbol is gone.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameDecoderOptions {
+    /// The number of frames a symbol may go unseen, because of blur or glare, before it is
+    /// reported as disappeared.
+    pub lost_after_frames: u32,
+    /// If set, a symbol is also kept until it has gone unseen this long, so that the window does
+    /// not depend on the frame rate.
+    pub lost_after: Option<Duration>,
+    /// While every tracked symbol is found in its region, the whole frame is still searched for new
+    /// symbols every this many frames. 1 searches every frame.
+    pub full_scan_interval: u32,
+    /// The pixels added on each side of the last position of a symbol to search for it in the next
+    /// frame. At least half the size of the symbol is always added.
+    pub region_margin: u32,
+    /// The distance, in pixels, the center of a symbol must move for an update to be reported.
+    pub movement_threshold: f32,
+}
+
+impl Default for FrameDecoderOptions {
+    fn default() -> Self {
+        Self {
+            lost_after_frames: 5,
+            lost_after: None,
+            full_scan_interval: 5,
+            region_margin: 16,
+            movement_threshold: 2.0,
+        }
+    }
+}
+
+/// A symbol followed across the frames given to a [`FrameDecoder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedSymbol {
+    /// Unique among the symbols of one decoder.
+    pub id: u64,
+    /// The last decode of the symbol, with its position in the last frame it was seen in.
+    pub result: RXingResult,
+    pub first_seen_frame: u64,
+    pub last_seen_frame: u64,
+    /// The timestamp of the last frame the symbol was seen in.
+    pub last_seen: Duration,
+    /// The number of frames the symbol was decoded in.
+    pub times_seen: u32,
+}
+
+/// A change in the symbols in view, reported by [`FrameDecoder::decode_frame`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SymbolEvent {
+    /// A symbol not tracked so far was decoded.
+    Appeared(TrackedSymbol),
+    /// A tracked symbol was decoded again, having moved by more than
+    /// [`FrameDecoderOptions::movement_threshold`].
+    Updated(TrackedSymbol),
+    /// A tracked symbol went unseen for longer than the windows of [`FrameDecoderOptions`].
+    Disappeared(TrackedSymbol),
+}
+
+/**
+ * Decodes a sequence of frames, such as a camera feed, and tracks the symbols in them.
+ *
+ * Each symbol is first looked for in the region it was found in the frame before, with only its
+ * format enabled, and the whole frame is only searched when a tracked symbol was missed, when
+ * nothing is tracked, or every [`FrameDecoderOptions::full_scan_interval`] frames. A symbol decoded
+ * again with the same format and contents near where it was last seen is the same symbol, so it is
+ * reported once when it appears, again only when it moves, and once when it has been gone for
+ * longer than the windows of the options. Two labels with the same contents in view at once are
+ * tracked separately.
+ */
+pub struct FrameDecoder {
+    options: FrameDecoderOptions,
+    hints: DecodeHints,
+    symbols: Vec<TrackedSymbol>,
+    frame: u64,
+    last_full_scan: u64,
+    next_id: u64,
+}
+
+impl FrameDecoder {
+    pub fn new(options: FrameDecoderOptions) -> Self {
+        Self::with_hints(options, DecodeHints::default())
+    }
+
+    /// A decoder that decodes every frame with `hints`.
+    pub fn with_hints(options: FrameDecoderOptions, hints: DecodeHints) -> Self {
+        Self {
+            options,
+            hints,
+            symbols: Vec::new(),
+            frame: 0,
+            last_full_scan: 0,
+            next_id: 0,
+        }
+    }
+
+    /// The symbols tracked after the last frame, including those not seen in it but not yet
+    /// reported as disappeared.
+    pub fn symbols(&self) -> &[TrackedSymbol] {
+        &self.symbols
+    }
+
+    /// The number of frames decoded so far.
+    pub fn frame_count(&self) -> u64 {
+        self.frame
+    }
+
+    /// Forgets all tracked symbols, without reporting them as disappeared.
+    pub fn reset(&mut self) {
+        self.symbols.clear();
+        self.frame = 0;
+        self.last_full_scan = 0;
+    }
+
+    /**
+     * Decodes the next frame, taken at `timestamp`, and returns the changes it brings to the
+     * symbols in view: those that appeared and moved first, in the order they were found, then
+     * those that disappeared.
+     */
+    pub fn decode_frame<L: LuminanceSource>(
+        &mut self,
+        source: L,
+        timestamp: Duration,
+    ) -> Vec<SymbolEvent> {
+        self.frame += 1;
+
+        // 1. look for each tracked symbol where it was last seen
+        let mut found: Vec<RXingResult> = Vec::new();
+        let mut missed = false;
+        for symbol in &self.symbols {
+            match self.decode_region(&source, &symbol.result) {
+                Some(result) if !Self::overlaps(&found, &result) => found.push(result),
+                Some(_) => {}
+                None => missed = true,
+            }
+        }
+
+        // 2. search the whole frame for the symbols not found that way, and for new ones
+        if missed
+            || self.symbols.is_empty()
+            || self.frame - self.last_full_scan >= self.options.full_scan_interval as u64
+        {
+            self.last_full_scan = self.frame;
+            let mut reader =
+                GenericMultipleBarcodeReader::new(MultiUseMultiFormatReader::default());
+            let results = reader
+                .decode_multiple_with_hints(
+                    &mut BinaryBitmap::new(HybridBinarizer::new(source)),
+                    &self.hints,
+                )
+                .unwrap_or_default();
+            for result in results {
+                if !Self::overlaps(&found, &result) {
+                    found.push(result);
+                }
+            }
+        }
+
+        // 3. match what was found to the tracked symbols
+        let mut events = Vec::new();
+        let mut seen = HashSet::new();
+        for result in found {
+            let matched = self
+                .symbols
+                .iter()
+                .enumerate()
+                .filter(|(_, symbol)| {
+                    !seen.contains(&symbol.id)
+                        && symbol.result.getBarcodeFormat() == result.getBarcodeFormat()
+                        && symbol.result.getText() == result.getText()
+                })
+                .filter_map(|(i, symbol)| {
+                    let distance = match (center(&symbol.result), center(&result)) {
+                        (Some(from), Some(to)) => from.distance(to),
+                        _ => 0.0,
+                    };
+                    (distance <= self.reach(&symbol.result)).then_some((i, distance))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1));
+
+            match matched {
+                Some((i, distance)) => {
+                    let symbol = &mut self.symbols[i];
+                    symbol.result = result;
+                    symbol.last_seen_frame = self.frame;
+                    symbol.last_seen = timestamp;
+                    symbol.times_seen += 1;
+                    seen.insert(symbol.id);
+                    if distance > self.options.movement_threshold {
+                        events.push(SymbolEvent::Updated(symbol.clone()));
+                    }
+                }
+                None => {
+                    let symbol = TrackedSymbol {
+                        id: self.next_id,
+                        result,
+                        first_seen_frame: self.frame,
+                        last_seen_frame: self.frame,
+                        last_seen: timestamp,
+                        times_seen: 1,
+                    };
+                    self.next_id += 1;
+                    seen.insert(symbol.id);
+                    events.push(SymbolEvent::Appeared(symbol.clone()));
+                    self.symbols.push(symbol);
+                }
+            }
+        }
+
+        // 4. drop the symbols gone for longer than the windows
+        let frame = self.frame;
+        let options = &self.options;
+        let (kept, lost): (Vec<_>, Vec<_>) = self.symbols.drain(..).partition(|symbol| {
+            frame - symbol.last_seen_frame <= options.lost_after_frames as u64
+                || options
+                    .lost_after
+                    .is_some_and(|window| timestamp.saturating_sub(symbol.last_seen) <= window)
+        });
+        self.symbols = kept;
+        events.extend(lost.into_iter().map(SymbolEvent::Disappeared));
+
+        events
+    }
+
+    /// Decodes the symbol of `last` in the region around its last position, with its format only.
+    fn decode_region<L: LuminanceSource>(
+        &self,
+        source: &L,
+        last: &RXingResult,
+    ) -> Option<RXingResult> {
+        if !source.is_crop_supported() {
+            return None;
+        }
+        let (left, top, right, bottom) = bounds(last.getPoints())?;
+        let size = (right - left).max(bottom - top);
+        let grow = (self.options.region_margin as f32).max(size / 2.0);
+        let left = (left - grow).max(0.0) as usize;
+        let top = (top - grow).max(0.0) as usize;
+        let right = ((right + grow) as usize).min(source.get_width());
+        let bottom = ((bottom + grow) as usize).min(source.get_height());
+        if right <= left || bottom <= top {
+            return None;
+        }
+
+        let region = source.crop(left, top, right - left, bottom - top).ok()?;
+        let mut hints = self.hints.clone();
+        hints.PossibleFormats = Some(HashSet::from([*last.getBarcodeFormat()]));
+        let mut result = MultiFormatReader::default()
+            .decode_with_hints(&mut BinaryBitmap::new(HybridBinarizer::new(region)), &hints)
+            .ok()?;
+        let offset = point(left as f32, top as f32);
+        for p in result.getPointsMut() {
+            *p += offset;
+        }
+        Some(result)
+    }
+
+    /// How far from its last position a decode of the same contents is taken to be the same
+    /// symbol.
+    fn reach(&self, last: &RXingResult) -> f32 {
+        match bounds(last.getPoints()) {
+            Some((left, top, right, bottom)) => {
+                (right - left).max(bottom - top) + self.options.region_margin as f32
+            }
+            None => f32::INFINITY,
+        }
+    }
+
+    /// Whether `result` is the same symbol as one of `found`, decoded twice in one frame.
+    fn overlaps(found: &[RXingResult], result: &RXingResult) -> bool {
+        found.iter().any(|other| {
+            other.getBarcodeFormat() == result.getBarcodeFormat()
+                && other.getText() == result.getText()
+                && match (bounds(other.getPoints()), center(result)) {
+                    (Some((left, top, right, bottom)), Some(c)) => {
+                        c.x >= left && c.x <= right && c.y >= top && c.y <= bottom
+                    }
+                    _ => true,
+                }
+        })
+    }
+}
+
+impl Default for FrameDecoder {
+    fn default() -> Self {
+        Self::new(FrameDecoderOptions::default())
+    }
+}
+
+/// The left, top, right and bottom of `points`.
+fn bounds(points: &[Point]) -> Option<(f32, f32, f32, f32)> {
+    let first = points.first()?;
+    Some(points.iter().fold(
+        (first.x, first.y, first.x, first.y),
+        |(left, top, right, bottom), p| {
+            (left.min(p.x), top.min(p.y), right.max(p.x), bottom.max(p.y))
+        },
+    ))
+}
+
+fn center(result: &RXingResult) -> Option<Point> {
+    let (left, top, right, bottom) = bounds(result.getPoints())?;
+    Some(point((left + right) / 2.0, (top + bottom) / 2.0))
+}
+
+#[cfg(test)]
+mod FrameDecoderTestCase {
+    use std::time::Duration;
+
+    use crate::{
+        common::test_image::TestImage, BarcodeFormat, Luma8LuminanceSource, MultiFormatWriter,
+        Writer,
+    };
+
+    use super::{FrameDecoder, FrameDecoderOptions, SymbolEvent};
+
+    /// A 320 by 200 frame with QR Codes of `contents` at the given top left corners.
+    fn frame(symbols: &[(&str, u32, u32)]) -> Luma8LuminanceSource {
+        let mut frame = TestImage::new(320, 200);
+        for (contents, left, top) in symbols {
+            let matrix = MultiFormatWriter
+                .encode(contents, &BarcodeFormat::QR_CODE, 0, 0)
+                .expect("encode");
+            frame.draw(&matrix, 3, *left, *top);
+        }
+        frame.source()
+    }
+
+    fn names(events: &[SymbolEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| match event {
+                SymbolEvent::Appeared(symbol) => format!("+{}", symbol.result.getText()),
+                SymbolEvent::Updated(symbol) => format!("~{}", symbol.result.getText()),
+                SymbolEvent::Disappeared(symbol) => format!("-{}", symbol.result.getText()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn testTracking() {
+        let mut decoder = FrameDecoder::new(FrameDecoderOptions {
+            lost_after_frames: 2,
+            full_scan_interval: 100,
+            ..Default::default()
+        });
+        let mut at = 0;
+        let mut decode = |decoder: &mut FrameDecoder, symbols: &[(&str, u32, u32)]| {
+            at += 40;
+            names(&decoder.decode_frame(frame(symbols), Duration::from_millis(at)))
+        };
+
+        assert_eq!(decode(&mut decoder, &[("ONE", 20, 20)]), ["+ONE"]);
+        // seen again in place: nothing to report
+        assert_eq!(
+            decode(&mut decoder, &[("ONE", 20, 20)]),
+            Vec::<String>::new()
+        );
+        // found in its region after moving, without a full scan
+        assert_eq!(decode(&mut decoder, &[("ONE", 30, 26)]), ["~ONE"]);
+        assert_eq!(decoder.symbols().len(), 1);
+        assert_eq!(decoder.symbols()[0].times_seen, 3);
+
+        // a flicker of one frame is not a disappearance
+        assert_eq!(decode(&mut decoder, &[]), Vec::<String>::new());
+        assert_eq!(
+            decode(&mut decoder, &[("ONE", 30, 26)]),
+            Vec::<String>::new()
+        );
+
+        // a new symbol is found by the full scan a missed one triggers, and the same contents at
+        // another place is another symbol
+        assert_eq!(
+            decode(&mut decoder, &[("TWO", 200, 20), ("ONE", 30, 120)]),
+            ["+TWO", "+ONE"]
+        );
+        assert_eq!(decoder.symbols().len(), 3);
+        let ids: Vec<u64> = decoder.symbols().iter().map(|symbol| symbol.id).collect();
+        assert_eq!(ids, [0, 1, 2]);
+
+        // the first symbol went unseen a frame before the others
+        assert_eq!(decode(&mut decoder, &[]), Vec::<String>::new());
+        assert_eq!(decode(&mut decoder, &[]), ["-ONE"]);
+        assert_eq!(decoder.symbols()[0].id, 1);
+        let mut gone = decode(&mut decoder, &[]);
+        gone.sort();
+        assert_eq!(gone, ["-ONE", "-TWO"]);
+        assert!(decoder.symbols().is_empty());
+    }
+
+    #[test]
+    fn testTimeWindow() {
+        let mut decoder = FrameDecoder::new(FrameDecoderOptions {
+            lost_after_frames: 1,
+            lost_after: Some(Duration::from_millis(500)),
+            ..Default::default()
+        });
+        let events = decoder.decode_frame(frame(&[("HELLO", 40, 40)]), Duration::ZERO);
+        assert_eq!(names(&events), ["+HELLO"]);
+
+        // many fast frames without the symbol stay within the time window
+        for ms in (10..=500).step_by(10) {
+            assert!(decoder
+                .decode_frame(frame(&[]), Duration::from_millis(ms))
+                .is_empty());
+        }
+        // reappearing within the window is not a new symbol
+        assert!(decoder
+            .decode_frame(frame(&[("HELLO", 40, 40)]), Duration::from_millis(600))
+            .is_empty());
+
+        decoder.decode_frame(frame(&[]), Duration::from_millis(1000));
+        let events = decoder.decode_frame(frame(&[]), Duration::from_millis(1101));
+        assert_eq!(names(&events), ["-HELLO"]);
+    }
+}
@@ -255,6 +255,17 @@ pub fn save_image(file_name: &str, bit_matrix: &BitMatrix) -> Result<()> {
     }
 }
 
+/// Saves `bit_matrix` as an image, drawn with the colours, module size, quiet zone and
+/// resolution in `options`.
+#[cfg(feature = "image")]
+pub fn save_image_with_options(
+    file_name: &str,
+    bit_matrix: &BitMatrix,
+    options: &crate::render::RenderOptions,
+) -> Result<()> {
+    crate::render::save_raster(file_name, crate::render::render(bit_matrix, options)?)
+}
+
 #[cfg(feature = "svg_write")]
 pub fn save_svg(file_name: &str, bit_matrix: &BitMatrix) -> Result<()> {
     let svg: svg::Document = bit_matrix.into();
@@ -267,16 +278,79 @@ pub fn save_svg(file_name: &str, bit_matrix: &BitMatrix) -> Result<()> {
     }
 }
 
+/// Like `save_file`, but raster images are drawn with `options`.
+pub fn save_file_with_options(
+    file_name: &str,
+    bit_matrix: &BitMatrix,
+    options: &crate::render::RenderOptions,
+) -> Result<()> {
+    let ext = PathBuf::from(file_name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if matches!(ext.as_str(), "pdf" | "eps" | "ps") {
+        return crate::render::save_vector(
+            file_name,
+            bit_matrix,
+            options,
+            &crate::render::PrintOptions::default(),
+        );
+    }
+
+    #[cfg(not(feature = "svg_write"))]
+    if ext == "svg" {
+        return Err(Exceptions::unsupported_operation_with(format!(
+            "could not save file '{file_name}': SVG output needs the svg_write feature"
+        )));
+    }
+
+    #[cfg(feature = "svg_write")]
+    if ext == "svg" {
+        return crate::render::save_svg_with_options(
+            file_name,
+            bit_matrix,
+            options,
+            &crate::render::SvgOptions::default(),
+        );
+    }
+
+    #[cfg(feature = "image")]
+    if !ext.is_empty() && ext != "txt" {
+        return save_image_with_options(file_name, bit_matrix, options);
+    }
+
+    #[cfg(not(feature = "image"))]
+    let _ = options;
+
+    save_file(file_name, bit_matrix)
+}
+
 pub fn save_file(file_name: &str, bit_matrix: &BitMatrix) -> Result<()> {
     let path = PathBuf::from(file_name);
 
-    #[allow(unused_variables)]
     let ext: String = if let Some(e) = path.extension() {
         e.to_string_lossy().to_string()
     } else {
         String::default()
     };
 
+    if matches!(ext.as_str(), "pdf" | "eps" | "ps") {
+        return crate::render::save_vector(
+            file_name,
+            bit_matrix,
+            &crate::render::RenderOptions::default(),
+            &crate::render::PrintOptions::default(),
+        );
+    }
+
+    #[cfg(not(feature = "svg_write"))]
+    if ext == "svg" {
+        return Err(Exceptions::unsupported_operation_with(format!(
+            "could not save file '{file_name}': SVG output needs the svg_write feature"
+        )));
+    }
+
     #[cfg(feature = "svg_write")]
     if ext == "svg" {
         return save_svg(file_name, bit_matrix);
@@ -47,6 +47,20 @@ pub type RXingResultPointCallback = PointCallback;
 mod decode_hints;
 pub use decode_hints::*;
 
+mod decode_region;
+
+mod cancellation_token;
+pub use cancellation_token::*;
+
+mod diagnostics;
+pub use diagnostics::*;
+
+mod best_effort;
+pub use best_effort::*;
+
+#[cfg(feature = "parallel")]
+mod parallel;
+
 mod writer;
 pub use writer::*;
 
@@ -85,13 +99,17 @@ pub use planar_yuv_luminance_source::*;
 mod rgb_luminance_source;
 pub use rgb_luminance_source::*;
 
+pub mod composite;
 pub mod datamatrix;
 pub mod multi;
 pub mod oned;
 pub mod pdf417;
+pub mod postal;
 
 mod multi_format_writer;
 pub use multi_format_writer::*;
+mod encoded_symbol;
+pub use encoded_symbol::*;
 mod multi_use_multi_format_reader;
 pub use multi_use_multi_format_reader::*;
 
@@ -101,12 +119,20 @@ pub use multi_format_reader::*;
 // Simple methods to help detect barcodes in common situations
 pub mod helpers;
 
+pub mod render;
+
 mod luma_luma_source;
 pub use luma_luma_source::*;
 
 mod filtered_image_reader;
 pub use filtered_image_reader::*;
 
+mod frame_decoder;
+pub use frame_decoder::*;
+
+mod structured_append_collector;
+pub use structured_append_collector::*;
+
 #[cfg(feature = "svg_read")]
 mod svg_luminance_source;
 #[cfg(feature = "svg_read")]
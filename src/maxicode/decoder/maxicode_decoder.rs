@@ -21,7 +21,7 @@ use crate::{
         reedsolomon::{get_predefined_genericgf, PredefinedGenericGF, ReedSolomonDecoder},
         BitMatrix, DecoderRXingResult, Result,
     },
-    DecodeHints, Exceptions,
+    BarcodeFormat, CodewordRecorder, DecodeHints, Exceptions,
 };
 
 use super::{decoded_bit_stream_parser, BitMatrixParser};
@@ -47,23 +47,27 @@ pub fn decode(bits: &BitMatrix) -> Result<DecoderRXingResult> {
     decode_with_hints(bits, &DecodeHints::default())
 }
 
-pub fn decode_with_hints(bits: &BitMatrix, _hints: &DecodeHints) -> Result<DecoderRXingResult> {
+pub fn decode_with_hints(bits: &BitMatrix, hints: &DecodeHints) -> Result<DecoderRXingResult> {
     let parser = BitMatrixParser::new(bits);
     let mut codewords = parser.readCodewords();
+    let mut recorder = CodewordRecorder::new(hints, BarcodeFormat::MAXICODE);
 
-    correctErrors(&mut codewords, 0, 10, 10, ALL)?;
+    let mut errorsCorrected = correctErrors(&mut codewords, 0, 10, 10, ALL, &mut recorder)?;
     let mode = codewords[0] & 0x0F;
     let mut datawords;
+    let ecCodewords;
     match mode {
         2..=4 => {
-            correctErrors(&mut codewords, 20, 84, 40, EVEN)?;
-            correctErrors(&mut codewords, 20, 84, 40, ODD)?;
+            errorsCorrected += correctErrors(&mut codewords, 20, 84, 40, EVEN, &mut recorder)?;
+            errorsCorrected += correctErrors(&mut codewords, 20, 84, 40, ODD, &mut recorder)?;
             datawords = vec![0u8; 94];
+            ecCodewords = 50;
         }
         5 => {
-            correctErrors(&mut codewords, 20, 68, 56, EVEN)?;
-            correctErrors(&mut codewords, 20, 68, 56, ODD)?;
+            errorsCorrected += correctErrors(&mut codewords, 20, 68, 56, EVEN, &mut recorder)?;
+            errorsCorrected += correctErrors(&mut codewords, 20, 68, 56, ODD, &mut recorder)?;
             datawords = vec![0u8; 78];
+            ecCodewords = 66;
         }
         _ => return Err(Exceptions::NOT_FOUND),
     }
@@ -74,7 +78,10 @@ pub fn decode_with_hints(bits: &BitMatrix, _hints: &DecodeHints) -> Result<Decod
     datawords[10..datawords_len].clone_from_slice(&codewords[20..datawords_len + 10]);
     // System.arraycopy(codewords, 20, datawords, 10, datawords.length - 10);
 
-    decoded_bit_stream_parser::decode(&datawords, mode)
+    let mut result = decoded_bit_stream_parser::decode(&datawords, mode)?;
+    result.setErrorsCorrected(errorsCorrected);
+    result.setErrorCorrectionCapacity(ecCodewords);
+    Ok(result)
 }
 
 fn correctErrors(
@@ -83,7 +90,8 @@ fn correctErrors(
     dataCodewords: u32,
     ecCodewords: u32,
     mode: u32,
-) -> Result<()> {
+    recorder: &mut CodewordRecorder,
+) -> Result<usize> {
     let codewords = dataCodewords + ecCodewords;
 
     // in EVEN or ODD mode only half the codewords
@@ -97,7 +105,9 @@ fn correctErrors(
         }
     }
 
-    RS_DECODER.decode(&mut codewordsInts, (ecCodewords / divisor) as i32)?;
+    recorder.received((dataCodewords / divisor) as usize, &codewordsInts);
+    let errorsCorrected = RS_DECODER.decode(&mut codewordsInts, (ecCodewords / divisor) as i32)?;
+    recorder.corrected(&codewordsInts);
 
     // Copy back into array of bytes -- only need to worry about the bytes that were data
     // We don't care about errors in the error-correction codewords
@@ -107,5 +117,5 @@ fn correctErrors(
             codewordBytes[(i + start) as usize] = codewordsInts[(i / divisor) as usize] as u8;
         }
     }
-    Ok(())
+    Ok(errorsCorrected)
 }
@@ -328,53 +328,82 @@ pub fn detect(image: &BitMatrix, try_harder: bool) -> Result<MaxicodeDetectionRe
     circles.sort_by(compare_circle);
 
     for circle in circles.iter_mut() {
-        // build a box around this circle, trying to find the barcode
-        let Ok(symbol_box) = box_symbol(image, circle) else {
-            if try_harder {
-                continue;
-            } else {
-                return Err(Exceptions::NOT_FOUND);
-            }
-        };
-        let grid_sampler = DefaultGridSampler;
-
-        let [tl, bl, tr, br] = symbol_box.0;
+        match sample_symbol(image, circle) {
+            Ok(result) => return Ok(result),
+            Err(_) if try_harder => continue,
+            Err(e) => return Err(e),
+        }
+    }
 
-        let target_width = Point::distance(tl, tr);
-        let target_height = Point::distance(br, tr);
+    Err(Exceptions::NOT_FOUND)
+}
 
-        // let target_width = (tr.0 - tl.0).round().abs() as u32;
-        // let target_height = (br.1 - tr.1).round().abs() as u32;
+/// Detects every MaxiCode in the image. The bullseyes found are grouped by center, each group
+/// being one symbol, and the best sampled bullseye of each group is returned, in row order.
+pub fn detect_multiple(image: &BitMatrix) -> Result<Vec<MaxicodeDetectionResult>> {
+    let Some(circles) = find_concentric_circles(image) else {
+        return Err(Exceptions::NOT_FOUND);
+    };
 
-        let dst = Quadrilateral::new(
-            point(0.0, 0.0),
-            point(target_width, 0.0),
-            point(target_width, target_height),
-            point(0.0, target_height),
-        );
-        let src = Quadrilateral::new(tl, tr, br, bl);
-
-        let Ok((bits, _)) = grid_sampler.sample_grid_detailed(
-            image,
-            target_width.round() as u32,
-            target_height.round() as u32,
-            dst,
-            src,
-        ) else {
-            if try_harder {
-                continue;
-            } else {
-                return Err(Exceptions::NOT_FOUND);
-            }
-        };
-        return Ok(MaxicodeDetectionResult {
-            bits,
-            points: symbol_box.0.to_vec(),
-            rotation: symbol_box.1,
-        });
+    let mut groups: Vec<Vec<Circle>> = Vec::new();
+    for circle in circles {
+        let center: Point = circle.center.into();
+        match groups
+            .iter_mut()
+            .find(|group| Point::distance(group[0].center.into(), center) <= group[0].radius as f32)
+        {
+            Some(group) => group.push(circle),
+            None => groups.push(vec![circle]),
+        }
     }
 
-    Err(Exceptions::NOT_FOUND)
+    Ok(groups
+        .into_iter()
+        .filter_map(|mut group| {
+            group.sort_by(compare_circle);
+            group
+                .iter_mut()
+                .find_map(|circle| sample_symbol(image, circle).ok())
+        })
+        .collect())
+}
+
+/// Builds a box around the symbol of a bullseye and samples it.
+fn sample_symbol(image: &BitMatrix, circle: &mut Circle) -> Result<MaxicodeDetectionResult> {
+    // build a box around this circle, trying to find the barcode
+    let symbol_box = box_symbol(image, circle)?;
+    let grid_sampler = DefaultGridSampler;
+
+    let [tl, bl, tr, br] = symbol_box.0;
+
+    let target_width = Point::distance(tl, tr);
+    let target_height = Point::distance(br, tr);
+
+    // let target_width = (tr.0 - tl.0).round().abs() as u32;
+    // let target_height = (br.1 - tr.1).round().abs() as u32;
+
+    let dst = Quadrilateral::new(
+        point(0.0, 0.0),
+        point(target_width, 0.0),
+        point(target_width, target_height),
+        point(0.0, target_height),
+    );
+    let src = Quadrilateral::new(tl, tr, br, bl);
+
+    let Ok((bits, _)) = grid_sampler.sample_grid_detailed(
+        image,
+        target_width.round() as u32,
+        target_height.round() as u32,
+        dst,
+        src,
+    ) else {
+        return Err(Exceptions::NOT_FOUND);
+    };
+    Ok(MaxicodeDetectionResult {
+        bits,
+        points: symbol_box.0.to_vec(),
+        rotation: symbol_box.1,
+    })
 }
 
 /// Locate concentric circles.
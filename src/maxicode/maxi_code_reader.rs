@@ -15,9 +15,10 @@
  */
 
 use crate::{
-    common::{BitMatrix, DetectorRXingResult, Result},
-    BarcodeFormat, Binarizer, DecodeHints, Exceptions, ImmutableReader, RXingResult,
-    RXingResultMetadataType, Reader,
+    common::{BitMatrix, DecoderRXingResult, DetectorRXingResult, Result},
+    decode_region::decode_in_region,
+    BarcodeFormat, Binarizer, DecodeHints, EstimatedModuleSize, Exceptions, ImmutableReader, Point,
+    RXingResult, RXingResultMetadataType, Reader,
 };
 
 use super::{decoder::maxicode_decoder, detector};
@@ -25,7 +26,7 @@ use super::{decoder::maxicode_decoder, detector};
 /**
  * This implementation can detect and decode a MaxiCode in an image.
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MaxiCodeReader {
     // private final Decoder decoder = new Decoder();
 }
@@ -59,7 +60,9 @@ impl Reader for MaxiCodeReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<crate::RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -69,7 +72,9 @@ impl ImmutableReader for MaxiCodeReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -126,23 +131,71 @@ impl MaxiCodeReader {
         let try_harder = hints.TryHarder.unwrap_or(false);
 
         let mut rotation = None;
+        let moduleSize;
 
         let decoderRXingResult = if try_harder {
-            let result = detector::detect(image.get_black_matrix_mut(), try_harder)?;
+            let result = hints.record_rejection(
+                BarcodeFormat::MAXICODE,
+                None,
+                detector::detect(image.get_black_matrix_mut(), try_harder),
+            )?;
+            if let Some(diagnostics) = &hints.Diagnostics {
+                diagnostics.quadrilateral(BarcodeFormat::MAXICODE, result.getPoints());
+            }
             rotation = Some(result.rotation());
+            moduleSize = EstimatedModuleSize::from_corners(
+                result.getPoints(),
+                Self::MATRIX_WIDTH,
+                Self::MATRIX_HEIGHT,
+            );
             let parsed_result = detector::read_bits(result.getBits())?;
-            maxicode_decoder::decode_with_hints(&parsed_result, hints)?
+            Self::decode_candidate(hints, &parsed_result, result.getPoints().first().copied())?
         } else {
             let bits = Self::extractPureBits(image.get_black_matrix())?;
-            maxicode_decoder::decode_with_hints(&bits, hints)?
+            moduleSize =
+                EstimatedModuleSize::from_pure_symbol(image.get_black_matrix(), Self::MATRIX_WIDTH);
+            Self::decode_candidate(hints, &bits, None)?
         };
 
         // let bits = Self::extractPureBits(image.getBlackMatrix())?;
         // let decoderRXingResult = maxicode_decoder::decode_with_hints(bits, hints)?;
+        Ok(Self::build_result(
+            &decoderRXingResult,
+            Vec::new(),
+            rotation,
+            moduleSize,
+        ))
+    }
+
+    /// Decodes the sampled `bits` of a symbol at `location`, recording them with the diagnostics
+    /// of the `hints`, if any, and why it was rejected if it does not decode.
+    fn decode_candidate(
+        hints: &DecodeHints,
+        bits: &BitMatrix,
+        location: Option<Point>,
+    ) -> Result<DecoderRXingResult> {
+        if let Some(diagnostics) = &hints.Diagnostics {
+            diagnostics.sampled_grid(BarcodeFormat::MAXICODE, bits);
+        }
+        hints.record_rejection(
+            BarcodeFormat::MAXICODE,
+            location,
+            maxicode_decoder::decode_with_hints(bits, hints),
+        )
+    }
+
+    /// Builds the result, with its metadata, of a decoded symbol found at `points` and turned by
+    /// `rotation` degrees.
+    pub(crate) fn build_result(
+        decoderRXingResult: &DecoderRXingResult,
+        points: Vec<Point>,
+        rotation: Option<f32>,
+        moduleSize: Option<EstimatedModuleSize>,
+    ) -> RXingResult {
         let mut result = RXingResult::new(
             decoderRXingResult.getText(),
             decoderRXingResult.getRawBytes().clone(),
-            Vec::new(),
+            points,
             BarcodeFormat::MAXICODE,
         );
 
@@ -162,7 +215,19 @@ impl MaxiCodeReader {
                 )
             }
         }
+        if let Some(errorCorrection) = decoderRXingResult.getErrorCorrectionStats() {
+            result.putMetadata(
+                RXingResultMetadataType::ERRORS_CORRECTED,
+                crate::RXingResultMetadataValue::ErrorsCorrected(errorCorrection),
+            );
+        }
+        if let Some(moduleSize) = moduleSize {
+            result.putMetadata(
+                RXingResultMetadataType::MODULE_SIZE,
+                crate::RXingResultMetadataValue::ModuleSize(moduleSize),
+            );
+        }
 
-        Ok(result)
+        result
     }
 }
@@ -0,0 +1,121 @@
+use crate::{
+    aztec::{decoder, detector::Detector, AztecReader},
+    common::{DetectorRXingResult, Result},
+    Binarizer, BinaryBitmap, DecodeHints, EstimatedModuleSize, Exceptions, RXingResult,
+};
+
+use super::{is_already_found, MultipleBarcodeReader};
+
+/**
+ * This implementation can detect and decode all Aztec codes in an image in one pass, from every
+ * bull's eye in it. Each result carries the corners of its symbol.
+ */
+#[derive(Default, Clone)]
+pub struct AztecMultiReader;
+
+impl MultipleBarcodeReader for AztecMultiReader {
+    fn decode_multiple<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<Vec<RXingResult>> {
+        self.decode_multiple_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_multiple_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<Vec<RXingResult>> {
+        let mut detector = Detector::new(image.get_black_matrix());
+        let mut symbols = detector
+            .detect_multiple(false)
+            .into_iter()
+            .map(|symbol| (symbol, false))
+            .collect::<Vec<_>>();
+        symbols.extend(
+            detector
+                .detect_multiple(true)
+                .into_iter()
+                .map(|symbol| (symbol, true)),
+        );
+
+        let mut results = Vec::new();
+        for (symbol, mirrored) in symbols {
+            if is_already_found(&results, symbol.getPoints()) {
+                continue;
+            }
+            match decoder::decode(&symbol) {
+                Ok(decoded) => {
+                    if let Some(cb) = &hints.NeedResultPointCallback {
+                        for point in symbol.getPoints() {
+                            cb(*point);
+                        }
+                    }
+                    let moduleSize = EstimatedModuleSize::from_corners(
+                        symbol.getPoints(),
+                        symbol.getBits().getWidth(),
+                        symbol.getBits().getHeight(),
+                    );
+                    results.push(AztecReader::build_result(
+                        &decoded,
+                        symbol.getPoints(),
+                        mirrored,
+                        moduleSize,
+                    ))
+                }
+                // a candidate that does not decode is not a symbol, or a damaged one
+                Err(_) => continue,
+            }
+        }
+
+        if results.is_empty() {
+            return Err(Exceptions::NOT_FOUND);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod AztecMultiReaderTestCase {
+    use std::collections::HashSet;
+
+    use crate::{
+        common::test_image::TestImage, multi::MultipleBarcodeReader, BarcodeFormat,
+        MultiFormatWriter, Writer,
+    };
+
+    use super::AztecMultiReader;
+
+    #[test]
+    fn testSheetOfLabels() {
+        let contents: Vec<String> = (0..9).map(|i| format!("Parcel {i} of 9")).collect();
+        let mut sheet = TestImage::new(3 * 160, 3 * 160);
+        for (i, text) in contents.iter().enumerate() {
+            let symbol = MultiFormatWriter
+                .encode(text, &BarcodeFormat::AZTEC, 100, 100)
+                .expect("encode");
+            let left = (i as u32 % 3) * 160 + 30;
+            let top = (i as u32 / 3) * 160 + 30;
+            sheet.draw(&symbol, 1, left, top);
+        }
+
+        let results = AztecMultiReader
+            .decode_multiple(&mut sheet.bitmap())
+            .expect("decode");
+
+        assert_eq!(results.len(), contents.len());
+        let found: HashSet<&str> = results.iter().map(|result| result.getText()).collect();
+        assert_eq!(found, contents.iter().map(String::as_str).collect());
+        for result in &results {
+            let index = contents
+                .iter()
+                .position(|text| text == result.getText())
+                .expect("content") as u32;
+            // the corners of each symbol lie on the label it was drawn on
+            for point in result.getPoints() {
+                assert_eq!((point.x / 160.0) as u32, index % 3);
+                assert_eq!((point.y / 160.0) as u32, index / 3);
+            }
+        }
+    }
+}
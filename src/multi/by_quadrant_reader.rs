@@ -15,7 +15,9 @@
  */
 
 use crate::common::Result;
-use crate::{point, Binarizer, DecodeHints, Exceptions, Point, RXingResult, Reader};
+#[cfg(feature = "parallel")]
+use crate::parallel::first_in_order;
+use crate::{point, Binarizer, BinaryBitmap, DecodeHints, Exceptions, Point, RXingResult, Reader};
 
 /**
  * This class attempts to decode a barcode from an image, not by scanning the whole image,
@@ -26,6 +28,7 @@ use crate::{point, Binarizer, DecodeHints, Exceptions, Point, RXingResult, Reade
  *
  * @see GenericMultipleBarcodeReader
  */
+#[derive(Clone)]
 pub struct ByQuadrantReader<T: Reader>(T);
 impl<T: Reader> Reader for ByQuadrantReader<T> {
     fn decode<B: Binarizer>(&mut self, image: &mut crate::BinaryBitmap<B>) -> Result<RXingResult> {
@@ -37,84 +40,91 @@ impl<T: Reader> Reader for ByQuadrantReader<T> {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<crate::RXingResult> {
-        let width = image.get_width();
-        let height = image.get_height();
-        let halfWidth = width / 2;
-        let halfHeight = height / 2;
-
-        let attempt = self
-            .0
-            .decode_with_hints(&mut image.crop(0, 0, halfWidth, halfHeight), hints);
-        // No need to call makeAbsolute as results will be relative to original top left here
-        // This is a match because only NotFoundExceptions should be ignored
-        match attempt {
-            Err(Exceptions::NotFoundException(_)) => {}
-            _ => return attempt,
-        }
+        let (corners, halfWidth, halfHeight) = Self::quadrants(image);
+        corners
+            .into_iter()
+            .map(|(left, top)| {
+                let mut quadrant = image.crop(left, top, halfWidth, halfHeight);
+                Self::decode_quadrant(&mut self.0, &mut quadrant, hints, left, top)
+            })
+            .find(Self::found)
+            .unwrap_or(Err(Exceptions::NOT_FOUND))
+    }
 
-        // try {
-        let result = self
-            .0
-            .decode_with_hints(&mut image.crop(halfWidth, 0, halfWidth, halfHeight), hints);
-        // This is a match because only NotFoundExceptions should be ignored
-        match result {
-            Ok(res) => {
-                let points = Self::makeAbsolute(res.getPoints(), halfWidth as f32, 0.0);
-                return Ok(res.with_point(points));
-            }
-            Err(Exceptions::NotFoundException(_)) => {}
-            _ => return result,
-        }
+    fn reset(&mut self) {
+        self.0.reset()
+    }
+}
 
-        let result = self
-            .0
-            .decode_with_hints(&mut image.crop(0, halfHeight, halfWidth, halfHeight), hints);
-        // This is a match because only NotFoundExceptions should be ignored
-        match result {
-            Ok(res) => {
-                let points = Self::makeAbsolute(res.getPoints(), 0.0, halfHeight as f32);
-                return Ok(res.with_point(points));
-            }
-            Err(Exceptions::NotFoundException(_)) => {}
-            _ => return result,
-        }
+impl<T: Reader> ByQuadrantReader<T> {
+    pub fn new(delegate: T) -> Self {
+        Self(delegate)
+    }
 
-        let result = self.0.decode_with_hints(
-            &mut image.crop(halfWidth, halfHeight, halfWidth, halfHeight),
-            hints,
-        );
-        // This is a match because only NotFoundExceptions should be ignored
-        match result {
-            Ok(res) => {
-                let points =
-                    Self::makeAbsolute(res.getPoints(), halfWidth as f32, halfHeight as f32);
-                return Ok(res.with_point(points));
-            }
-            Err(Exceptions::NotFoundException(_)) => {}
-            _ => return result,
-        }
+    /// [`Reader::decode_with_hints`], with the quadrants decoded at the same time on the rayon
+    /// thread pool, each with its own copy of the delegate. The result is the one
+    /// `decode_with_hints` would return.
+    #[cfg(feature = "parallel")]
+    pub fn decode_with_hints_parallel<B>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult>
+    where
+        T: Clone + Send + Sync,
+        B: Binarizer + Send,
+    {
+        let (corners, halfWidth, halfHeight) = Self::quadrants(image);
+        first_in_order(
+            corners
+                .into_iter()
+                .map(|(left, top)| {
+                    let quadrant = image.crop(left, top, halfWidth, halfHeight);
+                    (self.0.clone(), quadrant, left, top)
+                })
+                .collect(),
+            |(mut delegate, mut quadrant, left, top)| {
+                Self::decode_quadrant(&mut delegate, &mut quadrant, hints, left, top)
+            },
+            Self::found,
+        )
+        .unwrap_or(Err(Exceptions::NOT_FOUND))
+    }
 
+    /// The top left corners of the four quadrants, then of the center, and the size of each.
+    fn quadrants<B: Binarizer>(image: &BinaryBitmap<B>) -> ([(usize, usize); 5], usize, usize) {
+        let width = image.get_width();
+        let height = image.get_height();
+        let halfWidth = width / 2;
+        let halfHeight = height / 2;
         let quarterWidth = halfWidth / 2;
         let quarterHeight = halfHeight / 2;
-        let mut center = image.crop(quarterWidth, quarterHeight, halfWidth, halfHeight);
-        let result = self.0.decode_with_hints(&mut center, hints)?;
 
-        let points = Self::makeAbsolute(
-            result.getPoints(),
-            quarterWidth as f32,
-            quarterHeight as f32,
-        );
-        Ok(result.with_point(points))
+        let corners = [
+            (0, 0),
+            (halfWidth, 0),
+            (0, halfHeight),
+            (halfWidth, halfHeight),
+            (quarterWidth, quarterHeight),
+        ];
+        (corners, halfWidth, halfHeight)
     }
 
-    fn reset(&mut self) {
-        self.0.reset()
+    /// Only NotFoundExceptions should be ignored
+    fn found(result: &Result<RXingResult>) -> bool {
+        !matches!(result, Err(Exceptions::NotFoundException(_)))
     }
-}
 
-impl<T: Reader> ByQuadrantReader<T> {
-    pub fn new(delegate: T) -> Self {
-        Self(delegate)
+    fn decode_quadrant<B: Binarizer>(
+        delegate: &mut T,
+        quadrant: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+        left: usize,
+        top: usize,
+    ) -> Result<RXingResult> {
+        let result = delegate.decode_with_hints(quadrant, hints)?;
+        let points = Self::makeAbsolute(result.getPoints(), left as f32, top as f32);
+        Ok(result.with_point(points))
     }
 
     fn makeAbsolute(points: &[Point], leftOffset: f32, topOffset: f32) -> Vec<Point> {
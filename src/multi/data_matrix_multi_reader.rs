@@ -0,0 +1,114 @@
+use crate::{
+    common::{DetectorRXingResult, Result},
+    datamatrix::{detector::zxing_cpp_detector, DataMatrixReader},
+    Binarizer, BinaryBitmap, DecodeHints, EstimatedModuleSize, Exceptions, RXingResult,
+};
+
+use super::{is_already_found, MultipleBarcodeReader};
+
+/**
+ * This implementation can detect and decode all Data Matrix codes in an image in one pass, such as
+ * a sheet of labels. Each result carries the corners of its symbol.
+ */
+#[derive(Default, Clone)]
+pub struct DataMatrixMultiReader;
+
+impl MultipleBarcodeReader for DataMatrixMultiReader {
+    fn decode_multiple<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<Vec<RXingResult>> {
+        self.decode_multiple_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_multiple_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<Vec<RXingResult>> {
+        let mut results = Vec::new();
+        for symbol in zxing_cpp_detector::detect_multiple(image.get_black_matrix())? {
+            if is_already_found(&results, symbol.getPoints()) {
+                continue;
+            }
+            match DataMatrixReader::decode_bits(symbol.getBits()) {
+                Ok(decoded) => {
+                    if let Some(cb) = &hints.NeedResultPointCallback {
+                        for point in symbol.getPoints() {
+                            cb(*point);
+                        }
+                    }
+                    results.push(DataMatrixReader::build_result(
+                        &decoded,
+                        symbol.getPoints().to_vec(),
+                        EstimatedModuleSize::from_corners(
+                            symbol.getPoints(),
+                            symbol.getBits().getWidth(),
+                            symbol.getBits().getHeight(),
+                        ),
+                    ))
+                }
+                // a candidate that does not decode is not a symbol, or a damaged one
+                Err(_) => continue,
+            }
+        }
+
+        if results.is_empty() {
+            return Err(Exceptions::NOT_FOUND);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod DataMatrixMultiReaderTestCase {
+    use std::collections::HashSet;
+
+    use crate::{
+        common::test_image::TestImage, multi::MultipleBarcodeReader, BarcodeFormat,
+        MultiFormatWriter, Writer,
+    };
+
+    use super::DataMatrixMultiReader;
+
+    /// Lays the symbols out on a sheet of `columns` labels of 160 by 160 pixels.
+    fn sheet(format: BarcodeFormat, contents: &[String], columns: u32) -> TestImage {
+        let rows = (contents.len() as u32).div_ceil(columns);
+        let mut sheet = TestImage::new(columns * 160, rows * 160);
+        for (i, text) in contents.iter().enumerate() {
+            let symbol = MultiFormatWriter
+                .encode(text, &format, 100, 100)
+                .expect("encode");
+            let left = (i as u32 % columns) * 160 + 30;
+            let top = (i as u32 / columns) * 160 + 30;
+            sheet.draw(&symbol, 1, left, top);
+        }
+        sheet
+    }
+
+    #[test]
+    fn testSheetOfLabels() {
+        let contents: Vec<String> = (0..12).map(|i| format!("LABEL-{i:03}")).collect();
+        let sheet = sheet(BarcodeFormat::DATA_MATRIX, &contents, 4);
+        let results = DataMatrixMultiReader
+            .decode_multiple(&mut sheet.bitmap())
+            .expect("decode");
+
+        assert_eq!(results.len(), contents.len());
+        let found: HashSet<&str> = results.iter().map(|result| result.getText()).collect();
+        assert_eq!(found, contents.iter().map(String::as_str).collect());
+        for result in &results {
+            let index = contents
+                .iter()
+                .position(|text| text == result.getText())
+                .expect("content") as u32;
+            let points = result.getPoints();
+            assert_eq!(points.len(), 4);
+            // every corner lies on the label the symbol was drawn on
+            for point in points {
+                assert_eq!((point.x / 160.0) as u32, index % 4);
+                assert_eq!((point.y / 160.0) as u32, index / 4);
+            }
+        }
+    }
+}
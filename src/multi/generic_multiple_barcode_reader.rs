@@ -14,6 +14,8 @@
  * limitations under the License.
  */
 
+#[cfg(feature = "parallel")]
+use crate::parallel::map_in_order;
 use crate::{
     common::{Quadrilateral, Result},
     point, Binarizer, BinaryBitmap, DecodeHints, Exceptions, Point, RXingResult, Reader,
@@ -35,7 +37,7 @@ use super::MultipleBarcodeReader;
  *
  * @author Sean Owen
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct GenericMultipleBarcodeReader<T: Reader>(T);
 
 impl<T: Reader> MultipleBarcodeReader for GenericMultipleBarcodeReader<T> {
@@ -52,8 +54,39 @@ impl<T: Reader> MultipleBarcodeReader for GenericMultipleBarcodeReader<T> {
         hints: &DecodeHints,
     ) -> Result<Vec<RXingResult>> {
         let mut results = Vec::new();
-        self.do_decode_multiple(image, hints, &mut results, 0, 0, 0);
+        let finished = self.do_decode_multiple(image, hints, &mut results, 0, 0, 0);
+        Self::unique_results(results, finished)
+    }
+}
+impl<T: Reader> GenericMultipleBarcodeReader<T> {
+    const MIN_DIMENSION_TO_RECUR: f32 = 100.0;
+    const MAX_DEPTH: u32 = 4;
+
+    pub fn new(delegate: T) -> Self {
+        Self(delegate)
+    }
+
+    /// [`MultipleBarcodeReader::decode_multiple_with_hints`], with the sub-images around each
+    /// barcode searched at the same time on the rayon thread pool, each with its own copy of the
+    /// delegate. The results are the ones `decode_multiple_with_hints` would return, in the same
+    /// order.
+    #[cfg(feature = "parallel")]
+    pub fn decode_multiple_with_hints_parallel<B>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<Vec<RXingResult>>
+    where
+        T: Clone + Send + Sync,
+        B: Binarizer + Send,
+    {
+        let mut results = Vec::new();
+        let finished = self.do_decode_multiple_parallel(image, hints, &mut results, 0, 0, 0);
+        Self::unique_results(results, finished)
+    }
 
+    /// Drops the results found again in a sub-image, and reports a timeout with the rest.
+    fn unique_results(results: Vec<RXingResult>, finished: Result<()>) -> Result<Vec<RXingResult>> {
         let unique_results: Vec<RXingResult> = results
             .iter()
             .enumerate()
@@ -100,21 +133,85 @@ impl<T: Reader> MultipleBarcodeReader for GenericMultipleBarcodeReader<T> {
             .cloned()
             .collect();
 
+        if finished.is_err() {
+            return Err(Exceptions::timeout_with(unique_results));
+        }
         if unique_results.is_empty() {
             return Err(Exceptions::NOT_FOUND);
         }
         Ok(unique_results)
     }
-}
-impl<T: Reader> GenericMultipleBarcodeReader<T> {
-    const MIN_DIMENSION_TO_RECUR: f32 = 100.0;
-    const MAX_DEPTH: u32 = 4;
 
-    pub fn new(delegate: T) -> Self {
-        Self(delegate)
+    fn do_decode_multiple<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+        results: &mut Vec<RXingResult>,
+        xOffset: u32,
+        yOffset: u32,
+        currentDepth: u32,
+    ) -> Result<()> {
+        let regions = self.decode_image(image, hints, results, xOffset, yOffset, currentDepth)?;
+        for (left, top, width, height) in regions {
+            self.do_decode_multiple(
+                &mut image.crop(left, top, width, height),
+                hints,
+                results,
+                xOffset + left as u32,
+                yOffset + top as u32,
+                currentDepth + 1,
+            )?;
+        }
+        Ok(())
     }
 
-    fn do_decode_multiple<B: Binarizer>(
+    /// Each sub-image is searched with its own copy of the reader, and the results are put
+    /// together in the order the serial search finds them.
+    #[cfg(feature = "parallel")]
+    fn do_decode_multiple_parallel<B>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+        results: &mut Vec<RXingResult>,
+        xOffset: u32,
+        yOffset: u32,
+        currentDepth: u32,
+    ) -> Result<()>
+    where
+        T: Clone + Send + Sync,
+        B: Binarizer + Send,
+    {
+        let regions = self.decode_image(image, hints, results, xOffset, yOffset, currentDepth)?;
+        for (found, finished) in map_in_order(
+            regions
+                .into_iter()
+                .map(|(left, top, width, height)| {
+                    let sub_image = image.crop(left, top, width, height);
+                    (Self(self.0.clone()), sub_image, left, top)
+                })
+                .collect(),
+            |(mut reader, mut sub_image, left, top)| {
+                let mut found = Vec::new();
+                let finished = reader.do_decode_multiple_parallel(
+                    &mut sub_image,
+                    hints,
+                    &mut found,
+                    xOffset + left as u32,
+                    yOffset + top as u32,
+                    currentDepth + 1,
+                );
+                (found, finished)
+            },
+        ) {
+            results.extend(found);
+            finished?;
+        }
+        Ok(())
+    }
+
+    /// Decodes the image, adds what it finds to `results`, and returns the sub-images left,
+    /// above, right and below the barcode to search next.
+    fn decode_image<B: Binarizer>(
         &mut self,
         image: &mut BinaryBitmap<B>,
         hints: &DecodeHints,
@@ -122,14 +219,25 @@ impl<T: Reader> GenericMultipleBarcodeReader<T> {
         xOffset: u32,
         yOffset: u32,
         currentDepth: u32,
-    ) {
+    ) -> Result<Vec<(usize, usize, usize, usize)>> {
         if currentDepth > Self::MAX_DEPTH {
-            return;
+            return Ok(Vec::new());
         }
+        hints.check_cancelled()?;
 
         // let result;
-        let Ok(result) = self.0.decode_with_hints(image, hints) else {
-            return;
+        let result = match self.0.decode_with_hints(image, hints) {
+            Ok(result) => result,
+            // Keep what the reader decoded before the time ran out.
+            Err(Exceptions::TimeoutException(partial)) => {
+                results.extend(
+                    partial
+                        .into_iter()
+                        .map(|result| Self::translatePoints(result, xOffset, yOffset)),
+                );
+                return Err(Exceptions::TIMEOUT);
+            }
+            Err(_) => return Ok(Vec::new()),
         };
 
         let resultPoints = result.getPoints().to_vec();
@@ -139,7 +247,7 @@ impl<T: Reader> GenericMultipleBarcodeReader<T> {
         results.push(possible_new_result);
 
         if resultPoints.is_empty() {
-            return;
+            return Ok(Vec::new());
         }
 
         let width = image.get_width();
@@ -158,50 +266,22 @@ impl<T: Reader> GenericMultipleBarcodeReader<T> {
             maxY = f32::max(y, maxY);
         }
 
-        // Decode left of barcode
+        // The sub-images left, above, right and below the barcode
+        let mut regions = Vec::new();
         if minX > Self::MIN_DIMENSION_TO_RECUR {
-            self.do_decode_multiple(
-                &mut image.crop(0, 0, minX as usize, height),
-                hints,
-                results,
-                xOffset,
-                yOffset,
-                currentDepth + 1,
-            );
+            regions.push((0, 0, minX as usize, height));
         }
-        // Decode above barcode
         if minY > Self::MIN_DIMENSION_TO_RECUR {
-            self.do_decode_multiple(
-                &mut image.crop(0, 0, width, minY as usize),
-                hints,
-                results,
-                xOffset,
-                yOffset,
-                currentDepth + 1,
-            );
+            regions.push((0, 0, width, minY as usize));
         }
-        // Decode right of barcode
         if maxX < (width as f32) - Self::MIN_DIMENSION_TO_RECUR {
-            self.do_decode_multiple(
-                &mut image.crop(maxX as usize, 0, width - maxX as usize, height),
-                hints,
-                results,
-                xOffset + maxX as u32,
-                yOffset,
-                currentDepth + 1,
-            );
+            regions.push((maxX as usize, 0, width - maxX as usize, height));
         }
-        // Decode below barcode
         if maxY < (height as f32) - Self::MIN_DIMENSION_TO_RECUR {
-            self.do_decode_multiple(
-                &mut image.crop(0, maxY as usize, width, height - maxY as usize),
-                hints,
-                results,
-                xOffset,
-                yOffset + maxY as u32,
-                currentDepth + 1,
-            );
+            regions.push((0, maxY as usize, width, height - maxY as usize));
         }
+
+        Ok(regions)
     }
 
     fn translatePoints(result: RXingResult, xOffset: u32, yOffset: u32) -> RXingResult {
@@ -0,0 +1,134 @@
+use crate::{
+    common::{DetectorRXingResult, Result},
+    maxicode::{decoder::maxicode_decoder, detector, MaxiCodeReader},
+    Binarizer, BinaryBitmap, DecodeHints, EstimatedModuleSize, Exceptions, RXingResult,
+};
+
+use super::{is_already_found, MultipleBarcodeReader};
+
+/**
+ * This implementation can detect and decode all MaxiCodes in an image in one pass, from every
+ * bullseye in it. Unlike {@link MaxiCodeReader}, it always uses the detector, and each result
+ * carries the corners of its symbol.
+ */
+#[derive(Default, Clone)]
+pub struct MaxiCodeMultiReader;
+
+impl MultipleBarcodeReader for MaxiCodeMultiReader {
+    fn decode_multiple<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<Vec<RXingResult>> {
+        self.decode_multiple_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_multiple_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<Vec<RXingResult>> {
+        let mut results = Vec::new();
+        for symbol in detector::detect_multiple(image.get_black_matrix())? {
+            if is_already_found(&results, symbol.getPoints()) {
+                continue;
+            }
+            let decoded = detector::read_bits(symbol.getBits())
+                .and_then(|bits| maxicode_decoder::decode_with_hints(&bits, hints));
+            match decoded {
+                Ok(decoded) => {
+                    if let Some(cb) = &hints.NeedResultPointCallback {
+                        for point in symbol.getPoints() {
+                            cb(*point);
+                        }
+                    }
+                    results.push(MaxiCodeReader::build_result(
+                        &decoded,
+                        symbol.getPoints().to_vec(),
+                        Some(symbol.rotation()),
+                        EstimatedModuleSize::from_corners(
+                            symbol.getPoints(),
+                            MaxiCodeReader::MATRIX_WIDTH,
+                            MaxiCodeReader::MATRIX_HEIGHT,
+                        ),
+                    ))
+                }
+                // a candidate that does not decode is not a symbol, or a damaged one
+                Err(_) => continue,
+            }
+        }
+
+        if results.is_empty() {
+            return Err(Exceptions::NOT_FOUND);
+        }
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "image_formats")]
+mod MaxiCodeMultiReaderTestCase {
+    use std::collections::HashSet;
+
+    use image::{DynamicImage, GrayImage, Luma};
+
+    use crate::{
+        common::HybridBinarizer, multi::MultipleBarcodeReader, BinaryBitmap,
+        BufferedImageLuminanceSource,
+    };
+
+    use super::MaxiCodeMultiReader;
+
+    #[test]
+    fn testSideBySide() {
+        let names = ["MODE2", "MODE3", "MODE4"];
+        let images: Vec<DynamicImage> = names
+            .iter()
+            .map(|name| {
+                image::open(format!("test_resources/blackbox/maxicode-1/{name}.png"))
+                    .expect("image must open")
+            })
+            .collect();
+
+        // the labels side by side, 40 pixels apart
+        let width = images.iter().map(|image| image.width() + 40).sum();
+        let height = images.iter().map(|image| image.height()).max().unwrap_or(0);
+        let mut sheet = GrayImage::from_pixel(width, height, Luma([255]));
+        let mut left = 0;
+        for image in &images {
+            for (x, y, pixel) in image.to_luma8().enumerate_pixels() {
+                sheet.put_pixel(left + x, y, *pixel);
+            }
+            left += image.width() + 40;
+        }
+
+        let results = MaxiCodeMultiReader
+            .decode_multiple(&mut BinaryBitmap::new(HybridBinarizer::new(
+                BufferedImageLuminanceSource::new(DynamicImage::ImageLuma8(sheet)),
+            )))
+            .expect("decode");
+
+        let found: HashSet<&str> = results.iter().map(|result| result.getText()).collect();
+        let expected: HashSet<String> = names
+            .iter()
+            .map(|name| {
+                std::fs::read_to_string(format!("test_resources/blackbox/maxicode-1/{name}.txt"))
+                    .expect("text must read")
+            })
+            .collect();
+        assert_eq!(found, expected.iter().map(String::as_str).collect());
+        assert_eq!(results.len(), names.len());
+
+        // each symbol is reported where its label was placed
+        let mut left = 0.0;
+        for (image, name) in images.iter().zip(names) {
+            let result = results
+                .iter()
+                .find(|result| result.getText().ends_with(name))
+                .expect("symbol");
+            for point in result.getPoints() {
+                assert!(point.x >= left - 10.0 && point.x <= left + image.width() as f32 + 10.0);
+            }
+            left += image.width() as f32 + 40.0;
+        }
+    }
+}
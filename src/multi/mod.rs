@@ -8,6 +8,15 @@ pub use by_quadrant_reader::*;
 mod generic_multiple_barcode_reader;
 pub use generic_multiple_barcode_reader::*;
 
+mod data_matrix_multi_reader;
+pub use data_matrix_multi_reader::*;
+
+mod aztec_multi_reader;
+pub use aztec_multi_reader::*;
+
+mod maxi_code_multi_reader;
+pub use maxi_code_multi_reader::*;
+
 #[cfg(test)]
 #[cfg(feature = "image")]
 mod multi_test_case;
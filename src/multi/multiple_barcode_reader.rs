@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use crate::{common::Result, Binarizer, BinaryBitmap, DecodeHints, RXingResult};
+use crate::{common::Result, Binarizer, BinaryBitmap, DecodeHints, Point, RXingResult};
 
 /**
  * Implementation of this interface attempt to read several barcodes from one image.
@@ -34,3 +34,23 @@ pub trait MultipleBarcodeReader {
         hints: &DecodeHints,
     ) -> Result<Vec<RXingResult>>;
 }
+
+/**
+ * Whether the center of `points` lies within the bounding box of the points of one of `results`.
+ * Detectors that find the same symbol from several scan lines or bullseyes use this to decode and
+ * return each symbol only once.
+ */
+pub(crate) fn is_already_found(results: &[RXingResult], points: &[Point]) -> bool {
+    if points.is_empty() {
+        return false;
+    }
+    let center = points.iter().fold(Point::default(), |sum, p| sum + *p) / points.len() as f32;
+    results.iter().any(|result| {
+        let found = result.getPoints();
+        !found.is_empty()
+            && found.iter().any(|p| p.x <= center.x)
+            && found.iter().any(|p| p.x >= center.x)
+            && found.iter().any(|p| p.y <= center.y)
+            && found.iter().any(|p| p.y >= center.y)
+    })
+}
@@ -35,7 +35,7 @@ use super::detector::MultiDetector;
  * @author Sean Owen
  * @author Hannes Erven
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct QRCodeMultiReader(QRCodeReader);
 impl MultipleBarcodeReader for QRCodeMultiReader {
     fn decode_multiple<B: Binarizer>(
@@ -102,6 +102,27 @@ impl MultipleBarcodeReader for QRCodeMultiReader {
                         ),
                     );
                 }
+                if let Some(errorCorrection) = decoderRXingResult.getErrorCorrectionStats() {
+                    result.putMetadata(
+                        RXingResultMetadataType::ERRORS_CORRECTED,
+                        RXingResultMetadataValue::ErrorsCorrected(errorCorrection),
+                    );
+                }
+                if let Some(eci) = decoderRXingResult.getEci() {
+                    result.putMetadata(
+                        RXingResultMetadataType::CHARACTER_SET_ECI,
+                        RXingResultMetadataValue::CharacterSetEci(eci as u32),
+                    );
+                }
+                if let Some(moduleSize) = QRCodeReader::finderModuleSize(
+                    detectorRXingResult.getPoints(),
+                    detectorRXingResult.getBits().getWidth(),
+                ) {
+                    result.putMetadata(
+                        RXingResultMetadataType::MODULE_SIZE,
+                        RXingResultMetadataValue::ModuleSize(moduleSize),
+                    );
+                }
                 results.push(result);
 
                 Ok(())
@@ -17,13 +17,17 @@
 use std::collections::HashSet;
 
 use crate::common::Result;
+use crate::composite::complete_composite;
+use crate::decode_region::decode_in_region;
 #[cfg(feature = "experimental_features")]
 use crate::oned::cpp::ODReader;
+#[cfg(feature = "parallel")]
+use crate::parallel::decode_passes;
 use crate::qrcode::cpp_port::QrReader;
 use crate::{
     aztec::AztecReader, datamatrix::DataMatrixReader, maxicode::MaxiCodeReader,
-    oned::MultiFormatOneDReader, pdf417::PDF417Reader, qrcode::QRCodeReader, BarcodeFormat,
-    Binarizer, BinaryBitmap, Exceptions, RXingResult, Reader,
+    oned::MultiFormatOneDReader, pdf417::PDF417Reader, postal::PostalReader, qrcode::QRCodeReader,
+    BarcodeFormat, Binarizer, BinaryBitmap, Exceptions, ImmutableReader, RXingResult, Reader,
 };
 use crate::{DecodeHints, ONE_D_FORMATS};
 
@@ -35,7 +39,7 @@ use crate::{DecodeHints, ONE_D_FORMATS};
  * @author Sean Owen
  * @author dswitkin@google.com (Daniel Switkin)
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MultiFormatReader {
     hints: DecodeHints,
     possible_formats: HashSet<BarcodeFormat>,
@@ -120,15 +124,91 @@ impl MultiFormatReader {
         self.one_d_reader = MultiFormatOneDReader::new(hints);
     }
 
+    /// [`Reader::decode_with_hints`], with the readers and the inverted pass run at the same time
+    /// on the rayon thread pool. The result is the one `decode_with_hints` would return.
+    #[cfg(feature = "parallel")]
+    pub fn decode_with_hints_parallel<B>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult>
+    where
+        B: Binarizer + Send + Sync,
+        B::Source: Clone + Send + Sync,
+    {
+        self.set_hints(hints);
+        self.decode_passes_in_region(image, Self::decode_also_inverted_parallel)
+    }
+
     pub fn decode_internal<B: Binarizer>(
         &mut self,
         image: &mut BinaryBitmap<B>,
     ) -> Result<RXingResult> {
-        let res = self.decode_formats(image);
-        if res.is_ok() {
-            return res;
+        self.decode_passes_in_region(image, Self::decode_also_inverted)
+    }
+
+    /// Runs the `passes` over the region of interest of the image, then completes the result.
+    fn decode_passes_in_region<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        mut passes: impl FnMut(&mut Self, &mut BinaryBitmap<B>) -> Result<RXingResult>,
+    ) -> Result<RXingResult> {
+        self.hints.check_cancelled()?;
+        // The readers search the region of interest with the hints for it, so swap them in.
+        let hints = self.hints.clone();
+        let res = decode_in_region(image, &hints, |image, region_hints| {
+            self.hints.clone_from(region_hints);
+            passes(self, image)
+        });
+        self.hints = hints;
+        // The readers give up, each with its own error, once the decode is cancelled.
+        let res = res.map_err(|e| {
+            if self.hints.is_cancelled() {
+                Exceptions::TIMEOUT
+            } else {
+                e
+            }
+        })?;
+        Ok(complete_composite(res, image, &self.hints))
+    }
+
+    #[cfg(feature = "parallel")]
+    fn decode_also_inverted_parallel<B>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<RXingResult>
+    where
+        B: Binarizer + Send + Sync,
+        B::Source: Clone + Send + Sync,
+    {
+        let formats = self.matrix_formats();
+        let one_d = self.decodes_one_d();
+        let Self {
+            hints,
+            try_harder,
+            one_d_reader,
+            ..
+        } = self;
+        decode_passes(
+            image,
+            hints,
+            formats,
+            one_d,
+            *try_harder,
+            |image| one_d_reader.decode_with_hints(image, hints),
+            |format, image| Self::decode_format(format, image, hints),
+        )
+    }
+
+    fn decode_also_inverted<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<RXingResult> {
+        if let Ok(res) = self.decode_formats(image) {
+            return Ok(res);
         }
         if matches!(self.hints.AlsoInverted, Some(true)) {
+            self.hints.check_cancelled()?;
             // Calling all readers again with inverted image
             image.get_black_matrix_mut().flip_self();
             let res = self.decode_formats(image);
@@ -149,89 +229,88 @@ impl MultiFormatReader {
     }
 
     fn decode_formats<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
-        if !self.possible_formats.is_empty() {
-            let one_d = ONE_D_FORMATS
-                .iter()
-                .any(|e| self.possible_formats.contains(e));
-            if one_d && !self.try_harder {
-                if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
-                    return Ok(res);
-                }
-            }
-            for possible_format in self.possible_formats.iter() {
-                let res = match possible_format {
-                    BarcodeFormat::QR_CODE => {
-                        let cpp = QrReader.decode_with_hints(image, &self.hints);
-                        if cpp.is_ok() {
-                            cpp
-                        } else {
-                            QRCodeReader.decode_with_hints(image, &self.hints)
-                        }
-                    }
-                    BarcodeFormat::MICRO_QR_CODE => QrReader.decode_with_hints(image, &self.hints),
-                    BarcodeFormat::RECTANGULAR_MICRO_QR_CODE => {
-                        QrReader.decode_with_hints(image, &self.hints)
-                    }
-                    BarcodeFormat::DATA_MATRIX => {
-                        DataMatrixReader.decode_with_hints(image, &self.hints)
-                    }
-                    BarcodeFormat::AZTEC => AztecReader.decode_with_hints(image, &self.hints),
-                    BarcodeFormat::PDF_417 => PDF417Reader.decode_with_hints(image, &self.hints),
-                    BarcodeFormat::MAXICODE => {
-                        MaxiCodeReader::default().decode_with_hints(image, &self.hints)
-                    }
-                    #[cfg(feature = "experimental_features")]
-                    BarcodeFormat::DXFilmEdge => {
-                        ODReader::new(&self.hints).decode_with_hints(image, &self.hints)
-                    }
-                    _ => Err(Exceptions::UNSUPPORTED_OPERATION),
-                };
-                if res.is_ok() {
-                    return res;
-                }
-            }
-            if one_d && self.try_harder {
-                if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
-                    return Ok(res);
-                }
-            }
-        } else {
-            if !self.try_harder {
-                if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
-                    return Ok(res);
-                }
-            }
-
-            if let Ok(res) = QrReader.decode_with_hints(image, &self.hints) {
+        let one_d = self.decodes_one_d();
+        if one_d && !self.try_harder {
+            if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
                 return Ok(res);
             }
-            if let Ok(res) = QRCodeReader.decode_with_hints(image, &self.hints) {
+        }
+        for format in self.matrix_formats() {
+            self.hints.check_cancelled()?;
+            if let Ok(res) = Self::decode_format(format, image, &self.hints) {
                 return Ok(res);
             }
-            if let Ok(res) = DataMatrixReader.decode_with_hints(image, &self.hints) {
+        }
+        if one_d && self.try_harder {
+            if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
                 return Ok(res);
             }
-            if let Ok(res) = AztecReader.decode_with_hints(image, &self.hints) {
-                return Ok(res);
+        }
+
+        Err(Exceptions::UNSUPPORTED_OPERATION)
+    }
+
+    /// Whether the 1D readers are tried, before the matrix formats unless trying harder.
+    fn decodes_one_d(&self) -> bool {
+        self.possible_formats.is_empty()
+            || ONE_D_FORMATS
+                .iter()
+                .any(|e| self.possible_formats.contains(e))
+    }
+
+    /// The formats other than 1D to try, in order.
+    fn matrix_formats(&self) -> Vec<BarcodeFormat> {
+        if self.possible_formats.is_empty() {
+            vec![
+                BarcodeFormat::QR_CODE,
+                BarcodeFormat::DATA_MATRIX,
+                BarcodeFormat::AZTEC,
+                BarcodeFormat::PDF_417,
+                BarcodeFormat::MAXICODE,
+                #[cfg(feature = "experimental_features")]
+                BarcodeFormat::DXFilmEdge,
+            ]
+        } else {
+            self.possible_formats
+                .iter()
+                .filter(|format| !ONE_D_FORMATS.contains(format))
+                .copied()
+                .collect()
+        }
+    }
+
+    fn decode_format<B: Binarizer>(
+        format: BarcodeFormat,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        match format {
+            BarcodeFormat::QR_CODE => {
+                let cpp = QrReader.immutable_decode_with_hints(image, hints);
+                if cpp.is_ok() {
+                    cpp
+                } else {
+                    QRCodeReader.immutable_decode_with_hints(image, hints)
+                }
             }
-            if let Ok(res) = PDF417Reader.decode_with_hints(image, &self.hints) {
-                return Ok(res);
+            BarcodeFormat::MICRO_QR_CODE => QrReader.immutable_decode_with_hints(image, hints),
+            BarcodeFormat::RECTANGULAR_MICRO_QR_CODE => {
+                QrReader.immutable_decode_with_hints(image, hints)
             }
-            if let Ok(res) = MaxiCodeReader::default().decode_with_hints(image, &self.hints) {
-                return Ok(res);
+            BarcodeFormat::DATA_MATRIX => {
+                DataMatrixReader.immutable_decode_with_hints(image, hints)
             }
-            #[cfg(feature = "experimental_features")]
-            if let Ok(res) = ODReader::new(&self.hints).decode_with_hints(image, &self.hints) {
-                return Ok(res);
+            BarcodeFormat::AZTEC => AztecReader.immutable_decode_with_hints(image, hints),
+            BarcodeFormat::PDF_417 => PDF417Reader.immutable_decode_with_hints(image, hints),
+            BarcodeFormat::MAXICODE => {
+                MaxiCodeReader::default().immutable_decode_with_hints(image, hints)
             }
-
-            if self.try_harder {
-                if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
-                    return Ok(res);
-                }
+            BarcodeFormat::AUSTRALIA_POST | BarcodeFormat::POSTNET | BarcodeFormat::PLANET => {
+                PostalReader.immutable_decode_with_hints(image, hints)
             }
+            #[cfg(feature = "experimental_features")]
+            BarcodeFormat::DXFilmEdge => ODReader::new(hints).decode_with_hints(image, hints),
+            _ => Err(Exceptions::UNSUPPORTED_OPERATION),
         }
-
-        Err(Exceptions::UNSUPPORTED_OPERATION)
     }
 }
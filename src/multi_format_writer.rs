@@ -19,12 +19,14 @@ use crate::{
     common::Result,
     datamatrix::DataMatrixWriter,
     oned::{
-        CodaBarWriter, Code128Writer, Code39Writer, Code93Writer, EAN13Writer, EAN8Writer,
-        ITFWriter, TelepenWriter, UPCAWriter, UPCEWriter,
+        rss::RSS14Writer, CodaBarWriter, Code128Writer, Code39Writer, Code93Writer, EAN13Writer,
+        EAN8Writer, ITF14Writer, ITFWriter, IdentcodeWriter, LeitcodeWriter, TelepenWriter,
+        UPCAWriter, UPCEWriter,
     },
     pdf417::PDF417Writer,
+    postal::{AustraliaPostWriter, PLANETWriter, POSTNETWriter},
     qrcode::QRCodeWriter,
-    BarcodeFormat, EncodeHints, Exceptions, Writer,
+    BarcodeFormat, EncodeHints, EncodedSymbol, Exceptions, Writer,
 };
 
 /**
@@ -55,6 +57,24 @@ impl Writer for MultiFormatWriter {
         height: i32,
         hints: &EncodeHints,
     ) -> Result<crate::common::BitMatrix> {
+        Self::writer(format)?.encode_with_hints(contents, format, width, height, hints)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
+        Self::writer(format)?.encode_with_metadata(contents, format, width, height, hints)
+    }
+}
+
+impl MultiFormatWriter {
+    /// The writer of `format`.
+    fn writer(format: &BarcodeFormat) -> Result<Box<dyn Writer>> {
         let writer: Box<dyn Writer> = match format {
             BarcodeFormat::EAN_8 => Box::<EAN8Writer>::default(),
             BarcodeFormat::UPC_E => Box::<UPCEWriter>::default(),
@@ -64,12 +84,19 @@ impl Writer for MultiFormatWriter {
             BarcodeFormat::CODE_39 => Box::<Code39Writer>::default(),
             BarcodeFormat::CODE_93 => Box::<Code93Writer>::default(),
             BarcodeFormat::CODE_128 => Box::<Code128Writer>::default(),
+            BarcodeFormat::RSS_14 => Box::<RSS14Writer>::default(),
             BarcodeFormat::ITF => Box::<ITFWriter>::default(),
+            BarcodeFormat::ITF_14 => Box::<ITF14Writer>::default(),
+            BarcodeFormat::DEUTSCHE_POST_LEITCODE => Box::<LeitcodeWriter>::default(),
+            BarcodeFormat::DEUTSCHE_POST_IDENTCODE => Box::<IdentcodeWriter>::default(),
             BarcodeFormat::PDF_417 => Box::<PDF417Writer>::default(),
             BarcodeFormat::CODABAR => Box::<CodaBarWriter>::default(),
             BarcodeFormat::DATA_MATRIX => Box::<DataMatrixWriter>::default(),
             BarcodeFormat::TELEPEN => Box::<TelepenWriter>::default(),
             BarcodeFormat::AZTEC => Box::<AztecWriter>::default(),
+            BarcodeFormat::POSTNET => Box::<POSTNETWriter>::default(),
+            BarcodeFormat::PLANET => Box::<PLANETWriter>::default(),
+            BarcodeFormat::AUSTRALIA_POST => Box::<AustraliaPostWriter>::default(),
             _ => {
                 return Err(Exceptions::illegal_argument_with(format!(
                     "No encoder available for format {format:?}"
@@ -77,6 +104,6 @@ impl Writer for MultiFormatWriter {
             }
         };
 
-        writer.encode_with_hints(contents, format, width, height, hints)
+        Ok(writer)
     }
 }
@@ -17,15 +17,19 @@
 use std::collections::HashSet;
 
 use crate::common::Result;
+use crate::composite::complete_composite;
+use crate::decode_region::decode_in_region;
+#[cfg(feature = "parallel")]
+use crate::parallel::decode_passes;
 use crate::qrcode::cpp_port::QrReader;
 use crate::DecodeHints;
 use crate::{
     aztec::AztecReader, datamatrix::DataMatrixReader, maxicode::MaxiCodeReader,
-    oned::MultiFormatOneDReader, pdf417::PDF417Reader, qrcode::QRCodeReader, BarcodeFormat,
-    Binarizer, BinaryBitmap, Exceptions, RXingResult, Reader,
+    oned::MultiFormatOneDReader, pdf417::PDF417Reader, postal::PostalReader, qrcode::QRCodeReader,
+    BarcodeFormat, Binarizer, BinaryBitmap, Exceptions, ImmutableReader, RXingResult, Reader,
 };
 
-pub(crate) const ONE_D_FORMATS: [BarcodeFormat; 12] = [
+pub(crate) const ONE_D_FORMATS: [BarcodeFormat; 15] = [
     BarcodeFormat::UPC_A,
     BarcodeFormat::UPC_E,
     BarcodeFormat::EAN_13,
@@ -35,6 +39,9 @@ pub(crate) const ONE_D_FORMATS: [BarcodeFormat; 12] = [
     BarcodeFormat::CODE_93,
     BarcodeFormat::CODE_128,
     BarcodeFormat::ITF,
+    BarcodeFormat::ITF_14,
+    BarcodeFormat::DEUTSCHE_POST_LEITCODE,
+    BarcodeFormat::DEUTSCHE_POST_IDENTCODE,
     BarcodeFormat::RSS_14,
     BarcodeFormat::RSS_EXPANDED,
     BarcodeFormat::TELEPEN,
@@ -48,7 +55,7 @@ pub(crate) const ONE_D_FORMATS: [BarcodeFormat; 12] = [
  * @author Sean Owen
  * @author dswitkin@google.com (Daniel Switkin)
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MultiUseMultiFormatReader {
     hints: DecodeHints,
     possible_formats: HashSet<BarcodeFormat>,
@@ -60,6 +67,7 @@ pub struct MultiUseMultiFormatReader {
     pdf417_reader: PDF417Reader,
     maxicode_reader: MaxiCodeReader,
     cpp_qrcode_reader: QrReader,
+    postal_reader: PostalReader,
 }
 
 impl Reader for MultiUseMultiFormatReader {
@@ -144,15 +152,89 @@ impl MultiUseMultiFormatReader {
         self.one_d_reader = MultiFormatOneDReader::new(hints);
     }
 
+    /// [`Reader::decode_with_hints`], with the readers and the inverted pass run at the same time
+    /// on the rayon thread pool. The result is the one `decode_with_hints` would return.
+    #[cfg(feature = "parallel")]
+    pub fn decode_with_hints_parallel<B>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult>
+    where
+        B: Binarizer + Send + Sync,
+        B::Source: Clone + Send + Sync,
+    {
+        self.set_hints(hints);
+        self.decode_passes_in_region(image, Self::decode_also_inverted_parallel)
+    }
+
     pub fn decode_internal<B: Binarizer>(
         &mut self,
         image: &mut BinaryBitmap<B>,
     ) -> Result<RXingResult> {
-        let res = self.decode_formats(image);
-        if res.is_ok() {
-            return res;
+        self.decode_passes_in_region(image, Self::decode_also_inverted)
+    }
+
+    /// Runs the `passes` over the region of interest of the image, then completes the result.
+    fn decode_passes_in_region<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        mut passes: impl FnMut(&mut Self, &mut BinaryBitmap<B>) -> Result<RXingResult>,
+    ) -> Result<RXingResult> {
+        self.hints.check_cancelled()?;
+        // The readers search the region of interest with the hints for it, so swap them in.
+        let hints = self.hints.clone();
+        let res = decode_in_region(image, &hints, |image, region_hints| {
+            self.hints.clone_from(region_hints);
+            passes(self, image)
+        });
+        self.hints = hints;
+        // The readers give up, each with its own error, once the decode is cancelled.
+        let res = res.map_err(|e| {
+            if self.hints.is_cancelled() {
+                Exceptions::TIMEOUT
+            } else {
+                e
+            }
+        })?;
+        Ok(complete_composite(res, image, &self.hints))
+    }
+
+    #[cfg(feature = "parallel")]
+    fn decode_also_inverted_parallel<B>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<RXingResult>
+    where
+        B: Binarizer + Send + Sync,
+        B::Source: Clone + Send + Sync,
+    {
+        let formats = self.matrix_formats();
+        let one_d = self.decodes_one_d();
+        // the matrix readers are shared between threads while the 1D reader is in use
+        let mut one_d_reader = std::mem::take(&mut self.one_d_reader);
+        let res = decode_passes(
+            image,
+            &self.hints,
+            formats,
+            one_d,
+            self.try_harder,
+            |image| one_d_reader.decode_with_hints(image, &self.hints),
+            |format, image| self.decode_format(format, image),
+        );
+        self.one_d_reader = one_d_reader;
+        res
+    }
+
+    fn decode_also_inverted<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<RXingResult> {
+        if let Ok(res) = self.decode_formats(image) {
+            return Ok(res);
         }
         if matches!(self.hints.AlsoInverted, Some(true)) {
+            self.hints.check_cancelled()?;
             // Calling all readers again with inverted image
             image.get_black_matrix_mut().flip_self();
             let res = self.decode_formats(image);
@@ -172,96 +254,87 @@ impl MultiUseMultiFormatReader {
     }
 
     fn decode_formats<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
-        if !self.possible_formats.is_empty() {
-            let one_d = ONE_D_FORMATS
-                .iter()
-                .any(|e| self.possible_formats.contains(e));
-            // let one_d = self.possible_formats.contains(&BarcodeFormat::UPC_A)
-            //     || self.possible_formats.contains(&BarcodeFormat::UPC_E)
-            //     || self.possible_formats.contains()
-            //     || self.possible_formats.contains()
-            //     || self.possible_formats.contains()
-            //     || self.possible_formats.contains()
-            //     || self.possible_formats.contains()
-            //     || self.possible_formats.contains()
-            //     || self.possible_formats.contains()
-            //     || self.possible_formats.contains()
-            //     || self.possible_formats.contains()
-            //     || self.possible_formats.contains();
-            if one_d && !self.try_harder {
-                if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
-                    return Ok(res);
-                }
-            }
-            for possible_format in self.possible_formats.iter() {
-                let res = match possible_format {
-                    BarcodeFormat::QR_CODE => {
-                        let a = self.cpp_qrcode_reader.decode_with_hints(image, &self.hints);
-                        if a.is_ok() {
-                            a
-                        } else {
-                            self.qr_code_reader.decode_with_hints(image, &self.hints)
-                        }
-                    }
-                    BarcodeFormat::MICRO_QR_CODE => {
-                        self.cpp_qrcode_reader.decode_with_hints(image, &self.hints)
-                    }
-                    BarcodeFormat::DATA_MATRIX => self
-                        .data_matrix_reader
-                        .decode_with_hints(image, &self.hints),
-                    BarcodeFormat::AZTEC => self.aztec_reader.decode_with_hints(image, &self.hints),
-                    BarcodeFormat::PDF_417 => {
-                        self.pdf417_reader.decode_with_hints(image, &self.hints)
-                    }
-                    BarcodeFormat::MAXICODE => {
-                        self.maxicode_reader.decode_with_hints(image, &self.hints)
-                    }
-                    _ => Err(Exceptions::UNSUPPORTED_OPERATION),
-                };
-                if res.is_ok() {
-                    return res;
-                }
-            }
-            if one_d && self.try_harder {
-                if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
-                    return Ok(res);
-                }
-            }
-        } else {
-            if !self.try_harder {
-                if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
-                    return Ok(res);
-                }
-            }
-            if let Ok(res) = self.cpp_qrcode_reader.decode_with_hints(image, &self.hints) {
-                return Ok(res);
-            }
-            if let Ok(res) = self.qr_code_reader.decode_with_hints(image, &self.hints) {
-                return Ok(res);
-            }
-            if let Ok(res) = self
-                .data_matrix_reader
-                .decode_with_hints(image, &self.hints)
-            {
-                return Ok(res);
-            }
-            if let Ok(res) = self.aztec_reader.decode_with_hints(image, &self.hints) {
+        let one_d = self.decodes_one_d();
+        if one_d && !self.try_harder {
+            if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
                 return Ok(res);
             }
-            if let Ok(res) = self.pdf417_reader.decode_with_hints(image, &self.hints) {
+        }
+        for format in self.matrix_formats() {
+            self.hints.check_cancelled()?;
+            if let Ok(res) = self.decode_format(format, image) {
                 return Ok(res);
             }
-            if let Ok(res) = self.maxicode_reader.decode_with_hints(image, &self.hints) {
+        }
+        if one_d && self.try_harder {
+            if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
                 return Ok(res);
             }
+        }
 
-            if self.try_harder {
-                if let Ok(res) = self.one_d_reader.decode_with_hints(image, &self.hints) {
-                    return Ok(res);
+        Err(Exceptions::UNSUPPORTED_OPERATION)
+    }
+
+    /// Whether the 1D readers are tried, before the matrix formats unless trying harder.
+    fn decodes_one_d(&self) -> bool {
+        self.possible_formats.is_empty()
+            || ONE_D_FORMATS
+                .iter()
+                .any(|e| self.possible_formats.contains(e))
+    }
+
+    /// The formats other than 1D to try, in order.
+    fn matrix_formats(&self) -> Vec<BarcodeFormat> {
+        if self.possible_formats.is_empty() {
+            vec![
+                BarcodeFormat::QR_CODE,
+                BarcodeFormat::DATA_MATRIX,
+                BarcodeFormat::AZTEC,
+                BarcodeFormat::PDF_417,
+                BarcodeFormat::MAXICODE,
+            ]
+        } else {
+            self.possible_formats
+                .iter()
+                .filter(|format| !ONE_D_FORMATS.contains(format))
+                .copied()
+                .collect()
+        }
+    }
+
+    fn decode_format<B: Binarizer>(
+        &self,
+        format: BarcodeFormat,
+        image: &mut BinaryBitmap<B>,
+    ) -> Result<RXingResult> {
+        let hints = &self.hints;
+        match format {
+            BarcodeFormat::QR_CODE => {
+                let a = self
+                    .cpp_qrcode_reader
+                    .immutable_decode_with_hints(image, hints);
+                if a.is_ok() {
+                    a
+                } else {
+                    self.qr_code_reader
+                        .immutable_decode_with_hints(image, hints)
                 }
             }
+            BarcodeFormat::MICRO_QR_CODE => self
+                .cpp_qrcode_reader
+                .immutable_decode_with_hints(image, hints),
+            BarcodeFormat::DATA_MATRIX => self
+                .data_matrix_reader
+                .immutable_decode_with_hints(image, hints),
+            BarcodeFormat::AZTEC => self.aztec_reader.immutable_decode_with_hints(image, hints),
+            BarcodeFormat::PDF_417 => self.pdf417_reader.immutable_decode_with_hints(image, hints),
+            BarcodeFormat::MAXICODE => self
+                .maxicode_reader
+                .immutable_decode_with_hints(image, hints),
+            BarcodeFormat::AUSTRALIA_POST | BarcodeFormat::POSTNET | BarcodeFormat::PLANET => {
+                self.postal_reader.immutable_decode_with_hints(image, hints)
+            }
+            _ => Err(Exceptions::UNSUPPORTED_OPERATION),
         }
-
-        Err(Exceptions::UNSUPPORTED_OPERATION)
     }
 }
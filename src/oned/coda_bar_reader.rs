@@ -30,7 +30,7 @@ use super::OneDReader;
  * @author Bas Vijfwinkel
  * @author David Walker
  */
-#[derive(OneDReader)]
+#[derive(OneDReader, Clone)]
 pub struct CodaBarReader {
     // Keep some instance variables to avoid reallocations
     decodeRowRXingResult: String,
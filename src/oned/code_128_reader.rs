@@ -30,7 +30,7 @@ use super::{one_d_reader, OneDReader};
  *
  * @author Sean Owen
  */
-#[derive(OneDReader, Default)]
+#[derive(OneDReader, Default, Clone)]
 pub struct Code128Reader;
 
 impl OneDReader for Code128Reader {
@@ -49,6 +49,7 @@ impl OneDReader for Code128Reader {
 
         let mut rawCodes: Vec<u8> = Vec::with_capacity(20); //new ArrayList<>(20);
         rawCodes.push(startCode);
+        let mut codeSetSwitches = vec![false];
 
         let mut codeSet = match startCode {
             // switch (startCode) {
@@ -86,6 +87,11 @@ impl OneDReader for Code128Reader {
             code = self.decodeCode(row, &mut counters, nextStart)?;
 
             rawCodes.push(code);
+            codeSetSwitches.push(match codeSet {
+                CODE_CODE_A => code == CODE_CODE_B || code == CODE_CODE_C,
+                CODE_CODE_B => code == CODE_CODE_A || code == CODE_CODE_C,
+                _ => code == CODE_CODE_A || code == CODE_CODE_B,
+            });
 
             // Remember whether the last code was printable or not (excluding CODE_STOP)
             if code != CODE_STOP {
@@ -351,6 +357,14 @@ impl OneDReader for Code128Reader {
             RXingResultMetadataValue::SymbologyIdentifier(format!("]C{symbologyModifier}")),
         );
 
+        // a code set switch as the last data character flags the component of a GS1-128 composite
+        if symbologyModifier == 1 && codeSetSwitches[codeSetSwitches.len() - 3] {
+            resultObject.putMetadata(
+                RXingResultMetadataType::GS1_COMPOSITE_LINKAGE,
+                RXingResultMetadataValue::Gs1CompositeLinkage(true),
+            );
+        }
+
         Ok(resultObject)
     }
 }
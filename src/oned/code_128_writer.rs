@@ -85,10 +85,13 @@ impl OneDimensionalCodeWriter for Code128Writer {
         // let hasCompactionHint = hints != null && hints.containsKey(EncodeHintType::CODE128_COMPACT) &&
         //     Boolean.parseBoolean(hints.get(EncodeHintType::CODE128_COMPACT).toString());
 
+        // a GS1-128 symbol flags its composite component with a trailing code set switch
+        let linkage = hints.Gs1Composite.is_some();
+
         if hasCompactionHint {
-            MinimalEncoder::encode(contents)
+            MinimalEncoder::encode(contents, linkage)
         } else {
-            encodeFast(contents, forcedCodeSet)
+            encodeFast(contents, forcedCodeSet, linkage)
         }
     }
 }
@@ -180,7 +183,7 @@ fn check(contents: &str, hints: &crate::EncodeHints) -> Result<i32> {
     Ok(forcedCodeSet)
 }
 
-fn encodeFast(contents: &str, forcedCodeSet: i32) -> Result<Vec<bool>> {
+fn encodeFast(contents: &str, forcedCodeSet: i32, linkage: bool) -> Result<Vec<bool>> {
     let length = contents.chars().count();
 
     let mut patterns: Vec<Vec<usize>> = Vec::new(); //new ArrayList<>(); // temporary storage for patterns
@@ -296,12 +299,40 @@ fn encodeFast(contents: &str, forcedCodeSet: i32) -> Result<Vec<bool>> {
         }
     }
 
-    Ok(produceRXingResult(&mut patterns, checkSum as usize))
+    let linkage = linkage.then_some(codeSet);
+    Ok(produceRXingResult(
+        &mut patterns,
+        checkSum as usize,
+        linkage,
+    ))
 }
 
-fn produceRXingResult(patterns: &mut Vec<Vec<usize>>, checkSum: usize) -> Vec<bool> {
-    // Compute and append checksum
+/**
+ * Appends the check and stop characters to the patterns. With `linkageCodeSet`, the code set
+ * the symbol ends in, a code set switch is added first as the composite linkage flag.
+ */
+fn produceRXingResult(
+    patterns: &mut Vec<Vec<usize>>,
+    checkSum: usize,
+    linkageCodeSet: Option<usize>,
+) -> Vec<bool> {
     let mut checkSum = checkSum;
+    if let Some(codeSet) = linkageCodeSet {
+        let linkage = if codeSet == CODE_CODE_A {
+            CODE_CODE_C
+        } else {
+            CODE_CODE_A
+        };
+        checkSum += linkage * patterns.len();
+        patterns.push(
+            code_128_reader::CODE_PATTERNS[linkage]
+                .iter()
+                .map(|x| *x as usize)
+                .collect(),
+        );
+    }
+
+    // Compute and append checksum
     checkSum %= 103;
     patterns.push(
         code_128_reader::CODE_PATTERNS[checkSum]
@@ -472,7 +503,7 @@ stuvwxyz{|}~\u{007F}\u{00FF}";
 
     const CODE_SHIFT: usize = 98;
 
-    pub fn encode(contents: &str) -> Result<Vec<bool>> {
+    pub fn encode(contents: &str, linkage: bool) -> Result<Vec<bool>> {
         let length = contents.chars().count();
         let mut memoizedCost = vec![vec![0_u32; length]; 4]; //new int[4][contents.length()];
         let mut minPath = vec![vec![Latch::None; length]; 4]; //new Latch[4][contents.length()];
@@ -611,7 +642,12 @@ stuvwxyz{|}~\u{007F}\u{00FF}";
         // memoizedCost.clear();
         // minPath.clear();
 
-        Ok(produceRXingResult(&mut patterns, checkSum[0]))
+        let linkage = linkage.then_some(match charset {
+            Charset::A => CODE_CODE_A,
+            Charset::B => CODE_CODE_B,
+            _ => CODE_CODE_C,
+        });
+        Ok(produceRXingResult(&mut patterns, checkSum[0], linkage))
     }
 
     fn addPattern(
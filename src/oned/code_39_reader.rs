@@ -32,7 +32,7 @@ use crate::{RXingResultMetadataType, RXingResultMetadataValue};
  * @author Sean Owen
  * @see Code93Reader
  */
-#[derive(OneDReader)]
+#[derive(OneDReader, Clone)]
 pub struct Code39Reader {
     usingCheckDigit: bool,
     extendedMode: bool,
@@ -124,7 +124,9 @@ impl OneDReader for Code39Reader {
             return Err(Exceptions::NOT_FOUND);
         }
 
-        let resultString = if self.extendedMode {
+        let fullAscii =
+            self.extendedMode && self.decodeRowRXingResult.contains(['+', '$', '%', '/']);
+        let resultString = if fullAscii {
             Self::decodeExtended(&self.decodeRowRXingResult)?
         } else {
             self.decodeRowRXingResult.clone()
@@ -143,9 +145,16 @@ impl OneDReader for Code39Reader {
             BarcodeFormat::CODE_39,
         );
 
+        // modifier: 3 if the check digit was validated and stripped, plus 4 for Full ASCII
+        let symbologyModifier =
+            if self.usingCheckDigit { 3 } else { 0 } + if fullAscii { 4 } else { 0 };
         resultObject.putMetadata(
             RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
-            RXingResultMetadataValue::SymbologyIdentifier("]A0".to_owned()),
+            RXingResultMetadataValue::SymbologyIdentifier(format!("]A{symbologyModifier}")),
+        );
+        resultObject.putMetadata(
+            RXingResultMetadataType::FULL_ASCII,
+            RXingResultMetadataValue::FullAscii(fullAscii),
         );
 
         Ok(resultObject)
@@ -413,8 +422,9 @@ mod code_39_extended_mode_test_case {
 
     use crate::{
         common::BitMatrix,
-        oned::{Code39Reader, OneDReader},
-        DecodeHints,
+        oned::{Code39Reader, Code39Writer, OneDReader},
+        BarcodeFormat, DecodeHints, EncodeHintValue, EncodeHints, RXingResultMetadataType,
+        RXingResultMetadataValue, Writer,
     };
     #[test]
     fn testDecodeExtendedMode() {
@@ -429,6 +439,55 @@ mod code_39_extended_mode_test_case {
            "000001001011011010101001001001011001101010101001010010010110101001011010010100100101011010010110100101001001011011010010101001010010010101011001011010010100100101101011001010100101001001010110110010101001010010010101010011011010010100100101101010011010100101001001010110100110101001010010010101011001101010010100100101101010100110100101001001010110101001101001010010010110110101001010010100100101010110100110100101001001011010110100101001010010010101101101001010010100100101010101100110100101001001011010101100101001010010010101101011001010010100100101010110110010100101001001011001010101101001010010010100110101011010010100100101100110101010100101001001010010110101101001010010010110010110101010010100100101001101101010101001001001010110110100101010010010010101010110011010100100100101101010110010101001001001010110101100101010010010010101011011001010010110110100000");
     }
 
+    #[test]
+    fn testFullAsciiMetadata() {
+        // a Full ASCII reader takes "+A" for "a", unless Full ASCII mode is forced when encoding
+        for (contents, hints, expected) in [
+            ("a", EncodeHints::default(), "a"),
+            ("+A", EncodeHints::default(), "a"),
+            (
+                "+A",
+                EncodeHints::default().with(EncodeHintValue::FullAscii(true)),
+                "+A",
+            ),
+        ] {
+            let matrix = Code39Writer
+                .encode_with_hints(contents, &BarcodeFormat::CODE_39, 0, 0, &hints)
+                .expect("encode");
+            let result = Code39Reader::with_all_config(false, true)
+                .decode_row(0, &matrix.getRow(0), &DecodeHints::default())
+                .expect("decode row");
+            assert_eq!(expected, result.getText());
+            assert_eq!(
+                result
+                    .getRXingResultMetadata()
+                    .get(&RXingResultMetadataType::FULL_ASCII),
+                Some(&RXingResultMetadataValue::FullAscii(true))
+            );
+            assert_eq!(
+                result
+                    .getRXingResultMetadata()
+                    .get(&RXingResultMetadataType::SYMBOLOGY_IDENTIFIER),
+                Some(&RXingResultMetadataValue::SymbologyIdentifier(
+                    "]A4".to_owned()
+                ))
+            );
+        }
+
+        let matrix = Code39Writer
+            .encode("ABC", &BarcodeFormat::CODE_39, 0, 0)
+            .expect("encode");
+        let result = Code39Reader::with_all_config(false, true)
+            .decode_row(0, &matrix.getRow(0), &DecodeHints::default())
+            .expect("decode row");
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::FULL_ASCII),
+            Some(&RXingResultMetadataValue::FullAscii(false))
+        );
+    }
+
     fn doTest(expectedRXingResult: &str, encodedRXingResult: &str) {
         let mut sut = Code39Reader::with_all_config(false, true);
         let matrix =
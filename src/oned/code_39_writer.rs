@@ -31,6 +31,14 @@ pub struct Code39Writer;
 
 impl OneDimensionalCodeWriter for Code39Writer {
     fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+        self.encode_oned_with_hints(contents, &EncodeHints::default())
+    }
+
+    /**
+     * Contents outside the Code 39 character set are encoded in Full ASCII mode, unless the
+     * `FullAscii` hint forbids it. With the hint set, Full ASCII mode is always used.
+     */
+    fn encode_oned_with_hints(&self, contents: &str, hints: &EncodeHints) -> Result<Vec<bool>> {
         let mut contents = contents.to_owned();
         let mut length = contents.chars().count();
         if length > 80 {
@@ -39,27 +47,25 @@ impl OneDimensionalCodeWriter for Code39Writer {
             )));
         }
 
-        let mut i = 0;
-        while i < length {
-            // for i in 0..length {
-            // for (int i = 0; i < length; i++) {
-            if Code39Reader::ALPHABET_STRING
-                .find(
-                    contents
-                        .chars()
-                        .nth(i)
-                        .ok_or(Exceptions::INDEX_OUT_OF_BOUNDS)?,
-                )
-                .is_none()
-            {
-                contents = Self::tryToConvertToExtendedMode(&contents)?;
-                length = contents.chars().count();
-                if length > 80 {
-                    return Err(Exceptions::illegal_argument_with(format!("Requested contents should be less than 80 digits long, but got {length} (extended full ASCII mode)")));
-                }
-                break;
+        let needsFullAscii = contents
+            .chars()
+            .any(|c| !Code39Reader::ALPHABET_STRING.contains(c));
+        let useFullAscii = match hints.FullAscii {
+            Some(false) if needsFullAscii => {
+                return Err(Exceptions::illegal_argument_with(
+                    "Requested contents contain characters outside the Code 39 character set, \
+                     but Full ASCII mode is not allowed",
+                ))
+            }
+            Some(fullAscii) => fullAscii,
+            None => needsFullAscii,
+        };
+        if useFullAscii {
+            contents = Self::tryToConvertToExtendedMode(&contents)?;
+            length = contents.chars().count();
+            if length > 80 {
+                return Err(Exceptions::illegal_argument_with(format!("Requested contents should be less than 80 digits long, but got {length} (extended full ASCII mode)")));
             }
-            i += 1;
         }
 
         let mut widths = [0_usize; 9]; //new int[9];
@@ -190,7 +196,10 @@ impl Code39Writer {
  * Tests {@link Code39Writer}.
  */
 mod Code39WriterTestCase {
-    use crate::{common::bit_matrix_test_case, oned::Code39Writer, BarcodeFormat, Writer};
+    use crate::{
+        common::bit_matrix_test_case, oned::Code39Writer, BarcodeFormat, EncodeHintValue,
+        EncodeHints, Writer,
+    };
 
     #[test]
     fn testEncode() {
@@ -258,6 +267,17 @@ mod Code39WriterTestCase {
         );
     }
 
+    #[test]
+    fn testFullAsciiForbidden() {
+        let hints = EncodeHints::default().with(EncodeHintValue::FullAscii(false));
+        assert!(Code39Writer
+            .encode_with_hints("abc", &BarcodeFormat::CODE_39, 0, 0, &hints)
+            .is_err());
+        assert!(Code39Writer
+            .encode_with_hints("ABC+", &BarcodeFormat::CODE_39, 0, 0, &hints)
+            .is_ok());
+    }
+
     fn doTest(input: &str, expected: &str) {
         let result = Code39Writer
             .encode(input, &BarcodeFormat::CODE_39, 0, 0)
@@ -31,7 +31,7 @@ use crate::{RXingResultMetadataType, RXingResultMetadataValue};
  * @author Sean Owen
  * @see Code39Reader
  */
-#[derive(OneDReader)]
+#[derive(OneDReader, Clone)]
 pub struct Code93Reader {
     decodeRowRXingResult: String,
     counters: [u32; 6],
@@ -110,6 +110,10 @@ impl OneDReader for Code93Reader {
         self.decodeRowRXingResult
             .truncate(self.decodeRowRXingResult.chars().count() - 2);
 
+        // 'a' to 'd' stand for the shift characters of Full ASCII
+        let fullAscii = self
+            .decodeRowRXingResult
+            .contains(|c: char| ('a'..='d').contains(&c));
         let resultString = Self::decodeExtended(&self.decodeRowRXingResult)?;
 
         let left: f32 = (start[1] + start[0]) as f32 / 2.0;
@@ -129,6 +133,10 @@ impl OneDReader for Code93Reader {
             RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
             RXingResultMetadataValue::SymbologyIdentifier("]G0".to_owned()),
         );
+        resultObject.putMetadata(
+            RXingResultMetadataType::FULL_ASCII,
+            RXingResultMetadataValue::FullAscii(fullAscii),
+        );
 
         Ok(resultObject)
     }
@@ -360,7 +368,11 @@ impl Code93Reader {
 #[cfg(test)]
 mod Code93ReaderTestCase {
 
-    use crate::{common::BitMatrix, oned::OneDReader, DecodeHints};
+    use crate::{
+        common::BitMatrix,
+        oned::{Code93Writer, OneDReader},
+        BarcodeFormat, DecodeHints, RXingResultMetadataType, RXingResultMetadataValue, Writer,
+    };
 
     use super::Code93Reader;
 
@@ -370,6 +382,25 @@ mod Code93ReaderTestCase {
              "0000001010111101101000101001100101001011001001100101100101001001100101100100101000010101010000101110101101101010001001001101001101001110010101101011101011011101011101101110100101110101101001110101110110101101010001110110101100010101110110101000110101110110101000101101110110101101001101110110101100101101110110101100110101110110101011011001110110101011001101110110101001101101110110101001110101001100101101010001010111101111");
     }
 
+    #[test]
+    fn testFullAsciiMetadata() {
+        for (contents, fullAscii) in [("CODE93+", false), ("Code93", true)] {
+            let matrix = Code93Writer
+                .encode(contents, &BarcodeFormat::CODE_93, 0, 0)
+                .expect("encode");
+            let result = Code93Reader::new()
+                .decode_row(0, &matrix.getRow(0), &DecodeHints::default())
+                .expect("decode row");
+            assert_eq!(contents, result.getText());
+            assert_eq!(
+                result
+                    .getRXingResultMetadata()
+                    .get(&RXingResultMetadataType::FULL_ASCII),
+                Some(&RXingResultMetadataValue::FullAscii(fullAscii))
+            );
+        }
+    }
+
     fn doTest(expectedRXingResult: &str, encodedRXingResult: &str) {
         let mut sut = Code93Reader::new();
         let matrix = BitMatrix::parse_strings(encodedRXingResult, "1", "0").expect("must parse");
@@ -33,6 +33,25 @@ impl OneDimensionalCodeWriter for Code93Writer {
      * @return a {@code boolean[]} of horizontal pixels (false = white, true = black)
      */
     fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+        self.encode_oned_with_hints(contents, &EncodeHints::default())
+    }
+
+    /**
+     * Contents outside the Code 93 character set are encoded in Full ASCII mode, unless the
+     * `FullAscii` hint forbids it.
+     */
+    fn encode_oned_with_hints(&self, contents: &str, hints: &EncodeHints) -> Result<Vec<bool>> {
+        if hints.FullAscii == Some(false)
+            && contents
+                .chars()
+                .any(|c| !Code93Reader::ALPHABET_STRING[..43].contains(c))
+        {
+            return Err(Exceptions::illegal_argument_with(
+                "Requested contents contain characters outside the Code 93 character set, \
+                 but Full ASCII mode is not allowed",
+            ));
+        }
+
         let mut contents = Self::convertToExtended(contents)?;
         let length = contents.chars().count();
         if length > 80 {
@@ -244,7 +263,10 @@ impl Code93Writer {
  */
 #[cfg(test)]
 mod Code93WriterTestCase {
-    use crate::{common::bit_matrix_test_case, oned::Code93Writer, BarcodeFormat, Writer};
+    use crate::{
+        common::bit_matrix_test_case, oned::Code93Writer, BarcodeFormat, EncodeHintValue,
+        EncodeHints, Writer,
+    };
 
     #[test]
     fn testEncode() {
@@ -275,6 +297,17 @@ mod Code93WriterTestCase {
            "101011110" , "100000"));
     }
 
+    #[test]
+    fn testFullAsciiForbidden() {
+        let hints = EncodeHints::default().with(EncodeHintValue::FullAscii(false));
+        assert!(Code93Writer
+            .encode_with_hints("Code93", &BarcodeFormat::CODE_93, 0, 0, &hints)
+            .is_err());
+        assert!(Code93Writer
+            .encode_with_hints("CODE93+", &BarcodeFormat::CODE_93, 0, 0, &hints)
+            .is_ok());
+    }
+
     fn doTest(input: &str, expected: &str) {
         let result = Code93Writer
             .encode(input, &BarcodeFormat::CODE_93, 0, 0)
@@ -0,0 +1,171 @@
+use rxing_one_d_proc_derive::OneDReader;
+
+use crate::{
+    common::{BitArray, Result},
+    BarcodeFormat, DecodeHints, Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue,
+};
+
+use super::{deutsche_post_check_digit, deutsche_post_length, ITFReader, OneDReader};
+
+/**
+ * Implements decoding of the Deutsche Post Leitcode (14 digits) and Identcode (12 digits): ITF
+ * symbols whose last digit is the Deutsche Post 4/9 weighted check digit. Which of the two is
+ * read follows the length of the symbol, limited to the `PossibleFormats` hint if there is one.
+ */
+#[derive(OneDReader, Default, Clone)]
+pub struct DeutschePostReader(ITFReader);
+
+impl OneDReader for DeutschePostReader {
+    fn decode_row(
+        &mut self,
+        rowNumber: u32,
+        row: &BitArray,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let formats: Vec<BarcodeFormat> = [
+            BarcodeFormat::DEUTSCHE_POST_LEITCODE,
+            BarcodeFormat::DEUTSCHE_POST_IDENTCODE,
+        ]
+        .into_iter()
+        .filter(|format| {
+            hints
+                .PossibleFormats
+                .as_ref()
+                .is_none_or(|possible| possible.contains(format))
+        })
+        .collect();
+        let lengths = formats
+            .iter()
+            .filter_map(deutsche_post_length)
+            .map(|length| length as u32)
+            .collect::<Vec<_>>();
+        if lengths.is_empty() {
+            return Err(Exceptions::NOT_FOUND);
+        }
+
+        let itf_hints = hints
+            .clone()
+            .with(crate::DecodeHintValue::AllowedLengths(lengths));
+        let itf = self.0.decode_row(rowNumber, row, &itf_hints)?;
+
+        let digits = itf.getText();
+        let format = formats
+            .into_iter()
+            .find(|format| deutsche_post_length(format) == Some(digits.len()))
+            .ok_or(Exceptions::NOT_FOUND)?;
+        let length = digits.len();
+        if Some(deutsche_post_check_digit(&digits[..length - 1])?)
+            != digits[length - 1..]
+                .chars()
+                .next()
+                .and_then(|c| c.to_digit(10))
+        {
+            return Err(Exceptions::CHECKSUM);
+        }
+
+        let mut result = RXingResult::new(digits, Vec::new(), itf.getPoints().to_vec(), format);
+        result.putMetadata(
+            RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
+            RXingResultMetadataValue::SymbologyIdentifier("]I1".to_owned()),
+        );
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod DeutschePostReaderTestCase {
+    use crate::{
+        common::test_image::image,
+        oned::{ITFWriter, IdentcodeWriter, LeitcodeWriter},
+        BarcodeFormat, DecodeHintValue, DecodeHints, Exceptions, MultiFormatReader, Reader, Writer,
+    };
+
+    use super::DeutschePostReader;
+
+    #[test]
+    fn testLeitcode() {
+        let matrix = LeitcodeWriter
+            .encode(
+                "2134807501640",
+                &BarcodeFormat::DEUTSCHE_POST_LEITCODE,
+                400,
+                60,
+            )
+            .expect("encode");
+        let result = DeutschePostReader::default()
+            .decode(&mut image(&matrix, 1, 0))
+            .expect("decode");
+        assert_eq!(result.getText(), "21348075016401");
+        assert_eq!(
+            result.getBarcodeFormat(),
+            &BarcodeFormat::DEUTSCHE_POST_LEITCODE
+        );
+    }
+
+    #[test]
+    fn testIdentcode() {
+        let matrix = IdentcodeWriter
+            .encode(
+                "56310243031",
+                &BarcodeFormat::DEUTSCHE_POST_IDENTCODE,
+                400,
+                60,
+            )
+            .expect("encode");
+        let result = DeutschePostReader::default()
+            .decode(&mut image(&matrix, 1, 0))
+            .expect("decode");
+        assert_eq!(result.getText(), "563102430313");
+        assert_eq!(
+            result.getBarcodeFormat(),
+            &BarcodeFormat::DEUTSCHE_POST_IDENTCODE
+        );
+    }
+
+    #[test]
+    fn testPossibleFormats() {
+        let matrix = IdentcodeWriter
+            .encode(
+                "56310243031",
+                &BarcodeFormat::DEUTSCHE_POST_IDENTCODE,
+                400,
+                60,
+            )
+            .expect("encode");
+        let hints = DecodeHints::default().with(DecodeHintValue::PossibleFormats(
+            [BarcodeFormat::DEUTSCHE_POST_IDENTCODE].into(),
+        ));
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut image(&matrix, 1, 0), &hints)
+            .expect("decode");
+        assert_eq!(
+            result.getBarcodeFormat(),
+            &BarcodeFormat::DEUTSCHE_POST_IDENTCODE
+        );
+
+        let hints = DecodeHints::default().with(DecodeHintValue::PossibleFormats(
+            [BarcodeFormat::DEUTSCHE_POST_LEITCODE].into(),
+        ));
+        assert!(DeutschePostReader::default()
+            .decode_with_hints(&mut image(&matrix, 1, 0), &hints)
+            .is_err());
+
+        let result = MultiFormatReader::default()
+            .decode(&mut image(&matrix, 1, 0))
+            .expect("decode");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::ITF);
+    }
+
+    #[test]
+    fn testBadChecksum() {
+        let matrix = ITFWriter
+            .encode("563102430314", &BarcodeFormat::ITF, 400, 60)
+            .expect("encode");
+        assert!(matches!(
+            DeutschePostReader::default().decode(&mut image(&matrix, 1, 0)),
+            Err(Exceptions::NotFoundException(_))
+        ));
+    }
+}
@@ -0,0 +1,314 @@
+use crate::{
+    common::{BitMatrix, Result},
+    BarcodeFormat, EncodeHints, EncodedSymbol, Exceptions, Writer,
+};
+
+use super::ITFWriter;
+
+/**
+ * The Deutsche Post check digit of `digits`: weights of 4 and 9, alternating from the left.
+ */
+pub(crate) fn deutsche_post_check_digit(digits: &str) -> Result<u32> {
+    let sum = digits
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|d| if i % 2 == 0 { 4 * d } else { 9 * d })
+                .ok_or(Exceptions::FORMAT)
+        })
+        .sum::<Result<u32>>()?;
+    Ok((10 - sum % 10) % 10)
+}
+
+/**
+ * The number of digits, including the check digit, of a Leitcode or Identcode.
+ */
+pub(crate) fn deutsche_post_length(format: &BarcodeFormat) -> Option<usize> {
+    match format {
+        BarcodeFormat::DEUTSCHE_POST_LEITCODE => Some(14),
+        BarcodeFormat::DEUTSCHE_POST_IDENTCODE => Some(12),
+        _ => None,
+    }
+}
+
+/**
+ * The human readable form of a Leitcode or Identcode, grouped the way Deutsche Post prints it:
+ * `21348.075.016.40 1` for a Leitcode (postcode, street, house number, product and check digit)
+ * and `56.310 243.031 3` for an Identcode (mail centre, customer, delivery number and check
+ * digit).
+ */
+pub fn deutsche_post_human_readable(format: &BarcodeFormat, digits: &str) -> Result<String> {
+    if deutsche_post_length(format) != Some(digits.len())
+        || !digits.chars().all(|c| c.is_ascii_digit())
+    {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "{digits} is not a {format}"
+        )));
+    }
+    Ok(match format {
+        BarcodeFormat::DEUTSCHE_POST_LEITCODE => format!(
+            "{}.{}.{}.{} {}",
+            &digits[..5],
+            &digits[5..8],
+            &digits[8..11],
+            &digits[11..13],
+            &digits[13..]
+        ),
+        _ => format!(
+            "{}.{} {}.{} {}",
+            &digits[..2],
+            &digits[2..5],
+            &digits[5..8],
+            &digits[8..11],
+            &digits[11..]
+        ),
+    })
+}
+
+/**
+ * Appends the check digit to `contents`, or verifies it if it is already there.
+ */
+fn add_check_digit(format: &BarcodeFormat, contents: &str) -> Result<String> {
+    let length = deutsche_post_length(format).ok_or(Exceptions::UNSUPPORTED_OPERATION)?;
+    if !contents.chars().all(|c| c.is_ascii_digit()) {
+        return Err(Exceptions::illegal_argument_with(
+            "Input should only contain digits 0-9",
+        ));
+    }
+    match contents.len() {
+        l if l == length - 1 => Ok(format!(
+            "{contents}{}",
+            deutsche_post_check_digit(contents)?
+        )),
+        l if l == length => {
+            if Some(deutsche_post_check_digit(&contents[..length - 1])?)
+                != contents[length - 1..]
+                    .chars()
+                    .next()
+                    .and_then(|c| c.to_digit(10))
+            {
+                return Err(Exceptions::illegal_argument_with(
+                    "Contents do not pass checksum",
+                ));
+            }
+            Ok(contents.to_owned())
+        }
+        l => Err(Exceptions::illegal_argument_with(format!(
+            "Requested contents should be {} or {length} digits long, but got {l}",
+            length - 1
+        ))),
+    }
+}
+
+fn encode_deutsche_post(
+    contents: &str,
+    format: &BarcodeFormat,
+    width: i32,
+    height: i32,
+    hints: &EncodeHints,
+    supported: BarcodeFormat,
+) -> Result<EncodedSymbol> {
+    if *format != supported {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "Can only encode {supported:?}, but got {format:?}"
+        )));
+    }
+    let symbol = ITFWriter.encode_with_metadata(
+        &add_check_digit(format, contents)?,
+        &BarcodeFormat::ITF,
+        width,
+        height,
+        hints,
+    )?;
+    Ok(EncodedSymbol {
+        format: supported,
+        ..symbol
+    })
+}
+
+/**
+ * This object renders a Deutsche Post Leitcode as a `BitMatrix`. The contents are the 13 digits
+ * of the routing code, to which the check digit is added, or all 14 digits, in which case the
+ * check digit is verified.
+ */
+#[derive(Default)]
+pub struct LeitcodeWriter;
+
+impl Writer for LeitcodeWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
+        encode_deutsche_post(
+            contents,
+            format,
+            width,
+            height,
+            hints,
+            BarcodeFormat::DEUTSCHE_POST_LEITCODE,
+        )
+    }
+}
+
+/**
+ * This object renders a Deutsche Post Identcode as a `BitMatrix`. The contents are the 11
+ * digits of the identification code, to which the check digit is added, or all 12 digits, in
+ * which case the check digit is verified.
+ */
+#[derive(Default)]
+pub struct IdentcodeWriter;
+
+impl Writer for IdentcodeWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
+        encode_deutsche_post(
+            contents,
+            format,
+            width,
+            height,
+            hints,
+            BarcodeFormat::DEUTSCHE_POST_IDENTCODE,
+        )
+    }
+}
+
+#[cfg(test)]
+mod DeutschePostWriterTestCase {
+    use crate::{common::bit_matrix_test_case, oned::ITFWriter, BarcodeFormat, Writer};
+
+    use super::{
+        deutsche_post_check_digit, deutsche_post_human_readable, IdentcodeWriter, LeitcodeWriter,
+    };
+
+    #[test]
+    fn testCheckDigit() {
+        assert_eq!(deutsche_post_check_digit("2134807501640").unwrap(), 1);
+        assert_eq!(deutsche_post_check_digit("56310243031").unwrap(), 3);
+    }
+
+    #[test]
+    fn testHumanReadable() {
+        assert_eq!(
+            deutsche_post_human_readable(&BarcodeFormat::DEUTSCHE_POST_LEITCODE, "21348075016401")
+                .unwrap(),
+            "21348.075.016.40 1"
+        );
+        assert_eq!(
+            deutsche_post_human_readable(&BarcodeFormat::DEUTSCHE_POST_IDENTCODE, "563102430313")
+                .unwrap(),
+            "56.310 243.031 3"
+        );
+        assert!(deutsche_post_human_readable(
+            &BarcodeFormat::DEUTSCHE_POST_IDENTCODE,
+            "21348075016401"
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn testAddChecksumAndEncode() {
+        let itf = ITFWriter
+            .encode("21348075016401", &BarcodeFormat::ITF, 0, 0)
+            .expect("encode");
+        let leitcode = LeitcodeWriter
+            .encode(
+                "2134807501640",
+                &BarcodeFormat::DEUTSCHE_POST_LEITCODE,
+                0,
+                0,
+            )
+            .expect("encode");
+        assert_eq!(
+            bit_matrix_test_case::matrix_to_string(&itf),
+            bit_matrix_test_case::matrix_to_string(&leitcode)
+        );
+
+        let itf = ITFWriter
+            .encode("563102430313", &BarcodeFormat::ITF, 0, 0)
+            .expect("encode");
+        let identcode = IdentcodeWriter
+            .encode(
+                "563102430313",
+                &BarcodeFormat::DEUTSCHE_POST_IDENTCODE,
+                0,
+                0,
+            )
+            .expect("encode");
+        assert_eq!(
+            bit_matrix_test_case::matrix_to_string(&itf),
+            bit_matrix_test_case::matrix_to_string(&identcode)
+        );
+    }
+
+    #[test]
+    fn testBadChecksum() {
+        assert!(IdentcodeWriter
+            .encode(
+                "563102430314",
+                &BarcodeFormat::DEUTSCHE_POST_IDENTCODE,
+                0,
+                0
+            )
+            .is_err());
+        assert!(LeitcodeWriter
+            .encode("563102430313", &BarcodeFormat::DEUTSCHE_POST_LEITCODE, 0, 0)
+            .is_err());
+    }
+}
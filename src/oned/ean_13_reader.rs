@@ -32,7 +32,7 @@ use crate::Exceptions;
  * @author Sean Owen
  * @author alasdair@google.com (Alasdair Mackintosh)
  */
-#[derive(OneDReader, EANReader, Default)]
+#[derive(OneDReader, EANReader, Default, Clone)]
 pub struct EAN13Reader;
 impl UPCEANReader for EAN13Reader {
     fn getBarcodeFormat(&self) -> crate::BarcodeFormat {
@@ -27,7 +27,7 @@ use super::UPCEANReader;
  *
  * @author Sean Owen
  */
-#[derive(OneDReader, EANReader, Default)]
+#[derive(OneDReader, EANReader, Default, Clone)]
 pub struct EAN8Reader;
 
 impl UPCEANReader for EAN8Reader {
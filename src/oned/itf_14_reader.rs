@@ -0,0 +1,137 @@
+use rxing_one_d_proc_derive::OneDReader;
+
+use crate::{
+    common::{BitArray, Result},
+    BarcodeFormat, DecodeHints, Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue,
+};
+
+use super::{gs1_check_digit, ITFReader, OneDReader};
+
+/**
+ * Implements decoding of ITF-14: an ITF symbol of exactly 14 digits, the last of which is the
+ * GS1 mod 10 check digit. The text of the result is the GTIN-14.
+ */
+#[derive(OneDReader, Default, Clone)]
+pub struct ITF14Reader(ITFReader);
+
+impl OneDReader for ITF14Reader {
+    fn decode_row(
+        &mut self,
+        rowNumber: u32,
+        row: &BitArray,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let itf_hints = hints
+            .clone()
+            .with(crate::DecodeHintValue::AllowedLengths(vec![14]));
+        let itf = self.0.decode_row(rowNumber, row, &itf_hints)?;
+
+        let gtin = itf.getText();
+        if gtin.len() != 14
+            || Some(gs1_check_digit(&gtin[..13])?)
+                != gtin[13..].chars().next().and_then(|c| c.to_digit(10))
+        {
+            return Err(Exceptions::CHECKSUM);
+        }
+
+        let mut result = RXingResult::new(
+            gtin,
+            Vec::new(),
+            itf.getPoints().to_vec(),
+            BarcodeFormat::ITF_14,
+        );
+        result.putMetadata(
+            RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
+            RXingResultMetadataValue::SymbologyIdentifier("]I1".to_owned()),
+        );
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod ITF14ReaderTestCase {
+    use crate::{
+        common::{test_image::image, BitMatrix},
+        oned::{BearerBars, ITF14Writer, ITFWriter},
+        BarcodeFormat, DecodeHintValue, DecodeHints, EncodeHintValue, EncodeHints, Exceptions,
+        MultiFormatReader, RXingResultMetadataType, RXingResultMetadataValue, Reader, Writer,
+    };
+
+    use super::ITF14Reader;
+
+    fn itf_14(bearer_bars: BearerBars) -> BitMatrix {
+        ITF14Writer::default()
+            .encode_with_hints(
+                "1540014128876",
+                &BarcodeFormat::ITF_14,
+                400,
+                120,
+                &EncodeHints::default().with(EncodeHintValue::ItfBearerBars(bearer_bars)),
+            )
+            .expect("encode")
+    }
+
+    #[test]
+    fn testBearerBars() {
+        for bearer_bars in [BearerBars::None, BearerBars::Horizontal, BearerBars::Frame] {
+            let result = ITF14Reader::default()
+                .decode(&mut image(&itf_14(bearer_bars), 1, 0))
+                .expect("decode");
+            assert_eq!(result.getText(), "15400141288763");
+            assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::ITF_14);
+            assert_eq!(
+                result
+                    .getRXingResultMetadata()
+                    .get(&RXingResultMetadataType::SYMBOLOGY_IDENTIFIER),
+                Some(&RXingResultMetadataValue::SymbologyIdentifier(
+                    "]I1".to_owned()
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn testBearerBarsInsideQuietZone() {
+        let hints = EncodeHints::default().with(EncodeHintValue::Margin("6".to_owned()));
+        let matrix = ITF14Writer::default()
+            .encode_with_hints("1540014128876", &BarcodeFormat::ITF_14, 400, 120, &hints)
+            .expect("encode");
+        let result = ITF14Reader::default()
+            .decode(&mut image(&matrix, 1, 0))
+            .expect("decode");
+        assert_eq!(result.getText(), "15400141288763");
+    }
+
+    #[test]
+    fn testPlainITFReadsFrame() {
+        let result = MultiFormatReader::default()
+            .decode(&mut image(&itf_14(BearerBars::Frame), 1, 0))
+            .expect("decode");
+        assert_eq!(result.getText(), "15400141288763");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::ITF);
+    }
+
+    #[test]
+    fn testPossibleFormats() {
+        let hints = DecodeHints::default().with(DecodeHintValue::PossibleFormats(
+            [BarcodeFormat::ITF_14].into(),
+        ));
+        let result = MultiFormatReader::default()
+            .decode_with_hints(&mut image(&itf_14(BearerBars::Frame), 1, 0), &hints)
+            .expect("decode");
+        assert_eq!(result.getBarcodeFormat(), &BarcodeFormat::ITF_14);
+    }
+
+    #[test]
+    fn testBadChecksum() {
+        let matrix = ITFWriter
+            .encode("15400141288764", &BarcodeFormat::ITF, 400, 60)
+            .expect("encode");
+        assert!(matches!(
+            ITF14Reader::default().decode(&mut image(&matrix, 1, 0)),
+            Err(Exceptions::NotFoundException(_))
+        ));
+    }
+}
@@ -0,0 +1,307 @@
+use crate::{
+    common::{BitMatrix, Result},
+    encoded_symbol::Placement,
+    BarcodeFormat, EncodeHints, EncodedSymbol, Exceptions, Writer,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{linear_modules, ITFWriter, OneDimensionalCodeWriter};
+
+/** Quiet zone on either side of the bars, inside any bearer bars, in modules. */
+const DEFAULT_MARGIN: u32 = 10;
+
+/** Thickness of the bearer bars, in modules. */
+const BEARER_BAR_WIDTH: u32 = 5;
+
+/** Height of the bars when no larger height is requested, in modules. */
+const MIN_BAR_HEIGHT: u32 = 32;
+
+/**
+ * Bearer bars printed around an ITF-14 symbol. They even out the pressure of the printing plate
+ * on corrugated board and keep a short scan from being read as a valid, shorter symbol.
+ */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum BearerBars {
+    /** No bearer bars. */
+    None,
+    /** A bar along the top and the bottom of the bars. */
+    Horizontal,
+    /** A box around the bars and their quiet zones. This is the default. */
+    #[default]
+    Frame,
+}
+
+impl std::str::FromStr for BearerBars {
+    type Err = Exceptions;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(BearerBars::None),
+            "horizontal" => Ok(BearerBars::Horizontal),
+            "frame" | "box" => Ok(BearerBars::Frame),
+            _ => Err(Exceptions::illegal_argument_with(format!(
+                "Unknown bearer bars: {s}"
+            ))),
+        }
+    }
+}
+
+/**
+ * The GS1 mod 10 check digit of `digits`: weights of 3 and 1, alternating from the right.
+ */
+pub(crate) fn gs1_check_digit(digits: &str) -> Result<u32> {
+    let sum = digits
+        .chars()
+        .rev()
+        .enumerate()
+        .map(|(i, c)| {
+            c.to_digit(10)
+                .map(|d| if i % 2 == 0 { 3 * d } else { d })
+                .ok_or(Exceptions::FORMAT)
+        })
+        .sum::<Result<u32>>()?;
+    Ok((10 - sum % 10) % 10)
+}
+
+/**
+ * This object renders an ITF-14 code as a `BitMatrix`. The contents are the 13 digits of a
+ * GTIN-14 without its check digit, which is then added, or all 14 digits, in which case the
+ * check digit is verified.
+ *
+ * The bars are surrounded by quiet zones of 10 modules and, unless the `ItfBearerBars` hint
+ * says otherwise, by a bearer bar frame. The bearer bars are not modules of the symbol, and are
+ * left out of the module matrix of [`Writer::encode_with_metadata`].
+ */
+#[derive(Default)]
+pub struct ITF14Writer(ITFWriter);
+
+impl Writer for ITF14Writer {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
+        if format != &BarcodeFormat::ITF_14 {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Can only encode ITF_14, but got {format:?}"
+            )));
+        }
+        if width < 0 || height < 0 {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Negative size is not allowed. Input: {width}x{height}"
+            )));
+        }
+        ITFWriter::checkNumeric(contents)?;
+
+        let contents = match contents.chars().count() {
+            13 => format!("{contents}{}", gs1_check_digit(contents)?),
+            14 => {
+                if Some(gs1_check_digit(&contents[..13])?)
+                    != contents[13..].chars().next().and_then(|c| c.to_digit(10))
+                {
+                    return Err(Exceptions::illegal_argument_with(
+                        "Contents do not pass checksum",
+                    ));
+                }
+                contents.to_owned()
+            }
+            length => {
+                return Err(Exceptions::illegal_argument_with(format!(
+                    "Requested contents should be 13 or 14 digits long, but got {length}"
+                )))
+            }
+        };
+
+        let sides_margin = if let Some(margin) = &hints.Margin {
+            margin.parse::<u32>().map_err(|e| {
+                Exceptions::illegal_argument_with(format!("couldn't parse margin: {e}"))
+            })?
+        } else {
+            DEFAULT_MARGIN
+        };
+
+        let code = self.0.encode_oned(&contents)?;
+        Ok(EncodedSymbol::new(
+            *format,
+            linear_modules(&code)?,
+            render(
+                &code,
+                hints.ItfBearerBars.unwrap_or_default(),
+                width,
+                height,
+                sides_margin,
+            )?,
+        ))
+    }
+}
+
+/**
+ * Renders the bars of `code` with a quiet zone of `sides_margin` modules on either side, inside
+ * the `bearer_bars`, and returns where the bars are in the matrix.
+ */
+fn render(
+    code: &[bool],
+    bearer_bars: BearerBars,
+    width: i32,
+    height: i32,
+    sides_margin: u32,
+) -> Result<(BitMatrix, Placement)> {
+    let (bearer_x, bearer_y) = match bearer_bars {
+        BearerBars::None => (0, 0),
+        BearerBars::Horizontal => (0, BEARER_BAR_WIDTH),
+        BearerBars::Frame => (BEARER_BAR_WIDTH, BEARER_BAR_WIDTH),
+    };
+
+    let input_width = code.len() as u32;
+    let full_width = input_width + 2 * (sides_margin + bearer_x);
+    let output_width = (width.max(0) as u32).max(full_width);
+    let multiple = output_width / full_width;
+    let left = (output_width - full_width * multiple) / 2;
+
+    let output_height = (height.max(0) as u32).max((MIN_BAR_HEIGHT + 2 * bearer_y) * multiple);
+    let bearer_height = bearer_y * multiple;
+
+    let mut output = BitMatrix::new(output_width, output_height)?;
+
+    if bearer_y > 0 {
+        let bearer_width = full_width * multiple;
+        output.setRegion(left, 0, bearer_width, bearer_height)?;
+        output.setRegion(
+            left,
+            output_height - bearer_height,
+            bearer_width,
+            bearer_height,
+        )?;
+    }
+    if bearer_x > 0 {
+        let bearer_width = bearer_x * multiple;
+        output.setRegion(left, 0, bearer_width, output_height)?;
+        output.setRegion(
+            left + (full_width - bearer_x) * multiple,
+            0,
+            bearer_width,
+            output_height,
+        )?;
+    }
+
+    let bars_left = left + (sides_margin + bearer_x) * multiple;
+    let bars_height = output_height - 2 * bearer_height;
+    for (x, bar) in code.iter().enumerate() {
+        if *bar {
+            output.setRegion(
+                bars_left + x as u32 * multiple,
+                bearer_height,
+                multiple,
+                bars_height,
+            )?;
+        }
+    }
+
+    let placement = Placement {
+        scale_x: multiple,
+        scale_y: bars_height,
+        offset_x: bars_left,
+        offset_y: bearer_height,
+    };
+    Ok((output, placement))
+}
+
+#[cfg(test)]
+mod ITF14WriterTestCase {
+    use crate::{oned::ITFWriter, BarcodeFormat, EncodeHintValue, EncodeHints, Writer};
+
+    use super::{gs1_check_digit, BearerBars, ITF14Writer};
+
+    #[test]
+    fn testCheckDigit() {
+        assert_eq!(gs1_check_digit("1540014128876").unwrap(), 3);
+        assert_eq!(gs1_check_digit("0001234567890").unwrap(), 5);
+    }
+
+    #[test]
+    fn testAddChecksumAndEncode() {
+        let hints = EncodeHints::default().with(EncodeHintValue::ItfBearerBars(BearerBars::None));
+        let itf14 = ITF14Writer::default()
+            .encode_with_hints("1540014128876", &BarcodeFormat::ITF_14, 0, 1, &hints)
+            .expect("encode");
+        let itf = ITFWriter
+            .encode_with_hints(
+                "15400141288763",
+                &BarcodeFormat::ITF,
+                0,
+                1,
+                &EncodeHints::default().with(EncodeHintValue::Margin("20".to_owned())),
+            )
+            .expect("encode");
+
+        assert_eq!(itf14.getWidth(), itf.getWidth());
+        for x in 0..itf.getWidth() {
+            assert_eq!(itf14.get(x, itf14.getHeight() / 2), itf.get(x, 0));
+        }
+    }
+
+    #[test]
+    fn testFrame() {
+        let matrix = ITF14Writer::default()
+            .encode("15400141288763", &BarcodeFormat::ITF_14, 0, 0)
+            .expect("encode");
+        let width = matrix.getWidth();
+        let height = matrix.getHeight();
+        assert_eq!(width, 135 + 2 * (10 + 5));
+        assert_eq!(height, 32 + 2 * 5);
+        for x in 0..width {
+            assert!(matrix.get(x, 0) && matrix.get(x, height - 1));
+        }
+        for y in 0..height {
+            assert!(matrix.get(0, y) && matrix.get(width - 1, y));
+        }
+        // quiet zones inside the frame
+        for x in 5..15 {
+            assert!(!matrix.get(x, height / 2));
+            assert!(!matrix.get(width - 1 - x, height / 2));
+        }
+    }
+
+    #[test]
+    fn testBadChecksum() {
+        assert!(ITF14Writer::default()
+            .encode("15400141288764", &BarcodeFormat::ITF_14, 0, 0)
+            .is_err());
+    }
+
+    #[test]
+    fn testBadLength() {
+        assert!(ITF14Writer::default()
+            .encode("154001412887", &BarcodeFormat::ITF_14, 0, 0)
+            .is_err());
+    }
+}
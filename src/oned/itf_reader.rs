@@ -32,6 +32,12 @@ const W: u32 = 3; // Pixel width of a 3x wide line
 const W_LOWER: u32 = 2; // Pixel width of a 2x wide line
 const N: u32 = 1; // Pixed width of a narrow line
 
+/** Smallest quiet zone accepted between the bars and a bearer bar, in narrow line widths. */
+const MIN_BEARER_QUIET_ZONE: i32 = 5;
+
+/** Smallest width of a bearer bar, in narrow line widths; a wide line is at most 3. */
+const MIN_BEARER_BAR_WIDTH: i32 = 4;
+
 /** Valid ITF lengths. Anything longer than the largest value is also allowed. */
 const DEFAULT_ALLOWED_LENGTHS: [u32; 5] = [6, 8, 10, 12, 14];
 
@@ -91,7 +97,7 @@ const PATTERNS: [[u32; 5]; 20] = [
  *
  * @author kevin.osullivan@sita.aero, SITA Lab.
  */
-#[derive(OneDReader)]
+#[derive(OneDReader, Clone)]
 pub struct ITFReader {
     // Stores the actual narrow line width of the image being decoded.
     narrowLineWidth: i32,
@@ -259,7 +265,7 @@ impl ITFReader {
             i -= 1;
         }
 
-        if quietCount != 0 {
+        if quietCount != 0 && !self.isBearerBar(row, i, startPattern) {
             // Unable to find the necessary number of quiet zone pixels.
             Err(Exceptions::NOT_FOUND)
         } else {
@@ -267,6 +273,29 @@ impl ITFReader {
         }
     }
 
+    /**
+     * Bearer bars, as printed around ITF-14 symbols, may cut the quiet zone short. Accept a
+     * shorter quiet zone if it is bounded by a line too wide to be part of an ITF symbol.
+     *
+     * @param row bit array representing the scanned barcode.
+     * @param edge index of the last pixel of the line ending the quiet zone.
+     * @param startPattern index into row of the start or end pattern.
+     */
+    fn isBearerBar(&self, row: &BitArray, edge: isize, startPattern: usize) -> bool {
+        if edge < 0
+            || (startPattern as isize - edge - 1)
+                < (self.narrowLineWidth * MIN_BEARER_QUIET_ZONE) as isize
+        {
+            return false;
+        }
+        let mut i = edge;
+        while i >= 0 && row.get(i as usize) {
+            i -= 1;
+        }
+        // a line running into the edge of the image may be as wide as the bearer
+        i < 0 || edge - i >= (self.narrowLineWidth * MIN_BEARER_BAR_WIDTH) as isize
+    }
+
     /**
      * Skip all whitespace until we get to the first black line.
      *
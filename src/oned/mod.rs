@@ -24,6 +24,12 @@ pub use code_128_reader::*;
 mod itf_reader;
 pub use itf_reader::*;
 
+mod itf_14_reader;
+pub use itf_14_reader::*;
+
+mod deutsche_post_reader;
+pub use deutsche_post_reader::*;
+
 mod telepen_reader;
 pub use telepen_reader::*;
 
@@ -68,6 +74,12 @@ pub use code_93_writer::*;
 mod itf_writer;
 pub use itf_writer::*;
 
+mod itf_14_writer;
+pub use itf_14_writer::*;
+
+mod deutsche_post_writer;
+pub use deutsche_post_writer::*;
+
 mod code_128_writer;
 pub use code_128_writer::*;
 
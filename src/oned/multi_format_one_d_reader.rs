@@ -20,6 +20,8 @@ use super::CodaBarReader;
 use super::Code128Reader;
 use super::Code39Reader;
 use super::Code93Reader;
+use super::DeutschePostReader;
+use super::ITF14Reader;
 use super::ITFReader;
 use super::MultiFormatUPCEANReader;
 use super::OneDReader;
@@ -33,11 +35,12 @@ use crate::{BarcodeFormat, Binarizer, RXingResult};
  * @author dswitkin@google.com (Daniel Switkin)
  * @author Sean Owen
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct MultiFormatOneDReader {
     internal_hints: DecodeHints,
     possible_formats: HashSet<BarcodeFormat>,
     use_code_39_check_digit: bool,
+    use_code_39_full_ascii: bool,
     rss_14_reader: RSS14Reader,
     rss_expanded_reader: RSSExpandedReader,
 }
@@ -51,6 +54,7 @@ impl OneDReader for MultiFormatOneDReader {
         let Self {
             possible_formats,
             use_code_39_check_digit,
+            use_code_39_full_ascii,
             internal_hints,
             rss_14_reader,
             rss_expanded_reader,
@@ -69,8 +73,9 @@ impl OneDReader for MultiFormatOneDReader {
                 }
             }
             if possible_formats.contains(&BarcodeFormat::CODE_39) {
-                if let Ok(res) = Code39Reader::with_use_check_digit(*use_code_39_check_digit)
-                    .decode_row(row_number, row, hints)
+                if let Ok(res) =
+                    Code39Reader::with_all_config(*use_code_39_check_digit, *use_code_39_full_ascii)
+                        .decode_row(row_number, row, hints)
                 {
                     return Ok(res);
                 }
@@ -85,6 +90,18 @@ impl OneDReader for MultiFormatOneDReader {
                     return Ok(res);
                 }
             }
+            if possible_formats.contains(&BarcodeFormat::ITF_14) {
+                if let Ok(res) = ITF14Reader::default().decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
+            if possible_formats.contains(&BarcodeFormat::DEUTSCHE_POST_LEITCODE)
+                || possible_formats.contains(&BarcodeFormat::DEUTSCHE_POST_IDENTCODE)
+            {
+                if let Ok(res) = DeutschePostReader::default().decode_row(row_number, row, hints) {
+                    return Ok(res);
+                }
+            }
             if possible_formats.contains(&BarcodeFormat::ITF) {
                 if let Ok(res) = ITFReader::default().decode_row(row_number, row, hints) {
                     return Ok(res);
@@ -116,8 +133,9 @@ impl OneDReader for MultiFormatOneDReader {
             {
                 return Ok(res);
             }
-            if let Ok(res) = Code39Reader::with_use_check_digit(*use_code_39_check_digit)
-                .decode_row(row_number, row, hints)
+            if let Ok(res) =
+                Code39Reader::with_all_config(*use_code_39_check_digit, *use_code_39_full_ascii)
+                    .decode_row(row_number, row, hints)
             {
                 return Ok(res);
             }
@@ -150,6 +168,7 @@ impl OneDReader for MultiFormatOneDReader {
 impl MultiFormatOneDReader {
     pub fn new(hints: &DecodeHints) -> Self {
         let use_code_39_check_digit = matches!(hints.AssumeCode39CheckDigit, Some(true));
+        let use_code_39_full_ascii = matches!(hints.AssumeCode39FullAscii, Some(true));
         let possible_formats = if let Some(p) = &hints.PossibleFormats {
             p.clone()
         } else {
@@ -159,6 +178,7 @@ impl MultiFormatOneDReader {
         Self {
             possible_formats,
             use_code_39_check_digit,
+            use_code_39_full_ascii,
             rss_14_reader: RSS14Reader::default(),
             internal_hints: hints.clone(),
             rss_expanded_reader: RSSExpandedReader::default(),
@@ -186,9 +206,10 @@ impl Reader for MultiFormatOneDReader {
         if first_try.is_ok() {
             return first_try;
         }
+        hints.check_cancelled()?;
 
         let tryHarder = hints.TryHarder.unwrap_or(false);
-        if tryHarder && image.is_rotate_supported() {
+        if tryHarder && image.is_rotate_supported() && !hints.has_scan_region() {
             let mut rotatedImage = image.rotate_counter_clockwise();
             let mut result = self._do_decode(&mut rotatedImage, hints)?;
             // Record that we found it rotated 90 degrees CCW / 270 degrees CW
@@ -35,6 +35,7 @@ use super::{OneDReader, UPCEANReader};
  *
  * @author Sean Owen
  */
+#[derive(Clone)]
 pub struct MultiFormatUPCEANReader {
     possible_formats: HashSet<BarcodeFormat>,
 }
@@ -195,9 +196,10 @@ impl Reader for MultiFormatUPCEANReader {
         if first_try.is_ok() {
             return first_try;
         }
+        hints.check_cancelled()?;
 
         let tryHarder = hints.TryHarder.unwrap_or(false);
-        if tryHarder && image.is_rotate_supported() {
+        if tryHarder && image.is_rotate_supported() && !hints.has_scan_region() {
             let mut rotatedImage = image.rotate_counter_clockwise();
             let mut result = self._do_decode(&mut rotatedImage, hints)?;
             // Record that we found it rotated 90 degrees CCW / 270 degrees CW
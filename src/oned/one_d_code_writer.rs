@@ -16,6 +16,7 @@
 
 use crate::{
     common::{BitMatrix, Result},
+    encoded_symbol::Placement,
     BarcodeFormat, EncodeHints, Exceptions, Writer,
 };
 
@@ -59,29 +60,7 @@ pub trait OneDimensionalCodeWriter: Writer {
         height: i32,
         sidesMargin: u32,
     ) -> Result<BitMatrix> {
-        let inputWidth = code.len();
-        // Add quiet zone on both sides.
-        let fullWidth = inputWidth + sidesMargin as usize;
-        let outputWidth = width.max(fullWidth as i32);
-        let outputHeight = 1.max(height);
-
-        let multiple = outputWidth as usize / fullWidth;
-        let leftPadding = (outputWidth as isize - (inputWidth as isize * multiple as isize)) / 2;
-
-        let mut output = BitMatrix::new(outputWidth as u32, outputHeight as u32)?;
-
-        let mut inputX = 0;
-        let mut outputX = leftPadding;
-
-        while inputX < inputWidth {
-            if code[inputX] {
-                output.setRegion(outputX as u32, 0, multiple as u32, outputHeight as u32)?;
-            }
-
-            inputX += 1;
-            outputX += multiple as isize;
-        }
-        Ok(output)
+        Ok(render_linear(code, width, height, sidesMargin)?.0)
     }
 
     /**
@@ -132,3 +111,56 @@ pub trait OneDimensionalCodeWriter: Writer {
         10
     }
 }
+
+/**
+ * Renders the bars of `code` like [`OneDimensionalCodeWriter::renderRXingResult`], and returns
+ * where the modules are in the matrix.
+ */
+pub(crate) fn render_linear(
+    code: &[bool],
+    width: i32,
+    height: i32,
+    sidesMargin: u32,
+) -> Result<(BitMatrix, Placement)> {
+    let inputWidth = code.len();
+    // Add quiet zone on both sides.
+    let fullWidth = inputWidth + sidesMargin as usize;
+    let outputWidth = width.max(fullWidth as i32);
+    let outputHeight = 1.max(height);
+
+    let multiple = outputWidth as usize / fullWidth;
+    let leftPadding = (outputWidth as isize - (inputWidth as isize * multiple as isize)) / 2;
+
+    let mut output = BitMatrix::new(outputWidth as u32, outputHeight as u32)?;
+
+    let mut inputX = 0;
+    let mut outputX = leftPadding;
+
+    while inputX < inputWidth {
+        if code[inputX] {
+            output.setRegion(outputX as u32, 0, multiple as u32, outputHeight as u32)?;
+        }
+
+        inputX += 1;
+        outputX += multiple as isize;
+    }
+
+    let placement = Placement {
+        scale_x: multiple as u32,
+        scale_y: outputHeight as u32,
+        offset_x: leftPadding as u32,
+        offset_y: 0,
+    };
+    Ok((output, placement))
+}
+
+/// The bars of `code` as a matrix one module high.
+pub(crate) fn linear_modules(code: &[bool]) -> Result<BitMatrix> {
+    let mut modules = BitMatrix::new(code.len() as u32, 1)?;
+    for (x, bar) in code.iter().enumerate() {
+        if *bar {
+            modules.set(x as u32, 0);
+        }
+    }
+    Ok(modules)
+}
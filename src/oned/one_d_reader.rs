@@ -14,10 +14,12 @@
  * limitations under the License.
  */
 
+use std::{ops::Range, sync::Arc};
+
 use crate::{
     common::{BitArray, Result},
-    point, Binarizer, BinaryBitmap, DecodeHints, Exceptions, LuminanceSource, RXingResult,
-    RXingResultMetadataType, RXingResultMetadataValue, Reader,
+    point, Binarizer, BinaryBitmap, DecodeHints, EstimatedModuleSize, Exceptions, LuminanceSource,
+    Point, PointCallback, RXingResult, RXingResultMetadataType, RXingResultMetadataValue, Reader,
 };
 
 /**
@@ -37,17 +39,24 @@ pub trait OneDReader: Reader {
      * rowStep is bigger as the image is taller, but is always at least 1. We've somewhat arbitrarily
      * decided that moving up and down by about 1/16 of the image is pretty good; we try more of the
      * image if "trying harder".
+     * With a region of interest, scan lines or scan angles in the hints, the lines of
+     * decodeScanLines() are scanned instead.
      *
      * @param image The image to decode
      * @param hints Any hints that were requested
      * @return The contents of the decoded barcode
      * @throws NotFoundException Any spontaneous errors which occur
+     * @throws TimeoutException if the cancellation token of the hints is cancelled
      */
     fn _do_decode<B: Binarizer>(
         &mut self,
         image: &mut BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<RXingResult> {
+        if hints.has_scan_region() {
+            return self.decode_scan_lines(image, hints);
+        }
+
         let mut hints = hints.clone();
         let width = image.get_width();
         let height = image.get_height();
@@ -82,6 +91,7 @@ pub trait OneDReader: Reader {
 
         let middle = height / 2;
         for x in 0..max_lines {
+            hints.check_cancelled()?;
             // Scanning from the middle out. Determine which row we're looking at next:
             let row_steps_above_or_below = x.div_ceil(2);
             let is_above = (x & 0x01) == 0; // i.e. is x even?
@@ -124,6 +134,14 @@ pub trait OneDReader: Reader {
                     continue;
                 };
                 // We found our barcode
+                let span = symbol_span(&row, result.getPoints());
+                let module_size = estimate_module_size(&row, result.getPoints());
+                if let Some(module_size) = module_size {
+                    result.putMetadata(
+                        RXingResultMetadataType::MODULE_SIZE,
+                        RXingResultMetadataValue::ModuleSize(module_size),
+                    );
+                }
                 if attempt == 1 {
                     // But it was upside down, so note that
                     result.putMetadata(
@@ -137,6 +155,110 @@ pub trait OneDReader: Reader {
                         points[1] = point(width as f32 - points[1].x - 1.0, points[1].y);
                     }
                 }
+                if !hints.accepts_symbol(result.getPoints()) {
+                    continue;
+                }
+                if let Some(span) = span {
+                    let rows_agreed = count_agreeing_rows(
+                        image,
+                        &hints,
+                        &runs(&row, span.clone()),
+                        span,
+                        module_size.map_or(0.0, |EstimatedModuleSize(size)| size / 2.0),
+                        row_number as usize,
+                        row_step,
+                        attempt == 1,
+                    );
+                    result.putMetadata(
+                        RXingResultMetadataType::ROWS_AGREED,
+                        RXingResultMetadataValue::RowsAgreed(rows_agreed),
+                    );
+                }
+                return Ok(result);
+            }
+        }
+
+        Err(Exceptions::NOT_FOUND)
+    }
+
+    /**
+     * Scans the lines given by the scan lines of the hints or, without them, lines at the scan
+     * angles, 0 and, if "trying harder", 90 degrees by default, across the region of interest or the
+     * image. Like rows, they are spaced from the middle outward. Pixels outside the region of
+     * interest are taken to be white. A barcode read along a line at an angle is reported with the
+     * orientation that would make the line horizontal, so 270 for a line from top to bottom.
+     *
+     * @param image The image to decode
+     * @param hints Any hints that were requested
+     * @return The contents of the decoded barcode, with points in the coordinates of the image
+     * @throws NotFoundException if no barcode is found on any line
+     */
+    fn decode_scan_lines<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let mut hints = hints.clone();
+        let callback = hints.NeedResultPointCallback.take();
+
+        for (start, end) in scan_lines(image.get_width(), image.get_height(), &hints) {
+            hints.check_cancelled()?;
+            let length = (end - start).maxAbsComponent().ceil();
+            if length < 1.0 {
+                continue;
+            }
+            let step = (end - start) / length;
+            let Some(mut row) = sample_line(image, start, step, length as usize, &hints) else {
+                continue;
+            };
+
+            for attempt in 0..2 {
+                // Where the pixels of the row, reversed on the second attempt, are in the image.
+                let (first, step) = if attempt == 0 {
+                    (start, step)
+                } else {
+                    row.reverse();
+                    (start + step * (length - 1.0), -step)
+                };
+                let to_image = move |x: f32| first + step * x;
+
+                // As in _do_decode(), only draw the points of the first attempt.
+                hints.NeedResultPointCallback = match (&callback, attempt) {
+                    (Some(callback), 0) => {
+                        let callback = callback.clone();
+                        Some(Arc::new(move |p: Point| callback(to_image(p.x))) as PointCallback)
+                    }
+                    _ => None,
+                };
+                let Ok(mut result) = self.decode_row(first.y.max(0.0) as u32, &row, &hints) else {
+                    continue;
+                };
+                if let Some(EstimatedModuleSize(module_size)) =
+                    estimate_module_size(&row, result.getPoints())
+                {
+                    // a step along a line at an angle is longer than a pixel
+                    result.putMetadata(
+                        RXingResultMetadataType::MODULE_SIZE,
+                        RXingResultMetadataValue::ModuleSize(EstimatedModuleSize(
+                            module_size * step.length(),
+                        )),
+                    );
+                }
+                for p in result.getPointsMut() {
+                    *p = to_image(p.x);
+                }
+                if !hints.accepts_symbol(result.getPoints()) {
+                    continue;
+                }
+
+                let angle = step.y.atan2(step.x).to_degrees().round() as i32;
+                let orientation = (360 - angle).rem_euclid(360);
+                if orientation != 0 {
+                    result.putMetadata(
+                        RXingResultMetadataType::ORIENTATION,
+                        RXingResultMetadataValue::Orientation(orientation),
+                    );
+                }
                 return Ok(result);
             }
         }
@@ -175,8 +297,111 @@ pub trait OneDReader: Reader {
 
         let new_row = pad_bitarray(row, Self::QUIET_ZONE);
 
-        self.decode_row(rowNumber, &new_row, hints)
+        let mut result = self.decode_row(rowNumber, &new_row, hints)?;
+        if let Some(module_size) = estimate_module_size(&new_row, result.getPoints()) {
+            result.putMetadata(
+                RXingResultMetadataType::MODULE_SIZE,
+                RXingResultMetadataValue::ModuleSize(module_size),
+            );
+        }
+        Ok(result)
+    }
+}
+
+/// Counts the rows, `row_step` apart above and below `row_number`, that show the bars and spaces
+/// `runs` of the symbol read in `span`, read in the same direction, up to the first one on each
+/// side that does not. A row agrees when its elements are no more than `tolerance` pixels wider
+/// or narrower. Comparing the rows instead of decoding them again keeps the count cheap and does
+/// not disturb readers that remember the rows they have seen, like the RSS readers. The row at
+/// `row_number` is counted.
+#[allow(clippy::too_many_arguments)]
+fn count_agreeing_rows<B: Binarizer>(
+    image: &mut BinaryBitmap<B>,
+    hints: &DecodeHints,
+    runs: &[u32],
+    span: Range<usize>,
+    tolerance: f32,
+    row_number: usize,
+    row_step: usize,
+    reversed: bool,
+) -> u32 {
+    let agrees = |row: &BitArray| {
+        let other = self::runs(row, span.clone());
+        other.len() == runs.len()
+            && other
+                .iter()
+                .zip(runs)
+                .all(|(a, b)| a.abs_diff(*b) as f32 <= tolerance)
+    };
+
+    let mut agreed = 1;
+    for direction in [-1, 1] {
+        let mut row_number = row_number as isize + direction * row_step as isize;
+        while row_number >= 0 && (row_number as usize) < image.get_height() {
+            if hints.check_cancelled().is_err() {
+                break;
+            }
+            let Ok(mut row) = image.get_black_row(row_number as usize) else {
+                break;
+            };
+            if reversed {
+                row.to_mut().reverse();
+            }
+            if !agrees(&row) {
+                break;
+            }
+            agreed += 1;
+            row_number += direction * row_step as isize;
+        }
+    }
+    agreed
+}
+
+/// The part of `row` between the leftmost and the rightmost of `points`.
+fn symbol_span(row: &BitArray, points: &[Point]) -> Option<Range<usize>> {
+    let left = points.iter().map(|p| p.x).reduce(f32::min)?.max(0.0) as usize;
+    let right = (points.iter().map(|p| p.x).reduce(f32::max)? as usize).min(row.get_size());
+    Some(left..right)
+}
+
+/// The widths of the bars and spaces of `row` in `span`, the first space or bar starting at the
+/// start of the span.
+fn runs(row: &BitArray, span: Range<usize>) -> Vec<u32> {
+    let left = span.start;
+    let mut runs = Vec::new();
+    let mut run = 0;
+    for x in span {
+        if x > left && row.get(x) != row.get(x - 1) {
+            runs.push(run);
+            run = 0;
+        }
+        run += 1;
+    }
+    runs.push(run);
+    // tell a row starting with a bar from one starting with a space
+    if left < row.get_size() && row.get(left) {
+        runs.insert(0, 0);
+    }
+    runs
+}
+
+/// Estimates the module size of the symbol between the leftmost and the rightmost of `points` on
+/// `row` as the average width of its narrow bars and spaces: those no wider than one and a half
+/// times the first quartile of all of them.
+fn estimate_module_size(row: &BitArray, points: &[Point]) -> Option<EstimatedModuleSize> {
+    let mut runs = runs(row, symbol_span(row, points)?);
+    runs.retain(|run| *run > 0);
+    if runs.len() < 4 {
+        return None;
     }
+
+    runs.sort_unstable();
+    let narrow = runs[runs.len() / 4] as f32 * 1.5;
+    let narrow_runs = runs.iter().filter(|run| **run as f32 <= narrow);
+    let count = narrow_runs.clone().count();
+    Some(EstimatedModuleSize(
+        narrow_runs.sum::<u32>() as f32 / count as f32,
+    ))
 }
 
 // Add a buffer on either side of the row to mimic a quiet zone. This may not exist in a "pure barcode"
@@ -202,6 +427,94 @@ fn pad_bitarray(bits: &[u8], quiet_zone: usize) -> BitArray {
     new_row
 }
 
+/// The lines, from start to end, that decode_scan_lines() scans in an image of `width` by `height`.
+fn scan_lines(width: usize, height: usize, hints: &DecodeHints) -> Vec<(Point, Point)> {
+    if let Some(lines) = &hints.ScanLines {
+        return lines.clone();
+    }
+
+    let try_harder = hints.TryHarder.unwrap_or(false);
+    let angles = match &hints.ScanAngles {
+        Some(angles) => angles.clone(),
+        None if try_harder => vec![0.0, 90.0],
+        None => vec![0.0],
+    };
+    let (left, top, width, height) = hints
+        .region_bounds(width, height)
+        .unwrap_or((0, 0, width, height));
+    let (width, height) = (width as f32, height as f32);
+    let middle = point(left as f32 + width / 2.0, top as f32 + height / 2.0);
+
+    // Lines are numbered from the middle outward, as rows are in _do_decode(), and at each number
+    // every angle is tried before moving farther out.
+    let mut lines = Vec::new();
+    for angle in angles {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let along = point(cos, sin);
+        let across = point(-sin, cos);
+        let half_length = (width * cos.abs() + height * sin.abs()) / 2.0;
+        let breadth = width * sin.abs() + height * cos.abs();
+        let line_step = f32::max(1.0, breadth / if try_harder { 256.0 } else { 32.0 });
+        let max_lines = if try_harder { breadth as usize } else { 15 };
+        for x in 0..max_lines.max(1) {
+            let offset = x.div_ceil(2) as f32 * line_step * if x & 0x01 == 0 { 1.0 } else { -1.0 };
+            if offset.abs() > breadth / 2.0 {
+                break;
+            }
+            let center = middle + across * offset;
+            lines.push((
+                x,
+                center - along * half_length,
+                center + along * half_length,
+            ));
+        }
+    }
+    lines.sort_by_key(|(x, _, _)| *x);
+
+    lines
+        .into_iter()
+        .map(|(_, start, end)| (start, end))
+        .collect()
+}
+
+/// Samples `count` pixels from `start`, `step` apart. Horizontal lines are taken from the black
+/// rows of `image`, others from its black matrix. Pixels outside the image or the region of
+/// interest are white.
+fn sample_line<B: Binarizer>(
+    image: &BinaryBitmap<B>,
+    start: Point,
+    step: Point,
+    count: usize,
+    hints: &DecodeHints,
+) -> Option<BitArray> {
+    let (width, height) = (image.get_width() as f32, image.get_height() as f32);
+    let black_row = if step.y == 0.0 {
+        if start.y < 0.0 || start.y >= height {
+            return None;
+        }
+        Some(image.get_black_row(start.y as usize).ok()?)
+    } else {
+        None
+    };
+
+    let mut row = BitArray::with_size(count);
+    for i in 0..count {
+        let p = start + step * i as f32;
+        if p.x < 0.0 || p.y < 0.0 || p.x >= width || p.y >= height || !hints.in_region(p) {
+            continue;
+        }
+        let black = match &black_row {
+            Some(black_row) => black_row.get(p.x as usize),
+            None => image.get_black_matrix().get(p.x as u32, p.y as u32),
+        };
+        if black {
+            row.set(i);
+        }
+    }
+
+    Some(row)
+}
+
 /**
  * Determines how closely a set of observed counts of runs of black/white values matches a given
  * target pattern. This is reported as the ratio of the total variance from the expected pattern
@@ -126,7 +126,7 @@ const FINDER_PATTERN_SEQUENCES: [&[u32]; 10] = [
  * @author Pablo Orduña, University of Deusto (pablo.orduna@deusto.es)
  * @author Eduardo Castillejo, University of Deusto (eduardo.castillejo@deusto.es)
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct RSSExpandedReader {
     decodeFinderCounters: [u32; 4],
     dataCharacterCounters: [u32; 8],
@@ -177,8 +177,9 @@ impl Reader for RSSExpandedReader {
         if let Ok(res) = self._do_decode(image, hints) {
             Ok(res)
         } else {
+            hints.check_cancelled()?;
             let tryHarder = hints.TryHarder.unwrap_or(false);
-            if tryHarder && image.is_rotate_supported() {
+            if tryHarder && image.is_rotate_supported() && !hints.has_scan_region() {
                 let mut rotatedImage = image.rotate_counter_clockwise();
                 let mut result = self._do_decode(&mut rotatedImage, hints)?;
                 // Record that we found it rotated 90 degrees CCW / 270 degrees CW
@@ -565,6 +566,13 @@ impl RSSExpandedReader {
             RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
             RXingResultMetadataValue::SymbologyIdentifier("]e0".to_owned()),
         );
+        // the first bit of the data flags a composite component above the symbol
+        if binary.get(0) {
+            result.putMetadata(
+                RXingResultMetadataType::GS1_COMPOSITE_LINKAGE,
+                RXingResultMetadataValue::Gs1CompositeLinkage(true),
+            );
+        }
 
         Ok(result)
     }
@@ -16,3 +16,6 @@ pub use abstract_rss_reader::*;
 
 mod rss_14_reader;
 pub use rss_14_reader::*;
+
+mod rss_14_writer;
+pub use rss_14_writer::*;
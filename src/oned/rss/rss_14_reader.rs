@@ -28,7 +28,7 @@ use super::{
 /**
  * Decodes RSS-14, including truncated and stacked variants. See ISO/IEC 24724:2006.
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct RSS14Reader {
     possibleLeftPairs: Vec<Pair>,
     possibleRightPairs: Vec<Pair>,
@@ -83,8 +83,9 @@ impl Reader for RSS14Reader {
         if let Ok(res) = self._do_decode(image, hints) {
             Ok(res)
         } else {
+            hints.check_cancelled()?;
             let tryHarder = hints.TryHarder.unwrap_or(false);
-            if tryHarder && image.is_rotate_supported() {
+            if tryHarder && image.is_rotate_supported() && !hints.has_scan_region() {
                 let mut rotatedImage = image.rotate_counter_clockwise();
                 let mut result = self._do_decode(&mut rotatedImage, hints)?;
                 // Record that we found it rotated 90 degrees CCW / 270 degrees CW
@@ -125,14 +126,16 @@ impl Reader for RSS14Reader {
 }
 
 impl RSS14Reader {
-    const OUTSIDE_EVEN_TOTAL_SUBSET: [u32; 5] = [1, 10, 34, 70, 126];
-    const INSIDE_ODD_TOTAL_SUBSET: [u32; 4] = [4, 20, 48, 81];
-    const OUTSIDE_GSUM: [u32; 5] = [0, 161, 961, 2015, 2715];
-    const INSIDE_GSUM: [u32; 4] = [0, 336, 1036, 1516];
-    const OUTSIDE_ODD_WIDEST: [u32; 5] = [8, 6, 4, 3, 1];
-    const INSIDE_ODD_WIDEST: [u32; 4] = [2, 4, 6, 8];
-
-    const FINDER_PATTERNS: [[u32; 4]; 9] = [
+    pub(crate) const LINKAGE_VALUE: u64 = 10_000_000_000_000;
+
+    pub(crate) const OUTSIDE_EVEN_TOTAL_SUBSET: [u32; 5] = [1, 10, 34, 70, 126];
+    pub(crate) const INSIDE_ODD_TOTAL_SUBSET: [u32; 4] = [4, 20, 48, 81];
+    pub(crate) const OUTSIDE_GSUM: [u32; 5] = [0, 161, 961, 2015, 2715];
+    pub(crate) const INSIDE_GSUM: [u32; 4] = [0, 336, 1036, 1516];
+    pub(crate) const OUTSIDE_ODD_WIDEST: [u32; 5] = [8, 6, 4, 3, 1];
+    pub(crate) const INSIDE_ODD_WIDEST: [u32; 4] = [2, 4, 6, 8];
+
+    pub(crate) const FINDER_PATTERNS: [[u32; 4]; 9] = [
         [3, 8, 2, 1],
         [3, 5, 5, 1],
         [3, 3, 7, 1],
@@ -176,7 +179,11 @@ impl RSS14Reader {
     }
 
     fn constructRXingResult(&self, leftPair: &Pair, rightPair: &Pair) -> Option<RXingResult> {
-        let symbolValue: u64 = 4537077 * leftPair.getValue() as u64 + rightPair.getValue() as u64;
+        let mut symbolValue: u64 =
+            4537077 * leftPair.getValue() as u64 + rightPair.getValue() as u64;
+        // values past the 13 digits of the GTIN flag a composite component above the symbol
+        let linkage = symbolValue >= Self::LINKAGE_VALUE;
+        symbolValue %= Self::LINKAGE_VALUE;
         let text = symbolValue.to_string();
 
         let mut buffer = String::with_capacity(14);
@@ -210,6 +217,12 @@ impl RSS14Reader {
             RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
             RXingResultMetadataValue::SymbologyIdentifier("]e0".to_owned()),
         );
+        if linkage {
+            result.putMetadata(
+                RXingResultMetadataType::GS1_COMPOSITE_LINKAGE,
+                RXingResultMetadataValue::Gs1CompositeLinkage(true),
+            );
+        }
 
         Some(result)
     }
@@ -234,9 +247,32 @@ impl RSS14Reader {
         rowNumber: u32,
         hints: &DecodeHints,
     ) -> Option<Pair> {
-        let startEnd = self.findFinderPattern(row, right).ok()?;
+        // a data character can look like a finder pattern, so keep looking past candidates which
+        // do not decode
+        let mut rowOffset = 0;
+        loop {
+            let startEnd = self.findFinderPattern(row, right, rowOffset).ok()?;
+            if let Some(pair) = self.decodePairAt(row, right, rowNumber, hints, &startEnd) {
+                return Some(pair);
+            }
+            rowOffset = if right {
+                row.getNextSet(startEnd[0])
+            } else {
+                row.getNextUnset(startEnd[0])
+            };
+        }
+    }
+
+    fn decodePairAt(
+        &mut self,
+        row: &BitArray,
+        right: bool,
+        rowNumber: u32,
+        hints: &DecodeHints,
+        startEnd: &[usize; 2],
+    ) -> Option<Pair> {
         let pattern = self
-            .parseFoundFinderPattern(row, rowNumber, right, &startEnd)
+            .parseFoundFinderPattern(row, rowNumber, right, startEnd)
             .ok()?;
 
         if let Some(cb) = &hints.NeedResultPointCallback {
@@ -362,13 +398,14 @@ impl RSS14Reader {
         &mut self,
         row: &BitArray,
         rightFinderPattern: bool,
+        rowOffset: usize,
     ) -> Result<[usize; 2]> {
         let counters = &mut self.decodeFinderCounters;
         counters.fill(0);
 
         let width = row.get_size();
         let mut isWhite = false;
-        let mut rowOffset = 0;
+        let mut rowOffset = rowOffset;
         while rowOffset < width {
             isWhite = !row.get(rowOffset);
             if rightFinderPattern == isWhite {
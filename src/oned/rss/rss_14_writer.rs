@@ -0,0 +1,250 @@
+use rxing_one_d_proc_derive::OneDWriter;
+
+use crate::{
+    common::Result,
+    oned::{rss::rss_utils, OneDimensionalCodeWriter},
+    BarcodeFormat,
+};
+
+use super::RSS14Reader;
+
+/// The element widths of the outside and inside characters of a pair.
+type Pair = ([u32; 8], [u32; 8]);
+
+/**
+ * Renders a GS1 DataBar Omnidirectional (RSS-14) symbol of a GTIN. See ISO/IEC 24724:2006.
+ *
+ * The contents are the 13 digits of the GTIN, or all 14 with the check digit. With the
+ * {@link EncodeHintType#GS1_COMPOSITE} hint, the symbol flags the component above it.
+ */
+#[derive(OneDWriter, Default)]
+pub struct RSS14Writer;
+
+impl OneDimensionalCodeWriter for RSS14Writer {
+    fn encode_oned(&self, contents: &str) -> Result<Vec<bool>> {
+        self.encode_oned_with_hints(contents, &EncodeHints::default())
+    }
+
+    fn encode_oned_with_hints(&self, contents: &str, hints: &EncodeHints) -> Result<Vec<bool>> {
+        Self::checkNumeric(contents)?;
+        let digits = contents
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .collect::<Vec<_>>();
+        match digits.len() {
+            13 => {}
+            14 => {
+                if digits[13] != Self::check_digit(&digits[..13]) {
+                    return Err(Exceptions::illegal_argument_with(
+                        "Contents do not pass checksum",
+                    ));
+                }
+            }
+            length => {
+                return Err(Exceptions::illegal_argument_with(format!(
+                    "Requested contents should be 13 or 14 digits long, but got {length}"
+                )))
+            }
+        }
+
+        let mut value = digits[..13]
+            .iter()
+            .fold(0_u64, |value, digit| value * 10 + *digit as u64);
+        if hints.Gs1Composite.is_some() {
+            value += RSS14Reader::LINKAGE_VALUE;
+        }
+
+        let (left, leftChecksum) = Self::encode_pair((value / 4537077) as u32)?;
+        let (right, rightChecksum) = Self::encode_pair((value % 4537077) as u32)?;
+
+        // checksums 8 and 72 are skipped, they would be the finder pairs 0/8 and 8/0
+        let mut checksum = (leftChecksum + 16 * rightChecksum) % 79;
+        if checksum >= 8 {
+            checksum += 1;
+        }
+        if checksum >= 72 {
+            checksum += 1;
+        }
+
+        let mut widths = Self::half(&left, checksum / 9);
+        widths.extend(Self::half(&right, checksum % 9).iter().rev());
+
+        let mut result = vec![false; widths.iter().sum::<u32>() as usize];
+        Self::appendPattern(&mut result, 0, &widths, false);
+        Ok(result)
+    }
+
+    fn getSupportedWriteFormats(&self) -> Option<Vec<BarcodeFormat>> {
+        Some(vec![BarcodeFormat::RSS_14])
+    }
+}
+
+impl RSS14Writer {
+    fn check_digit(digits: &[u32]) -> u32 {
+        let sum = digits
+            .iter()
+            .enumerate()
+            .map(|(i, digit)| if i % 2 == 0 { 3 * digit } else { *digit })
+            .sum::<u32>();
+        (10 - sum % 10) % 10
+    }
+
+    /// The element widths of the outside and inside characters of a pair, in the order the
+    /// reader records them, and the checksum portion of the pair.
+    fn encode_pair(value: u32) -> Result<(Pair, u32)> {
+        let (outside, outsideChecksum) = Self::encode_outside_character(value / 1597)?;
+        let (inside, insideChecksum) = Self::encode_inside_character(value % 1597)?;
+        Ok(((outside, inside), outsideChecksum + 4 * insideChecksum))
+    }
+
+    fn encode_outside_character(value: u32) -> Result<([u32; 8], u32)> {
+        let group = RSS14Reader::OUTSIDE_GSUM
+            .iter()
+            .rposition(|gSum| *gSum <= value)
+            .ok_or(Exceptions::ILLEGAL_STATE)?;
+        let value = value - RSS14Reader::OUTSIDE_GSUM[group];
+        let tEven = RSS14Reader::OUTSIDE_EVEN_TOTAL_SUBSET[group];
+        let oddSum = 12 - 2 * group as u32;
+        let oddWidest = RSS14Reader::OUTSIDE_ODD_WIDEST[group];
+        let odd = Self::widths(value / tEven, oddSum, oddWidest, false)?;
+        let even = Self::widths(value % tEven, 16 - oddSum, 9 - oddWidest, true)?;
+        Ok(Self::character(odd, even))
+    }
+
+    fn encode_inside_character(value: u32) -> Result<([u32; 8], u32)> {
+        let group = RSS14Reader::INSIDE_GSUM
+            .iter()
+            .rposition(|gSum| *gSum <= value)
+            .ok_or(Exceptions::ILLEGAL_STATE)?;
+        let value = value - RSS14Reader::INSIDE_GSUM[group];
+        let tOdd = RSS14Reader::INSIDE_ODD_TOTAL_SUBSET[group];
+        let evenSum = 10 - 2 * group as u32;
+        let oddWidest = RSS14Reader::INSIDE_ODD_WIDEST[group];
+        let odd = Self::widths(value % tOdd, 15 - evenSum, oddWidest, true)?;
+        let even = Self::widths(value / tOdd, evenSum, 9 - oddWidest, false)?;
+        Ok(Self::character(odd, even))
+    }
+
+    /// Interleaves the odd and even element widths and computes the checksum portion.
+    fn character(odd: [u32; 4], even: [u32; 4]) -> ([u32; 8], u32) {
+        let mut counters = [0; 8];
+        let mut checksum = 0;
+        for i in (0..4).rev() {
+            counters[2 * i] = odd[i];
+            counters[2 * i + 1] = even[i];
+            checksum = checksum * 9 + odd[i] + 3 * even[i];
+        }
+        (counters, checksum)
+    }
+
+    /// Finds the widths of four elements of `modules` in total which `getRSSvalue` maps to
+    /// `value`.
+    fn widths(value: u32, modules: u32, maxWidth: u32, noNarrow: bool) -> Result<[u32; 4]> {
+        for a in 1..=maxWidth {
+            for b in 1..=maxWidth {
+                for c in 1..=maxWidth {
+                    let Some(d) = modules.checked_sub(a + b + c) else {
+                        continue;
+                    };
+                    let widths = [a, b, c, d];
+                    if !(1..=maxWidth).contains(&d) || (noNarrow && !widths.contains(&1)) {
+                        continue;
+                    }
+                    if rss_utils::getRSSvalue(&widths, maxWidth, noNarrow) == value {
+                        return Ok(widths);
+                    }
+                }
+            }
+        }
+        Err(Exceptions::ILLEGAL_STATE)
+    }
+
+    /// The widths of one half of the symbol, from its guard to the centre.
+    fn half((outside, inside): &Pair, finder: u32) -> Vec<u32> {
+        let finder = RSS14Reader::FINDER_PATTERNS[finder as usize];
+        let mut widths = vec![1, 1];
+        widths.extend(outside);
+        widths.extend(finder);
+        widths.push(15 - finder.iter().sum::<u32>());
+        widths.extend(inside.iter().rev());
+        widths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::BitMatrix,
+        oned::{rss::RSS14Reader, OneDReader, OneDimensionalCodeWriter},
+        BarcodeFormat, DecodeHints, EncodeHintValue, EncodeHints, RXingResult,
+        RXingResultMetadataType, RXingResultMetadataValue, Writer,
+    };
+
+    use super::RSS14Writer;
+
+    fn decode(matrix: &BitMatrix) -> RXingResult {
+        // the reader reports a symbol once it has read its pairs on three rows
+        let mut reader = RSS14Reader::default();
+        let row = matrix.getRow(0);
+        (0..3)
+            .find_map(|rowNumber| {
+                reader
+                    .decode_row(rowNumber, &row, &DecodeHints::default())
+                    .ok()
+            })
+            .expect("decode")
+    }
+
+    #[test]
+    fn round_trip() {
+        for gtin in [
+            "0000000000000",
+            "2001234567890",
+            "0950110153001",
+            "9999999999999",
+        ] {
+            assert_eq!(RSS14Writer.encode_oned(gtin).expect("encode").len(), 96);
+            let matrix = RSS14Writer
+                .encode(gtin, &BarcodeFormat::RSS_14, 0, 40)
+                .expect("encode");
+            let result = decode(&matrix);
+            assert_eq!(&result.getText()[..13], gtin);
+            assert!(!result
+                .getRXingResultMetadata()
+                .contains_key(&RXingResultMetadataType::GS1_COMPOSITE_LINKAGE));
+        }
+    }
+
+    #[test]
+    fn check_digit() {
+        let matrix = RSS14Writer
+            .encode("20012345678909", &BarcodeFormat::RSS_14, 0, 40)
+            .expect("encode");
+        assert_eq!(decode(&matrix).getText(), "20012345678909");
+
+        assert!(RSS14Writer
+            .encode("20012345678900", &BarcodeFormat::RSS_14, 0, 40)
+            .is_err());
+        assert!(RSS14Writer
+            .encode("200123456789", &BarcodeFormat::RSS_14, 0, 40)
+            .is_err());
+    }
+
+    #[test]
+    fn linkage() {
+        let hints =
+            EncodeHints::default().with(EncodeHintValue::Gs1Composite("(10)ABC".to_owned()));
+        let code = RSS14Writer
+            .encode_oned_with_hints("9999999999999", &hints)
+            .expect("encode");
+        let matrix = RSS14Writer::renderRXingResult(&code, 0, 40, 10).expect("render");
+        let result = decode(&matrix);
+        assert_eq!(result.getText(), "99999999999997");
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::GS1_COMPOSITE_LINKAGE),
+            Some(&RXingResultMetadataValue::Gs1CompositeLinkage(true))
+        );
+    }
+}
@@ -24,7 +24,7 @@ use super::{EAN13Reader, OneDReader, UPCEANReader};
  * @author dswitkin@google.com (Daniel Switkin)
  * @author Sean Owen
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct UPCAReader(EAN13Reader);
 
 impl Reader for UPCAReader {
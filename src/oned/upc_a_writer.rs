@@ -14,7 +14,7 @@
  * limitations under the License.
  */
 
-use crate::{common::Result, BarcodeFormat, EncodeHints, Exceptions, Writer};
+use crate::{common::Result, BarcodeFormat, EncodeHints, EncodedSymbol, Exceptions, Writer};
 
 use super::EAN13Writer;
 
@@ -45,19 +45,36 @@ impl Writer for UPCAWriter {
         height: i32,
         hints: &crate::EncodeHints,
     ) -> Result<crate::common::BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
         if format != &BarcodeFormat::UPC_A {
             return Err(Exceptions::illegal_argument_with(format!(
                 "Can only encode UPC-A, but got {format:?}"
             )));
         }
         // Transform a UPC-A code into the equivalent EAN-13 code and write it that way
-        self.0.encode_with_hints(
+        let symbol = self.0.encode_with_metadata(
             &format!("0{contents}"),
             &BarcodeFormat::EAN_13,
             width,
             height,
             hints,
-        )
+        )?;
+        Ok(EncodedSymbol {
+            format: BarcodeFormat::UPC_A,
+            ..symbol
+        })
     }
 }
 
@@ -25,7 +25,7 @@ use rxing_one_d_proc_derive::{EANReader, OneDReader};
  *
  * @author Sean Owen
  */
-#[derive(OneDReader, EANReader, Default)]
+#[derive(OneDReader, EANReader, Default, Clone)]
 pub struct UPCEReader;
 
 impl UPCEANReader for UPCEReader {
@@ -0,0 +1,257 @@
+//! Helpers for the `parallel` feature, which runs the independent strategies of a decode on the
+//! rayon thread pool.
+//!
+//! The readers keep their serial `Reader` implementations; the parallel entry points are separate
+//! `*_parallel` methods, which require the image and the wrapped readers to be `Clone + Send + Sync`.
+
+use rayon::prelude::*;
+
+use crate::{
+    common::Result, BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, Exceptions, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue,
+};
+
+/// Runs `decode` on the `tasks` at the same time and returns the first result, in the order of
+/// `tasks`, that is `accepted`: the one a loop over `tasks` would have stopped at. Tasks after it
+/// are skipped if they have not started by the time it is found.
+pub(crate) fn first_in_order<T: Send, R: Send>(
+    tasks: Vec<T>,
+    decode: impl Fn(T) -> R + Send + Sync,
+    accepted: impl Fn(&R) -> bool + Send + Sync,
+) -> Option<R> {
+    tasks.into_par_iter().map(decode).find_first(accepted)
+}
+
+/// Runs `decode` on the `tasks` at the same time and returns the results in the order of `tasks`.
+pub(crate) fn map_in_order<T: Send, R: Send>(
+    tasks: Vec<T>,
+    decode: impl Fn(T) -> R + Send + Sync,
+) -> Vec<R> {
+    tasks.into_par_iter().map(decode).collect()
+}
+
+/// The parallel version of the passes of `MultiFormatReader` and `MultiUseMultiFormatReader`.
+///
+/// Each of the matrix `formats` is tried on its own copy of the image and, with `AlsoInverted`,
+/// of its inverse, all at the same time. The 1D readers keep state from one row to the next, so
+/// `decode_one_d` runs on this thread alongside them, and on the inverse only once the image
+/// itself has failed. The result is the one the serial passes, which try the 1D readers first
+/// unless `try_harder`, would have returned.
+pub(crate) fn decode_passes<B>(
+    image: &mut BinaryBitmap<B>,
+    hints: &DecodeHints,
+    formats: Vec<BarcodeFormat>,
+    one_d: bool,
+    try_harder: bool,
+    mut decode_one_d: impl FnMut(&mut BinaryBitmap<B>) -> Result<RXingResult> + Send,
+    decode_format: impl Fn(BarcodeFormat, &mut BinaryBitmap<B>) -> Result<RXingResult> + Send + Sync,
+) -> Result<RXingResult>
+where
+    B: Binarizer + Send + Sync,
+    B::Source: Clone + Send + Sync,
+{
+    let also_inverted = matches!(hints.AlsoInverted, Some(true));
+
+    let mut tasks = Vec::new();
+    if !formats.is_empty() {
+        // binarize once, before copying
+        image.get_black_matrix();
+        tasks.extend(formats.iter().map(|format| (false, *format, image.fork())));
+        if also_inverted {
+            let mut inverted = image.fork();
+            inverted.get_black_matrix_mut().flip_self();
+            tasks.extend(
+                formats
+                    .iter()
+                    .map(|format| (true, *format, inverted.fork())),
+            );
+        }
+    }
+
+    let (one_d_normal, matrix) = rayon::join(
+        || one_d.then(|| decode_one_d(image)).and_then(Result::ok),
+        || {
+            first_in_order(
+                tasks,
+                |(inverted, format, mut image)| {
+                    hints.check_cancelled()?;
+                    decode_format(format, &mut image).map(|res| (inverted, res))
+                },
+                Result::is_ok,
+            )
+            .and_then(Result::ok)
+        },
+    );
+    let (matrix_normal, matrix_inverted) = match matrix {
+        Some((false, res)) => (Some(res), None),
+        Some((true, res)) => (None, Some(res)),
+        None => (None, None),
+    };
+
+    let normal = if try_harder {
+        matrix_normal.or(one_d_normal)
+    } else {
+        one_d_normal.or(matrix_normal)
+    };
+    if let Some(res) = normal {
+        return Ok(res);
+    }
+    if !also_inverted {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    hints.check_cancelled()?;
+    image.get_black_matrix_mut().flip_self();
+    let mut one_d_inverted = || one_d.then(|| decode_one_d(image)).and_then(Result::ok);
+    let inverted = if try_harder {
+        matrix_inverted.or_else(one_d_inverted)
+    } else {
+        one_d_inverted().or(matrix_inverted)
+    };
+    let mut res = inverted.ok_or(Exceptions::NOT_FOUND)?;
+    res.putMetadata(
+        RXingResultMetadataType::IS_INVERTED,
+        RXingResultMetadataValue::IsInverted(true),
+    );
+    Ok(res)
+}
+
+#[cfg(test)]
+mod ParallelTestCase {
+    use crate::{
+        common::{test_image::TestImage, HybridBinarizer},
+        multi::{GenericMultipleBarcodeReader, MultipleBarcodeReader},
+        qrcode::cpp_port::QrReader,
+        BarcodeFormat, BinaryBitmap, DecodeHintValue, DecodeHints, FilteredImageReader,
+        Luma8LuminanceSource, MultiFormatReader, MultiFormatWriter, MultiUseMultiFormatReader,
+        RXingResultMetadataType, RXingResultMetadataValue, Reader, Writer,
+    };
+
+    /// An image with the symbols at the given top left corners, scaled by 3. Inverted symbols
+    /// are drawn light on dark, with a dark quiet zone of 2 modules.
+    fn image(
+        width: u32,
+        height: u32,
+        symbols: &[(BarcodeFormat, &str, u32, u32, bool)],
+    ) -> BinaryBitmap<HybridBinarizer<Luma8LuminanceSource>> {
+        let mut image = TestImage::new(width, height);
+        for (format, contents, left, top, inverted) in symbols {
+            // 1D symbols are 20 modules high
+            let modules_high = if *format == BarcodeFormat::CODE_128 {
+                20
+            } else {
+                0
+            };
+            let matrix = MultiFormatWriter
+                .encode(contents, format, 0, modules_high)
+                .expect("encode");
+            if *inverted {
+                image.draw_inverted(&matrix, 3, *left, *top, 6);
+            } else {
+                image.draw(&matrix, 3, *left, *top);
+            }
+        }
+        image.bitmap()
+    }
+
+    #[test]
+    fn testFirstStrategyWins() {
+        let symbols = [
+            (BarcodeFormat::QR_CODE, "QR", 20, 20, false),
+            (BarcodeFormat::DATA_MATRIX, "DM", 300, 20, false),
+            (BarcodeFormat::CODE_128, "ONE D", 20, 170, false),
+        ];
+        let try_harder = DecodeHints::default().with(DecodeHintValue::TryHarder(true));
+        for _ in 0..5 {
+            // the 1D readers come first, unless trying harder
+            let result = MultiFormatReader::default()
+                .decode_with_hints_parallel(&mut image(400, 300, &symbols), &DecodeHints::default())
+                .expect("decode");
+            assert_eq!(result.getText(), "ONE D");
+            let result = MultiUseMultiFormatReader::default()
+                .decode_with_hints_parallel(&mut image(400, 300, &symbols), &try_harder)
+                .expect("decode");
+            assert_eq!(result.getText(), "QR");
+        }
+    }
+
+    #[test]
+    fn testInvertedPass() {
+        let hints = DecodeHints::default()
+            .with(DecodeHintValue::AlsoInverted(true))
+            .with(DecodeHintValue::TryHarder(true));
+        for _ in 0..5 {
+            // the whole image is tried, down to the 1D readers, before its inverse
+            let symbols = [
+                (BarcodeFormat::DATA_MATRIX, "INVERTED", 20, 20, true),
+                (BarcodeFormat::CODE_128, "ONE D", 20, 170, false),
+            ];
+            let result = MultiFormatReader::default()
+                .decode_with_hints_parallel(&mut image(400, 300, &symbols), &hints)
+                .expect("decode");
+            assert_eq!(result.getText(), "ONE D");
+            assert!(!result
+                .getRXingResultMetadata()
+                .contains_key(&RXingResultMetadataType::IS_INVERTED));
+
+            let symbols = [(BarcodeFormat::DATA_MATRIX, "INVERTED", 20, 20, true)];
+            let result = MultiFormatReader::default()
+                .decode_with_hints_parallel(&mut image(400, 300, &symbols), &hints)
+                .expect("decode");
+            assert_eq!(result.getText(), "INVERTED");
+            assert_eq!(
+                result
+                    .getRXingResultMetadata()
+                    .get(&RXingResultMetadataType::IS_INVERTED),
+                Some(&RXingResultMetadataValue::IsInverted(true))
+            );
+        }
+    }
+
+    #[test]
+    fn testPyramidLayers() {
+        let symbols = [(BarcodeFormat::QR_CODE, "LAYER", 200, 200, false)];
+        for _ in 0..5 {
+            // the full resolution layer is tried first
+            let result = FilteredImageReader::new(QrReader)
+                .decode_with_hints_parallel(
+                    &mut image(1200, 900, &symbols),
+                    &DecodeHints::default(),
+                )
+                .expect("decode");
+            assert_eq!(result.getText(), "LAYER");
+            assert_eq!(
+                result
+                    .getRXingResultMetadata()
+                    .get(&RXingResultMetadataType::FILTERED_RESOLUTION),
+                Some(&RXingResultMetadataValue::FilteredResolution((1200, 900)))
+            );
+        }
+    }
+
+    #[test]
+    fn testSubImagesInOrder() {
+        let symbols = [
+            (BarcodeFormat::QR_CODE, "ONE", 20, 20, false),
+            (BarcodeFormat::QR_CODE, "TWO", 280, 20, false),
+            (BarcodeFormat::QR_CODE, "THREE", 20, 180, false),
+            (BarcodeFormat::QR_CODE, "FOUR", 280, 180, false),
+        ];
+        let texts = || {
+            GenericMultipleBarcodeReader::new(QrReader)
+                .decode_multiple_with_hints_parallel(
+                    &mut image(400, 300, &symbols),
+                    &DecodeHints::default(),
+                )
+                .expect("decode")
+                .iter()
+                .map(|result| result.getText().to_owned())
+                .collect::<Vec<_>>()
+        };
+        let first = texts();
+        assert_eq!(first.len(), 4);
+        for _ in 0..5 {
+            assert_eq!(texts(), first);
+        }
+    }
+}
@@ -52,6 +52,7 @@ const BEGIN_MACRO_PDF417_CONTROL_BLOCK: u32 = 928;
 const BEGIN_MACRO_PDF417_OPTIONAL_FIELD: u32 = 923;
 const MACRO_PDF417_TERMINATOR: u32 = 922;
 const MODE_SHIFT_TO_BYTE_COMPACTION_MODE: u32 = 913;
+const LINKAGE_FLAG_CC_C: u32 = 920;
 const MAX_NUMERIC_CODEWORDS: usize = 15;
 
 const MACRO_PDF417_OPTIONAL_FIELD_FILE_NAME: u32 = 0;
@@ -107,6 +108,10 @@ static EXP900: Lazy<Vec<BigUint>> = Lazy::new(|| {
 const NUMBER_OF_SEQUENCE_CODEWORDS: usize = 2;
 
 pub fn decode(codewords: &[u32], ecLevel: &str) -> Result<DecoderRXingResult> {
+    if codewords.len() > 2 && codewords[1] == LINKAGE_FLAG_CC_C {
+        return decodeCompositeComponent(codewords, ecLevel);
+    }
+
     let mut result = ECIStringBuilder::with_capacity(codewords.len() * 2);
     let mut codeIndex = textCompaction(codewords, 1, &mut result)?;
     let mut resultMetadata = PDF417RXingResultMetadata::default();
@@ -176,6 +181,31 @@ pub fn decode(codewords: &[u32], ecLevel: &str) -> Result<DecoderRXingResult> {
     Ok(decoderRXingResult)
 }
 
+/**
+ * Decodes the CC-C component of a GS1 Composite symbol. The linkage flag is followed by the
+ * data bit stream of the component in Byte Compaction mode (ISO/IEC 24723, 5.9).
+ */
+fn decodeCompositeComponent(codewords: &[u32], ecLevel: &str) -> Result<DecoderRXingResult> {
+    let mode = codewords[2];
+    if mode != BYTE_COMPACTION_MODE_LATCH && mode != BYTE_COMPACTION_MODE_LATCH_6 {
+        return Err(Exceptions::FORMAT);
+    }
+    let mut bytes = ECIStringBuilder::with_capacity(codewords.len() * 6 / 5);
+    byteCompaction(mode, codewords, 3, &mut bytes)?;
+    let bytes = bytes.bytes().to_vec();
+
+    let text = crate::composite::decode_data(&bytes)?;
+
+    let mut resultMetadata = PDF417RXingResultMetadata::default();
+    resultMetadata.setCompositeComponent(true);
+
+    let mut decoderRXingResult =
+        DecoderRXingResult::new(bytes, text, Vec::new(), ecLevel.to_owned());
+    decoderRXingResult.setOther(Some(Arc::new(resultMetadata)));
+
+    Ok(decoderRXingResult)
+}
+
 pub fn decodeMacroBlock(
     codewords: &[u32],
     codeIndex: usize,
@@ -19,7 +19,7 @@ use std::sync::Arc;
 use crate::{
     common::{BitMatrix, DecoderRXingResult, Result},
     pdf417::pdf_417_common,
-    Exceptions, Point,
+    BarcodeFormat, CancellationToken, CodewordRecorder, DecodeHints, Exceptions, Point,
 };
 
 use super::{
@@ -51,6 +51,61 @@ pub fn decode(
     minCodewordWidth: u32,
     maxCodewordWidth: u32,
 ) -> Result<DecoderRXingResult> {
+    decode_with_cancellation(
+        image,
+        image_top_left,
+        imageBottomLeft,
+        image_top_right,
+        imageBottomRight,
+        minCodewordWidth,
+        maxCodewordWidth,
+        None,
+    )
+}
+
+/// Like [`decode`], stopping with [`Exceptions::TIMEOUT`] between columns and decode attempts once
+/// `cancellation` is cancelled.
+#[allow(clippy::too_many_arguments)]
+pub fn decode_with_cancellation(
+    image: &BitMatrix,
+    image_top_left: Option<Point>,
+    imageBottomLeft: Option<Point>,
+    image_top_right: Option<Point>,
+    imageBottomRight: Option<Point>,
+    minCodewordWidth: u32,
+    maxCodewordWidth: u32,
+    cancellation: Option<&CancellationToken>,
+) -> Result<DecoderRXingResult> {
+    decode_with_hints(
+        image,
+        image_top_left,
+        imageBottomLeft,
+        image_top_right,
+        imageBottomRight,
+        minCodewordWidth,
+        maxCodewordWidth,
+        &DecodeHints {
+            CancellationToken: cancellation.cloned(),
+            ..Default::default()
+        },
+    )
+}
+
+/// Like [`decode_with_cancellation`], with the cancellation token of the `hints`, also recording
+/// the codewords before and after error correction with their diagnostics, if any, and collecting
+/// them with their best effort collector, if any, if error correction fails.
+#[allow(clippy::too_many_arguments)]
+pub fn decode_with_hints(
+    image: &BitMatrix,
+    image_top_left: Option<Point>,
+    imageBottomLeft: Option<Point>,
+    image_top_right: Option<Point>,
+    imageBottomRight: Option<Point>,
+    minCodewordWidth: u32,
+    maxCodewordWidth: u32,
+    hints: &DecodeHints,
+) -> Result<DecoderRXingResult> {
+    let check_cancelled = || hints.check_cancelled();
     let mut minCodewordWidth = minCodewordWidth;
     let mut maxCodewordWidth = maxCodewordWidth;
     let mut boundingBox = BoundingBox::new(
@@ -113,6 +168,7 @@ pub fn decode(
     // let leftToRight = leftRowIndicatorColumn.is_some();
     for barcodeColumnCount in 1..=maxBarcodeColumn {
         // for (int barcodeColumnCount = 1; barcodeColumnCount <= maxBarcodeColumn; barcodeColumnCount++) {
+        check_cancelled()?;
         let barcodeColumn = if leftToRight {
             barcodeColumnCount
         } else {
@@ -174,7 +230,7 @@ pub fn decode(
         }
     }
 
-    createDecoderRXingResult(&mut detectionRXingResult)
+    createDecoderRXingResult(&mut detectionRXingResult, &check_cancelled, hints)
 }
 
 fn merge<'a, T: DetectionRXingResultRowIndicatorColumn>(
@@ -426,6 +482,8 @@ fn adjustCodewordCount(
 
 fn createDecoderRXingResult(
     detectionRXingResult: &mut DetectionRXingResult,
+    check_cancelled: &dyn Fn() -> Result<()>,
+    hints: &DecodeHints,
 ) -> Result<DecoderRXingResult> {
     let mut barcodeMatrix = createBarcodeMatrix(detectionRXingResult);
     adjustCodewordCount(detectionRXingResult, &mut barcodeMatrix)?;
@@ -466,6 +524,8 @@ fn createDecoderRXingResult(
         &mut erasures,
         &mut ambiguousIndexesList,
         &ambiguousIndexValues,
+        check_cancelled,
+        hints,
     )
 }
 
@@ -488,17 +548,23 @@ fn createDecoderRXingResultFromAmbiguousValues(
     erasureArray: &mut [u32],
     ambiguousIndexes: &mut [u32],
     ambiguousIndexValues: &[Vec<u32>],
+    check_cancelled: &dyn Fn() -> Result<()>,
+    hints: &DecodeHints,
 ) -> Result<DecoderRXingResult> {
     let mut ambiguousIndexCount = vec![0; ambiguousIndexes.len()];
+    // only the codewords of the last attempt are recorded
+    let mut recorder = CodewordRecorder::new(hints, BarcodeFormat::PDF_417);
 
     let mut tries = 100;
     while tries > 0 {
+        check_cancelled()?;
         for i in 0..ambiguousIndexCount.len() {
             // for (int i = 0; i < ambiguousIndexCount.length; i++) {
             codewords[ambiguousIndexes[i] as usize] =
                 ambiguousIndexValues[i][ambiguousIndexCount[i]];
         }
-        let attempted_decode = decodeCodewords(codewords, ecLevel, erasureArray);
+        recorder.restart();
+        let attempted_decode = decodeCodewords(codewords, ecLevel, erasureArray, &mut recorder);
         if attempted_decode.is_ok() {
             return attempted_decode;
         }
@@ -843,13 +909,43 @@ fn decodeCodewords(
     codewords: &mut [u32],
     ecLevel: u32,
     erasures: &mut [u32],
+    recorder: &mut CodewordRecorder,
 ) -> Result<DecoderRXingResult> {
     if codewords.is_empty() {
         return Err(Exceptions::FORMAT);
     }
 
     let numECCodewords = 1 << (ecLevel + 1);
-    let correctedErrorsCount = correctErrors(codewords, erasures, numECCodewords)?;
+    let numDataCodewords = codewords.len().saturating_sub(numECCodewords as usize);
+    recorder.received(numDataCodewords, codewords);
+    // error correction may have changed some of the codewords when it fails
+    let received = recorder.best_effort().then(|| codewords.to_vec());
+    let correctedErrorsCount = match correctErrors(codewords, erasures, numECCodewords) {
+        Ok(count) => count,
+        Err(e) => {
+            if let Some(received) = received {
+                let data = received[..numDataCodewords].to_vec();
+                recorder.failed(data, move |data| {
+                    // the parser reads up to the length in the first codeword, and past it with
+                    // the codewords that follow it, as it does with the error correction ones
+                    let mut codewords = received.clone();
+                    let Some(first) = codewords.first_mut() else {
+                        return Err(Exceptions::FORMAT);
+                    };
+                    if *first == 0 || *first as usize > data.len() {
+                        *first = data.len().max(1) as u32;
+                    }
+                    Ok(
+                        decoded_bit_stream_parser::decode(&codewords, &ecLevel.to_string())?
+                            .getText()
+                            .to_owned(),
+                    )
+                });
+            }
+            return Err(e);
+        }
+    };
+    recorder.corrected(codewords);
     verifyCodewordCount(codewords, numECCodewords)?;
 
     // Decode the codewords
@@ -857,6 +953,7 @@ fn decodeCodewords(
         decoded_bit_stream_parser::decode(codewords, &ecLevel.to_string())?;
     decoderRXingResult.setErrorsCorrected(correctedErrorsCount);
     decoderRXingResult.setErasures(erasures.len());
+    decoderRXingResult.setErrorCorrectionCapacity(numECCodewords as usize);
 
     Ok(decoderRXingResult)
 }
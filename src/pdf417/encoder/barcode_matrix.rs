@@ -52,6 +52,20 @@ impl BarcodeMatrix {
         }
     }
 
+    /**
+     * @return the number of rows of codewords
+     */
+    pub fn getRows(&self) -> usize {
+        self.height
+    }
+
+    /**
+     * @return the number of data columns, without the row indicators
+     */
+    pub fn getColumns(&self) -> usize {
+        self.width / 17
+    }
+
     pub fn set(&mut self, x: usize, y: usize, value: u8) {
         self.matrix[y].set(x, value);
     }
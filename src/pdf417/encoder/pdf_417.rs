@@ -37,6 +37,7 @@ pub struct PDF417 {
     maxCols: u32,
     maxRows: u32,
     minRows: u32,
+    numSourceCodewords: u32,
 }
 
 impl Default for PDF417 {
@@ -60,6 +61,7 @@ impl PDF417 {
             maxCols: 30,
             maxRows: 30,
             minRows: 2,
+            numSourceCodewords: 0,
         }
     }
 
@@ -67,6 +69,14 @@ impl PDF417 {
         &self.barcodeMatrix
     }
 
+    /**
+     * @return the number of codewords the message was encoded to, without the symbol length
+     * descriptor and the padding
+     */
+    pub fn getNumSourceCodewords(&self) -> u32 {
+        self.numSourceCodewords
+    }
+
     /**
      * Calculates the necessary number of rows as described in annex Q of ISO/IEC 15438:2001(E).
      *
@@ -199,14 +209,49 @@ impl PDF417 {
         autoECI: bool,
     ) -> Result<()> {
         //1. step: High-level encoding
-        let errorCorrectionCodeWords =
-            pdf_417_error_correction::getErrorCorrectionCodewordCount(errorCorrectionLevel)?;
         let highLevel = pdf_417_high_level_encoder::encodeHighLevel(
             msg,
             self.compaction,
             self.encoding,
             autoECI,
         )?;
+
+        self.generateBarcodeLogicFromHighLevel(
+            &highLevel,
+            errorCorrectionLevel,
+            msg.chars().count(),
+        )
+    }
+
+    /**
+     * Generates the symbol for codewords that were produced by a high-level encoder elsewhere,
+     * for example the CC-C component of a GS1 Composite symbol.
+     *
+     * @param codewords the data codewords, without the symbol length descriptor
+     * @param errorCorrectionLevel PDF417 error correction level to use
+     * @throws WriterException if the codewords do not fit in a symbol
+     */
+    pub fn generateBarcodeLogicFromCodewords(
+        &mut self,
+        codewords: &[u32],
+        errorCorrectionLevel: u32,
+    ) -> Result<()> {
+        let highLevel = codewords
+            .iter()
+            .map(|cw| char::from_u32(*cw).ok_or(Exceptions::PARSE))
+            .collect::<Result<String>>()?;
+
+        self.generateBarcodeLogicFromHighLevel(&highLevel, errorCorrectionLevel, codewords.len())
+    }
+
+    fn generateBarcodeLogicFromHighLevel(
+        &mut self,
+        highLevel: &str,
+        errorCorrectionLevel: u32,
+        messageLength: usize,
+    ) -> Result<()> {
+        let errorCorrectionCodeWords =
+            pdf_417_error_correction::getErrorCorrectionCodewordCount(errorCorrectionLevel)?;
         let sourceCodeWords = highLevel.chars().count() as u32;
 
         let dimension = self.determineDimensions(sourceCodeWords, errorCorrectionCodeWords)?;
@@ -221,14 +266,13 @@ impl PDF417 {
         if sourceCodeWords + errorCorrectionCodeWords + 1 > 929 {
             // +1 for symbol length CW
             return Err(Exceptions::writer_with(format!(
-                "Encoded message contains too many code words, message too big ({} bytes)",
-                msg.chars().count()
+                "Encoded message contains too many code words, message too big ({messageLength} bytes)"
             )));
         }
         let n = sourceCodeWords + pad + 1;
         let mut sb = String::with_capacity(n as usize);
         sb.push(char::from_u32(n).ok_or(Exceptions::PARSE)?);
-        sb.push_str(&highLevel);
+        sb.push_str(highLevel);
         for _i in 0..pad {
             sb.push(char::from_u32(900).ok_or(Exceptions::PARSE)?);
             //PAD characters
@@ -251,6 +295,7 @@ impl PDF417 {
             &mut barcode_matrix,
         )?;
         self.barcodeMatrix = Some(barcode_matrix);
+        self.numSourceCodewords = sourceCodeWords;
         Ok(())
     }
 
@@ -361,7 +406,7 @@ const STOP_PATTERN: u32 = 0x3fa29;
 /**
  * The codeword table from the Annex A of ISO/IEC 15438:2001(E).
  */
-const CODEWORD_TABLE: [[u32; 929]; 3] = [
+pub(crate) const CODEWORD_TABLE: [[u32; 929]; 3] = [
     [
         0x1d5c0, 0x1eaf0, 0x1f57c, 0x1d4e0, 0x1ea78, 0x1f53e, 0x1a8c0, 0x1d470, 0x1a860, 0x15040,
         0x1a830, 0x15020, 0x1adc0, 0x1d6f0, 0x1eb7c, 0x1ace0, 0x1d678, 0x1eb3e, 0x158c0, 0x1ac70,
@@ -15,9 +15,11 @@
  */
 
 use crate::{
-    common::Result, multi::MultipleBarcodeReader, BarcodeFormat, Binarizer, BinaryBitmap,
-    DecodeHints, Exceptions, ImmutableReader, Point, RXingResult, RXingResultMetadataType,
-    RXingResultMetadataValue, Reader,
+    common::Result,
+    decode_region::{decode_in_region, decode_multiple_in_region},
+    multi::MultipleBarcodeReader,
+    BarcodeFormat, Binarizer, BinaryBitmap, DecodeHints, EstimatedModuleSize, Exceptions,
+    ImmutableReader, Point, RXingResult, RXingResultMetadataType, RXingResultMetadataValue, Reader,
 };
 
 use super::{
@@ -30,7 +32,7 @@ use super::{
  *
  * @author Guenther Grau
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct PDF417Reader;
 
 impl Reader for PDF417Reader {
@@ -50,7 +52,9 @@ impl Reader for PDF417Reader {
         image: &mut BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<crate::RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -60,7 +64,9 @@ impl ImmutableReader for PDF417Reader {
         image: &mut BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -77,7 +83,9 @@ impl MultipleBarcodeReader for PDF417Reader {
         image: &mut BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<Vec<RXingResult>> {
-        Self::decode(image, hints, true)
+        decode_multiple_in_region(image, hints, |image, hints| {
+            Self::decode(image, hints, true)
+        })
     }
 }
 
@@ -92,13 +100,25 @@ impl PDF417Reader {
         multiple: bool,
     ) -> Result<Vec<RXingResult>> {
         let mut results = Vec::new();
-        let detectorRXingResult = pdf_417_detector::detect_with_hints(image, hints, multiple)?;
+        let detectorRXingResult = hints.record_rejection(
+            BarcodeFormat::PDF_417,
+            None,
+            pdf_417_detector::detect_with_hints(image, hints, multiple),
+        )?;
 
         for points in detectorRXingResult.getPoints() {
-            let points_filtered = points.iter().flatten().copied().collect();
+            let points_filtered: Vec<Point> = points.iter().flatten().copied().collect();
             // let points_filtered = points.iter().filter_map(|e| *e).collect();
+            if let Some(diagnostics) = &hints.Diagnostics {
+                // top left, top right, bottom right, bottom left
+                let corners: Vec<Point> = [points[0], points[2], points[3], points[1]]
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                diagnostics.quadrilateral(BarcodeFormat::PDF_417, &corners);
+            }
 
-            let decoderRXingResult = pdf_417_scanning_decoder::decode(
+            let decoded = pdf_417_scanning_decoder::decode_with_hints(
                 detectorRXingResult.getBits(),
                 points[4],
                 points[5],
@@ -106,7 +126,21 @@ impl PDF417Reader {
                 points[7],
                 Self::getMinCodewordWidth(points),
                 Self::getMaxCodewordWidth(points),
-            )?;
+                &hints.located(&points_filtered),
+            );
+            let decoded = hints.record_rejection(
+                BarcodeFormat::PDF_417,
+                points_filtered.first().copied(),
+                decoded,
+            );
+            let decoderRXingResult = match decoded {
+                Ok(decoderRXingResult) => decoderRXingResult,
+                // Keep the symbols decoded before the time ran out.
+                Err(Exceptions::TimeoutException(_)) => {
+                    return Err(Exceptions::timeout_with(results))
+                }
+                Err(e) => return Err(e),
+            };
 
             let mut result = RXingResult::new(
                 decoderRXingResult.getText(),
@@ -150,6 +184,18 @@ impl PDF417Reader {
                     decoderRXingResult.getSymbologyModifier()
                 )),
             );
+            if let Some(errorCorrection) = decoderRXingResult.getErrorCorrectionStats() {
+                result.putMetadata(
+                    RXingResultMetadataType::ERRORS_CORRECTED,
+                    RXingResultMetadataValue::ErrorsCorrected(errorCorrection),
+                );
+            }
+            if let Some(moduleSize) = Self::getModuleSize(points) {
+                result.putMetadata(
+                    RXingResultMetadataType::MODULE_SIZE,
+                    RXingResultMetadataValue::ModuleSize(moduleSize),
+                );
+            }
             results.push(result);
         }
         Ok(results)
@@ -195,6 +241,22 @@ impl PDF417Reader {
             )) as u32
     }
 
+    /// The average module size of the start and stop patterns found at the top and bottom of
+    /// the symbol.
+    fn getModuleSize(p: &[Option<Point>]) -> Option<EstimatedModuleSize> {
+        let widths = [
+            (p[0], p[4], pdf_417_common::MODULES_IN_CODEWORD),
+            (p[1], p[5], pdf_417_common::MODULES_IN_CODEWORD),
+            (p[6], p[2], pdf_417_common::MODULES_IN_STOP_PATTERN),
+            (p[7], p[3], pdf_417_common::MODULES_IN_STOP_PATTERN),
+        ]
+        .into_iter()
+        .filter_map(|(a, b, modules)| Some(a?.distance(b?) / modules as f32))
+        .collect::<Vec<_>>();
+        (!widths.is_empty())
+            .then(|| EstimatedModuleSize(widths.iter().sum::<f32>() / widths.len() as f32))
+    }
+
     fn internal_decode_with_hints<B: Binarizer>(
         &self,
         image: &mut BinaryBitmap<B>,
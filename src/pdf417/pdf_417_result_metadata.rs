@@ -34,6 +34,7 @@ pub struct PDF417RXingResultMetadata {
     timestamp: i64,
     checksum: i32,
     optionalData: Vec<u32>,
+    compositeComponent: bool,
 }
 
 impl Default for PDF417RXingResultMetadata {
@@ -50,6 +51,7 @@ impl Default for PDF417RXingResultMetadata {
             timestamp: -1,
             checksum: -1,
             optionalData: Default::default(),
+            compositeComponent: false,
         }
     }
 }
@@ -188,4 +190,18 @@ impl PDF417RXingResultMetadata {
     pub fn setTimestamp(&mut self, timestamp: i64) {
         self.timestamp = timestamp;
     }
+
+    /**
+     * Whether the symbol is the CC-C component of a GS1 Composite symbol, signalled by the
+     * linkage flag in its first data codeword.
+     *
+     * @return true if the symbol is a composite component
+     */
+    pub fn isCompositeComponent(&self) -> bool {
+        self.compositeComponent
+    }
+
+    pub fn setCompositeComponent(&mut self, compositeComponent: bool) {
+        self.compositeComponent = compositeComponent;
+    }
 }
@@ -16,10 +16,12 @@
 
 use crate::{
     common::{BitMatrix, CharacterSet, Result},
-    BarcodeFormat, EncodeHints, Exceptions, Writer,
+    encoded_symbol::Placement,
+    BarcodeFormat, EncodeHints, EncodedSymbol, Exceptions, SymbolEcLevel, SymbolMetadata,
+    SymbolVersion, Writer,
 };
 
-use super::encoder::PDF417;
+use super::encoder::{pdf_417_error_correction, PDF417};
 
 /**
  * default white space (margin) around the code
@@ -57,12 +59,49 @@ impl Writer for PDF417Writer {
         height: i32,
         hints: &EncodeHints,
     ) -> Result<crate::common::BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
         if format != &BarcodeFormat::PDF_417 {
             return Err(Exceptions::illegal_argument_with(format!(
                 "Can only encode PDF_417, but got {format}"
             )));
         }
 
+        let (mut encoder, margin, errorCorrectionLevel, autoECI) = Self::configureEncoder(hints)?;
+
+        let (modules, drawn) = Self::bitMatrixFromEncoder(
+            &mut encoder,
+            contents,
+            errorCorrectionLevel,
+            width as u32,
+            height as u32,
+            margin,
+            autoECI,
+        )?;
+
+        Ok(EncodedSymbol::new(*format, modules, drawn)
+            .with_metadata(Self::symbol_metadata(&encoder, errorCorrectionLevel)?))
+    }
+}
+
+impl PDF417Writer {
+    /**
+     * Reads the hints into an encoder
+     *
+     * @return the encoder, the margin, the error correction level and whether to insert ECIs
+     */
+    fn configureEncoder(hints: &EncodeHints) -> Result<(PDF417, u32, u32, bool)> {
         let mut encoder = PDF417::new();
         let mut margin = WHITE_SPACE;
         let mut errorCorrectionLevel = DEFAULT_ERROR_CORRECTION_LEVEL;
@@ -105,21 +144,38 @@ impl Writer for PDF417Writer {
         }
         // }
 
-        Self::bitMatrixFromEncoder(
-            &mut encoder,
-            contents,
-            errorCorrectionLevel,
-            width as u32,
-            height as u32,
-            margin,
-            autoECI,
-        )
+        Ok((encoder, margin, errorCorrectionLevel, autoECI))
+    }
+
+    /**
+     * Describes the symbol the `encoder` generated.
+     */
+    fn symbol_metadata(encoder: &PDF417, errorCorrectionLevel: u32) -> Result<SymbolMetadata> {
+        let matrix = encoder
+            .getBarcodeMatrix()
+            .as_ref()
+            .ok_or(Exceptions::ILLEGAL_STATE)?;
+        let rows = matrix.getRows() as u32;
+        let columns = matrix.getColumns() as u32;
+        let ec_codewords =
+            pdf_417_error_correction::getErrorCorrectionCodewordCount(errorCorrectionLevel)?;
+
+        Ok(SymbolMetadata {
+            version: SymbolVersion::Pdf417 { rows, columns },
+            ec_level: SymbolEcLevel::Pdf417(errorCorrectionLevel),
+            data_codewords: rows * columns - ec_codewords,
+            // with the symbol length descriptor
+            used_data_codewords: encoder.getNumSourceCodewords() + 1,
+            ec_codewords,
+            mask: None,
+            data_bits: None,
+            capacity_bits: None,
+        })
     }
-}
 
-impl PDF417Writer {
     /**
-     * Takes encoder, accounts for width/height, and retrieves bit matrix
+     * Takes encoder, accounts for width/height, and retrieves the module matrix, with one module
+     * per row, and the bit matrix drawn from it
      */
     fn bitMatrixFromEncoder(
         encoder: &mut PDF417,
@@ -129,39 +185,54 @@ impl PDF417Writer {
         height: u32,
         margin: u32,
         autoECI: bool,
-    ) -> Result<BitMatrix> {
+    ) -> Result<(BitMatrix, (BitMatrix, Placement))> {
         encoder.generateBarcodeLogicWithAutoECI(contents, errorCorrectionLevel, autoECI)?;
-
-        let aspectRatio = 4;
-        let mut originalScale = encoder
+        let barcodeMatrix = encoder
             .getBarcodeMatrix()
             .as_ref()
-            .ok_or(Exceptions::ILLEGAL_STATE)?
-            .getScaledMatrix(1, aspectRatio);
+            .ok_or(Exceptions::ILLEGAL_STATE)?;
+
+        let aspectRatio = 4;
+        let mut originalScale = barcodeMatrix.getScaledMatrix(1, aspectRatio);
+        let mut modules = barcodeMatrix.getScaledMatrix(1, 1);
         let mut rotated = false;
         if (height > width) != (originalScale[0].len() < originalScale.len()) {
             originalScale = Self::rotateArray(&originalScale);
+            modules = Self::rotateArray(&modules);
             rotated = true;
         }
+        let modules = Self::bitMatrixFromBitArray(&modules, 0).ok_or(Exceptions::ILLEGAL_STATE)?;
 
         let scaleX = width as usize / originalScale[0].len();
         let scaleY = height as usize / originalScale.len();
         let scale = scaleX.min(scaleY);
 
-        if scale > 1 {
-            let mut scaledMatrix = encoder
-                .getBarcodeMatrix()
-                .as_ref()
-                .ok_or(Exceptions::ILLEGAL_STATE)?
-                .getScaledMatrix(scale, scale * aspectRatio);
+        let drawn = if scale > 1 {
+            let mut scaledMatrix = barcodeMatrix.getScaledMatrix(scale, scale * aspectRatio);
             if rotated {
                 scaledMatrix = Self::rotateArray(&scaledMatrix);
             }
-            return Self::bitMatrixFromBitArray(&scaledMatrix, margin)
-                .ok_or(Exceptions::ILLEGAL_STATE);
+            Self::bitMatrixFromBitArray(&scaledMatrix, margin)
+        } else {
+            Self::bitMatrixFromBitArray(&originalScale, margin)
         }
+        .ok_or(Exceptions::ILLEGAL_STATE)?;
+
+        // the rows are aspectRatio times as high as the modules are wide
+        let scale = scale.max(1) as u32;
+        let (scale_x, scale_y) = if rotated {
+            (scale * aspectRatio as u32, scale)
+        } else {
+            (scale, scale * aspectRatio as u32)
+        };
+        let placement = Placement {
+            scale_x,
+            scale_y,
+            offset_x: margin,
+            offset_y: margin,
+        };
 
-        Self::bitMatrixFromBitArray(&originalScale, margin).ok_or(Exceptions::ILLEGAL_STATE)
+        Ok((modules, (drawn, placement)))
     }
 
     /**
@@ -0,0 +1,420 @@
+use crate::common::reedsolomon::{
+    get_predefined_genericgf, PredefinedGenericGF, ReedSolomonDecoder, ReedSolomonEncoder,
+};
+use crate::common::Result;
+use crate::{ErrorCorrectionStats, Exceptions};
+
+use super::BarState;
+
+/**
+ * Format Control Codes and the number of bars of the symbol they select.
+ *
+ * * 11 - Standard Customer Barcode
+ * * 45 - Reply Paid Barcode
+ * * 59 - Customer Barcode 2
+ * * 62 - Customer Barcode 3
+ * * 87 - Routing Barcode
+ * * 92 - Redirection Barcode
+ */
+const FORMAT_CONTROL_CODES: [(u8, usize); 6] =
+    [(11, 37), (45, 37), (59, 52), (62, 67), (87, 37), (92, 37)];
+
+/** Ascender followed by tracker, used both as start and as stop bars. */
+const START_STOP: [BarState; 2] = [BarState::Ascender, BarState::Tracker];
+
+/** Number of Reed-Solomon parity symbols, each one made of three bars. */
+const PARITY_SYMBOLS: usize = 4;
+
+/** Bars used by the Format Control Code (2 digits) and the Delivery Point Identifier (8 digits). */
+const HEADER_BARS: usize = 20;
+
+/** Smallest number of bars an Australia Post symbol can have. */
+pub const MIN_BAR_COUNT: usize = 37;
+
+/** The N encoding table, two bars per digit. */
+const N_TABLE: [[u8; 2]; 10] = [
+    [0, 0],
+    [0, 1],
+    [0, 2],
+    [1, 0],
+    [1, 1],
+    [1, 2],
+    [2, 0],
+    [2, 1],
+    [2, 2],
+    [3, 0],
+];
+
+/** The characters of the C encoding table, in table order. */
+const C_CHARSET: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz #";
+
+/** The C encoding table, three bars per character of `C_CHARSET`. */
+const C_TABLE: [[u8; 3]; 64] = [
+    [2, 2, 2],
+    [3, 0, 0],
+    [3, 0, 1],
+    [3, 0, 2],
+    [3, 1, 0],
+    [3, 1, 1],
+    [3, 1, 2],
+    [3, 2, 0],
+    [3, 2, 1],
+    [3, 2, 2],
+    [0, 0, 0],
+    [0, 0, 1],
+    [0, 0, 2],
+    [0, 1, 0],
+    [0, 1, 1],
+    [0, 1, 2],
+    [0, 2, 0],
+    [0, 2, 1],
+    [0, 2, 2],
+    [1, 0, 0],
+    [1, 0, 1],
+    [1, 0, 2],
+    [1, 1, 0],
+    [1, 1, 1],
+    [1, 1, 2],
+    [1, 2, 0],
+    [1, 2, 1],
+    [1, 2, 2],
+    [2, 0, 0],
+    [2, 0, 1],
+    [2, 0, 2],
+    [2, 1, 0],
+    [2, 1, 1],
+    [2, 1, 2],
+    [2, 2, 0],
+    [2, 2, 1],
+    [0, 2, 3],
+    [0, 3, 0],
+    [0, 3, 1],
+    [0, 3, 2],
+    [0, 3, 3],
+    [1, 0, 3],
+    [1, 1, 3],
+    [1, 2, 3],
+    [1, 3, 0],
+    [1, 3, 1],
+    [1, 3, 2],
+    [1, 3, 3],
+    [2, 0, 3],
+    [2, 1, 3],
+    [2, 2, 3],
+    [2, 3, 0],
+    [2, 3, 1],
+    [2, 3, 2],
+    [2, 3, 3],
+    [3, 0, 3],
+    [3, 1, 3],
+    [3, 2, 3],
+    [3, 3, 0],
+    [3, 3, 1],
+    [3, 3, 2],
+    [3, 3, 3],
+    [0, 0, 3],
+    [0, 1, 3],
+];
+
+const FILLER: u8 = BarState::Tracker as u8;
+
+/** The C table character made of three filler bars. */
+const FILLER_CHARACTER: char = 'z';
+
+/**
+ * Number of data bars (excluding start, stop and parity bars) for a symbol of `bar_count` bars.
+ */
+fn data_bar_count(bar_count: usize) -> usize {
+    bar_count - 2 * START_STOP.len() - 3 * PARITY_SYMBOLS
+}
+
+fn bar_count_for(fcc: u8) -> Option<usize> {
+    FORMAT_CONTROL_CODES
+        .iter()
+        .find(|(code, _)| *code == fcc)
+        .map(|(_, count)| *count)
+}
+
+fn push_n(target: &mut Vec<u8>, digits: &str) -> Result<()> {
+    for c in digits.chars() {
+        let digit = c.to_digit(10).ok_or(Exceptions::illegal_argument_with(
+            "Input should only contain digits 0-9",
+        ))?;
+        target.extend_from_slice(&N_TABLE[digit as usize]);
+    }
+    Ok(())
+}
+
+fn push_c(target: &mut Vec<u8>, text: &str) -> Result<()> {
+    for c in text.chars() {
+        let index = C_CHARSET
+            .find(c)
+            .ok_or(Exceptions::illegal_argument_with(format!(
+                "Character {c} cannot be encoded with the C table"
+            )))?;
+        target.extend_from_slice(&C_TABLE[index]);
+    }
+    Ok(())
+}
+
+/**
+ * Encodes an Australia Post 4-state customer barcode.
+ *
+ * `contents` is the two digit Format Control Code, the eight digit Delivery Point Identifier
+ * and, for FCC 59 and 62, the optional customer information. Numeric customer information
+ * is encoded with the N table, anything else with the C table.
+ */
+pub fn encode(contents: &str) -> Result<Vec<BarState>> {
+    if !contents.is_ascii() || contents.len() < 10 {
+        return Err(Exceptions::illegal_argument_with(
+            "Requested contents should be a 2 digit FCC followed by an 8 digit DPID",
+        ));
+    }
+
+    let (header, customer_info) = contents.split_at(10);
+    let fcc = header[..2]
+        .parse::<u8>()
+        .map_err(|_| Exceptions::illegal_argument_with("FCC should only contain digits 0-9"))?;
+    let bar_count = bar_count_for(fcc).ok_or(Exceptions::illegal_argument_with(format!(
+        "Unsupported format control code {fcc}"
+    )))?;
+
+    let data_bars = data_bar_count(bar_count);
+    // the last data bar is always a filler bar when the customer information does not use it
+    let info_bars = data_bars - HEADER_BARS;
+
+    let mut data = Vec::with_capacity(data_bars);
+    push_n(&mut data, header)?;
+
+    if customer_info.chars().all(|c| c.is_ascii_digit()) {
+        if 2 * customer_info.len() > info_bars {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "FCC {fcc} allows at most {} digits of customer information, but got {}",
+                info_bars / 2,
+                customer_info.len()
+            )));
+        }
+        push_n(&mut data, customer_info)?;
+    } else {
+        let capacity = info_bars / 3;
+        if customer_info.len() > capacity {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "FCC {fcc} allows at most {capacity} characters of customer information, but got {}",
+                customer_info.len()
+            )));
+        }
+        // the filler bars padding the customer information read as the C table entry "333"
+        if customer_info.ends_with(FILLER_CHARACTER) {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Customer information cannot end with {FILLER_CHARACTER}, which reads as filler bars"
+            )));
+        }
+        push_c(&mut data, customer_info)?;
+    }
+    data.resize(data_bars, FILLER);
+
+    let mut codewords = data
+        .chunks_exact(3)
+        .map(|triple| ((triple[0] << 4) | (triple[1] << 2) | triple[2]) as i32)
+        .collect::<Vec<i32>>();
+    codewords.resize(codewords.len() + PARITY_SYMBOLS, 0);
+
+    let mut encoder = ReedSolomonEncoder::new(get_predefined_genericgf(
+        PredefinedGenericGF::AustraliaPostField64,
+    ))?;
+    encoder.encode(&mut codewords, PARITY_SYMBOLS)?;
+
+    let mut bars = Vec::with_capacity(bar_count);
+    bars.extend_from_slice(&START_STOP);
+    for codeword in codewords {
+        for shift in [4, 2, 0] {
+            bars.push(
+                BarState::from_value(((codeword >> shift) & 0x03) as u8)
+                    .ok_or(Exceptions::ILLEGAL_STATE)?,
+            );
+        }
+    }
+    bars.extend_from_slice(&START_STOP);
+
+    Ok(bars)
+}
+
+/**
+ * Decodes an Australia Post 4-state customer barcode, correcting errors with the
+ * Reed-Solomon parity symbols.
+ *
+ * The N and C tables overlap, so customer information that is valid in the N table is
+ * reported as digits. The filler bars padding the customer information are removed.
+ */
+pub fn decode(bars: &[BarState]) -> Result<String> {
+    decode_with_stats(bars).map(|(text, _)| text)
+}
+
+/**
+ * Decodes an Australia Post 4-state customer barcode, as `decode()` does, along with the work
+ * the Reed-Solomon error correction did.
+ */
+pub fn decode_with_stats(bars: &[BarState]) -> Result<(String, ErrorCorrectionStats)> {
+    if FORMAT_CONTROL_CODES
+        .iter()
+        .all(|(_, count)| *count != bars.len())
+    {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    if bars[..2] != START_STOP || bars[bars.len() - 2..] != START_STOP {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    let mut codewords = bars[2..bars.len() - 2]
+        .chunks_exact(3)
+        .map(|triple| {
+            ((triple[0].value() << 4) | (triple[1].value() << 2) | triple[2].value()) as i32
+        })
+        .collect::<Vec<i32>>();
+
+    let errors = ReedSolomonDecoder::new(get_predefined_genericgf(
+        PredefinedGenericGF::AustraliaPostField64,
+    ))
+    .decode(&mut codewords, PARITY_SYMBOLS as i32)
+    .map_err(|_| Exceptions::CHECKSUM)?;
+    let stats = ErrorCorrectionStats {
+        errors: errors as u32,
+        erasures: 0,
+        capacity: PARITY_SYMBOLS as u32,
+    };
+
+    let data = codewords[..codewords.len() - PARITY_SYMBOLS]
+        .iter()
+        .flat_map(|codeword| [4, 2, 0].map(|shift| ((codeword >> shift) & 0x03) as u8))
+        .collect::<Vec<u8>>();
+
+    let header = decode_n(&data[..HEADER_BARS]).ok_or(Exceptions::FORMAT)?;
+    let fcc = header[..2].parse::<u8>().map_err(|_| Exceptions::FORMAT)?;
+    if bar_count_for(fcc) != Some(bars.len()) {
+        return Err(Exceptions::FORMAT);
+    }
+
+    let info = &data[HEADER_BARS..];
+    let mut trimmed = info.len();
+    while trimmed > 0 && info[trimmed - 1] == FILLER {
+        trimmed -= 1;
+    }
+
+    let customer_info = if let Some(digits) = decode_n(&info[..trimmed]) {
+        digits
+    } else {
+        let mut characters = info.len() / 3;
+        while characters > 0 && info[3 * characters - 3..3 * characters] == [FILLER; 3] {
+            characters -= 1;
+        }
+        decode_c(&info[..3 * characters]).ok_or(Exceptions::FORMAT)?
+    };
+
+    Ok((format!("{header}{customer_info}"), stats))
+}
+
+fn decode_n(bars: &[u8]) -> Option<String> {
+    if !bars.len().is_multiple_of(2) {
+        return None;
+    }
+    bars.chunks_exact(2)
+        .map(|pair| {
+            N_TABLE
+                .iter()
+                .position(|entry| entry == pair)
+                .map(|digit| (b'0' + digit as u8) as char)
+        })
+        .collect()
+}
+
+fn decode_c(bars: &[u8]) -> Option<String> {
+    bars.chunks_exact(3)
+        .map(|triple| {
+            C_TABLE
+                .iter()
+                .position(|entry| entry == triple)
+                .and_then(|index| C_CHARSET.chars().nth(index))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bars_to_string(bars: &[BarState]) -> String {
+        bars.iter().map(|b| (b'0' + b.value()) as char).collect()
+    }
+
+    #[test]
+    fn standard_customer_barcode() {
+        let bars = encode("1139987520").expect("encode");
+        assert_eq!(bars.len(), 37);
+        // start bars, FCC 11 and DPID 39987520 in the N table
+        assert_eq!(
+            bars_to_string(&bars[..22]),
+            "13\
+0101\
+1030302221120200"
+        );
+        assert_eq!(bars_to_string(&bars[35..]), "13");
+        assert_eq!(decode(&bars).expect("decode"), "1139987520");
+    }
+
+    #[test]
+    fn customer_information() {
+        for contents in [
+            "5939987520",
+            "593998752012345678",
+            "5939987520Ab 1",
+            "6239987520123456789012345",
+            "6239987520Hello #",
+        ] {
+            let bars = encode(contents).expect("encode");
+            assert_eq!(decode(&bars).expect("decode"), contents);
+        }
+        assert_eq!(encode("5939987520").unwrap().len(), 52);
+        assert_eq!(encode("6239987520").unwrap().len(), 67);
+    }
+
+    #[test]
+    fn short_customer_information() {
+        let bars = encode("5939987520ABC").expect("encode");
+        // three characters of the five that fit, then filler bars up to the parity symbols
+        assert_eq!(bars_to_string(&bars[31..38]), "3333333");
+        assert_eq!(decode(&bars).expect("decode"), "5939987520ABC");
+        // trailing spaces are customer information, not padding
+        let bars = encode("5939987520ABC ").expect("encode");
+        assert_eq!(decode(&bars).expect("decode"), "5939987520ABC ");
+    }
+
+    #[test]
+    fn other_format_control_codes() {
+        for fcc in ["45", "87", "92"] {
+            let contents = format!("{fcc}12345678");
+            let bars = encode(&contents).expect("encode");
+            assert_eq!(bars.len(), 37);
+            assert_eq!(decode(&bars).expect("decode"), contents);
+        }
+    }
+
+    #[test]
+    fn corrects_errors() {
+        let mut bars = encode("5939987520ABCDE").expect("encode");
+        // corrupt two complete symbols, the most four parity symbols can recover
+        bars[5..8].fill(BarState::Tracker);
+        bars[20..23].fill(BarState::Tracker);
+        assert_eq!(decode(&bars).expect("decode"), "5939987520ABCDE");
+    }
+
+    #[test]
+    fn illegal_contents() {
+        assert!(encode("1212345678").is_err()); // unknown FCC
+        assert!(encode("113998752").is_err()); // short DPID
+        assert!(encode("11399875201").is_err()); // no customer information for FCC 11
+        assert!(encode("5939987520123456789").is_err()); // too many digits
+        assert!(encode("5939987520ABCDEF").is_err()); // too many characters
+        assert!(encode("5939987520AB-D").is_err()); // not in the C table
+        assert!(encode("5939987520Az").is_err()); // reads as filler bars
+    }
+}
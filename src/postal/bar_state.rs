@@ -0,0 +1,68 @@
+/**
+ * The vertical extent of a single bar in a height modulated postal barcode.
+ *
+ * Four-state symbologies (such as Australia Post) use all four states. Two-state
+ * symbologies (POSTNET and PLANET) only use `Full` (tall) and `Descender` (short)
+ * bars, since their short bars rest on the same baseline as the tall ones.
+ *
+ * The numeric values match the bar values of the Australia Post specification.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BarState {
+    /// Spans the ascender, tracker and descender regions
+    Full = 0,
+    /// Spans the ascender and tracker regions
+    Ascender = 1,
+    /// Spans the tracker and descender regions
+    Descender = 2,
+    /// Only the tracker region
+    Tracker = 3,
+}
+
+impl BarState {
+    pub fn value(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn from_value(value: u8) -> Option<Self> {
+        match value {
+            0 => Some(BarState::Full),
+            1 => Some(BarState::Ascender),
+            2 => Some(BarState::Descender),
+            3 => Some(BarState::Tracker),
+            _ => None,
+        }
+    }
+
+    /**
+     * The state this bar appears to have when the symbol is rotated by 180 degrees.
+     */
+    pub fn rotated(&self) -> Self {
+        match self {
+            BarState::Ascender => BarState::Descender,
+            BarState::Descender => BarState::Ascender,
+            other => *other,
+        }
+    }
+
+    /**
+     * Row span of this bar inside a symbol that is `units` rows tall, as `(top, bottom)`
+     * with `bottom` exclusive. Four-state symbols use a height of 8 units with the tracker
+     * in rows 3..5, two-state symbols use 5 units with the short bars in rows 3..5.
+     */
+    pub(crate) fn span(&self, four_state: bool) -> (u32, u32) {
+        if four_state {
+            match self {
+                BarState::Full => (0, 8),
+                BarState::Ascender => (0, 5),
+                BarState::Descender => (3, 8),
+                BarState::Tracker => (3, 5),
+            }
+        } else {
+            match self {
+                BarState::Full | BarState::Ascender => (0, 5),
+                BarState::Descender | BarState::Tracker => (3, 5),
+            }
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use crate::common::BitMatrix;
+
+use super::BarState;
+
+/**
+ * A run of evenly spaced bars found while scanning across a row of the image.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BarSequence {
+    /// The classified bars, left to right
+    pub bars: Vec<BarState>,
+    /// The row on which the bars were found
+    pub row: u32,
+    /// Left edge of the first bar
+    pub left: u32,
+    /// Right edge of the last bar
+    pub right: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bar {
+    start: u32,
+    end: u32,
+    top: u32,
+    bottom: u32,
+}
+
+impl Bar {
+    fn width(&self) -> u32 {
+        self.end - self.start
+    }
+}
+
+/**
+ * Locates height modulated barcodes in `image`.
+ *
+ * Rows are scanned from the middle outwards. Every row that crosses at least `min_bars`
+ * evenly spaced bars yields a candidate, with each bar classified by its vertical extent
+ * relative to the tallest bars in the same sequence. A row through the tracker region of a
+ * four-state symbol, or through the baseline of a two-state one, crosses every bar.
+ */
+pub fn detect(image: &BitMatrix, min_bars: usize, try_harder: bool) -> Vec<BarSequence> {
+    let height = image.getHeight();
+    let row_step = 1.max(height >> if try_harder { 8 } else { 6 });
+    let middle = height / 2;
+
+    let mut candidates: Vec<BarSequence> = Vec::new();
+    let mut x: u32 = 0;
+    loop {
+        // Scanning from the middle out, alternating above and below
+        let steps = x.div_ceil(2);
+        let row = if x & 0x01 == 0 {
+            middle.checked_add(row_step * steps)
+        } else {
+            middle.checked_sub(row_step * steps)
+        };
+        x += 1;
+        let Some(row) = row.filter(|r| *r < height) else {
+            break;
+        };
+
+        for group in group_bars(&find_bars(image, row), min_bars) {
+            let sequence = BarSequence {
+                bars: classify(&group),
+                row,
+                left: group[0].start,
+                right: group[group.len() - 1].end,
+            };
+            if !candidates.iter().any(|c| c.bars == sequence.bars) {
+                candidates.push(sequence);
+            }
+        }
+    }
+
+    candidates
+}
+
+/**
+ * Every run of set bits on `row`, together with the vertical extent of the bar it belongs to.
+ */
+fn find_bars(image: &BitMatrix, row: u32) -> Vec<Bar> {
+    let bits = image.getRow(row);
+    let width = image.getWidth() as usize;
+
+    let mut bars = Vec::new();
+    let mut start = bits.getNextSet(0);
+    while start < width {
+        let end = bits.getNextUnset(start);
+        let center = ((start + end) / 2) as u32;
+
+        let mut top = row;
+        while top > 0 && image.get(center, top - 1) {
+            top -= 1;
+        }
+        let mut bottom = row;
+        while bottom + 1 < image.getHeight() && image.get(center, bottom + 1) {
+            bottom += 1;
+        }
+
+        bars.push(Bar {
+            start: start as u32,
+            end: end as u32,
+            top,
+            bottom,
+        });
+        start = bits.getNextSet(end);
+    }
+    bars
+}
+
+fn is_close(value: u32, reference: u32) -> bool {
+    let value = value as f32;
+    let reference = reference as f32;
+    value >= reference * 0.5 - 1.0 && value <= reference * 1.5 + 1.0
+}
+
+/**
+ * Splits the bars of a row into sequences of similar width and spacing.
+ */
+fn group_bars(bars: &[Bar], min_bars: usize) -> Vec<Vec<Bar>> {
+    let mut groups = Vec::new();
+    let mut current: Vec<Bar> = Vec::new();
+
+    for bar in bars {
+        if let Some(last) = current.last() {
+            let gap = bar.start - last.end;
+            let fits = if current.len() == 1 {
+                is_close(bar.width(), last.width()) && gap <= 3 * last.width().max(1)
+            } else {
+                is_close(bar.width(), current[0].width())
+                    && is_close(gap, current[1].start - current[0].end)
+            };
+            if !fits {
+                if current.len() >= min_bars {
+                    groups.push(std::mem::take(&mut current));
+                } else {
+                    current.clear();
+                }
+            }
+        }
+        current.push(*bar);
+    }
+    if current.len() >= min_bars {
+        groups.push(current);
+    }
+
+    groups
+}
+
+/**
+ * Classifies each bar by whether it reaches into the ascender and descender regions, which
+ * are taken to be the top and bottom quarter of the sequence's overall height.
+ */
+fn classify(bars: &[Bar]) -> Vec<BarState> {
+    let top = bars.iter().map(|b| b.top).min().unwrap_or_default();
+    let bottom = bars.iter().map(|b| b.bottom).max().unwrap_or_default();
+    let quarter = (bottom - top + 1) / 4;
+
+    bars.iter()
+        .map(|bar| {
+            let ascends = bar.top <= top + quarter;
+            let descends = bar.bottom + quarter >= bottom;
+            match (ascends, descends) {
+                (true, true) => BarState::Full,
+                (true, false) => BarState::Ascender,
+                (false, true) => BarState::Descender,
+                (false, false) => BarState::Tracker,
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,15 @@
+//! Height modulated postal barcodes: POSTNET, PLANET and the Australia Post 4-state
+//! customer barcodes.
+
+mod bar_state;
+pub use bar_state::*;
+
+pub mod australia_post;
+pub mod detector;
+pub mod postnet;
+
+mod postal_reader;
+pub use postal_reader::*;
+
+mod postal_writer;
+pub use postal_writer::*;
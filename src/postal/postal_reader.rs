@@ -0,0 +1,234 @@
+use crate::{
+    common::Result, decode_region::decode_in_region, point, BarcodeFormat, Binarizer, BinaryBitmap,
+    DecodeHints, ErrorCorrectionStats, EstimatedModuleSize, Exceptions, ImmutableReader,
+    RXingResult, RXingResultMetadataType, RXingResultMetadataValue, Reader,
+};
+
+use super::{australia_post, detector, postnet, BarState};
+
+/** The formats handled by `PostalReader`, in the order they are tried. */
+pub const POSTAL_FORMATS: [BarcodeFormat; 3] = [
+    BarcodeFormat::AUSTRALIA_POST,
+    BarcodeFormat::POSTNET,
+    BarcodeFormat::PLANET,
+];
+
+/**
+ * Reads height modulated postal barcodes: POSTNET, PLANET and the Australia Post
+ * 4-state customer barcodes.
+ *
+ * Only the formats listed in `PossibleFormats` are attempted, or all of them if the hint is
+ * absent. Symbols printed upside down are reported with an orientation of 180.
+ */
+#[derive(Default, Clone)]
+pub struct PostalReader;
+
+impl Reader for PostalReader {
+    fn decode<B: Binarizer>(&mut self, image: &mut BinaryBitmap<B>) -> Result<RXingResult> {
+        self.decode_with_hints(image, &DecodeHints::default())
+    }
+
+    fn decode_with_hints<B: Binarizer>(
+        &mut self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
+    }
+}
+
+impl ImmutableReader for PostalReader {
+    fn immutable_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
+    }
+}
+
+impl PostalReader {
+    fn internal_decode_with_hints<B: Binarizer>(
+        &self,
+        image: &mut BinaryBitmap<B>,
+        hints: &DecodeHints,
+    ) -> Result<RXingResult> {
+        let formats = POSTAL_FORMATS
+            .iter()
+            .filter(|f| {
+                hints
+                    .PossibleFormats
+                    .as_ref()
+                    .is_none_or(|possible| possible.contains(f))
+            })
+            .copied()
+            .collect::<Vec<_>>();
+        if formats.is_empty() {
+            return Err(Exceptions::NOT_FOUND);
+        }
+
+        let min_bars = if formats.contains(&BarcodeFormat::POSTNET)
+            || formats.contains(&BarcodeFormat::PLANET)
+        {
+            postnet::MIN_BAR_COUNT
+        } else {
+            australia_post::MIN_BAR_COUNT
+        };
+        let try_harder = hints.TryHarder.unwrap_or(false);
+
+        for candidate in detector::detect(image.get_black_matrix(), min_bars, try_harder) {
+            let rotated = candidate
+                .bars
+                .iter()
+                .rev()
+                .map(BarState::rotated)
+                .collect::<Vec<_>>();
+
+            for (bars, upside_down) in [(&candidate.bars, false), (&rotated, true)] {
+                let Some((text, format, error_correction)) = Self::decode_bars(bars, &formats)
+                else {
+                    continue;
+                };
+
+                let (left, right) = (candidate.left as f32, candidate.right as f32);
+                let row = candidate.row as f32;
+                let mut result = RXingResult::new(
+                    &text,
+                    Vec::new(), // no natural byte representation for these barcodes
+                    if upside_down {
+                        vec![point(right, row), point(left, row)]
+                    } else {
+                        vec![point(left, row), point(right, row)]
+                    },
+                    format,
+                );
+                if upside_down {
+                    result.putMetadata(
+                        RXingResultMetadataType::ORIENTATION,
+                        RXingResultMetadataValue::Orientation(180),
+                    );
+                }
+                if let Some(error_correction) = error_correction {
+                    result.putMetadata(
+                        RXingResultMetadataType::ERRORS_CORRECTED,
+                        RXingResultMetadataValue::ErrorsCorrected(error_correction),
+                    );
+                }
+                // bars and the gaps between them are one module wide
+                result.putMetadata(
+                    RXingResultMetadataType::MODULE_SIZE,
+                    RXingResultMetadataValue::ModuleSize(EstimatedModuleSize(
+                        (right - left + 1.0) / (2 * bars.len() - 1) as f32,
+                    )),
+                );
+                return Ok(result);
+            }
+        }
+
+        Err(Exceptions::NOT_FOUND)
+    }
+
+    fn decode_bars(
+        bars: &[BarState],
+        formats: &[BarcodeFormat],
+    ) -> Option<(String, BarcodeFormat, Option<ErrorCorrectionStats>)> {
+        formats.iter().find_map(|format| {
+            let decoded = match format {
+                BarcodeFormat::AUSTRALIA_POST => {
+                    australia_post::decode_with_stats(bars).map(|(text, stats)| (text, Some(stats)))
+                }
+                BarcodeFormat::POSTNET => postnet::decode_postnet(bars).map(|text| (text, None)),
+                BarcodeFormat::PLANET => postnet::decode_planet(bars).map(|text| (text, None)),
+                _ => Err(Exceptions::UNSUPPORTED_OPERATION),
+            };
+            decoded.ok().map(|(text, stats)| (text, *format, stats))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        common::test_image::image,
+        postal::{AustraliaPostWriter, PLANETWriter, POSTNETWriter},
+        BarcodeFormat, DecodeHints, RXingResultMetadataValue, Reader, Writer,
+    };
+
+    use super::PostalReader;
+
+    fn round_trip(writer: &impl Writer, contents: &str, format: BarcodeFormat) {
+        let matrix = writer.encode(contents, &format, 400, 60).expect("encode");
+        let result = PostalReader
+            .decode(&mut image(&matrix, 1, 0))
+            .expect("decode");
+        assert_eq!(result.getText(), contents);
+        assert_eq!(result.getBarcodeFormat(), &format);
+    }
+
+    #[test]
+    fn postnet() {
+        round_trip(&POSTNETWriter, "12345", BarcodeFormat::POSTNET);
+        round_trip(&POSTNETWriter, "123456789", BarcodeFormat::POSTNET);
+        round_trip(&POSTNETWriter, "12345678901", BarcodeFormat::POSTNET);
+    }
+
+    #[test]
+    fn planet() {
+        round_trip(&PLANETWriter, "12345678901", BarcodeFormat::PLANET);
+        round_trip(&PLANETWriter, "1234567890123", BarcodeFormat::PLANET);
+    }
+
+    #[test]
+    fn australia_post() {
+        round_trip(
+            &AustraliaPostWriter,
+            "1139987520",
+            BarcodeFormat::AUSTRALIA_POST,
+        );
+        round_trip(
+            &AustraliaPostWriter,
+            "5939987520AB12",
+            BarcodeFormat::AUSTRALIA_POST,
+        );
+        round_trip(
+            &AustraliaPostWriter,
+            "6239987520123456789012345",
+            BarcodeFormat::AUSTRALIA_POST,
+        );
+    }
+
+    #[test]
+    fn upside_down() {
+        let mut matrix = AustraliaPostWriter
+            .encode("5939987520Hello", &BarcodeFormat::AUSTRALIA_POST, 300, 40)
+            .expect("encode");
+        matrix.rotate180();
+        let result = PostalReader
+            .decode(&mut image(&matrix, 1, 0))
+            .expect("decode");
+        assert_eq!(result.getText(), "5939987520Hello");
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&crate::RXingResultMetadataType::ORIENTATION),
+            Some(&RXingResultMetadataValue::Orientation(180))
+        );
+    }
+
+    #[test]
+    fn respects_possible_formats() {
+        let matrix = POSTNETWriter
+            .encode("12345", &BarcodeFormat::POSTNET, 300, 40)
+            .expect("encode");
+        let hints = DecodeHints::default().with(crate::DecodeHintValue::PossibleFormats(
+            [BarcodeFormat::AUSTRALIA_POST].into(),
+        ));
+        assert!(PostalReader
+            .decode_with_hints(&mut image(&matrix, 1, 0), &hints)
+            .is_err());
+    }
+}
@@ -0,0 +1,286 @@
+use crate::{
+    common::{BitMatrix, Result},
+    encoded_symbol::Placement,
+    BarcodeFormat, EncodeHints, EncodedSymbol, Exceptions, Writer,
+};
+
+use super::{australia_post, postnet, BarState};
+
+/** Default quiet zone on either side of the symbol, in modules. */
+const DEFAULT_MARGIN: u32 = 10;
+
+/**
+ * Renders height modulated bars as a `BitMatrix`. Each bar and each space is one module wide,
+ * the vertical unit is at least as large as the module width so that the default rendering
+ * keeps the proportions of the printed symbols. Returns where the modules, a vertical unit high,
+ * are in the matrix.
+ */
+fn render_bars(
+    bars: &[BarState],
+    four_state: bool,
+    width: i32,
+    height: i32,
+    sides_margin: u32,
+) -> Result<(BitMatrix, Placement)> {
+    let units = if four_state { 8 } else { 5 };
+
+    let input_width = 2 * bars.len() as u32 - 1;
+    let full_width = input_width + 2 * sides_margin;
+    let output_width = (width.max(0) as u32).max(full_width);
+    let multiple = output_width / full_width;
+    let left_padding = (output_width - input_width * multiple) / 2;
+
+    let unit_height = (height.max(0) as u32 / units).max(multiple);
+    let output_height = (height.max(0) as u32).max(unit_height * units);
+    let top_padding = (output_height - unit_height * units) / 2;
+
+    let mut output = BitMatrix::new(output_width, output_height)?;
+    for (i, bar) in bars.iter().enumerate() {
+        let (top, bottom) = bar.span(four_state);
+        output.setRegion(
+            left_padding + 2 * i as u32 * multiple,
+            top_padding + top * unit_height,
+            multiple,
+            (bottom - top) * unit_height,
+        )?;
+    }
+
+    let placement = Placement {
+        scale_x: multiple,
+        scale_y: unit_height,
+        offset_x: left_padding,
+        offset_y: top_padding,
+    };
+    Ok((output, placement))
+}
+
+fn encode_postal(
+    contents: &str,
+    format: &BarcodeFormat,
+    width: i32,
+    height: i32,
+    hints: &EncodeHints,
+    supported: BarcodeFormat,
+) -> Result<EncodedSymbol> {
+    if contents.is_empty() {
+        return Err(Exceptions::illegal_argument_with("Found empty contents"));
+    }
+    if width < 0 || height < 0 {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "Negative size is not allowed. Input: {width}x{height}"
+        )));
+    }
+    if *format != supported {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "Can only encode {supported:?}, but got {format:?}"
+        )));
+    }
+
+    let sides_margin = if let Some(margin) = &hints.Margin {
+        margin
+            .parse::<u32>()
+            .map_err(|e| Exceptions::illegal_argument_with(format!("couldn't parse margin: {e}")))?
+    } else {
+        DEFAULT_MARGIN
+    };
+
+    let (bars, four_state) = match supported {
+        BarcodeFormat::POSTNET => (postnet::encode_postnet(contents)?, false),
+        BarcodeFormat::PLANET => (postnet::encode_planet(contents)?, false),
+        BarcodeFormat::AUSTRALIA_POST => (australia_post::encode(contents)?, true),
+        _ => return Err(Exceptions::UNSUPPORTED_OPERATION),
+    };
+
+    Ok(EncodedSymbol::new(
+        *format,
+        render_bars(&bars, four_state, 0, 0, 0)?.0,
+        render_bars(&bars, four_state, width, height, sides_margin)?,
+    ))
+}
+
+/**
+ * This object renders a POSTNET code as a `BitMatrix`. The contents are the 5, 9 or 11
+ * digits of the ZIP, ZIP+4 or delivery point code; the check digit is added automatically.
+ */
+#[derive(Default)]
+pub struct POSTNETWriter;
+
+impl Writer for POSTNETWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
+        encode_postal(
+            contents,
+            format,
+            width,
+            height,
+            hints,
+            BarcodeFormat::POSTNET,
+        )
+    }
+}
+
+/**
+ * This object renders a PLANET code as a `BitMatrix`. The contents are 11 or 13 digits;
+ * the check digit is added automatically.
+ */
+#[derive(Default)]
+pub struct PLANETWriter;
+
+impl Writer for PLANETWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
+        encode_postal(
+            contents,
+            format,
+            width,
+            height,
+            hints,
+            BarcodeFormat::PLANET,
+        )
+    }
+}
+
+/**
+ * This object renders an Australia Post 4-state customer barcode as a `BitMatrix`.
+ * The contents are the FCC, the DPID and the optional customer information,
+ * see `australia_post::encode`.
+ */
+#[derive(Default)]
+pub struct AustraliaPostWriter;
+
+impl Writer for AustraliaPostWriter {
+    fn encode(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+    ) -> Result<BitMatrix> {
+        self.encode_with_hints(contents, format, width, height, &EncodeHints::default())
+    }
+
+    fn encode_with_hints(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
+        encode_postal(
+            contents,
+            format,
+            width,
+            height,
+            hints,
+            BarcodeFormat::AUSTRALIA_POST,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BarcodeFormat, Writer};
+
+    use super::{AustraliaPostWriter, POSTNETWriter};
+
+    #[test]
+    fn renders_bar_heights() {
+        let matrix = POSTNETWriter
+            .encode("12345", &BarcodeFormat::POSTNET, 0, 0)
+            .expect("encode");
+        // 32 bars and 31 spaces, plus the default margin on both sides
+        assert_eq!(matrix.getWidth(), 63 + 20);
+        assert_eq!(matrix.getHeight(), 5);
+        // frame bar is tall, the first bar of "1" (00011) is short
+        assert!(matrix.get(10, 0) && matrix.get(10, 4));
+        assert!(!matrix.get(12, 0) && matrix.get(12, 4));
+
+        let matrix = AustraliaPostWriter
+            .encode("1139987520", &BarcodeFormat::AUSTRALIA_POST, 0, 0)
+            .expect("encode");
+        assert_eq!(matrix.getWidth(), 73 + 20);
+        assert_eq!(matrix.getHeight(), 8);
+        // start bars are an ascender and a tracker
+        assert!(matrix.get(10, 0) && !matrix.get(10, 7));
+        assert!(!matrix.get(12, 0) && matrix.get(12, 3) && !matrix.get(12, 7));
+    }
+
+    #[test]
+    fn rejects_other_formats() {
+        assert!(POSTNETWriter
+            .encode("12345", &BarcodeFormat::PLANET, 0, 0)
+            .is_err());
+    }
+}
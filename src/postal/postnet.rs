@@ -0,0 +1,185 @@
+use crate::common::Result;
+use crate::Exceptions;
+
+use super::BarState;
+
+/**
+ * POSTNET digit patterns, `true` being a tall bar. Each digit has exactly two tall bars,
+ * with weights 7, 4, 2, 1, 0 (zero is encoded as 11).
+ */
+const POSTNET_PATTERNS: [[bool; 5]; 10] = [
+    [true, true, false, false, false], // 0
+    [false, false, false, true, true], // 1
+    [false, false, true, false, true], // 2
+    [false, false, true, true, false], // 3
+    [false, true, false, false, true], // 4
+    [false, true, false, true, false], // 5
+    [false, true, true, false, false], // 6
+    [true, false, false, false, true], // 7
+    [true, false, false, true, false], // 8
+    [true, false, true, false, false], // 9
+];
+
+/** Lengths of the payload (without check digit) allowed for POSTNET: ZIP, ZIP+4 and DPBC. */
+const POSTNET_LENGTHS: [usize; 3] = [5, 9, 11];
+
+/** Lengths of the payload (without check digit) allowed for PLANET. */
+const PLANET_LENGTHS: [usize; 2] = [11, 13];
+
+/**
+ * Smallest number of bars a POSTNET or PLANET symbol can have.
+ */
+pub const MIN_BAR_COUNT: usize = 2 + 5 * (POSTNET_LENGTHS[0] + 1);
+
+/**
+ * Encodes `contents` as a POSTNET symbol. The check digit is calculated and appended.
+ */
+pub fn encode_postnet(contents: &str) -> Result<Vec<BarState>> {
+    encode(contents, &POSTNET_LENGTHS, false)
+}
+
+/**
+ * Encodes `contents` as a PLANET symbol. The check digit is calculated and appended.
+ */
+pub fn encode_planet(contents: &str) -> Result<Vec<BarState>> {
+    encode(contents, &PLANET_LENGTHS, true)
+}
+
+/**
+ * Decodes a POSTNET symbol, validating and stripping the check digit.
+ */
+pub fn decode_postnet(bars: &[BarState]) -> Result<String> {
+    decode(bars, &POSTNET_LENGTHS, false)
+}
+
+/**
+ * Decodes a PLANET symbol, validating and stripping the check digit.
+ */
+pub fn decode_planet(bars: &[BarState]) -> Result<String> {
+    decode(bars, &PLANET_LENGTHS, true)
+}
+
+fn check_digit(digits: &[u8]) -> u8 {
+    let sum: u32 = digits.iter().map(|d| *d as u32).sum();
+    ((10 - sum % 10) % 10) as u8
+}
+
+fn encode(contents: &str, allowed_lengths: &[usize], planet: bool) -> Result<Vec<BarState>> {
+    let mut digits = contents
+        .chars()
+        .map(|c| {
+            c.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or(Exceptions::illegal_argument_with(
+                    "Input should only contain digits 0-9",
+                ))
+        })
+        .collect::<Result<Vec<u8>>>()?;
+
+    if !allowed_lengths.contains(&digits.len()) {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "Requested contents should be {allowed_lengths:?} digits long, but got {}",
+            digits.len()
+        )));
+    }
+
+    digits.push(check_digit(&digits));
+
+    let mut bars = Vec::with_capacity(2 + 5 * digits.len());
+    bars.push(BarState::Full);
+    for digit in digits {
+        for tall in POSTNET_PATTERNS[digit as usize] {
+            // PLANET is the inverse of POSTNET, three tall bars and two short ones per digit
+            bars.push(if tall != planet {
+                BarState::Full
+            } else {
+                BarState::Descender
+            });
+        }
+    }
+    bars.push(BarState::Full);
+
+    Ok(bars)
+}
+
+fn decode(bars: &[BarState], allowed_lengths: &[usize], planet: bool) -> Result<String> {
+    if bars.len() < MIN_BAR_COUNT || !(bars.len() - 2).is_multiple_of(5) {
+        return Err(Exceptions::NOT_FOUND);
+    }
+    if bars[0] != BarState::Full || bars[bars.len() - 1] != BarState::Full {
+        return Err(Exceptions::NOT_FOUND);
+    }
+
+    let digit_count = (bars.len() - 2) / 5;
+    if !allowed_lengths.contains(&(digit_count - 1)) {
+        return Err(Exceptions::FORMAT);
+    }
+
+    let mut digits = Vec::with_capacity(digit_count);
+    for chunk in bars[1..bars.len() - 1].chunks_exact(5) {
+        let mut pattern = [false; 5];
+        for (tall, bar) in pattern.iter_mut().zip(chunk) {
+            *tall = match bar {
+                BarState::Full => !planet,
+                BarState::Descender => planet,
+                _ => return Err(Exceptions::NOT_FOUND),
+            };
+        }
+        let digit = POSTNET_PATTERNS
+            .iter()
+            .position(|p| *p == pattern)
+            .ok_or(Exceptions::NOT_FOUND)?;
+        digits.push(digit as u8);
+    }
+
+    let check = digits.pop().ok_or(Exceptions::NOT_FOUND)?;
+    if check != check_digit(&digits) {
+        return Err(Exceptions::CHECKSUM);
+    }
+
+    Ok(digits.iter().map(|d| (b'0' + d) as char).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postnet_round_trip() {
+        for contents in ["12345", "123456789", "12345678901"] {
+            let bars = encode_postnet(contents).expect("encode");
+            assert_eq!(bars.len(), 2 + 5 * (contents.len() + 1));
+            assert_eq!(decode_postnet(&bars).expect("decode"), contents);
+            assert!(decode_planet(&bars).is_err());
+        }
+    }
+
+    #[test]
+    fn planet_round_trip() {
+        for contents in ["12345678901", "1234567890123"] {
+            let bars = encode_planet(contents).expect("encode");
+            assert_eq!(decode_planet(&bars).expect("decode"), contents);
+            assert!(decode_postnet(&bars).is_err());
+        }
+    }
+
+    #[test]
+    fn postnet_check_digit() {
+        // 5 + 5 + 5 + 5 + 5 = 25, the check digit is 5
+        let bars = encode_postnet("55555").expect("encode");
+        assert_eq!(&bars[26..31], &bars[1..6]);
+
+        let mut corrupted = bars.clone();
+        // swap the first 5 (01010) for a 6 (01100)
+        corrupted[3] = BarState::Full;
+        corrupted[4] = BarState::Descender;
+        assert_eq!(decode_postnet(&corrupted), Err(Exceptions::CHECKSUM));
+    }
+
+    #[test]
+    fn illegal_contents() {
+        assert!(encode_postnet("1234").is_err());
+        assert!(encode_postnet("1234a").is_err());
+        assert!(encode_planet("12345").is_err());
+    }
+}
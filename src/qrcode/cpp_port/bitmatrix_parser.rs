@@ -11,6 +11,7 @@ use crate::{
 };
 
 use super::{data_mask::GetDataMaskBit, detector::AppendBit, Type};
+use crate::point;
 
 pub fn getBit(bitMatrix: &BitMatrix, x: u32, y: u32, mirrored: Option<bool>) -> bool {
     let mirrored = mirrored.unwrap_or(false);
@@ -21,7 +22,31 @@ pub fn getBit(bitMatrix: &BitMatrix, x: u32, y: u32, mirrored: Option<bool>) ->
     }
 }
 
+/// Whether the matrix is that of a mirrored rMQR symbol, which comes transposed, tall rather than wide.
+pub fn IsMirroredRMQR(bitMatrix: &BitMatrix) -> bool {
+    Version::IsValidSize(
+        point(bitMatrix.height() as i32, bitMatrix.width() as i32),
+        Type::RectMicro,
+    )
+}
+
+/// The width and height of the symbol, whether it is mirrored or not.
+fn SymbolDimensions(bitMatrix: &BitMatrix, mirrored: bool) -> (u32, u32) {
+    if mirrored {
+        (bitMatrix.height(), bitMatrix.width())
+    } else {
+        (bitMatrix.width(), bitMatrix.height())
+    }
+}
+
 pub fn ReadVersion(bitMatrix: &BitMatrix, qr_type: Type) -> Result<VersionRef> {
+    if qr_type == Type::RectMicro && IsMirroredRMQR(bitMatrix) {
+        return Version::rMQR(Version::NumberPoint(point(
+            bitMatrix.height() as i32,
+            bitMatrix.width() as i32,
+        )));
+    }
+
     if !Version::HasValidSize(bitMatrix) {
         return Err(Exceptions::FORMAT);
     }
@@ -52,29 +77,30 @@ pub fn ReadFormatInformation(bitMatrix: &BitMatrix) -> Result<FormatInformation>
         return Ok(FormatInformation::DecodeMQR(formatInfoBits as u32));
     }
 
-    if Version::HasValidSizeType(bitMatrix, Type::RectMicro) {
+    let mirrored = IsMirroredRMQR(bitMatrix);
+    if mirrored || Version::HasValidSizeType(bitMatrix, Type::RectMicro) {
+        let mirror = Some(mirrored);
         // Read top-left format info bits
         let mut formatInfoBits1 = 0;
         for y in (1..=3).rev() {
             // for (int y = 3; y >= 1; y--){
-            AppendBit(&mut formatInfoBits1, getBit(bitMatrix, 11, y, None));
+            AppendBit(&mut formatInfoBits1, getBit(bitMatrix, 11, y, mirror));
         }
         for x in (8..=10).rev() {
             // for (int x = 10; x >= 8; x--){
             for y in (1..=5).rev() {
                 // for (int y = 5; y >= 1; y--){
-                AppendBit(&mut formatInfoBits1, getBit(bitMatrix, x, y, None));
+                AppendBit(&mut formatInfoBits1, getBit(bitMatrix, x, y, mirror));
             }
         }
         // Read bottom-right format info bits
         let mut formatInfoBits2 = 0;
-        let width = bitMatrix.width();
-        let height = bitMatrix.height();
+        let (width, height) = SymbolDimensions(bitMatrix, mirrored);
         for x in 3..=5 {
             // for (int x = 3; x <= 5; x++){
             AppendBit(
                 &mut formatInfoBits2,
-                getBit(bitMatrix, width - x, height - 6, None),
+                getBit(bitMatrix, width - x, height - 6, mirror),
             );
         }
         for x in 6..=8 {
@@ -83,14 +109,13 @@ pub fn ReadFormatInformation(bitMatrix: &BitMatrix) -> Result<FormatInformation>
                 // for (int y = 2; y <= 6; y++){
                 AppendBit(
                     &mut formatInfoBits2,
-                    getBit(bitMatrix, width - x, height - y, None),
+                    getBit(bitMatrix, width - x, height - y, mirror),
                 );
             }
         }
-        return Ok(FormatInformation::DecodeRMQR(
-            formatInfoBits1 as u32,
-            formatInfoBits2 as u32,
-        ));
+        let mut fi = FormatInformation::DecodeRMQR(formatInfoBits1 as u32, formatInfoBits2 as u32);
+        fi.isMirrored = mirrored;
+        return Ok(fi);
     }
 
     // Read top-left format info bits
@@ -364,8 +389,7 @@ pub fn ReadRMQRCodewords(
     let mut currentByte = 0;
     let mut readingUp = true;
     let mut bitsRead = 0;
-    let width = bitMatrix.width();
-    let height = bitMatrix.height();
+    let (width, height) = SymbolDimensions(bitMatrix, formatInfo.isMirrored);
     // Read columns in pairs, from right to left
     let mut x = width as i32 - 1 - 1;
     while x > 0 {
@@ -12,10 +12,11 @@ use crate::common::{
     AIFlag, BitMatrix, BitSource, CharacterSet, ECIStringBuilder, Eci, Result, SymbologyIdentifier,
 };
 use crate::qrcode::cpp_port::bitmatrix_parser::{
-    ReadCodewords, ReadFormatInformation, ReadVersion,
+    IsMirroredRMQR, ReadCodewords, ReadFormatInformation, ReadVersion,
 };
+use crate::qrcode::cpp_port::Type;
 use crate::qrcode::decoder::{DataBlock, ErrorCorrectionLevel, Mode, Version};
-use crate::Exceptions;
+use crate::{BarcodeFormat, CodewordRecorder, DecodeHints, ErrorCorrectionStats, Exceptions};
 
 /**
 * <p>Given data and error-correction codewords received, possibly corrupted by errors, attempts to
@@ -23,9 +24,9 @@ use crate::Exceptions;
 *
 * @param codewordBytes data and error correction codewords
 * @param numDataCodewords number of codewords that are data bytes
-* @return false if error correction fails
+* @return the number of errors corrected
 */
-pub fn CorrectErrors(codewordBytes: &mut [u8], numDataCodewords: u32) -> Result<bool> {
+pub fn CorrectErrors(codewordBytes: &mut [u8], numDataCodewords: u32) -> Result<usize> {
     // First read into an array of ints
     // std::vector<int> codewordsInts(codewordBytes.begin(), codewordBytes.end());
     let mut codewordsInts: Vec<i32> = codewordBytes.iter().copied().map(|b| b as i32).collect();
@@ -35,7 +36,7 @@ pub fn CorrectErrors(codewordBytes: &mut [u8], numDataCodewords: u32) -> Result<
         PredefinedGenericGF::QrCodeField256,
     ));
 
-    rs.decode(&mut codewordsInts, numECCodewords)?;
+    let errorsCorrected = rs.decode(&mut codewordsInts, numECCodewords)?;
 
     // if rs.decode(&mut codewordsInts, numECCodewords)? != 0
     // // if (!ReedSolomonDecode(GenericGF::QRCodeField256(), codewordsInts, numECCodewords))
@@ -54,7 +55,7 @@ pub fn CorrectErrors(codewordBytes: &mut [u8], numDataCodewords: u32) -> Result<
     );
     // std::copy_n(codewordsInts.begin(), numDataCodewords, codewordBytes.begin());
 
-    Ok(true)
+    Ok(errorsCorrected)
 }
 
 /**
@@ -388,7 +389,14 @@ pub fn DecodeBitStream(
 }
 
 pub fn Decode(bits: &BitMatrix) -> Result<DecoderResult<bool>> {
-    if !Version::HasValidSize(bits) {
+    DecodeWithHints(bits, &DecodeHints::default())
+}
+
+/// Like [`Decode`], recording the codewords before and after error correction with the
+/// diagnostics of the `hints`, if any, and collecting them with their best effort collector, if
+/// any, if error correction fails.
+pub fn DecodeWithHints(bits: &BitMatrix, hints: &DecodeHints) -> Result<DecoderResult<bool>> {
+    if !Version::HasValidSize(bits) && !IsMirroredRMQR(bits) {
         return Err(Exceptions::format_with("Invalid symbol size"));
     }
     let Ok(formatInfo) = ReadFormatInformation(bits) else {
@@ -422,14 +430,29 @@ pub fn Decode(bits: &BitMatrix) -> Result<DecoderResult<bool>> {
     let totalBytes = dataBlocks.iter().fold(0, op); // std::accumulate(std::begin(dataBlocks), std::end(dataBlocks), int{}, op);
     let mut resultBytes = vec![0u8; totalBytes as usize];
     let mut resultIterator = 0; //resultBytes.begin();
+    let mut errorsCorrected = 0;
+    let format = match version.qr_type {
+        Type::Micro => BarcodeFormat::MICRO_QR_CODE,
+        Type::RectMicro => BarcodeFormat::RECTANGULAR_MICRO_QR_CODE,
+        Type::Model1 | Type::Model2 => BarcodeFormat::QR_CODE,
+    };
+    let mut recorder = CodewordRecorder::new(hints, format);
+    let mut failure = None;
 
     // Error-correct and copy data blocks together into a stream of bytes
     for dataBlock in dataBlocks.iter() {
         let mut codewordBytes = dataBlock.getCodewords().to_vec();
         let numDataCodewords = dataBlock.getNumDataCodewords() as usize;
 
-        if !CorrectErrors(&mut codewordBytes, numDataCodewords as u32)? {
-            return Err(Exceptions::CHECKSUM);
+        recorder.received(numDataCodewords, &codewordBytes);
+        match CorrectErrors(&mut codewordBytes, numDataCodewords as u32) {
+            Ok(errors) => {
+                errorsCorrected += errors;
+                recorder.corrected(&codewordBytes);
+            }
+            // go on with the codewords as they were read
+            Err(e) if recorder.best_effort() => failure = failure.or(Some(e)),
+            Err(e) => return Err(e),
         }
 
         // resultIterator = std::copy_n(codewordBytes.begin(), numDataCodewords, resultIterator);
@@ -437,11 +460,24 @@ pub fn Decode(bits: &BitMatrix) -> Result<DecoderResult<bool>> {
             .copy_from_slice(&codewordBytes[..numDataCodewords]);
         resultIterator += numDataCodewords;
     }
+    if let Some(e) = failure {
+        // the text decoded up to an error is kept with it
+        let ecLevel = formatInfo.error_correction_level;
+        recorder.failed(resultBytes, move |bytes| {
+            Ok(DecodeBitStream(bytes, version, ecLevel)?.text())
+        });
+        return Err(e);
+    }
 
     // Decode the contents of that stream of bytes
     Ok(
         DecodeBitStream(&resultBytes, version, formatInfo.error_correction_level)?
-            .withIsMirrored(formatInfo.isMirrored),
+            .withIsMirrored(formatInfo.isMirrored)
+            .withErrorCorrection(ErrorCorrectionStats {
+                errors: errorsCorrected as u32,
+                erasures: 0,
+                capacity: (codewords.len() - totalBytes as usize) as u32,
+            }),
     )
 }
 
@@ -1206,13 +1206,15 @@ pub fn SampleRMQR(image: &BitMatrix, fp: ConcentricPattern) -> Result<QRCodeDete
 
     let mut bestFI: FormatInformation = FormatInformation::default();
     let mut bestPT: PerspectiveTransform = PerspectiveTransform::default();
+    let mut bestMirrored = false;
     let cur = EdgeTracer::new(image, Point::default(), Point::default());
 
-    for i in 0..4 {
+    // a mirrored symbol is one read with the corners of its finder pattern in the other direction
+    for (i, mirrored) in (0..4).flat_map(|i| [(i, false), (i, true)]) {
         // for (int i = 0; i < 4; ++i) {
         let mod2Pix = PerspectiveTransform::quadrilateralToQuadrilateral(
             srcQuad,
-            fpQuad.rotated_corners(Some(i), None),
+            fpQuad.rotated_corners(Some(i), Some(mirrored)),
         )?;
 
         let check = |i: usize, on: bool| {
@@ -1242,6 +1244,7 @@ pub fn SampleRMQR(image: &BitMatrix, fp: ConcentricPattern) -> Result<QRCodeDete
         if fi.hammingDistance < bestFI.hammingDistance {
             bestFI = fi;
             bestPT = mod2Pix;
+            bestMirrored = mirrored;
         }
     }
 
@@ -1336,7 +1339,11 @@ pub fn SampleRMQR(image: &BitMatrix, fp: ConcentricPattern) -> Result<QRCodeDete
         if let Some(spQuad) = FindConcentricPatternCorners(image, found, fp.size / 2, 1) {
             // if (auto spQuad = FindConcentricPatternCorners(image, *found, fp.size / 2, 1)) {
             let mut dest = intersectQuads(&fpQuad, &spQuad)?;
-            if dim.y <= 9 {
+            if bestMirrored {
+                // the corners follow the image, the other way round to the symbol
+                dest.0.swap(1, 3);
+            }
+            if dim.y <= 9 && !bestMirrored {
                 bestPT = PerspectiveTransform::quadrilateralToQuadrilateral(
                     Quadrilateral::from([
                         point(6.5, 0.5),
@@ -1353,7 +1360,7 @@ pub fn SampleRMQR(image: &BitMatrix, fp: ConcentricPattern) -> Result<QRCodeDete
                 )?;
             // bestPT = PerspectiveTransform({{6.5, 0.5}, {dim.x - 1.5, dim.y - 3.5}, {dim.x - 1.5, dim.y - 1.5}, {6.5, 6.5}},
             // 							  {fpQuad->topRight(), spQuad->topRight(), spQuad->bottomRight(), fpQuad->bottomRight()});
-            } else {
+            } else if dim.y > 9 {
                 dest[0] = fp.p;
                 dest[2] = found;
                 bestPT = PerspectiveTransform::quadrilateralToQuadrilateral(
@@ -1380,6 +1387,25 @@ pub fn SampleRMQR(image: &BitMatrix, fp: ConcentricPattern) -> Result<QRCodeDete
             transform: bestPT,
         }],
     )?;
+    // pass a mirrored symbol on transposed, as it is for a mirrored QR Code, for the decoder to
+    // read it mirrored and tell so
+    let sample = if bestMirrored {
+        Transposed(&sample)?
+    } else {
+        sample
+    };
     Ok(QRCodeDetectorResult::new(sample, rps.to_vec()))
     //  SampleGrid(image, dim.x, dim.y, bestPT)
 }
+
+fn Transposed(bits: &BitMatrix) -> Result<BitMatrix> {
+    let mut transposed = BitMatrix::new(bits.height(), bits.width())?;
+    for y in 0..bits.height() {
+        for x in 0..bits.width() {
+            if bits.get(x, y) {
+                transposed.set(y, x);
+            }
+        }
+    }
+    Ok(transposed)
+}
@@ -118,20 +118,26 @@
 // } // namespace ZXing::QRCode
 
 use crate::{
-    common::{cpp_essentials::ConcentricPattern, DetectorRXingResult},
+    common::{
+        cpp_essentials::{ConcentricPattern, DecoderResult},
+        DetectorRXingResult, Result,
+    },
+    decode_region::{decode_in_region, decode_multiple_in_region},
     multi::MultipleBarcodeReader,
-    BarcodeFormat, DecodeHints, Exceptions, ImmutableReader, RXingResult, Reader,
+    qrcode::detector::QRCodeDetectorResult,
+    BarcodeFormat, DecodeHints, EstimatedModuleSize, Exceptions, FinderPatternKind,
+    ImmutableReader, RXingResult, RXingResultMetadataType, RXingResultMetadataValue, Reader,
 };
 
 use super::{
-    decoder::Decode,
+    decoder::DecodeWithHints,
     detector::{
         DetectPureMQR, DetectPureQR, DetectPureRMQR, FindFinderPatterns, GenerateFinderPatternSets,
         SampleMQR, SampleQR, SampleRMQR,
     },
 };
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct QrReader;
 
 impl Reader for QrReader {
@@ -147,7 +153,9 @@ impl Reader for QrReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> crate::common::Result<RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -157,7 +165,9 @@ impl ImmutableReader for QrReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> crate::common::Result<RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -174,7 +184,9 @@ impl MultipleBarcodeReader for QrReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> crate::common::Result<Vec<crate::RXingResult>> {
-        self.decode_set_number_with_hints(image, hints, u32::MAX)
+        decode_multiple_in_region(image, hints, |image, hints| {
+            self.decode_set_number_with_hints(image, hints, u32::MAX)
+        })
     }
 }
 
@@ -196,6 +208,16 @@ impl QrReader {
         let try_harder = hints.TryHarder.unwrap_or(false);
 
         let mut allFPs = FindFinderPatterns(binImg, try_harder);
+        allFPs.retain(|fp| hints.in_region(fp.p));
+        if let Some(diagnostics) = &hints.Diagnostics {
+            for fp in &allFPs {
+                diagnostics.finder_pattern(
+                    FinderPatternKind::Concentric,
+                    fp.p,
+                    fp.size as f32 / 7.0,
+                );
+            }
+        }
 
         // #ifdef PRINT_DEBUG
         // 	printf("allFPs: %d\n", Size(allFPs));
@@ -228,10 +250,15 @@ impl QrReader {
 
                 // logFPSet(fpSet);
 
-                let detectorResult = SampleQR(binImg, &fpSet);
+                let detectorResult = hints.record_rejection(
+                    BarcodeFormat::QR_CODE,
+                    Some(fpSet.tl.p),
+                    SampleQR(binImg, &fpSet),
+                );
                 if let Ok(detectorResult) = detectorResult {
                     // if (detectorResult.is_ok()) {
-                    let decoderResult = Decode(detectorResult.getBits());
+                    let decoderResult =
+                        decode_candidate(hints, BarcodeFormat::QR_CODE, &detectorResult);
                     let position = detectorResult.getPoints();
                     if let Ok(decoderResult) = decoderResult {
                         if decoderResult.isValid() {
@@ -240,11 +267,14 @@ impl QrReader {
                             usedFPs.push(fpSet.tr);
                         }
 
-                        if decoderResult.isValid() {
-                            results.push(RXingResult::with_decoder_result(
-                                decoderResult,
-                                position,
-                                BarcodeFormat::QR_CODE,
+                        if decoderResult.isValid() && hints.accepts_symbol(position) {
+                            results.push(with_module_size(
+                                RXingResult::with_decoder_result(
+                                    decoderResult,
+                                    position,
+                                    BarcodeFormat::QR_CODE,
+                                ),
+                                &detectorResult,
                             ));
 
                             if maxSymbols != 0 && (results.len() as u32) == maxSymbols {
@@ -263,17 +293,25 @@ impl QrReader {
                     continue;
                 }
 
-                let detectorResult = SampleMQR(binImg, *fp);
+                let detectorResult = hints.record_rejection(
+                    BarcodeFormat::MICRO_QR_CODE,
+                    Some(fp.p),
+                    SampleMQR(binImg, *fp),
+                );
                 if let Ok(detectorResult) = detectorResult {
                     // if (detectorResult.is_ok()) {
-                    let decoderResult = Decode(detectorResult.getBits());
+                    let decoderResult =
+                        decode_candidate(hints, BarcodeFormat::MICRO_QR_CODE, &detectorResult);
                     let position = detectorResult.getPoints();
                     if let Ok(decoderResult) = decoderResult {
-                        if decoderResult.isValid() {
-                            results.push(RXingResult::with_decoder_result(
-                                decoderResult,
-                                position,
-                                BarcodeFormat::MICRO_QR_CODE,
+                        if decoderResult.isValid() && hints.accepts_symbol(position) {
+                            results.push(with_module_size(
+                                RXingResult::with_decoder_result(
+                                    decoderResult,
+                                    position,
+                                    BarcodeFormat::MICRO_QR_CODE,
+                                ),
+                                &detectorResult,
                             ));
 
                             if maxSymbols != 0 && (results.len() as u32) == maxSymbols {
@@ -291,17 +329,28 @@ impl QrReader {
                     continue;
                 }
 
-                let detectorResult = SampleRMQR(binImg, *fp);
+                let detectorResult = hints.record_rejection(
+                    BarcodeFormat::RECTANGULAR_MICRO_QR_CODE,
+                    Some(fp.p),
+                    SampleRMQR(binImg, *fp),
+                );
                 if let Ok(detectorResult) = detectorResult {
                     // if (detectorResult.is_ok()) {
-                    let decoderResult = Decode(detectorResult.getBits());
+                    let decoderResult = decode_candidate(
+                        hints,
+                        BarcodeFormat::RECTANGULAR_MICRO_QR_CODE,
+                        &detectorResult,
+                    );
                     let position = detectorResult.getPoints();
                     if let Ok(decoderResult) = decoderResult {
-                        if decoderResult.isValid() {
-                            results.push(RXingResult::with_decoder_result(
-                                decoderResult,
-                                position,
-                                BarcodeFormat::RECTANGULAR_MICRO_QR_CODE,
+                        if decoderResult.isValid() && hints.accepts_symbol(position) {
+                            results.push(with_module_size(
+                                RXingResult::with_decoder_result(
+                                    decoderResult,
+                                    position,
+                                    BarcodeFormat::RECTANGULAR_MICRO_QR_CODE,
+                                ),
+                                &detectorResult,
                             ));
 
                             if maxSymbols != 0 && (results.len() as u32) == maxSymbols {
@@ -363,7 +412,8 @@ impl QrReader {
             }
         }
 
-        let detectorResult = detectorResult?;
+        let detectorResult =
+            hints.record_rejection(BarcodeFormat::QR_CODE, None, detectorResult)?;
 
         // let detectorResult: DetectorResult;
         // if (_hints.hasFormat(BarcodeFormat::QR_CODE))
@@ -375,19 +425,65 @@ impl QrReader {
         // if (!detectorResult.isValid())
         // 	{return {};}
 
-        let decoderResult = Decode(detectorResult.getBits())?;
+        let format = if detectorResult.getBits().width() != detectorResult.getBits().height() {
+            BarcodeFormat::RECTANGULAR_MICRO_QR_CODE
+        } else if detectorResult.getBits().width() < 21 {
+            BarcodeFormat::MICRO_QR_CODE
+        } else {
+            BarcodeFormat::QR_CODE
+        };
+        let decoderResult = decode_candidate(hints, format, &detectorResult)?;
         let position = detectorResult.getPoints();
 
-        Ok(RXingResult::with_decoder_result(
-            decoderResult,
-            position,
-            if detectorResult.getBits().width() != detectorResult.getBits().height() {
-                BarcodeFormat::RECTANGULAR_MICRO_QR_CODE
-            } else if detectorResult.getBits().width() < 21 {
-                BarcodeFormat::MICRO_QR_CODE
-            } else {
-                BarcodeFormat::QR_CODE
-            },
-        ))
+        let result = RXingResult::with_decoder_result(decoderResult, position, format);
+        Ok(with_module_size(result, &detectorResult))
+    }
+}
+
+/// Decodes a sampled symbol, recording its stages with the diagnostics of the `hints`, if any,
+/// and why it was rejected if it does not decode, and collecting what was read with their best
+/// effort collector, if any, if error correction fails.
+fn decode_candidate(
+    hints: &DecodeHints,
+    format: BarcodeFormat,
+    detectorResult: &QRCodeDetectorResult,
+) -> Result<DecoderResult<bool>> {
+    let diagnostics = hints.Diagnostics.as_ref();
+    let location = detectorResult.getPoints().first().copied();
+    if let Some(diagnostics) = diagnostics {
+        diagnostics.quadrilateral(format, detectorResult.getPoints());
+        diagnostics.sampled_grid(format, detectorResult.getBits());
+    }
+    let decoderResult = hints.record_rejection(
+        format,
+        location,
+        DecodeWithHints(
+            detectorResult.getBits(),
+            &hints.located(detectorResult.getPoints()),
+        ),
+    )?;
+    if let (Some(diagnostics), false) = (diagnostics, decoderResult.isValid()) {
+        match decoderResult.error() {
+            Some(error) => diagnostics.rejection(format, location, error),
+            None => diagnostics.rejection(format, location, "no content"),
+        }
+    }
+    Ok(decoderResult)
+}
+
+/// Adds the module size, estimated from the corners of the sampled symbol, to `result`.
+fn with_module_size(
+    mut result: RXingResult,
+    detectorResult: &impl DetectorRXingResult,
+) -> RXingResult {
+    let bits = detectorResult.getBits();
+    if let Some(moduleSize) =
+        EstimatedModuleSize::from_corners(detectorResult.getPoints(), bits.width(), bits.height())
+    {
+        result.putMetadata(
+            RXingResultMetadataType::MODULE_SIZE,
+            RXingResultMetadataValue::ModuleSize(moduleSize),
+        );
     }
+    result
 }
@@ -0,0 +1,73 @@
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::HybridBinarizer, qrcode::cpp_port::QrReader, BarcodeFormat, BinaryBitmap,
+    BufferedImageLuminanceSource, DecodeHints, RXingResultMetadataType, RXingResultMetadataValue,
+    Reader,
+};
+
+fn DecodeFlipped(
+    path: &str,
+    format: BarcodeFormat,
+    flip: fn(&image::DynamicImage) -> image::DynamicImage,
+) {
+    let image = image::open(format!("test_resources/blackbox/cpp/{path}.png")).unwrap();
+    let expected =
+        std::fs::read_to_string(format!("test_resources/blackbox/cpp/{path}.txt")).unwrap();
+
+    for (image, mirrored) in [(flip(&image), true), (image, false)] {
+        let result = QrReader
+            .decode_with_hints(
+                &mut BinaryBitmap::new(HybridBinarizer::new(BufferedImageLuminanceSource::new(
+                    image,
+                ))),
+                &DecodeHints::default().with(crate::DecodeHintValue::TryHarder(true)),
+            )
+            .unwrap();
+        assert_eq!(result.getBarcodeFormat(), &format);
+        assert_eq!(result.getText(), expected.trim_end_matches('\n'));
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::IS_MIRRORED),
+            Some(&RXingResultMetadataValue::IsMirrored(mirrored))
+        );
+    }
+}
+
+#[test]
+fn QRCodeMirrored() {
+    DecodeFlipped(
+        "qrcode-2/1",
+        BarcodeFormat::QR_CODE,
+        image::DynamicImage::fliph,
+    );
+    DecodeFlipped(
+        "qrcode-2/1",
+        BarcodeFormat::QR_CODE,
+        image::DynamicImage::flipv,
+    );
+}
+
+#[test]
+fn MQRCodeMirrored() {
+    DecodeFlipped(
+        "microqrcode-1/1",
+        BarcodeFormat::MICRO_QR_CODE,
+        image::DynamicImage::fliph,
+    );
+}
+
+#[test]
+fn RMQRCodeMirrored() {
+    DecodeFlipped(
+        "rmqrcode-1/R17x139",
+        BarcodeFormat::RECTANGULAR_MICRO_QR_CODE,
+        image::DynamicImage::fliph,
+    );
+    DecodeFlipped(
+        "rmqrcode-1/R7x43-H",
+        BarcodeFormat::RECTANGULAR_MICRO_QR_CODE,
+        image::DynamicImage::flipv,
+    );
+}
@@ -248,3 +248,35 @@ XXX X X X X X X X X X XXX X X X X X X X X X X X XXX X X X X X X X X X X X XXX X
         "1234567890ABCDEFGHIJKLMNOPQRSTUVWXYZ1234567890________________________"
     );
 }
+
+#[test]
+fn RMQRCodeR7x43MMirrored() {
+    let bitMatrix = BitMatrix::parse_strings(
+        r"XXXXXXX X X X X X X XXX X X X X X X X X XXX
+X     X  X XXX  XXXXX XXX      X X XX   X X
+X XXX X X XXX X X X XXXX XXXX X  X XXXXXXXX
+X XXX X  XX    XXXXX   XXXXXX   X X   X   X
+X XXX X   XX  XXX   XXXXXXX  X X  XX  X X X
+X     X XXXXX XXX XXX XXXXX    XXXXXX X   X
+XXXXXXX X X X X X X XXX X X X X X X X XXXXX
+",
+        "X",
+        " ",
+    )
+    .unwrap();
+
+    // a mirrored symbol is sampled transposed
+    let mut mirrored = BitMatrix::new(bitMatrix.height(), bitMatrix.width()).unwrap();
+    for y in 0..bitMatrix.height() {
+        for x in 0..bitMatrix.width() {
+            if bitMatrix.get(x, y) {
+                mirrored.set(y, x);
+            }
+        }
+    }
+
+    let result = Decode(&mirrored).unwrap();
+    assert_eq!(result.text(), "ABCDEFG");
+    assert!(result.isMirrored());
+    assert!(!Decode(&bitMatrix).unwrap().isMirrored());
+}
@@ -14,3 +14,6 @@ mod QRBitMatrixParserTest;
 mod MQRDecoderTest;
 
 mod RMQRDecoderTest;
+
+#[cfg(feature = "image_formats")]
+mod QRMirroredTest;
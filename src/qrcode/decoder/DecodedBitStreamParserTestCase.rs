@@ -15,7 +15,7 @@
  */
 
 use crate::{
-    common::BitSourceBuilder,
+    common::{BitSourceBuilder, Eci},
     qrcode::decoder::{decoded_bit_stream_parser, ErrorCorrectionLevel, Version},
     DecodeHints,
 };
@@ -84,10 +84,9 @@ fn testECI() {
         ErrorCorrectionLevel::H,
         &DecodeHints::default(),
     )
-    .expect("unwrap")
-    .getText()
-    .to_owned();
-    assert_eq!("\u{00ed}\u{00f3}\u{00fa}", result);
+    .expect("unwrap");
+    assert_eq!("\u{00ed}\u{00f3}\u{00fa}", result.getText());
+    assert_eq!(Some(Eci::Cp437), result.getEci());
 }
 
 #[test]
@@ -59,6 +59,7 @@ pub fn decode(
     let mut parityData = -1;
 
     let mut currentCharacterSetECI = None;
+    let mut firstECI = None;
     let mut fc1InEffect = false;
     let mut hasFNC1first = false;
     let mut hasFNC1second = false;
@@ -104,6 +105,9 @@ pub fn decode(
                         "Value of {value} not valid"
                     )));
                 }
+                if value != Eci::Unknown {
+                    firstECI.get_or_insert(value);
+                }
             }
             Mode::HANZI => {
                 // First handle Hanzi mode which does not start with character count
@@ -155,7 +159,7 @@ pub fn decode(
         hasFNC1second,
     );
 
-    Ok(DecoderRXingResult::with_all(
+    let mut decoderResult = DecoderRXingResult::with_all(
         bytes.to_owned(),
         result.build_result().to_string(),
         byteSegments.to_vec(),
@@ -165,7 +169,10 @@ pub fn decode(
         symbologyModifier,
         String::default(),
         false,
-    ))
+    );
+    decoderResult.setEci(firstECI);
+
+    Ok(decoderResult)
 }
 
 fn get_symbology_identifier(has_charset: bool, hasFNC1first: bool, hasFNC1second: bool) -> u32 {
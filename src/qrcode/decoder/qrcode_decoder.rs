@@ -29,7 +29,7 @@ use crate::{
         reedsolomon::{get_predefined_genericgf, PredefinedGenericGF, ReedSolomonDecoder},
         BitMatrix, DecoderRXingResult, Result,
     },
-    DecodeHints, Exceptions,
+    BarcodeFormat, CodewordRecorder, DecodeHints, Exceptions,
 };
 
 use super::{decoded_bit_stream_parser, BitMatrixParser, DataBlock, QRCodeDecoderMetaData};
@@ -83,7 +83,7 @@ pub fn decode_bitmatrix_with_hints(
     let mut parser = BitMatrixParser::new(bits.clone())?;
     let mut fe = None;
     let mut ce = None;
-    match decode_bitmatrix_parser_with_hints(&mut parser, hints) {
+    match decode_bitmatrix_parser_with_hints(&mut parser, hints, false) {
         Ok(ok) => return Ok(ok),
         Err(er) => match er {
             Exceptions::FormatException(_) => fe = Some(er),
@@ -114,7 +114,7 @@ pub fn decode_bitmatrix_with_hints(
         // Prepare for a mirrored reading.
         parser.mirror();
 
-        let mut result = decode_bitmatrix_parser_with_hints(&mut parser, hints)?;
+        let mut result = decode_bitmatrix_parser_with_hints(&mut parser, hints, true)?;
 
         // Success! Notify the caller that the code was mirrored.
         result.setOther(Some(Arc::new(QRCodeDecoderMetaData::new(true))));
@@ -140,6 +140,7 @@ pub fn decode_bitmatrix_with_hints(
 fn decode_bitmatrix_parser_with_hints(
     parser: &mut BitMatrixParser,
     hints: &DecodeHints,
+    mirrored: bool,
 ) -> Result<DecoderRXingResult> {
     let version = parser.readVersion()?;
     let ecLevel = parser.readFormatInformation()?.getErrorCorrectionLevel();
@@ -156,20 +157,48 @@ fn decode_bitmatrix_parser_with_hints(
 
     let mut resultBytes = vec![0u8; totalBytes];
     let mut resultOffset = 0;
+    let mut errorsCorrected = 0;
+    let mut recorder = CodewordRecorder::new(hints, BarcodeFormat::QR_CODE);
+    let mut failure = None;
 
     // Error-correct and copy data blocks together into a stream of bytes
     for dataBlock in &dataBlocks {
         let mut codewordBytes = dataBlock.getCodewords().to_vec();
         let numDataCodewords = dataBlock.getNumDataCodewords() as usize;
-        correctErrors(&mut codewordBytes, numDataCodewords)?;
+        recorder.received(numDataCodewords, &codewordBytes);
+        match correctErrors(&mut codewordBytes, numDataCodewords) {
+            Ok(errors) => {
+                errorsCorrected += errors;
+                recorder.corrected(&codewordBytes);
+            }
+            // go on with the codewords as they were read
+            Err(e) if recorder.best_effort() => failure = failure.or(Some(e)),
+            Err(e) => return Err(e),
+        }
         for codeword_byte in codewordBytes.iter().take(numDataCodewords) {
             resultBytes[resultOffset] = *codeword_byte;
             resultOffset += 1;
         }
     }
+    if let Some(e) = failure {
+        // a mirrored reading of a symbol that is not mirrored fails as well
+        if !mirrored {
+            recorder.failed(resultBytes, move |bytes| {
+                Ok(
+                    decoded_bit_stream_parser::decode(bytes, version, ecLevel, hints)?
+                        .getText()
+                        .to_owned(),
+                )
+            });
+        }
+        return Err(e);
+    }
 
     // Decode the contents of that stream of bytes
-    decoded_bit_stream_parser::decode(&resultBytes, version, ecLevel, hints)
+    let mut result = decoded_bit_stream_parser::decode(&resultBytes, version, ecLevel, hints)?;
+    result.setErrorsCorrected(errorsCorrected);
+    result.setErrorCorrectionCapacity(codewords.len() - totalBytes);
+    Ok(result)
 }
 
 /**
@@ -178,9 +207,10 @@ fn decode_bitmatrix_parser_with_hints(
  *
  * @param codewordBytes data and error correction codewords
  * @param numDataCodewords number of codewords that are data bytes
+ * @return the number of errors corrected
  * @throws ChecksumException if error correction fails
  */
-fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<()> {
+fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<usize> {
     let numCodewords = codewordBytes.len();
     // First read into an array of ints
     let mut codewordsInts = vec![0u8; numCodewords];
@@ -188,12 +218,16 @@ fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<()
 
     let mut sending_code_words: Vec<i32> = codewordsInts.iter().map(|x| *x as i32).collect();
 
-    if let Err(Exceptions::ReedSolomonException(error_str)) = RS_DECODER.decode(
+    let errorsCorrected = match RS_DECODER.decode(
         &mut sending_code_words,
         (codewordBytes.len() - numDataCodewords) as i32,
     ) {
-        return Err(Exceptions::ChecksumException(error_str));
-    }
+        Ok(errorsCorrected) => errorsCorrected,
+        Err(Exceptions::ReedSolomonException(error_str)) => {
+            return Err(Exceptions::ChecksumException(error_str))
+        }
+        Err(_) => 0,
+    };
 
     // Copy back into array of bytes -- only need to worry about the bytes that were data
     // We don't care about errors in the error-correction codewords
@@ -205,5 +239,5 @@ fn correctErrors(codewordBytes: &mut [u8], numDataCodewords: usize) -> Result<()
         *code_word = *sent_code_word as u8;
     }
 
-    Ok(())
+    Ok(errorsCorrected)
 }
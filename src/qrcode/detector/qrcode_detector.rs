@@ -21,7 +21,7 @@ use crate::{
     },
     point,
     qrcode::decoder::Version,
-    DecodeHints, Exceptions, Point, PointCallback,
+    BarcodeFormat, DecodeHints, Diagnostics, Exceptions, FinderPatternKind, Point, PointCallback,
 };
 
 use super::{
@@ -38,6 +38,7 @@ use super::{
 pub struct Detector<'a> {
     image: &'a BitMatrix,
     resultPointCallback: Option<PointCallback>,
+    diagnostics: Option<Diagnostics>,
 }
 
 impl<'a> Detector<'_> {
@@ -45,6 +46,7 @@ impl<'a> Detector<'_> {
         Detector {
             image,
             resultPointCallback: None,
+            diagnostics: None,
         }
     }
 
@@ -77,10 +79,21 @@ impl<'a> Detector<'_> {
      */
     pub fn detect_with_hints(&mut self, hints: &DecodeHints) -> Result<QRCodeDetectorResult> {
         self.resultPointCallback = hints.NeedResultPointCallback.clone();
+        self.diagnostics = hints.Diagnostics.clone();
 
         let mut finder =
             FinderPatternFinder::with_callback(self.image, self.resultPointCallback.clone());
-        let info = finder.find(hints)?;
+        let info = finder.find(hints);
+        if let Some(diagnostics) = &self.diagnostics {
+            for center in finder.getPossibleCenters() {
+                diagnostics.finder_pattern(
+                    FinderPatternKind::QrCode,
+                    center.point,
+                    center.getEstimatedModuleSize(),
+                );
+            }
+        }
+        let info = info?;
 
         self.processFinderPatternInfo(info)
     }
@@ -139,7 +152,24 @@ impl<'a> Detector<'_> {
         )
         .ok_or(Exceptions::NOT_FOUND)?;
 
+        if let Some(diagnostics) = &self.diagnostics {
+            let size = dimension as f32;
+            let corners = [
+                point(0.0, 0.0),
+                point(size, 0.0),
+                point(size, size),
+                point(0.0, size),
+            ];
+            diagnostics.quadrilateral(
+                BarcodeFormat::QR_CODE,
+                &corners.map(|p| transform.transform_point(p)),
+            );
+        }
+
         let bits = Detector::sampleGrid(self.image, transform, dimension)?;
+        if let Some(diagnostics) = &self.diagnostics {
+            diagnostics.sampled_grid(BarcodeFormat::QR_CODE, &bits);
+        }
 
         let mut points = vec![
             Point::from(bottomLeft),
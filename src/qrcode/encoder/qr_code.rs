@@ -32,6 +32,7 @@ pub struct QRCode {
     version: Option<VersionRef>,
     maskPattern: i32,
     matrix: Option<ByteMatrix>,
+    numDataBits: u32,
 }
 
 impl QRCode {
@@ -44,6 +45,7 @@ impl QRCode {
             version: None,
             maskPattern: -1,
             matrix: None,
+            numDataBits: 0,
         }
     }
 
@@ -70,6 +72,13 @@ impl QRCode {
         &self.matrix
     }
 
+    /**
+     * @return the number of header and data bits, before the terminator and padding
+     */
+    pub fn getNumDataBits(&self) -> u32 {
+        self.numDataBits
+    }
+
     pub fn setMode(&mut self, value: Mode) {
         self.mode = Some(value);
     }
@@ -90,6 +99,10 @@ impl QRCode {
         self.matrix = Some(value);
     }
 
+    pub fn setNumDataBits(&mut self, value: u32) {
+        self.numDataBits = value;
+    }
+
     // Check if "mask_pattern" is valid.
     pub fn isValidMaskPattern(maskPattern: i32) -> bool {
         (0..Self::NUM_MASK_PATTERNS).contains(&maskPattern)
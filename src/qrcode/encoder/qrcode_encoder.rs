@@ -175,6 +175,8 @@ pub fn encode_with_hints(
     let ec_blocks = version.getECBlocksForLevel(ec_level);
     let num_data_bytes = version.getTotalCodewords() - ec_blocks.getTotalECCodewords();
 
+    let num_data_bits = header_and_data_bits.get_size() as u32;
+
     // Terminate the bits properly.
     terminateBits(num_data_bytes, &mut header_and_data_bits)?;
 
@@ -191,6 +193,7 @@ pub fn encode_with_hints(
     qrCode.setECLevel(ec_level);
     qrCode.setMode(mode);
     qrCode.setVersion(version);
+    qrCode.setNumDataBits(num_data_bits);
 
     //  Choose the mask pattern and set to "qrCode".
     let dimension = version.getDimensionForVersion();
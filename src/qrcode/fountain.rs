@@ -0,0 +1,588 @@
+/*!
+ * Carries a byte payload over a stream of QR codes, for example an animation shown on one screen
+ * and filmed by the camera of an air-gapped device.
+ *
+ * The payload is cut into chunks of equal size. The first frames carry the chunks themselves and
+ * every later frame carries the XOR of a pseudo-random subset of them, so the frames form a
+ * fountain code: any set of frames whose subsets are independent rebuilds the payload, whichever
+ * frames were missed. In practice that takes the number of chunks plus one or two frames.
+ *
+ * Each frame is a 16 byte header followed by one chunk, written in Base45 (RFC 9285) so that it
+ * fits the alphanumeric mode of QR codes and reads back unchanged as text:
+ *
+ * | bytes | field                                          |
+ * |-------|------------------------------------------------|
+ * | 2     | `RF`                                           |
+ * | 4     | message id, the CRC-32 of the payload          |
+ * | 4     | payload length in bytes                        |
+ * | 2     | chunk size in bytes                            |
+ * | 4     | frame index                                    |
+ *
+ * All numbers are big endian. A payload is cut into at most 4096 chunks, which keeps what a
+ * receiver has to hold in memory bounded whatever the headers it reads.
+ */
+
+use crate::{
+    common::{BitMatrix, Result},
+    BarcodeFormat, EncodeHintValue, EncodeHints, Exceptions, RXingResult, Writer,
+};
+
+use super::{decoder::ErrorCorrectionLevel, QRCodeWriter};
+
+#[cfg(feature = "image")]
+use crate::render::RenderOptions;
+
+const MAGIC: &[u8; 2] = b"RF";
+const HEADER_SIZE: usize = 16;
+/// The largest number of chunks of a payload. Every frame a collector keeps has one bit per chunk.
+const MAX_CHUNK_COUNT: usize = 4096;
+
+const BASE45_ALPHABET: &[u8; 45] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ $%*+-./:";
+
+/// How a payload is cut into frames.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FountainOptions {
+    /// Bytes of payload per frame. Larger chunks need fewer frames and larger QR versions.
+    pub chunk_size: u16,
+    /// Extra frames on top of the number of chunks, as a fraction of it.
+    pub redundancy: f32,
+    /// Error correction level of every frame.
+    pub ec_level: ErrorCorrectionLevel,
+    /// Quiet zone around every frame, in modules.
+    pub quiet_zone: u32,
+}
+
+impl Default for FountainOptions {
+    fn default() -> Self {
+        Self {
+            chunk_size: 256,
+            redundancy: 0.5,
+            ec_level: ErrorCorrectionLevel::M,
+            quiet_zone: 4,
+        }
+    }
+}
+
+/// Cuts a payload into fountain coded QR frames.
+pub struct FountainEncoder {
+    chunks: Vec<Vec<u8>>,
+    message_id: u32,
+    length: u32,
+    options: FountainOptions,
+}
+
+impl FountainEncoder {
+    pub fn new(payload: &[u8], options: FountainOptions) -> Result<Self> {
+        if options.chunk_size == 0 {
+            return Err(Exceptions::illegal_argument_with(
+                "chunk size must be at least one byte",
+            ));
+        }
+        if options.redundancy.is_nan() || options.redundancy < 0.0 {
+            return Err(Exceptions::illegal_argument_with(
+                "redundancy must not be negative",
+            ));
+        }
+        let length = u32::try_from(payload.len())
+            .map_err(|_| Exceptions::illegal_argument_with("payload too large"))?;
+
+        let chunk_size = options.chunk_size as usize;
+        let mut chunks: Vec<Vec<u8>> = payload.chunks(chunk_size).map(<[u8]>::to_vec).collect();
+        if chunks.is_empty() {
+            chunks.push(Vec::new());
+        }
+        if chunks.len() > MAX_CHUNK_COUNT {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "payload needs more than {MAX_CHUNK_COUNT} chunks, use larger chunks"
+            )));
+        }
+        // the last chunk is padded with zeros
+        for chunk in &mut chunks {
+            chunk.resize(chunk_size, 0);
+        }
+
+        Ok(Self {
+            chunks,
+            message_id: crc32(payload),
+            length,
+            options,
+        })
+    }
+
+    /// The identifier shared by all frames of this payload.
+    pub fn message_id(&self) -> u32 {
+        self.message_id
+    }
+
+    /// The number of chunks, which is the least number of frames a receiver needs.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    /// The number of frames of `frames`: the chunks and the redundant frames.
+    pub fn frame_count(&self) -> usize {
+        let chunks = self.chunks.len();
+        chunks + (chunks as f32 * self.options.redundancy).ceil() as usize
+    }
+
+    /// The text of the frame at `index`. Any index gives a valid frame, so a sender can keep
+    /// going past `frame_count` until the receiver has what it needs.
+    pub fn frame_contents(&self, index: u32) -> String {
+        let mut frame = Vec::with_capacity(HEADER_SIZE + self.options.chunk_size as usize);
+        frame.extend_from_slice(MAGIC);
+        frame.extend_from_slice(&self.message_id.to_be_bytes());
+        frame.extend_from_slice(&self.length.to_be_bytes());
+        frame.extend_from_slice(&self.options.chunk_size.to_be_bytes());
+        frame.extend_from_slice(&index.to_be_bytes());
+
+        let mut chunk = vec![0; self.options.chunk_size as usize];
+        for (i, selected) in frame_chunks(self.message_id, index, self.chunks.len())
+            .into_iter()
+            .enumerate()
+        {
+            if selected {
+                xor_into(&mut chunk, &self.chunks[i]);
+            }
+        }
+        frame.extend_from_slice(&chunk);
+
+        base45_encode(&frame)
+    }
+
+    /// The QR code of the frame at `index`, one pixel per module, with the quiet zone.
+    pub fn encode_frame(&self, index: u32) -> Result<BitMatrix> {
+        let hints = EncodeHints::default().with(EncodeHintValue::ErrorCorrection(format!(
+            "{:?}",
+            self.options.ec_level
+        )));
+        QRCodeWriter.encode_modules(
+            &self.frame_contents(index),
+            &BarcodeFormat::QR_CODE,
+            self.options.quiet_zone,
+            &hints,
+        )
+    }
+
+    /// The QR codes of the first `frame_count` frames.
+    pub fn encode_frames(&self) -> Result<Vec<BitMatrix>> {
+        (0..self.frame_count() as u32)
+            .map(|index| self.encode_frame(index))
+            .collect()
+    }
+
+    /**
+     * Saves the frames as an animated GIF, showing each for `frame_delay_ms`, when `file_name`
+     * ends in `.gif`. Otherwise each frame goes to its own file, with the frame number added to
+     * the file name: `frames.png` becomes `frames-0000.png`, `frames-0001.png`, ...
+     */
+    #[cfg(feature = "image")]
+    pub fn save(
+        &self,
+        file_name: &str,
+        options: &RenderOptions,
+        frame_delay_ms: u32,
+    ) -> Result<()> {
+        let frames = self.encode_frames()?;
+        let path = std::path::Path::new(file_name);
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if extension == "gif" {
+            return crate::render::save_animated_gif(file_name, &frames, options, frame_delay_ms);
+        }
+
+        let stem = path.with_extension("");
+        for (index, frame) in frames.iter().enumerate() {
+            let frame_name = format!("{}-{index:04}.{extension}", stem.to_string_lossy());
+            crate::render::save_raster(&frame_name, crate::render::render(frame, options)?)?;
+        }
+        Ok(())
+    }
+}
+
+/// A frame reduced against the frames received before it.
+struct Equation {
+    chunks: Vec<bool>,
+    data: Vec<u8>,
+}
+
+/**
+ * Rebuilds a payload from the frames of a `FountainEncoder`, decoded in any order, with
+ * repeated and missing frames.
+ */
+#[derive(Default)]
+pub struct FountainCollector {
+    message_id: Option<u32>,
+    length: u32,
+    chunk_size: usize,
+    // indexed by the first chunk of each equation
+    equations: Vec<Option<Equation>>,
+    received: usize,
+}
+
+impl FountainCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Adds a frame read by `QRCodeReader`.
+     *
+     * @return whether the frame brought anything new
+     * @throws FormatException if the result is not a fountain frame, or belongs to another
+     *   payload than the frames before it
+     */
+    pub fn add(&mut self, result: &RXingResult) -> Result<bool> {
+        if result.getBarcodeFormat() != &BarcodeFormat::QR_CODE {
+            return Err(Exceptions::format_with("fountain frames are QR codes"));
+        }
+        self.add_contents(result.getText())
+    }
+
+    /// Adds the decoded text of a frame. See `add`.
+    pub fn add_contents(&mut self, contents: &str) -> Result<bool> {
+        let frame = base45_decode(contents)?;
+        if frame.len() < HEADER_SIZE || &frame[..2] != MAGIC {
+            return Err(Exceptions::format_with("not a fountain frame"));
+        }
+        let read_u32 = |offset: usize| {
+            u32::from_be_bytes([
+                frame[offset],
+                frame[offset + 1],
+                frame[offset + 2],
+                frame[offset + 3],
+            ])
+        };
+        let message_id = read_u32(2);
+        let length = read_u32(6);
+        let chunk_size = u16::from_be_bytes([frame[10], frame[11]]) as usize;
+        let index = read_u32(12);
+        let data = &frame[HEADER_SIZE..];
+        if chunk_size == 0 || data.len() != chunk_size {
+            return Err(Exceptions::format_with("fountain frame of the wrong size"));
+        }
+        let chunk_count = (length as usize).div_ceil(chunk_size).max(1);
+        if chunk_count > MAX_CHUNK_COUNT {
+            return Err(Exceptions::format_with(format!(
+                "fountain payload of more than {MAX_CHUNK_COUNT} chunks"
+            )));
+        }
+        // the last chunk is padded with zeros, so a frame carrying it alone shows the length
+        if index as usize == chunk_count - 1
+            && data[length as usize - (chunk_count - 1) * chunk_size..]
+                .iter()
+                .any(|b| *b != 0)
+        {
+            return Err(Exceptions::format_with(
+                "fountain frame does not match its payload length",
+            ));
+        }
+
+        match self.message_id {
+            None => {
+                self.message_id = Some(message_id);
+                self.length = length;
+                self.chunk_size = chunk_size;
+                self.equations = (0..chunk_count).map(|_| None).collect();
+            }
+            Some(id)
+                if id != message_id || length != self.length || chunk_size != self.chunk_size =>
+            {
+                return Err(Exceptions::format_with("fountain frame of another payload"));
+            }
+            Some(_) => {}
+        }
+
+        let mut equation = Equation {
+            chunks: frame_chunks(message_id, index, self.equations.len()),
+            data: data.to_vec(),
+        };
+        for chunk in 0..self.equations.len() {
+            if !equation.chunks[chunk] {
+                continue;
+            }
+            match &self.equations[chunk] {
+                Some(known) => {
+                    for (selected, other) in equation.chunks.iter_mut().zip(&known.chunks) {
+                        *selected ^= other;
+                    }
+                    xor_into(&mut equation.data, &known.data);
+                }
+                None => {
+                    self.equations[chunk] = Some(equation);
+                    self.received += 1;
+                    return Ok(true);
+                }
+            }
+        }
+        // the frame is a combination of frames we already have
+        Ok(false)
+    }
+
+    /// The number of independent frames received so far.
+    pub fn received(&self) -> usize {
+        self.received
+    }
+
+    /// The number of independent frames needed, once a first frame has been added.
+    pub fn needed(&self) -> Option<usize> {
+        self.message_id.map(|_| self.equations.len())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.message_id.is_some() && self.received == self.equations.len()
+    }
+
+    /**
+     * @return the payload, once enough frames were added
+     * @throws ChecksumException if the rebuilt payload does not match its message id
+     */
+    pub fn payload(&self) -> Result<Option<Vec<u8>>> {
+        if !self.is_complete() {
+            return Ok(None);
+        }
+
+        // every equation starts at its own chunk, solve them from the last one up
+        let chunk_count = self.equations.len();
+        let mut chunks: Vec<Vec<u8>> = vec![Vec::new(); chunk_count];
+        for chunk in (0..chunk_count).rev() {
+            let equation = self.equations[chunk]
+                .as_ref()
+                .ok_or(Exceptions::ILLEGAL_STATE)?;
+            let mut data = equation.data.clone();
+            for (other, solved) in chunks.iter().enumerate().skip(chunk + 1) {
+                if equation.chunks[other] {
+                    xor_into(&mut data, solved);
+                }
+            }
+            chunks[chunk] = data;
+        }
+
+        let mut payload = chunks.concat();
+        payload.truncate(self.length as usize);
+        if Some(crc32(&payload)) != self.message_id {
+            return Err(Exceptions::checksum_with(
+                "rebuilt payload does not match its checksum",
+            ));
+        }
+        Ok(Some(payload))
+    }
+}
+
+/**
+ * The chunks XORed into the frame at `index`. The first frames carry one chunk each, the later
+ * ones about half of the chunks, picked by a generator seeded with the message id and the index.
+ */
+fn frame_chunks(message_id: u32, index: u32, chunk_count: usize) -> Vec<bool> {
+    let mut chunks = vec![false; chunk_count];
+    if (index as usize) < chunk_count {
+        chunks[index as usize] = true;
+        return chunks;
+    }
+
+    // splitmix64
+    let mut state = ((message_id as u64) << 32) | index as u64;
+    let mut next = || {
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    };
+    for selected in chunks.iter_mut() {
+        *selected = next() & 1 == 1;
+    }
+    if !chunks.contains(&true) {
+        chunks[index as usize % chunk_count] = true;
+    }
+    chunks
+}
+
+fn xor_into(target: &mut [u8], source: &[u8]) {
+    for (t, s) in target.iter_mut().zip(source) {
+        *t ^= s;
+    }
+}
+
+/// CRC-32 as used by zip and PNG.
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn base45_encode(data: &[u8]) -> String {
+    let mut text = String::with_capacity(data.len().div_ceil(2) * 3);
+    for pair in data.chunks(2) {
+        let (mut value, digits) = match pair {
+            [a, b] => ((*a as u32) << 8 | *b as u32, 3),
+            [a] => (*a as u32, 2),
+            _ => unreachable!("chunks of two"),
+        };
+        for _ in 0..digits {
+            text.push(BASE45_ALPHABET[(value % 45) as usize] as char);
+            value /= 45;
+        }
+    }
+    text
+}
+
+fn base45_decode(text: &str) -> Result<Vec<u8>> {
+    let invalid = || Exceptions::format_with("not a fountain frame");
+    let values = text
+        .bytes()
+        .map(|c| {
+            BASE45_ALPHABET
+                .iter()
+                .position(|a| *a == c)
+                .map(|v| v as u32)
+                .ok_or_else(invalid)
+        })
+        .collect::<Result<Vec<u32>>>()?;
+
+    let mut data = Vec::with_capacity(values.len() / 3 * 2 + 1);
+    for group in values.chunks(3) {
+        match group {
+            [c, d, e] => {
+                let value = c + d * 45 + e * 45 * 45;
+                if value > 0xFFFF {
+                    return Err(invalid());
+                }
+                data.extend_from_slice(&(value as u16).to_be_bytes());
+            }
+            [c, d] => {
+                let value = c + d * 45;
+                if value > 0xFF {
+                    return Err(invalid());
+                }
+                data.push(value as u8);
+            }
+            _ => return Err(invalid()),
+        }
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod FountainTestCase {
+    use crate::{
+        common::{test_image::image, BitMatrix},
+        qrcode::QRCodeReader,
+        DecodeHintValue, DecodeHints, Exceptions, Reader,
+    };
+
+    use super::{
+        base45_decode, base45_encode, crc32, FountainCollector, FountainEncoder, FountainOptions,
+    };
+
+    fn read(matrix: &BitMatrix) -> crate::RXingResult {
+        // the frames are drawn without distortion, as they would be saved
+        QRCodeReader
+            .decode_with_hints(
+                &mut image(matrix, 3, 0),
+                &DecodeHints::default().with(DecodeHintValue::PureBarcode(true)),
+            )
+            .expect("frame decodes")
+    }
+
+    #[test]
+    fn testBase45() {
+        // RFC 9285, section 4.3
+        assert_eq!(base45_encode(b"AB"), "BB8");
+        assert_eq!(base45_encode(b"Hello!!"), "%69 VD92EX0");
+        assert_eq!(base45_decode("QED8WEX0").unwrap(), b"ietf!");
+        assert!(base45_decode("GGW").is_err());
+        assert!(base45_decode("a").is_err());
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn testRebuildFromAnySubset() {
+        let payload: Vec<u8> = (0..1000u32).map(|i| (i * 7 + i / 13) as u8).collect();
+        let options = FountainOptions {
+            chunk_size: 120,
+            ..Default::default()
+        };
+        let encoder = FountainEncoder::new(&payload, options).unwrap();
+        assert_eq!(encoder.chunk_count(), 9);
+        assert_eq!(encoder.frame_count(), 14);
+
+        let frames = encoder.encode_frames().unwrap();
+        let mut collector = FountainCollector::new();
+        // the receiver misses the first three chunks and sees one frame twice
+        for index in [5, 3, 8, 5, 9, 4, 12, 10, 6, 7, 11, 13] {
+            collector.add(&read(&frames[index])).unwrap();
+            if collector.is_complete() {
+                break;
+            }
+        }
+        assert_eq!(collector.needed(), Some(9));
+        assert!(collector.is_complete());
+        assert_eq!(collector.payload().unwrap(), Some(payload));
+
+        assert!(collector.add_contents("HELLO").is_err());
+        let other = FountainEncoder::new(b"other", FountainOptions::default()).unwrap();
+        assert!(collector.add_contents(&other.frame_contents(0)).is_err());
+    }
+
+    #[test]
+    fn testSmallPayloads() {
+        for payload in [&b""[..], &b"x"[..]] {
+            let encoder = FountainEncoder::new(payload, FountainOptions::default()).unwrap();
+            let mut collector = FountainCollector::new();
+            assert_eq!(collector.payload().unwrap(), None);
+            // a redundant frame of a single chunk is that chunk
+            assert!(collector.add_contents(&encoder.frame_contents(1)).unwrap());
+            assert!(!collector.add_contents(&encoder.frame_contents(0)).unwrap());
+            assert_eq!(collector.payload().unwrap(), Some(payload.to_vec()));
+        }
+    }
+    #[test]
+    fn testHostileHeaders() {
+        let frame = |length: u32, chunk_size: u16, index: u32, data: &[u8]| {
+            let mut frame = b"RF".to_vec();
+            frame.extend_from_slice(&0x1234_5678u32.to_be_bytes());
+            frame.extend_from_slice(&length.to_be_bytes());
+            frame.extend_from_slice(&chunk_size.to_be_bytes());
+            frame.extend_from_slice(&index.to_be_bytes());
+            frame.extend_from_slice(data);
+            base45_encode(&frame)
+        };
+        let mut collector = FountainCollector::new();
+        // billions of chunks of one byte
+        assert!(matches!(
+            collector.add_contents(&frame(u32::MAX, 1, 0, &[0])),
+            Err(Exceptions::FormatException(_))
+        ));
+        // a chunk size the data does not have
+        assert!(matches!(
+            collector.add_contents(&frame(4, 8, 0, &[1, 2, 3, 4])),
+            Err(Exceptions::FormatException(_))
+        ));
+        // a length shorter than the data of the last chunk
+        assert!(matches!(
+            collector.add_contents(&frame(6, 4, 1, &[5, 6, 7, 8])),
+            Err(Exceptions::FormatException(_))
+        ));
+        assert_eq!(collector.needed(), None);
+        assert!(collector
+            .add_contents(&frame(8, 4, 1, &[5, 6, 7, 8]))
+            .unwrap());
+
+        let options = FountainOptions {
+            chunk_size: 1,
+            ..FountainOptions::default()
+        };
+        assert!(FountainEncoder::new(&[0; 5000], options).is_err());
+    }
+}
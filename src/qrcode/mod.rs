@@ -1,6 +1,7 @@
 pub mod decoder;
 pub mod detector;
 pub mod encoder;
+pub mod fountain;
 
 mod qr_code_reader;
 pub use qr_code_reader::*;
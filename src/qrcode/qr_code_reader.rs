@@ -16,8 +16,9 @@
 
 use crate::{
     common::{BitMatrix, DecoderRXingResult, DetectorRXingResult, Result},
-    point, BarcodeFormat, Binarizer, DecodeHints, Exceptions, ImmutableReader, Point, RXingResult,
-    RXingResultMetadataType, RXingResultMetadataValue, Reader,
+    decode_region::decode_in_region,
+    point, BarcodeFormat, Binarizer, DecodeHints, EstimatedModuleSize, Exceptions, ImmutableReader,
+    Point, RXingResult, RXingResultMetadataType, RXingResultMetadataValue, Reader,
 };
 
 use super::{
@@ -30,7 +31,7 @@ use super::{
  *
  * @author Sean Owen
  */
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct QRCodeReader;
 // pub struct QRCodeReader;  {
 
@@ -55,7 +56,9 @@ impl Reader for QRCodeReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -65,7 +68,9 @@ impl ImmutableReader for QRCodeReader {
         image: &mut crate::BinaryBitmap<B>,
         hints: &DecodeHints,
     ) -> Result<RXingResult> {
-        self.internal_decode_with_hints(image, hints)
+        decode_in_region(image, hints, |image, hints| {
+            self.internal_decode_with_hints(image, hints)
+        })
     }
 }
 
@@ -194,26 +199,45 @@ impl QRCodeReader {
     ) -> Result<RXingResult> {
         let decoderRXingResult: DecoderRXingResult;
         let mut points: Vec<Point>;
+        let moduleSize;
         if matches!(hints.PureBarcode, Some(true)) {
             let bits = Self::extractPureBits(image.get_black_matrix())?;
-            decoderRXingResult = qrcode_decoder::decode_bitmatrix_with_hints(&bits, hints)?;
+            decoderRXingResult = hints.record_rejection(
+                BarcodeFormat::QR_CODE,
+                None,
+                qrcode_decoder::decode_bitmatrix_with_hints(&bits, hints),
+            )?;
             points = Vec::new();
+            moduleSize =
+                EstimatedModuleSize::from_pure_symbol(image.get_black_matrix(), bits.getWidth());
         } else {
-            let detectorRXingResult =
-                Detector::new(image.get_black_matrix()).detect_with_hints(hints)?;
-            decoderRXingResult =
-                qrcode_decoder::decode_bitmatrix_with_hints(detectorRXingResult.getBits(), hints)?;
+            let detectorRXingResult = hints.record_rejection(
+                BarcodeFormat::QR_CODE,
+                None,
+                Detector::new(image.get_black_matrix()).detect_with_hints(hints),
+            )?;
+            decoderRXingResult = hints.record_rejection(
+                BarcodeFormat::QR_CODE,
+                detectorRXingResult.getPoints().first().copied(),
+                qrcode_decoder::decode_bitmatrix_with_hints(
+                    detectorRXingResult.getBits(),
+                    &hints.located(detectorRXingResult.getPoints()),
+                ),
+            )?;
             points = detectorRXingResult.getPoints().to_vec();
+            moduleSize = Self::finderModuleSize(&points, detectorRXingResult.getBits().getWidth());
         }
 
         // If the code was mirrored: swap the bottom-left and the top-right points.
+        let mut mirrored = false;
         if let Some(other) = decoderRXingResult.getOther() {
             if other.is::<QRCodeDecoderMetaData>() {
                 // if (decoderRXingResult.getOther() instanceof QRCodeDecoderMetaData) {
-                other
+                let metadata = other
                     .downcast_ref::<QRCodeDecoderMetaData>()
-                    .ok_or(Exceptions::ILLEGAL_STATE)?
-                    .applyMirroredCorrection(&mut points);
+                    .ok_or(Exceptions::ILLEGAL_STATE)?;
+                metadata.applyMirroredCorrection(&mut points);
+                mirrored = metadata.isMirrored();
             }
         }
 
@@ -262,7 +286,80 @@ impl QRCodeReader {
                 decoderRXingResult.getSymbologyModifier()
             )),
         );
+        result.putMetadata(
+            RXingResultMetadataType::IS_MIRRORED,
+            RXingResultMetadataValue::IsMirrored(mirrored),
+        );
+        if let Some(errorCorrection) = decoderRXingResult.getErrorCorrectionStats() {
+            result.putMetadata(
+                RXingResultMetadataType::ERRORS_CORRECTED,
+                RXingResultMetadataValue::ErrorsCorrected(errorCorrection),
+            );
+        }
+        if let Some(eci) = decoderRXingResult.getEci() {
+            result.putMetadata(
+                RXingResultMetadataType::CHARACTER_SET_ECI,
+                RXingResultMetadataValue::CharacterSetEci(eci as u32),
+            );
+        }
+        if let Some(moduleSize) = moduleSize {
+            result.putMetadata(
+                RXingResultMetadataType::MODULE_SIZE,
+                RXingResultMetadataValue::ModuleSize(moduleSize),
+            );
+        }
 
         Ok(result)
     }
+
+    /**
+     * The centers of the bottom left, top left and top right finder patterns of a symbol
+     * `dimension` modules wide are `dimension - 7` modules apart.
+     */
+    pub(crate) fn finderModuleSize(
+        points: &[Point],
+        dimension: u32,
+    ) -> Option<EstimatedModuleSize> {
+        let [bottomLeft, topLeft, topRight, ..] = points else {
+            return None;
+        };
+        let distance = (topLeft.distance(*topRight) + topLeft.distance(*bottomLeft)) / 2.0;
+        (dimension > 7).then(|| EstimatedModuleSize(distance / (dimension - 7) as f32))
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "image_formats")]
+mod QRCodeReaderTestCase {
+    use crate::{
+        common::HybridBinarizer, BinaryBitmap, BufferedImageLuminanceSource, DecodeHints,
+        RXingResultMetadataType, RXingResultMetadataValue, Reader,
+    };
+
+    use super::QRCodeReader;
+
+    #[test]
+    fn testMirrored() {
+        let image = image::open("test_resources/blackbox/qrcode-1/1.png").expect("image must open");
+        let expected = std::fs::read_to_string("test_resources/blackbox/qrcode-1/1.txt")
+            .expect("text must read");
+
+        for (image, mirrored) in [(image.fliph(), true), (image, false)] {
+            let result = QRCodeReader
+                .decode_with_hints(
+                    &mut BinaryBitmap::new(HybridBinarizer::new(
+                        BufferedImageLuminanceSource::new(image),
+                    )),
+                    &DecodeHints::default().with(crate::DecodeHintValue::TryHarder(true)),
+                )
+                .expect("decode");
+            assert_eq!(expected, result.getText());
+            assert_eq!(
+                Some(&RXingResultMetadataValue::IsMirrored(mirrored)),
+                result
+                    .getRXingResultMetadata()
+                    .get(&RXingResultMetadataType::IS_MIRRORED)
+            );
+        }
+    }
 }
@@ -16,7 +16,9 @@
 
 use crate::{
     common::{BitMatrix, Result},
-    BarcodeFormat, EncodeHints, Exceptions, Writer,
+    encoded_symbol::Placement,
+    BarcodeFormat, EncodeHints, EncodedSymbol, Exceptions, SymbolEcLevel, SymbolMetadata,
+    SymbolVersion, Writer,
 };
 
 use super::{
@@ -56,6 +58,19 @@ impl Writer for QRCodeWriter {
         height: i32,
         hints: &EncodeHints,
     ) -> Result<crate::common::BitMatrix> {
+        Ok(self
+            .encode_with_metadata(contents, format, width, height, hints)?
+            .matrix)
+    }
+
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
         if contents.is_empty() {
             return Err(Exceptions::illegal_argument_with("found empty contents"));
         }
@@ -73,11 +88,7 @@ impl Writer for QRCodeWriter {
             )));
         }
 
-        let errorCorrectionLevel = if let Some(ec_level) = &hints.ErrorCorrection {
-            ec_level.parse()?
-        } else {
-            ErrorCorrectionLevel::L
-        };
+        let errorCorrectionLevel = Self::errorCorrectionLevel(hints)?;
 
         let quietZone = if let Some(margin) = &hints.Margin {
             margin
@@ -88,12 +99,50 @@ impl Writer for QRCodeWriter {
         };
 
         let code = qrcode_encoder::encode_with_hints(contents, errorCorrectionLevel, hints)?;
-
-        Self::renderRXingResult(&code, width, height, quietZone)
+        let modules = code
+            .getMatrix()
+            .clone()
+            .ok_or(Exceptions::ILLEGAL_STATE)?
+            .try_into()?;
+
+        Ok(EncodedSymbol::new(
+            *format,
+            modules,
+            Self::renderRXingResult(&code, width, height, quietZone)?,
+        )
+        .with_metadata(Self::symbol_metadata(&code, errorCorrectionLevel)?))
     }
 }
 
 impl QRCodeWriter {
+    fn errorCorrectionLevel(hints: &EncodeHints) -> Result<ErrorCorrectionLevel> {
+        if let Some(ec_level) = &hints.ErrorCorrection {
+            ec_level.parse()
+        } else {
+            Ok(ErrorCorrectionLevel::L)
+        }
+    }
+
+    /**
+     * Describes the encoded `code`.
+     */
+    fn symbol_metadata(code: &QRCode, ec_level: ErrorCorrectionLevel) -> Result<SymbolMetadata> {
+        let version = code.getVersion().ok_or(Exceptions::ILLEGAL_STATE)?;
+        let ec_codewords = version.getECBlocksForLevel(ec_level).getTotalECCodewords();
+        let data_codewords = version.getTotalCodewords() - ec_codewords;
+
+        Ok(SymbolMetadata {
+            version: SymbolVersion::QrCode(version.getVersionNumber()),
+            ec_level: SymbolEcLevel::QrCode(ec_level),
+            data_codewords,
+            used_data_codewords: code.getNumDataBits().div_ceil(8),
+            ec_codewords,
+            mask: Some(code.getMaskPattern() as u32),
+            data_bits: Some(code.getNumDataBits()),
+            capacity_bits: Some(data_codewords * 8),
+        })
+    }
+
     // Note that the input matrix uses 0 == white, 1 == black, while the output matrix uses
     // 0 == black, 255 == white (i.e. an 8 bit greyscale bitmap).
     fn renderRXingResult(
@@ -101,7 +150,7 @@ impl QRCodeWriter {
         width: i32,
         height: i32,
         quietZone: i32,
-    ) -> Result<BitMatrix> {
+    ) -> Result<(BitMatrix, Placement)> {
         let input = code.getMatrix();
         if input.is_none() {
             return Err(Exceptions::illegal_state_with("matrix is empty"));
@@ -152,6 +201,9 @@ impl QRCodeWriter {
             outputY += multiple;
         }
 
-        Ok(output)
+        Ok((
+            output,
+            Placement::square(multiple as u32, leftPadding as u32, topPadding as u32),
+        ))
     }
 }
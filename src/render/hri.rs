@@ -0,0 +1,381 @@
+use crate::{
+    common::{BitMatrix, Result},
+    oned::{
+        convertUPCEtoUPCA, deutsche_post_check_digit, deutsche_post_human_readable,
+        deutsche_post_length, gs1_check_digit,
+    },
+    BarcodeFormat, EncodeHintValue, EncodeHints, Exceptions, MultiFormatWriter, Writer,
+};
+
+use super::{draw_text, text_width, GLYPH_HEIGHT};
+
+const ESCAPE_FNC_1: char = '\u{00f1}';
+
+/// Height of the bars when no other height is given, in modules.
+pub const DEFAULT_BAR_HEIGHT: u32 = 50;
+
+/// How far the guard bars of EAN and UPC symbols reach below the other bars, in modules.
+const GUARD_EXTENSION: u32 = 5;
+
+/// Width of the space of one EAN/UPC digit, in modules.
+const DIGIT_WIDTH: i64 = 7;
+
+/**
+ * A piece of the human readable interpretation, placed relative to the left edge of the
+ * bars. Negative positions lie in the left quiet zone.
+ */
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct TextPiece {
+    text: String,
+    /// Left edge of the space the text is centred in, in modules.
+    left: i64,
+    /// Width of the space the text is centred in, in modules.
+    width: i64,
+}
+
+/// The human readable interpretation of a linear symbol, laid out under its bars.
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct HumanReadableLayout {
+    pieces: Vec<TextPiece>,
+    /// Module ranges of the bars that reach down between the digits.
+    guards: Vec<(i64, i64)>,
+    /// Minimum quiet zones, left and right, in modules.
+    quiet_zones: (i64, i64),
+}
+
+fn digits(contents: &str, lengths: [usize; 2]) -> Result<String> {
+    if !contents.chars().all(|c| c.is_ascii_digit()) || !lengths.contains(&contents.len()) {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "{contents} should be {} or {} digits",
+            lengths[0], lengths[1]
+        )));
+    }
+    Ok(contents.to_owned())
+}
+
+fn with_check_digit(contents: &str, length: usize) -> Result<String> {
+    let contents = digits(contents, [length - 1, length])?;
+    if contents.len() == length {
+        return Ok(contents);
+    }
+    Ok(format!("{contents}{}", gs1_check_digit(&contents)?))
+}
+
+/// Digits placed one per 7-module space, starting at module `left`.
+fn digit_pieces(digits: &str, left: i64) -> impl Iterator<Item = TextPiece> + '_ {
+    digits.chars().enumerate().map(move |(i, c)| TextPiece {
+        text: c.to_string(),
+        left: left + i as i64 * DIGIT_WIDTH,
+        width: DIGIT_WIDTH,
+    })
+}
+
+/**
+ * The text printed under a GS1-128 symbol: the element string with its Application
+ * Identifiers in parentheses.
+ */
+fn gs1_128_text(contents: &str) -> Result<String> {
+    let mut text = String::new();
+    for field in contents.split(ESCAPE_FNC_1).filter(|f| !f.is_empty()) {
+        text.push_str(
+            &crate::oned::rss::expanded::decoders::field_parser::parseFieldsInGeneralPurpose(
+                field,
+            )?,
+        );
+    }
+    Ok(text)
+}
+
+fn centred(text: String, width: i64) -> Vec<TextPiece> {
+    vec![TextPiece {
+        text,
+        left: 0,
+        width,
+    }]
+}
+
+/// Lays out the human readable interpretation of `contents` under a symbol `width` modules wide.
+fn layout(
+    contents: &str,
+    format: &BarcodeFormat,
+    width: i64,
+    hints: &EncodeHints,
+) -> Result<HumanReadableLayout> {
+    Ok(match format {
+        BarcodeFormat::EAN_13 => {
+            let digits = with_check_digit(contents, 13)?;
+            HumanReadableLayout {
+                pieces: digit_pieces(&digits[..1], -DIGIT_WIDTH)
+                    .chain(digit_pieces(&digits[1..7], 3))
+                    .chain(digit_pieces(&digits[7..], 50))
+                    .collect(),
+                guards: vec![(0, 3), (45, 50), (92, 95)],
+                quiet_zones: (11, 7),
+            }
+        }
+        BarcodeFormat::EAN_8 => {
+            let digits = with_check_digit(contents, 8)?;
+            HumanReadableLayout {
+                pieces: digit_pieces(&digits[..4], 3)
+                    .chain(digit_pieces(&digits[4..], 36))
+                    .collect(),
+                guards: vec![(0, 3), (31, 36), (64, 67)],
+                quiet_zones: (7, 7),
+            }
+        }
+        BarcodeFormat::UPC_A => {
+            let digits = with_check_digit(contents, 12)?;
+            // the bars of the first and the last digit are as long as the guards
+            HumanReadableLayout {
+                pieces: digit_pieces(&digits[..1], -DIGIT_WIDTH)
+                    .chain(digit_pieces(&digits[1..6], 10))
+                    .chain(digit_pieces(&digits[6..11], 50))
+                    .chain(digit_pieces(&digits[11..], 95))
+                    .collect(),
+                guards: vec![(0, 10), (45, 50), (85, 95)],
+                quiet_zones: (9, 9),
+            }
+        }
+        BarcodeFormat::UPC_E => {
+            let digits = digits(contents, [7, 8])?;
+            let digits = if digits.len() == 7 {
+                let upca = convertUPCEtoUPCA(&digits).ok_or(Exceptions::ILLEGAL_ARGUMENT)?;
+                format!("{digits}{}", gs1_check_digit(&upca[..11])?)
+            } else {
+                digits
+            };
+            HumanReadableLayout {
+                pieces: digit_pieces(&digits[..1], -DIGIT_WIDTH)
+                    .chain(digit_pieces(&digits[1..7], 3))
+                    .chain(digit_pieces(&digits[7..], 51))
+                    .collect(),
+                guards: vec![(0, 3), (45, 51)],
+                quiet_zones: (9, 9),
+            }
+        }
+        BarcodeFormat::CODE_39 => HumanReadableLayout {
+            pieces: centred(format!("*{contents}*"), width),
+            guards: Vec::new(),
+            quiet_zones: (10, 10),
+        },
+        BarcodeFormat::CODE_128
+            if contents.starts_with(ESCAPE_FNC_1) || hints.Gs1Format == Some(true) =>
+        {
+            HumanReadableLayout {
+                pieces: centred(gs1_128_text(contents)?, width),
+                guards: Vec::new(),
+                quiet_zones: (10, 10),
+            }
+        }
+        BarcodeFormat::DEUTSCHE_POST_LEITCODE | BarcodeFormat::DEUTSCHE_POST_IDENTCODE => {
+            let length = deutsche_post_length(format).ok_or(Exceptions::ILLEGAL_ARGUMENT)?;
+            let digits = if contents.len() == length - 1 {
+                format!("{contents}{}", deutsche_post_check_digit(contents)?)
+            } else {
+                contents.to_owned()
+            };
+            HumanReadableLayout {
+                pieces: centred(deutsche_post_human_readable(format, &digits)?, width),
+                guards: Vec::new(),
+                quiet_zones: (10, 10),
+            }
+        }
+        BarcodeFormat::ITF_14 => HumanReadableLayout {
+            pieces: centred(with_check_digit(contents, 14)?, width),
+            guards: Vec::new(),
+            quiet_zones: (0, 0),
+        },
+        BarcodeFormat::CODABAR
+        | BarcodeFormat::CODE_93
+        | BarcodeFormat::CODE_128
+        | BarcodeFormat::ITF
+        | BarcodeFormat::TELEPEN => HumanReadableLayout {
+            pieces: centred(contents.replace(ESCAPE_FNC_1, ""), width),
+            guards: Vec::new(),
+            quiet_zones: (10, 10),
+        },
+        _ => {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "no human readable interpretation for {format}"
+            )))
+        }
+    })
+}
+
+/**
+ * Encodes `contents` as a linear symbol with its human readable interpretation printed under
+ * the bars in the built-in font: the digits of EAN and UPC symbols grouped between and outside
+ * the longer guard bars, the Application Identifiers of GS1-128 in parentheses and the `*`
+ * start and stop characters of Code 39.
+ *
+ * The result has one module per pixel, with the bars `bar_height` modules high, and can be
+ * drawn with any of the renderers of this module.
+ */
+pub fn encode_with_human_readable(
+    contents: &str,
+    format: &BarcodeFormat,
+    bar_height: u32,
+    hints: &EncodeHints,
+) -> Result<BitMatrix> {
+    let mut bar_hints = hints.clone();
+    if format != &BarcodeFormat::ITF_14 {
+        bar_hints = bar_hints.with(EncodeHintValue::Margin("0".to_owned()));
+    }
+    let bars = MultiFormatWriter.encode_with_hints(
+        contents,
+        format,
+        0,
+        bar_height.max(1) as i32,
+        &bar_hints,
+    )?;
+    let bar_width = bars.getWidth() as i64;
+    let layout = layout(contents, format, bar_width, hints)?;
+
+    // widen the quiet zones until all of the text fits
+    let (mut left, mut right) = layout.quiet_zones;
+    for piece in &layout.pieces {
+        let text_left = piece.left + (piece.width - text_width(&piece.text) as i64) / 2;
+        left = left.max(-text_left);
+        right = right.max(text_left + text_width(&piece.text) as i64 - bar_width);
+    }
+
+    let has_guards = !layout.guards.is_empty();
+    let text_top = bars.getHeight() + if has_guards { 1 } else { 2 };
+    let width = (left + bar_width + right) as u32;
+    let height = text_top + GLYPH_HEIGHT + 1;
+    let mut label = BitMatrix::new(width, height)?;
+
+    for y in 0..bars.getHeight() {
+        for x in 0..bars.getWidth() {
+            if bars.get(x, y) {
+                label.set(x + left as u32, y);
+            }
+        }
+    }
+
+    if has_guards {
+        let bottom = bars.getHeight() - 1;
+        for (start, end) in &layout.guards {
+            for x in *start..*end {
+                if bars.get(x as u32, bottom) {
+                    label.setRegion((x + left) as u32, bars.getHeight(), 1, GUARD_EXTENSION)?;
+                }
+            }
+        }
+    }
+
+    for piece in &layout.pieces {
+        let text_left = piece.left + (piece.width - text_width(&piece.text) as i64) / 2;
+        draw_text(&mut label, (text_left + left) as u32, text_top, &piece.text);
+    }
+
+    Ok(label)
+}
+
+#[cfg(test)]
+mod HumanReadableTestCase {
+    use crate::{
+        common::{test_image::image, BitMatrix},
+        BarcodeFormat, EncodeHints, MultiFormatReader, Reader,
+    };
+
+    use super::{encode_with_human_readable, gs1_128_text, layout};
+
+    fn decode(matrix: &BitMatrix) -> String {
+        MultiFormatReader::default()
+            .decode(&mut image(matrix, 1, 0))
+            .expect("decode")
+            .getText()
+            .to_owned()
+    }
+
+    #[test]
+    fn testEan13() {
+        let label = encode_with_human_readable(
+            "590123412345",
+            &BarcodeFormat::EAN_13,
+            40,
+            &EncodeHints::default(),
+        )
+        .expect("encode");
+        assert_eq!(label.getWidth(), 11 + 95 + 7);
+        assert_eq!(label.getHeight(), 40 + 1 + 7 + 1);
+        // guard bars reach into the text line, the other bars do not
+        assert!(label.get(11, 44));
+        assert!(label.get(11 + 46, 44));
+        assert!(!label.get(11 + 4, 41));
+        // the leading digit is drawn in the left quiet zone
+        assert!((0..11).any(|x| (41..48).any(|y| label.get(x, y))));
+        assert_eq!(decode(&label), "5901234123457");
+
+        let pieces = layout(
+            "5901234123457",
+            &BarcodeFormat::EAN_13,
+            95,
+            &EncodeHints::default(),
+        )
+        .unwrap()
+        .pieces;
+        let text = pieces.iter().map(|p| p.text.as_str()).collect::<String>();
+        assert_eq!(text, "5901234123457");
+        assert_eq!(pieces[1].left, 3);
+        assert_eq!(pieces[7].left, 50);
+    }
+
+    #[test]
+    fn testUpcE() {
+        let label = encode_with_human_readable(
+            "0123456",
+            &BarcodeFormat::UPC_E,
+            40,
+            &EncodeHints::default(),
+        )
+        .expect("encode");
+        assert_eq!(label.getWidth(), 9 + 51 + 9);
+        assert_eq!(decode(&label), "01234565");
+    }
+
+    #[test]
+    fn testCode39() {
+        let label =
+            encode_with_human_readable("ABC", &BarcodeFormat::CODE_39, 30, &EncodeHints::default())
+                .expect("encode");
+        let pieces = layout("ABC", &BarcodeFormat::CODE_39, 100, &EncodeHints::default())
+            .unwrap()
+            .pieces;
+        assert_eq!(pieces[0].text, "*ABC*");
+        assert_eq!(decode(&label), "ABC");
+    }
+
+    #[test]
+    fn testGs1128() {
+        assert_eq!(
+            gs1_128_text("\u{f1}0101234567890128\u{f1}10ABC\u{f1}").unwrap(),
+            "(01)01234567890128(10)ABC"
+        );
+        assert_eq!(
+            gs1_128_text("\u{f1}10ABC\u{f1}3103000123").unwrap(),
+            "(10)ABC(3103)000123"
+        );
+        let label = encode_with_human_readable(
+            "\u{f1}0101234567890128",
+            &BarcodeFormat::CODE_128,
+            30,
+            &EncodeHints::default(),
+        )
+        .expect("encode");
+        // the text is wider than the bars
+        assert!(label.getWidth() >= 20 * 6);
+    }
+
+    #[test]
+    fn testUnsupported() {
+        assert!(encode_with_human_readable(
+            "hello",
+            &BarcodeFormat::QR_CODE,
+            30,
+            &EncodeHints::default()
+        )
+        .is_err());
+    }
+}
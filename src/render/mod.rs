@@ -0,0 +1,365 @@
+//! Rendering of the module matrices produced by the writers.
+//!
+//! Every `Writer` returns a `BitMatrix`, in which each set bit is a dark module. The functions in
+//! this module turn such a matrix into pixels, with the colours, module size, quiet zone and
+//! resolution given in a [`RenderOptions`].
+
+use std::str::FromStr;
+
+use crate::{
+    common::{BitMatrix, Result},
+    Exceptions,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+mod font;
+pub use font::*;
+
+mod hri;
+pub use hri::*;
+
+mod outline;
+pub use outline::*;
+
+#[cfg(feature = "image")]
+mod raster;
+#[cfg(feature = "image")]
+pub use raster::*;
+
+mod printer;
+pub use printer::*;
+
+mod styled_qr;
+pub use styled_qr::*;
+
+mod terminal;
+pub use terminal::*;
+
+mod vector;
+pub use vector::*;
+
+#[cfg(feature = "svg_write")]
+mod svg;
+#[cfg(feature = "svg_write")]
+pub use self::svg::*;
+
+/// A colour with an alpha channel, as `[red, green, blue, alpha]`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Rgba(pub [u8; 4]);
+
+impl Rgba {
+    pub const BLACK: Rgba = Rgba([0, 0, 0, u8::MAX]);
+    pub const WHITE: Rgba = Rgba([u8::MAX, u8::MAX, u8::MAX, u8::MAX]);
+    pub const TRANSPARENT: Rgba = Rgba([u8::MAX, u8::MAX, u8::MAX, 0]);
+
+    pub fn is_opaque(&self) -> bool {
+        self.0[3] == u8::MAX
+    }
+}
+
+impl FromStr for Rgba {
+    type Err = Exceptions;
+
+    /// Parses `#RRGGBB`, `#RRGGBBAA` (the `#` is optional), `black`, `white` or `transparent`.
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "black" => return Ok(Rgba::BLACK),
+            "white" => return Ok(Rgba::WHITE),
+            "transparent" | "none" => return Ok(Rgba::TRANSPARENT),
+            _ => {}
+        }
+        let hex = s.strip_prefix('#').unwrap_or(s);
+        if !(hex.len() == 6 || hex.len() == 8) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "Unknown colour: {s}"
+            )));
+        }
+        let mut rgba = [u8::MAX; 4];
+        for (i, channel) in rgba.iter_mut().enumerate().take(hex.len() / 2) {
+            *channel = u8::from_str_radix(&hex[2 * i..2 * i + 2], 16)
+                .map_err(|e| Exceptions::illegal_argument_with(format!("Unknown colour: {e}")))?;
+        }
+        Ok(Rgba(rgba))
+    }
+}
+
+/// How large each module of the matrix is drawn.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ModuleSize {
+    /// Each module is a square of this many pixels.
+    Pixels(u32),
+    /// The image is exactly this large, with the symbol centred in it at the largest whole
+    /// number of pixels per module that fits. If the symbol does not fit at one pixel per
+    /// module, the image is made as large as it needs to be.
+    Target { width: u32, height: u32 },
+}
+
+impl Default for ModuleSize {
+    fn default() -> Self {
+        ModuleSize::Pixels(1)
+    }
+}
+
+/// Options for rendering a module matrix.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct RenderOptions {
+    /// Colour of the dark modules.
+    pub foreground: Rgba,
+    /// Colour of the light modules and the quiet zone. May be transparent.
+    pub background: Rgba,
+    /// Size of each module, or of the whole image.
+    pub module_size: ModuleSize,
+    /// Quiet zone added around the matrix, in modules. This comes on top of any margin the
+    /// writer already put into the matrix.
+    pub quiet_zone: u32,
+    /// Draws light modules on a dark background, for symbols printed in reverse reflectance.
+    /// The foreground colour is then used for the light modules and the quiet zone.
+    pub inverted: bool,
+    /// Resolution stored in the file when saving to a format that records one (PNG and TIFF).
+    pub dpi: Option<u32>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            foreground: Rgba::BLACK,
+            background: Rgba::WHITE,
+            module_size: ModuleSize::default(),
+            quiet_zone: 0,
+            inverted: false,
+            dpi: None,
+        }
+    }
+}
+
+/// Where the modules of a matrix land in a rendered image.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct RenderLayout {
+    /// Width of the whole image, in pixels.
+    pub width: u32,
+    /// Height of the whole image, in pixels.
+    pub height: u32,
+    /// Pixels per module.
+    pub scale: u32,
+    /// Left edge of the first module column, in pixels.
+    pub left: u32,
+    /// Top edge of the first module row, in pixels.
+    pub top: u32,
+}
+
+impl RenderOptions {
+    /// Computes the size of the image and the position of the modules of a `width` x `height`
+    /// module matrix.
+    pub fn layout(&self, width: u32, height: u32) -> Result<RenderLayout> {
+        let overflow = || Exceptions::illegal_argument_with("rendered image would be too large");
+        let full_width = self
+            .quiet_zone
+            .checked_mul(2)
+            .and_then(|q| q.checked_add(width))
+            .ok_or_else(overflow)?;
+        let full_height = self
+            .quiet_zone
+            .checked_mul(2)
+            .and_then(|q| q.checked_add(height))
+            .ok_or_else(overflow)?;
+        if full_width == 0 || full_height == 0 {
+            return Err(Exceptions::illegal_argument_with("nothing to render"));
+        }
+
+        let (scale, image_width, image_height) = match self.module_size {
+            ModuleSize::Pixels(0) => {
+                return Err(Exceptions::illegal_argument_with(
+                    "module size must be at least 1 pixel",
+                ))
+            }
+            ModuleSize::Pixels(scale) => (
+                scale,
+                full_width.checked_mul(scale).ok_or_else(overflow)?,
+                full_height.checked_mul(scale).ok_or_else(overflow)?,
+            ),
+            ModuleSize::Target { width, height } => {
+                let scale = (width / full_width).min(height / full_height).max(1);
+                (
+                    scale,
+                    width.max(full_width * scale),
+                    height.max(full_height * scale),
+                )
+            }
+        };
+
+        Ok(RenderLayout {
+            width: image_width,
+            height: image_height,
+            scale,
+            left: (image_width - full_width * scale) / 2 + self.quiet_zone * scale,
+            top: (image_height - full_height * scale) / 2 + self.quiet_zone * scale,
+        })
+    }
+
+    /// The colours of the dark modules and of everything else, after inversion.
+    pub fn colors(&self) -> (Rgba, Rgba) {
+        if self.inverted {
+            (self.background, self.foreground)
+        } else {
+            (self.foreground, self.background)
+        }
+    }
+}
+
+/// A rendered image: RGBA pixels, row by row.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RgbaRaster {
+    pub width: u32,
+    pub height: u32,
+    /// Resolution to record when the image is saved.
+    pub dpi: Option<u32>,
+    /// Four bytes per pixel, `width * height` pixels.
+    pub pixels: Vec<u8>,
+}
+
+impl RgbaRaster {
+    pub fn get(&self, x: u32, y: u32) -> Rgba {
+        let offset = 4 * (y as usize * self.width as usize + x as usize);
+        let mut rgba = [0; 4];
+        rgba.copy_from_slice(&self.pixels[offset..offset + 4]);
+        Rgba(rgba)
+    }
+}
+
+/// Renders `matrix`, one of the writers' outputs, into RGBA pixels.
+pub fn render(matrix: &BitMatrix, options: &RenderOptions) -> Result<RgbaRaster> {
+    let layout = options.layout(matrix.getWidth(), matrix.getHeight())?;
+    let (dark, light) = options.colors();
+
+    let pixel_count = (layout.width as usize)
+        .checked_mul(layout.height as usize)
+        .and_then(|count| count.checked_mul(4))
+        .ok_or_else(|| Exceptions::illegal_argument_with("rendered image would be too large"))?;
+    let mut pixels = Vec::with_capacity(pixel_count);
+    for _ in 0..pixel_count / 4 {
+        pixels.extend_from_slice(&light.0);
+    }
+
+    let row_bytes = 4 * layout.width as usize;
+    for y in 0..matrix.getHeight() {
+        for x in 0..matrix.getWidth() {
+            if !matrix.get(x, y) {
+                continue;
+            }
+            let px = (layout.left + x * layout.scale) as usize;
+            let py = (layout.top + y * layout.scale) as usize;
+            for row in py..py + layout.scale as usize {
+                let start = row * row_bytes + 4 * px;
+                for pixel in pixels[start..start + 4 * layout.scale as usize].chunks_exact_mut(4) {
+                    pixel.copy_from_slice(&dark.0);
+                }
+            }
+        }
+    }
+
+    Ok(RgbaRaster {
+        width: layout.width,
+        height: layout.height,
+        dpi: options.dpi,
+        pixels,
+    })
+}
+
+#[cfg(test)]
+mod RenderTestCase {
+    use crate::common::BitMatrix;
+
+    use super::{render, ModuleSize, RenderOptions, Rgba};
+
+    fn checkerboard() -> BitMatrix {
+        let mut matrix = BitMatrix::new(2, 2).unwrap();
+        matrix.set(0, 0);
+        matrix.set(1, 1);
+        matrix
+    }
+
+    #[test]
+    fn testModuleSizeAndQuietZone() {
+        let options = RenderOptions {
+            module_size: ModuleSize::Pixels(3),
+            quiet_zone: 1,
+            ..Default::default()
+        };
+        let raster = render(&checkerboard(), &options).unwrap();
+        assert_eq!((raster.width, raster.height), (12, 12));
+        assert_eq!(raster.get(2, 2), Rgba::WHITE);
+        assert_eq!(raster.get(3, 3), Rgba::BLACK);
+        assert_eq!(raster.get(5, 5), Rgba::BLACK);
+        assert_eq!(raster.get(6, 3), Rgba::WHITE);
+        assert_eq!(raster.get(8, 8), Rgba::BLACK);
+        assert_eq!(raster.get(9, 9), Rgba::WHITE);
+    }
+
+    #[test]
+    fn testColorsAndInversion() {
+        let red: Rgba = "#ff000080".parse().unwrap();
+        assert_eq!(red, Rgba([255, 0, 0, 128]));
+        assert_eq!("00ff00".parse::<Rgba>().unwrap(), Rgba([0, 255, 0, 255]));
+        assert!("#12345".parse::<Rgba>().is_err());
+
+        let options = RenderOptions {
+            foreground: red,
+            background: Rgba::TRANSPARENT,
+            quiet_zone: 1,
+            ..Default::default()
+        };
+        let raster = render(&checkerboard(), &options).unwrap();
+        assert_eq!(raster.get(0, 0), Rgba::TRANSPARENT);
+        assert_eq!(raster.get(1, 1), red);
+        assert_eq!(raster.get(2, 1), Rgba::TRANSPARENT);
+
+        let options = RenderOptions {
+            inverted: true,
+            ..options
+        };
+        let raster = render(&checkerboard(), &options).unwrap();
+        assert_eq!(raster.get(0, 0), red);
+        assert_eq!(raster.get(1, 1), Rgba::TRANSPARENT);
+        assert_eq!(raster.get(2, 1), red);
+    }
+
+    #[test]
+    fn testTargetSize() {
+        let options = RenderOptions {
+            module_size: ModuleSize::Target {
+                width: 13,
+                height: 9,
+            },
+            ..Default::default()
+        };
+        let layout = options.layout(2, 2).unwrap();
+        assert_eq!((layout.width, layout.height, layout.scale), (13, 9, 4));
+        assert_eq!((layout.left, layout.top), (2, 0));
+
+        // too small a target grows to one pixel per module
+        let options = RenderOptions {
+            module_size: ModuleSize::Target {
+                width: 1,
+                height: 1,
+            },
+            quiet_zone: 2,
+            ..Default::default()
+        };
+        let raster = render(&checkerboard(), &options).unwrap();
+        assert_eq!((raster.width, raster.height), (6, 6));
+    }
+
+    #[test]
+    fn testZeroModuleSize() {
+        let options = RenderOptions {
+            module_size: ModuleSize::Pixels(0),
+            ..Default::default()
+        };
+        assert!(render(&checkerboard(), &options).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use crate::common::BitMatrix;
+
+/// A point on the module grid: `(x, y)` of a module corner.
+pub type GridPoint = (u32, u32);
+
+const RIGHT: u8 = 0;
+const DOWN: u8 = 1;
+const LEFT: u8 = 2;
+const UP: u8 = 3;
+
+/**
+ * Traces the outlines of the dark regions of `matrix`.
+ *
+ * Each outline is a closed polygon, given by its corners in module units. Outer boundaries run
+ * clockwise and the boundaries of holes counter-clockwise, so filling all of them with the
+ * nonzero rule paints exactly the dark modules. Regions that only touch at a corner get
+ * separate outlines.
+ */
+pub fn trace_outlines(matrix: &BitMatrix) -> Vec<Vec<GridPoint>> {
+    let width = matrix.getWidth();
+    let height = matrix.getHeight();
+    let stride = width as usize + 1;
+    let dark = |x: i64, y: i64| {
+        x >= 0 && y >= 0 && x < width as i64 && y < height as i64 && matrix.get(x as u32, y as u32)
+    };
+
+    // the unit edges leaving each grid point, one bit per direction, with the dark side on
+    // the right of the direction of travel
+    let mut edges = vec![0u8; stride * (height as usize + 1)];
+    let index = |x: u32, y: u32| y as usize * stride + x as usize;
+    for y in 0..height {
+        for x in 0..width {
+            if !matrix.get(x, y) {
+                continue;
+            }
+            let (cx, cy) = (x as i64, y as i64);
+            if !dark(cx, cy - 1) {
+                edges[index(x, y)] |= 1 << RIGHT;
+            }
+            if !dark(cx + 1, cy) {
+                edges[index(x + 1, y)] |= 1 << DOWN;
+            }
+            if !dark(cx, cy + 1) {
+                edges[index(x + 1, y + 1)] |= 1 << LEFT;
+            }
+            if !dark(cx - 1, cy) {
+                edges[index(x, y + 1)] |= 1 << UP;
+            }
+        }
+    }
+
+    let mut outlines = Vec::new();
+    for start_y in 0..=height {
+        for start_x in 0..=width {
+            while edges[index(start_x, start_y)] != 0 {
+                let first = edges[index(start_x, start_y)].trailing_zeros() as u8;
+                let mut direction = first;
+                let (mut x, mut y) = (start_x, start_y);
+                let mut corners = Vec::new();
+                loop {
+                    edges[index(x, y)] &= !(1 << direction);
+                    match direction {
+                        RIGHT => x += 1,
+                        DOWN => y += 1,
+                        LEFT => x -= 1,
+                        _ => y -= 1,
+                    }
+                    if (x, y) == (start_x, start_y) {
+                        if direction != first {
+                            corners.insert(0, (start_x, start_y));
+                        }
+                        break;
+                    }
+                    // turning right first keeps regions that touch at a corner apart
+                    let available = edges[index(x, y)];
+                    let next = [(direction + 1) % 4, direction, (direction + 3) % 4]
+                        .into_iter()
+                        .find(|d| available & (1 << d) != 0)
+                        .expect("every grid point on an outline is left as often as entered");
+                    if next != direction {
+                        corners.push((x, y));
+                    }
+                    direction = next;
+                }
+                outlines.push(corners);
+            }
+        }
+    }
+    outlines
+}
+
+/// SVG path data for `outlines`, shifted by `offset` modules.
+pub fn outline_path_data(outlines: &[Vec<GridPoint>], offset: (u32, u32)) -> String {
+    let mut data = String::new();
+    for outline in outlines {
+        let Some(&(x0, y0)) = outline.first() else {
+            continue;
+        };
+        data.push_str(&format!("M{} {}", x0 + offset.0, y0 + offset.1));
+        let mut previous = (x0, y0);
+        for &(x, y) in &outline[1..] {
+            if y == previous.1 {
+                data.push_str(&format!("H{}", x + offset.0));
+            } else {
+                data.push_str(&format!("V{}", y + offset.1));
+            }
+            previous = (x, y);
+        }
+        data.push('Z');
+    }
+    data
+}
+
+#[cfg(test)]
+mod OutlineTestCase {
+    use crate::common::BitMatrix;
+
+    use super::{outline_path_data, trace_outlines};
+
+    #[test]
+    fn testSquare() {
+        let mut matrix = BitMatrix::new(4, 4).unwrap();
+        matrix.setRegion(1, 1, 2, 2).unwrap();
+        let outlines = trace_outlines(&matrix);
+        assert_eq!(outlines, vec![vec![(1, 1), (3, 1), (3, 3), (1, 3)]]);
+        assert_eq!(outline_path_data(&outlines, (0, 0)), "M1 1H3V3H1Z");
+        assert_eq!(outline_path_data(&outlines, (2, 1)), "M3 2H5V4H3Z");
+    }
+
+    #[test]
+    fn testHole() {
+        let mut matrix = BitMatrix::new(3, 3).unwrap();
+        matrix.setRegion(0, 0, 3, 3).unwrap();
+        matrix.unset(1, 1);
+        let outlines = trace_outlines(&matrix);
+        assert_eq!(outlines.len(), 2);
+        assert_eq!(outlines[0], vec![(0, 0), (3, 0), (3, 3), (0, 3)]);
+        // the hole runs the other way round
+        assert_eq!(outlines[1], vec![(1, 1), (1, 2), (2, 2), (2, 1)]);
+    }
+
+    #[test]
+    fn testDiagonal() {
+        let mut matrix = BitMatrix::new(2, 2).unwrap();
+        matrix.set(0, 0);
+        matrix.set(1, 1);
+        let outlines = trace_outlines(&matrix);
+        assert_eq!(
+            outlines,
+            vec![
+                vec![(0, 0), (1, 0), (1, 1), (0, 1)],
+                vec![(1, 1), (2, 1), (2, 2), (1, 2)]
+            ]
+        );
+    }
+}
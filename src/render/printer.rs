@@ -0,0 +1,351 @@
+use crate::{
+    common::{BitMatrix, Result},
+    BarcodeFormat, EncodeHints, Exceptions, MultiFormatWriter, Writer, ONE_D_FORMATS,
+};
+
+use super::PhysicalLength;
+
+const ESCAPE_FNC_1: char = '\u{00f1}';
+
+/// Options for printing a symbol at an exact size on a printer of a given resolution.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PrinterOptions {
+    /// Resolution of the printer, in dots per inch.
+    pub dpi: u32,
+    /// The width of a module that is asked for. It is printed as the nearest whole number of
+    /// dots.
+    pub x_dimension: PhysicalLength,
+    /// How far the printed X-dimension may be from `x_dimension`, as a fraction of it.
+    pub x_dimension_tolerance: f32,
+    /// Taken off every bar, or every dark area of a 2D symbol, to make up for ink spread. Half
+    /// of it comes off each edge, the odd dot off the right and bottom edges.
+    pub bar_width_reduction: Option<PhysicalLength>,
+    /// Height of the bars of linear symbols, including any bearer bars. If not given, the GS1
+    /// minimum for the symbology is used where there is one, and otherwise 15% of the width of
+    /// the symbol or 6.35 mm, whichever is more.
+    pub bar_height: Option<PhysicalLength>,
+}
+
+impl Default for PrinterOptions {
+    /// The nominal EAN/UPC X-dimension of 0.33 mm at 300 dpi.
+    fn default() -> Self {
+        Self {
+            dpi: 300,
+            x_dimension: PhysicalLength::Millimeters(0.33),
+            x_dimension_tolerance: 0.05,
+            bar_width_reduction: None,
+            bar_height: None,
+        }
+    }
+}
+
+/// A symbol rendered in printer dots.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PrinterRendering {
+    /// One bit per printer dot, set for the dots to print.
+    pub matrix: BitMatrix,
+    pub dpi: u32,
+    /// Width and height of a module, in dots.
+    pub dots_per_module: u32,
+    /// Dots taken off each bar.
+    pub bar_width_reduction: u32,
+}
+
+impl PrinterRendering {
+    /// The X-dimension that is actually printed.
+    pub fn x_dimension(&self) -> PhysicalLength {
+        PhysicalLength::Mils(self.dots_per_module as f32 * 1000.0 / self.dpi as f32)
+    }
+}
+
+/**
+ * The minimum bar height of the GS1 General Specifications for the given linear symbol, at an
+ * X-dimension of `x_dimension_mm`: proportional to the X-dimension for EAN/UPC, and the 31.75 mm
+ * of the general distribution table for ITF-14 and GS1-128.
+ */
+pub fn gs1_minimum_bar_height(
+    contents: &str,
+    format: &BarcodeFormat,
+    hints: &EncodeHints,
+    x_dimension_mm: f32,
+) -> Option<PhysicalLength> {
+    match format {
+        BarcodeFormat::EAN_13 | BarcodeFormat::UPC_A | BarcodeFormat::UPC_E => {
+            Some(PhysicalLength::Millimeters(22.85 / 0.33 * x_dimension_mm))
+        }
+        BarcodeFormat::EAN_8 => Some(PhysicalLength::Millimeters(18.23 / 0.33 * x_dimension_mm)),
+        BarcodeFormat::ITF_14 => Some(PhysicalLength::Millimeters(31.75)),
+        BarcodeFormat::CODE_128
+            if contents.starts_with(ESCAPE_FNC_1) || hints.Gs1Format == Some(true) =>
+        {
+            Some(PhysicalLength::Millimeters(31.75))
+        }
+        _ => None,
+    }
+}
+
+/// Draws every module of `modules` as a `scale` x `scale` square of dots.
+fn scale_up(modules: &BitMatrix, scale: u32) -> Result<BitMatrix> {
+    let mut dots = BitMatrix::new(modules.getWidth() * scale, modules.getHeight() * scale)?;
+    for y in 0..modules.getHeight() {
+        for x in 0..modules.getWidth() {
+            if modules.get(x, y) {
+                dots.setRegion(x * scale, y * scale, scale, scale)?;
+            }
+        }
+    }
+    Ok(dots)
+}
+
+/**
+ * Takes `reduction` dots off every run of set dots in `dots`, along the rows if `horizontal`
+ * and along the columns otherwise. Returns the dots to clear.
+ */
+fn reduced_edges(dots: &BitMatrix, reduction: u32, horizontal: bool) -> Vec<(u32, u32)> {
+    let (length, lines) = if horizontal {
+        (dots.getWidth(), dots.getHeight())
+    } else {
+        (dots.getHeight(), dots.getWidth())
+    };
+    let at = |line: u32, i: u32| if horizontal { (i, line) } else { (line, i) };
+    let mut cleared = Vec::new();
+    for line in 0..lines {
+        let mut i = 0;
+        while i < length {
+            let (x, y) = at(line, i);
+            if !dots.get(x, y) {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < length && {
+                let (x, y) = at(line, i);
+                dots.get(x, y)
+            } {
+                i += 1;
+            }
+            let before = reduction / 2;
+            let after = reduction - before;
+            cleared.extend((start..start + before).map(|j| at(line, j)));
+            cleared.extend((i - after..i).map(|j| at(line, j)));
+        }
+    }
+    cleared
+}
+
+/**
+ * Encodes `contents` for a printer: every module is the same whole number of printer dots
+ * wide, as close to the requested X-dimension as the resolution allows. Fails if that is
+ * further from the request than `x_dimension_tolerance`, if the bar width reduction would
+ * remove whole modules, or if a requested bar height is below the GS1 minimum.
+ *
+ * The quiet zones are the writer's, or those of a `Margin` hint, in modules. The result can be
+ * drawn with [`render`](super::render) at one pixel per module and saved with its resolution.
+ */
+pub fn encode_for_printer(
+    contents: &str,
+    format: &BarcodeFormat,
+    hints: &EncodeHints,
+    options: &PrinterOptions,
+) -> Result<PrinterRendering> {
+    if options.dpi == 0 {
+        return Err(Exceptions::illegal_argument_with(
+            "printer resolution must be at least 1 dpi",
+        ));
+    }
+    let exact = options.x_dimension.to_dots(options.dpi);
+    let dots_per_module = exact.round() as u32;
+    if dots_per_module == 0
+        || (dots_per_module as f32 - exact).abs() > exact * options.x_dimension_tolerance
+    {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "an X-dimension of {:.3} mm cannot be printed at {} dpi, the nearest is {} dots or {:.3} mm",
+            options.x_dimension.to_millimeters(),
+            options.dpi,
+            dots_per_module.max(1),
+            dots_per_module.max(1) as f32 / options.dpi as f32 * 25.4
+        )));
+    }
+    let bar_width_reduction = options
+        .bar_width_reduction
+        .map(|reduction| reduction.to_dots(options.dpi).round() as u32)
+        .unwrap_or(0);
+    if bar_width_reduction >= dots_per_module {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "a bar width reduction of {bar_width_reduction} dots leaves nothing of a module of {dots_per_module} dots"
+        )));
+    }
+
+    let linear = ONE_D_FORMATS.contains(format);
+    let modules = if linear {
+        let unscaled = MultiFormatWriter.encode_with_hints(contents, format, 0, 0, hints)?;
+        let x_dimension_mm = dots_per_module as f32 / options.dpi as f32 * 25.4;
+        let minimum = gs1_minimum_bar_height(contents, format, hints, x_dimension_mm);
+        let bar_height = match (options.bar_height, minimum) {
+            (Some(height), Some(minimum)) if height.to_millimeters() < minimum.to_millimeters() => {
+                return Err(Exceptions::illegal_argument_with(format!(
+                    "a bar height of {:.2} mm is below the GS1 minimum of {:.2} mm",
+                    height.to_millimeters(),
+                    minimum.to_millimeters()
+                )))
+            }
+            (Some(height), _) => height,
+            (None, Some(minimum)) => minimum,
+            (None, None) => PhysicalLength::Millimeters(
+                (0.15 * unscaled.getWidth() as f32 * x_dimension_mm).max(6.35),
+            ),
+        };
+        let rows = (bar_height.to_dots(options.dpi) / dots_per_module as f32).ceil() as i32;
+        MultiFormatWriter.encode_with_hints(contents, format, 0, rows.max(1), hints)?
+    } else {
+        MultiFormatWriter.encode_with_hints(contents, format, 0, 0, hints)?
+    };
+
+    let mut matrix = scale_up(&modules, dots_per_module)?;
+    if bar_width_reduction > 0 {
+        let mut cleared = reduced_edges(&matrix, bar_width_reduction, true);
+        if !linear {
+            cleared.extend(reduced_edges(&matrix, bar_width_reduction, false));
+        }
+        for (x, y) in cleared {
+            matrix.unset(x, y);
+        }
+    }
+
+    Ok(PrinterRendering {
+        matrix,
+        dpi: options.dpi,
+        dots_per_module,
+        bar_width_reduction,
+    })
+}
+
+#[cfg(test)]
+mod PrinterTestCase {
+    use crate::{
+        common::{test_image::image, BitMatrix},
+        render::PhysicalLength,
+        BarcodeFormat, EncodeHints, MultiFormatReader, Reader,
+    };
+
+    use super::{encode_for_printer, PrinterOptions};
+
+    fn decode(matrix: &BitMatrix) -> String {
+        MultiFormatReader::default()
+            .decode(&mut image(matrix, 1, 0))
+            .expect("decode")
+            .getText()
+            .to_owned()
+    }
+
+    #[test]
+    fn testEan13() {
+        let options = PrinterOptions {
+            bar_width_reduction: Some(PhysicalLength::Mils(3.5)),
+            ..Default::default()
+        };
+        let printed = encode_for_printer(
+            "5901234123457",
+            &BarcodeFormat::EAN_13,
+            &EncodeHints::default(),
+            &options,
+        )
+        .unwrap();
+        // 0.33 mm is 3.9 dots at 300 dpi
+        assert_eq!(printed.dots_per_module, 4);
+        assert_eq!(printed.bar_width_reduction, 1);
+        assert_eq!(printed.matrix.getWidth() % 4, 0);
+        // the GS1 minimum of 69.24 modules, rounded up to whole modules
+        assert_eq!(printed.matrix.getHeight(), 70 * 4);
+        assert!((printed.x_dimension().to_millimeters() - 0.3387).abs() < 1e-3);
+        assert_eq!(decode(&printed.matrix), "5901234123457");
+
+        // every bar is one dot narrower than its modules
+        let row: Vec<bool> = (0..printed.matrix.getWidth())
+            .map(|x| printed.matrix.get(x, 0))
+            .collect();
+        let first_bar = row.iter().position(|&dark| dark).unwrap();
+        let first_bar_width = row[first_bar..].iter().take_while(|&&dark| dark).count();
+        assert_eq!(first_bar_width, 3);
+    }
+
+    #[test]
+    fn testUnachievableXDimension() {
+        let options = PrinterOptions {
+            dpi: 100,
+            ..Default::default()
+        };
+        assert!(encode_for_printer(
+            "5901234123457",
+            &BarcodeFormat::EAN_13,
+            &EncodeHints::default(),
+            &options
+        )
+        .is_err());
+
+        let options = PrinterOptions {
+            bar_width_reduction: Some(PhysicalLength::Millimeters(0.33)),
+            ..Default::default()
+        };
+        assert!(encode_for_printer(
+            "5901234123457",
+            &BarcodeFormat::EAN_13,
+            &EncodeHints::default(),
+            &options
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn testBarHeight() {
+        let options = PrinterOptions {
+            bar_height: Some(PhysicalLength::Millimeters(10.0)),
+            ..Default::default()
+        };
+        assert!(encode_for_printer(
+            "5901234123457",
+            &BarcodeFormat::EAN_13,
+            &EncodeHints::default(),
+            &options
+        )
+        .is_err());
+
+        // Code 39 has no GS1 minimum, so any height goes
+        let printed = encode_for_printer(
+            "ABC",
+            &BarcodeFormat::CODE_39,
+            &EncodeHints::default(),
+            &options,
+        )
+        .unwrap();
+        // 10 mm is 118.1 dots, 30 modules of 4 dots
+        assert_eq!(printed.matrix.getHeight(), 120);
+    }
+
+    #[test]
+    fn testQrCode() {
+        let options = PrinterOptions {
+            dpi: 203,
+            x_dimension: PhysicalLength::Millimeters(0.5),
+            bar_width_reduction: Some(PhysicalLength::Points(0.4)),
+            ..Default::default()
+        };
+        let printed = encode_for_printer(
+            "hello",
+            &BarcodeFormat::QR_CODE,
+            &EncodeHints::default(),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(printed.dots_per_module, 4);
+        assert_eq!(printed.bar_width_reduction, 1);
+        // a version 1 symbol with a quiet zone of 4 modules
+        assert_eq!(printed.matrix.getWidth(), 29 * 4);
+        // the top left finder pattern starts at module 4, its last dot on either axis is cleared
+        assert!(printed.matrix.get(16, 16));
+        assert!(printed.matrix.get(42, 16));
+        assert!(!printed.matrix.get(43, 16));
+        assert!(!printed.matrix.get(16, 43));
+        assert_eq!(decode(&printed.matrix), "hello");
+    }
+}
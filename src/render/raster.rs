@@ -0,0 +1,290 @@
+use std::path::Path;
+
+use crate::{
+    common::{BitMatrix, Result},
+    Exceptions,
+};
+
+use super::{render, RenderOptions, RgbaRaster};
+
+/// Fails with an `IllegalArgumentException` if the pixels are not `width * height` pixels.
+impl TryFrom<RgbaRaster> for image::RgbaImage {
+    type Error = Exceptions;
+
+    fn try_from(value: RgbaRaster) -> Result<Self> {
+        let (width, height, length) = (value.width, value.height, value.pixels.len());
+        image::RgbaImage::from_raw(width, height, value.pixels).ok_or_else(|| {
+            Exceptions::illegal_argument_with(format!(
+                "{length} bytes are not {width} by {height} RGBA pixels"
+            ))
+        })
+    }
+}
+
+impl TryFrom<RgbaRaster> for image::DynamicImage {
+    type Error = Exceptions;
+
+    fn try_from(value: RgbaRaster) -> Result<Self> {
+        image::RgbaImage::try_from(value).map(Into::into)
+    }
+}
+
+/// Renders `matrix` into an `image::RgbaImage`.
+pub fn render_image(matrix: &BitMatrix, options: &RenderOptions) -> Result<image::RgbaImage> {
+    render(matrix, options)?.try_into()
+}
+
+/// Saves `raster` to `file_name`, in the format given by its extension. The resolution of the
+/// raster is recorded in PNG and TIFF files.
+pub fn save_raster(file_name: &str, raster: RgbaRaster) -> Result<()> {
+    let save_error = |err: &dyn std::fmt::Display| -> Exceptions {
+        Exceptions::illegal_argument_with(format!("could not save file '{file_name}': {err}"))
+    };
+
+    let extension = Path::new(file_name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    #[cfg(feature = "image_formats")]
+    if let Some(dpi) = raster.dpi {
+        match extension.as_str() {
+            "png" => return save_png(file_name, &raster, dpi).map_err(|e| save_error(&e)),
+            "tif" | "tiff" => {
+                return save_tiff(file_name, &raster, dpi).map_err(|e| save_error(&e))
+            }
+            _ => {}
+        }
+    }
+
+    let image = image::DynamicImage::try_from(raster)?;
+    // formats without an alpha channel get the pixels as they would look on white
+    let image = match extension.as_str() {
+        "jpg" | "jpeg" | "bmp" | "pnm" | "pbm" | "pgm" | "ppm" => {
+            let mut rgba = image.into_rgba8();
+            for pixel in rgba.pixels_mut() {
+                let alpha = pixel.0[3] as u32;
+                for channel in &mut pixel.0[..3] {
+                    *channel = ((*channel as u32 * alpha + 255 * (255 - alpha)) / 255) as u8;
+                }
+            }
+            image::DynamicImage::ImageRgb8(image::DynamicImage::ImageRgba8(rgba).into_rgb8())
+        }
+        _ => image,
+    };
+    image.save(file_name).map_err(|e| save_error(&e))
+}
+
+/// Saves `frames` as an animated GIF that loops forever, showing each frame for `frame_delay_ms`.
+/// Frames of different sizes are centred on a canvas of the largest of them.
+pub fn save_animated_gif(
+    file_name: &str,
+    frames: &[BitMatrix],
+    options: &RenderOptions,
+    frame_delay_ms: u32,
+) -> Result<()> {
+    #[cfg(feature = "image_formats")]
+    {
+        use image::{
+            codecs::gif::{GifEncoder, Repeat},
+            Delay, Frame,
+        };
+
+        let save_error = |err: &dyn std::fmt::Display| -> Exceptions {
+            Exceptions::illegal_argument_with(format!("could not save file '{file_name}': {err}"))
+        };
+
+        let images = frames
+            .iter()
+            .map(|frame| render_image(frame, options))
+            .collect::<Result<Vec<_>>>()?;
+        let width = images.iter().map(|i| i.width()).max().unwrap_or(1);
+        let height = images.iter().map(|i| i.height()).max().unwrap_or(1);
+        let (_, background) = options.colors();
+
+        let file = std::fs::File::create(file_name).map_err(|e| save_error(&e))?;
+        let mut encoder = GifEncoder::new(std::io::BufWriter::new(file));
+        encoder
+            .set_repeat(Repeat::Infinite)
+            .map_err(|e| save_error(&e))?;
+        for image in images {
+            let mut canvas = image::RgbaImage::from_pixel(width, height, image::Rgba(background.0));
+            image::imageops::overlay(
+                &mut canvas,
+                &image,
+                ((width - image.width()) / 2) as i64,
+                ((height - image.height()) / 2) as i64,
+            );
+            encoder
+                .encode_frame(Frame::from_parts(
+                    canvas,
+                    0,
+                    0,
+                    Delay::from_numer_denom_ms(frame_delay_ms, 1),
+                ))
+                .map_err(|e| save_error(&e))?;
+        }
+        Ok(())
+    }
+
+    #[cfg(not(feature = "image_formats"))]
+    {
+        let _ = (frames, options, frame_delay_ms);
+        Err(Exceptions::unsupported_operation_with(format!(
+            "could not save file '{file_name}': GIF output needs the image_formats feature"
+        )))
+    }
+}
+
+#[cfg(feature = "image_formats")]
+fn save_png(file_name: &str, raster: &RgbaRaster, dpi: u32) -> std::result::Result<(), String> {
+    let file = std::fs::File::create(file_name).map_err(|e| e.to_string())?;
+    let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), raster.width, raster.height);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    // PNG records pixels per metre
+    let pixels_per_metre = (dpi as f64 / 0.0254).round() as u32;
+    encoder.set_pixel_dims(Some(png::PixelDimensions {
+        xppu: pixels_per_metre,
+        yppu: pixels_per_metre,
+        unit: png::Unit::Meter,
+    }));
+    let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+    writer
+        .write_image_data(&raster.pixels)
+        .map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())
+}
+
+#[cfg(feature = "image_formats")]
+fn save_tiff(file_name: &str, raster: &RgbaRaster, dpi: u32) -> std::result::Result<(), String> {
+    use tiff::encoder::{colortype::RGBA8, Rational, TiffEncoder};
+    use tiff::tags::ResolutionUnit;
+
+    let file = std::fs::File::create(file_name).map_err(|e| e.to_string())?;
+    let mut encoder = TiffEncoder::new(std::io::BufWriter::new(file)).map_err(|e| e.to_string())?;
+    let mut image = encoder
+        .new_image::<RGBA8>(raster.width, raster.height)
+        .map_err(|e| e.to_string())?;
+    image.resolution(ResolutionUnit::Inch, Rational { n: dpi, d: 1 });
+    image.write_data(&raster.pixels).map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod RasterTestCase {
+    use crate::{
+        common::BitMatrix,
+        render::{render, ModuleSize, RenderOptions, Rgba, RgbaRaster},
+        Exceptions,
+    };
+
+    use super::{render_image, save_animated_gif, save_raster};
+
+    fn matrix() -> BitMatrix {
+        let mut matrix = BitMatrix::new(3, 3).unwrap();
+        matrix.set(1, 1);
+        matrix
+    }
+
+    #[test]
+    fn testRenderImage() {
+        let options = RenderOptions {
+            module_size: ModuleSize::Pixels(2),
+            background: Rgba::TRANSPARENT,
+            ..Default::default()
+        };
+        let image = render_image(&matrix(), &options).unwrap();
+        assert_eq!(image.dimensions(), (6, 6));
+        assert_eq!(image.get_pixel(2, 3).0, Rgba::BLACK.0);
+        assert_eq!(image.get_pixel(0, 0).0, Rgba::TRANSPARENT.0);
+    }
+
+    #[test]
+    fn testMismatchedRaster() {
+        let raster = RgbaRaster {
+            width: 2,
+            height: 2,
+            dpi: None,
+            pixels: vec![0; 12],
+        };
+        assert!(matches!(
+            image::RgbaImage::try_from(raster),
+            Err(Exceptions::IllegalArgumentException(_))
+        ));
+    }
+
+    #[cfg(feature = "image_formats")]
+    #[test]
+    fn testAnimatedGif() {
+        use image::AnimationDecoder;
+
+        let path = std::env::temp_dir().join("rxing_render_animated_test.gif");
+        let mut large = BitMatrix::new(5, 5).unwrap();
+        large.set(0, 0);
+        save_animated_gif(
+            path.to_str().unwrap(),
+            &[matrix(), large],
+            &RenderOptions::default(),
+            100,
+        )
+        .unwrap();
+
+        let decoder = image::codecs::gif::GifDecoder::new(std::io::BufReader::new(
+            std::fs::File::open(&path).unwrap(),
+        ))
+        .unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].delay().numer_denom_ms(), (100, 1));
+        // the small frame is centred on the canvas of the large one
+        let first = frames[0].buffer();
+        assert_eq!(first.dimensions(), (5, 5));
+        assert_eq!(first.get_pixel(2, 2).0, Rgba::BLACK.0);
+        assert_eq!(first.get_pixel(0, 0).0, Rgba::WHITE.0);
+        assert_eq!(frames[1].buffer().get_pixel(0, 0).0, Rgba::BLACK.0);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "image_formats")]
+    #[test]
+    fn testPngDpi() {
+        let path = std::env::temp_dir().join("rxing_render_dpi_test.png");
+        let options = RenderOptions {
+            dpi: Some(300),
+            ..Default::default()
+        };
+        save_raster(path.to_str().unwrap(), render(&matrix(), &options).unwrap()).unwrap();
+
+        let decoder =
+            png::Decoder::new(std::io::BufReader::new(std::fs::File::open(&path).unwrap()));
+        let reader = decoder.read_info().unwrap();
+        let dims = reader.info().pixel_dims.unwrap();
+        assert_eq!(dims.unit, png::Unit::Meter);
+        assert_eq!(dims.xppu, 11811);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(feature = "image_formats")]
+    #[test]
+    fn testTiffDpi() {
+        let path = std::env::temp_dir().join("rxing_render_dpi_test.tiff");
+        let options = RenderOptions {
+            dpi: Some(600),
+            module_size: ModuleSize::Pixels(4),
+            ..Default::default()
+        };
+        save_raster(path.to_str().unwrap(), render(&matrix(), &options).unwrap()).unwrap();
+
+        let mut decoder = tiff::decoder::Decoder::new(std::fs::File::open(&path).unwrap()).unwrap();
+        assert_eq!(decoder.dimensions().unwrap(), (12, 12));
+        assert_eq!(
+            decoder
+                .get_tag_u32(tiff::tags::Tag::ResolutionUnit)
+                .unwrap(),
+            2
+        );
+        let image = image::open(&path).unwrap().into_rgba8();
+        assert_eq!(image.get_pixel(5, 5).0, Rgba::BLACK.0);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,530 @@
+use crate::{
+    common::{BitMatrix, HybridBinarizer, Result},
+    qrcode::{
+        cpp_port::QrReader,
+        decoder::{ErrorCorrectionLevel, Version},
+        encoder::{qrcode_encoder, QRCode},
+    },
+    BinaryBitmap, EncodeHints, Exceptions, Luma8LuminanceSource, Reader,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{Rgba, RgbaRaster};
+
+/// Side of a finder pattern, in modules.
+const FINDER_SIZE: u32 = 7;
+
+/// How the dark data modules of a styled QR code are drawn.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum ModuleShape {
+    #[default]
+    Square,
+    /// A round dot in the middle of each module.
+    Dot,
+    /// Squares that join their dark neighbours, with the free corners rounded off.
+    Rounded,
+}
+
+/// How the three finder patterns, the "eyes", of a styled QR code are drawn.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub enum FinderShape {
+    #[default]
+    Square,
+    /// Square rings and centres with rounded corners.
+    Rounded,
+    /// A circular ring around a round centre.
+    Circle,
+}
+
+/// An area in the middle of the symbol that is left empty, optionally with an image drawn into it.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Logo {
+    /// Width of the cutout, in modules.
+    pub width: u32,
+    /// Height of the cutout, in modules.
+    pub height: u32,
+    /// Scaled to fit the cutout, keeping its proportions.
+    pub image: Option<RgbaRaster>,
+}
+
+/// The look of a styled QR code.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct QrStyle {
+    pub module_shape: ModuleShape,
+    pub finder_shape: FinderShape,
+    pub foreground: Rgba,
+    pub background: Rgba,
+    /// Colour of the finder patterns, if it differs from the foreground.
+    pub finder_color: Option<Rgba>,
+    /// Size of a module, in pixels.
+    pub module_size: u32,
+    /// Quiet zone around the symbol, in modules.
+    pub quiet_zone: u32,
+    pub logo: Option<Logo>,
+}
+
+impl Default for QrStyle {
+    fn default() -> Self {
+        Self {
+            module_shape: ModuleShape::default(),
+            finder_shape: FinderShape::default(),
+            foreground: Rgba::BLACK,
+            background: Rgba::WHITE,
+            finder_color: None,
+            module_size: 10,
+            quiet_zone: 4,
+            logo: None,
+        }
+    }
+}
+
+/// The area of the logo cutout, as `(left, top, width, height)` in modules.
+fn cutout(dimension: u32, logo: &Option<Logo>) -> Result<Option<(u32, u32, u32, u32)>> {
+    let Some(logo) = logo else {
+        return Ok(None);
+    };
+    if logo.width > dimension || logo.height > dimension {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "a logo of {}x{} modules does not fit a symbol of {dimension}x{dimension}",
+            logo.width, logo.height
+        )));
+    }
+    Ok(Some((
+        (dimension - logo.width) / 2,
+        (dimension - logo.height) / 2,
+        logo.width,
+        logo.height,
+    )))
+}
+
+fn in_area((left, top, width, height): (u32, u32, u32, u32), x: u32, y: u32) -> bool {
+    x >= left && x < left + width && y >= top && y < top + height
+}
+
+/// The modules of the alignment patterns of `version`.
+fn alignment_patterns(version: &Version, dimension: u32) -> Result<BitMatrix> {
+    let mut patterns = BitMatrix::with_single_dimension(dimension)?;
+    let centers = version.getAlignmentPatternCenters();
+    let last = centers.len().saturating_sub(1);
+    for (i, &x) in centers.iter().enumerate() {
+        for (j, &y) in centers.iter().enumerate() {
+            // the corners with finder patterns have none
+            if (i == 0 && (j == 0 || j == last)) || (i == last && j == 0) {
+                continue;
+            }
+            patterns.setRegion(x - 2, y - 2, 5, 5)?;
+        }
+    }
+    Ok(patterns)
+}
+
+/**
+ * The number of codewords of each error correction block that the decoder can restore, after
+ * the misdecode protection codewords that small symbols keep back (ISO 18004, table 9).
+ */
+fn correctable_per_block(version: &Version, ec_level: ErrorCorrectionLevel) -> u32 {
+    let ec_codewords = version
+        .getECBlocksForLevel(ec_level)
+        .getECCodewordsPerBlock();
+    let protection = match (version.getVersionNumber(), ec_level) {
+        (1, ErrorCorrectionLevel::L) => 3,
+        (1, ErrorCorrectionLevel::M) | (2, ErrorCorrectionLevel::L) => 2,
+        (1, _) | (3, ErrorCorrectionLevel::L) => 1,
+        _ => 0,
+    };
+    (ec_codewords - protection) / 2
+}
+
+/**
+ * Checks that the error correction of `version` at `ec_level` can restore everything under
+ * `area`: it must leave the finder patterns, timing patterns, format and version information
+ * alone, and damage no more codewords of any block than that block can correct.
+ */
+fn check_cutout(
+    version: &Version,
+    ec_level: ErrorCorrectionLevel,
+    area: (u32, u32, u32, u32),
+) -> Result<()> {
+    let function_pattern = version.buildFunctionPattern()?;
+    let dimension = function_pattern.getWidth();
+    let alignment = alignment_patterns(version, dimension)?;
+    for y in 0..dimension {
+        for x in 0..dimension {
+            if in_area(area, x, y) && function_pattern.get(x, y) && !alignment.get(x, y) {
+                return Err(Exceptions::illegal_argument_with(
+                    "the logo covers finder, timing, format or version modules",
+                ));
+            }
+        }
+    }
+
+    // the codewords in the order they are placed, two columns at a time from the right
+    let mut damaged = vec![false; version.getTotalCodewords() as usize];
+    let mut bit = 0;
+    let mut upwards = true;
+    let mut column = dimension as i32 - 1;
+    while column > 0 {
+        if column == 6 {
+            column -= 1;
+        }
+        for count in 0..dimension {
+            let y = if upwards {
+                dimension - 1 - count
+            } else {
+                count
+            };
+            for x in [column as u32, column as u32 - 1] {
+                if function_pattern.get(x, y) {
+                    continue;
+                }
+                if let Some(codeword) = damaged.get_mut(bit / 8) {
+                    *codeword |= in_area(area, x, y);
+                }
+                bit += 1;
+            }
+        }
+        upwards = !upwards;
+        column -= 2;
+    }
+
+    // codewords are interleaved: the data codewords of all blocks in turn, then their EC
+    // codewords
+    let ec_blocks = version.getECBlocksForLevel(ec_level);
+    let data_lengths: Vec<u32> = ec_blocks
+        .getECBlocks()
+        .iter()
+        .flat_map(|block| std::iter::repeat_n(block.getDataCodewords(), block.getCount() as usize))
+        .collect();
+    let mut owners = Vec::with_capacity(damaged.len());
+    for i in 0..data_lengths.iter().copied().max().unwrap_or(0) {
+        owners.extend((0..data_lengths.len()).filter(|&block| i < data_lengths[block]));
+    }
+    for _ in 0..ec_blocks.getECCodewordsPerBlock() {
+        owners.extend(0..data_lengths.len());
+    }
+    let mut damaged_per_block = vec![0; data_lengths.len()];
+    for (codeword, block) in owners.into_iter().enumerate() {
+        if damaged[codeword] {
+            damaged_per_block[block] += 1;
+        }
+    }
+    let correctable = correctable_per_block(version, ec_level);
+    if let Some(worst) = damaged_per_block
+        .into_iter()
+        .max()
+        .filter(|&worst| worst > correctable)
+    {
+        return Err(Exceptions::illegal_argument_with(format!(
+            "the logo damages {worst} codewords of a block, but error correction level {ec_level:?} only restores {correctable}"
+        )));
+    }
+    Ok(())
+}
+
+/// Whether the point `(u, v)` lies in a square of half size `half` around `center` with
+/// corners rounded to `radius`.
+fn in_rounded_square(u: f32, v: f32, center: f32, half: f32, radius: f32) -> bool {
+    let qx = ((u - center).abs() - (half - radius)).max(0.0);
+    let qy = ((v - center).abs() - (half - radius)).max(0.0);
+    (u - center).abs() <= half && (v - center).abs() <= half && qx * qx + qy * qy <= radius * radius
+}
+
+/// Whether the point `(u, v)`, in modules from the corner of a finder pattern, is dark.
+fn finder_dark(shape: FinderShape, u: f32, v: f32) -> bool {
+    let center = FINDER_SIZE as f32 / 2.0;
+    match shape {
+        FinderShape::Square => {
+            let ring = u.min(v) < 1.0 || u.max(v) >= 6.0;
+            let middle = (2.0..5.0).contains(&u) && (2.0..5.0).contains(&v);
+            ring || middle
+        }
+        FinderShape::Rounded => {
+            (in_rounded_square(u, v, center, 3.5, 1.5)
+                && !in_rounded_square(u, v, center, 2.5, 1.0))
+                || in_rounded_square(u, v, center, 1.5, 0.75)
+        }
+        FinderShape::Circle => {
+            let distance = ((u - center).powi(2) + (v - center).powi(2)).sqrt();
+            (2.5..=3.5).contains(&distance) || distance <= 1.5
+        }
+    }
+}
+
+fn decode(raster: &RgbaRaster) -> Result<String> {
+    let luma = raster
+        .pixels
+        .chunks_exact(4)
+        .map(|pixel| {
+            let gray = 0.299 * pixel[0] as f32 + 0.587 * pixel[1] as f32 + 0.114 * pixel[2] as f32;
+            let alpha = pixel[3] as f32 / u8::MAX as f32;
+            (gray * alpha + u8::MAX as f32 * (1.0 - alpha)) as u8
+        })
+        .collect();
+    let result = QrReader.decode(&mut BinaryBitmap::new(HybridBinarizer::new(
+        Luma8LuminanceSource::new(luma, raster.width, raster.height),
+    )))?;
+    Ok(result.getText().to_owned())
+}
+
+/**
+ * Draws `code`, the QR code of `contents`, in `style`. The finder patterns and the data
+ * modules take the shapes of the style; the timing and alignment patterns and the format and
+ * version information stay square, as the readers expect them.
+ *
+ * The logo cutout must leave the finder, timing, format and version modules alone and damage
+ * no more codewords than the error correction level of `code` restores. Before returning, the
+ * drawing is decoded to make sure it reads as `contents`.
+ */
+pub fn render_styled_qr(code: &QRCode, contents: &str, style: &QrStyle) -> Result<RgbaRaster> {
+    let (Some(version), Some(ec_level), Some(matrix)) =
+        (code.getVersion(), code.getECLevel(), code.getMatrix())
+    else {
+        return Err(Exceptions::illegal_argument_with(
+            "the QR code is not encoded",
+        ));
+    };
+    if style.module_size == 0 {
+        return Err(Exceptions::illegal_argument_with(
+            "module size must be at least 1 pixel",
+        ));
+    }
+    let dimension = matrix.getWidth();
+    let area = cutout(dimension, &style.logo)?;
+    if let Some(area) = area {
+        check_cutout(version, *ec_level, area)?;
+    }
+    let function_pattern = version.buildFunctionPattern()?;
+    let finders = [
+        (0, 0),
+        (dimension - FINDER_SIZE, 0),
+        (0, dimension - FINDER_SIZE),
+    ];
+    let finder_at = |x: u32, y: u32| {
+        finders
+            .iter()
+            .copied()
+            .find(|&(left, top)| in_area((left, top, FINDER_SIZE, FINDER_SIZE), x, y))
+    };
+    let dark = |x: i64, y: i64| {
+        x >= 0
+            && y >= 0
+            && x < dimension as i64
+            && y < dimension as i64
+            && matrix.get(x as u32, y as u32) == 1
+            && !area.is_some_and(|area| in_area(area, x as u32, y as u32))
+    };
+
+    let scale = style.module_size;
+    let size = (dimension + 2 * style.quiet_zone) * scale;
+    let mut raster = RgbaRaster {
+        width: size,
+        height: size,
+        dpi: None,
+        pixels: style
+            .background
+            .0
+            .iter()
+            .copied()
+            .cycle()
+            .take(4 * size as usize * size as usize)
+            .collect(),
+    };
+    let offset = style.quiet_zone * scale;
+    let mut paint = |px: u32, py: u32, color: Rgba| {
+        let index = 4 * (py as usize * size as usize + px as usize);
+        raster.pixels[index..index + 4].copy_from_slice(&color.0);
+    };
+
+    for y in 0..dimension {
+        for x in 0..dimension {
+            for sy in 0..scale {
+                for sx in 0..scale {
+                    let fx = (sx as f32 + 0.5) / scale as f32;
+                    let fy = (sy as f32 + 0.5) / scale as f32;
+                    let (filled, color) = if let Some((left, top)) = finder_at(x, y) {
+                        let u = (x - left) as f32 + fx;
+                        let v = (y - top) as f32 + fy;
+                        (
+                            finder_dark(style.finder_shape, u, v),
+                            style.finder_color.unwrap_or(style.foreground),
+                        )
+                    } else if !dark(x as i64, y as i64) {
+                        (false, style.foreground)
+                    } else if function_pattern.get(x, y) {
+                        (true, style.foreground)
+                    } else {
+                        let round = (fx - 0.5).powi(2) + (fy - 0.5).powi(2);
+                        let filled = match style.module_shape {
+                            ModuleShape::Square => true,
+                            ModuleShape::Dot => round <= 0.45 * 0.45,
+                            ModuleShape::Rounded => {
+                                let dx = if fx < 0.5 { -1 } else { 1 };
+                                let dy = if fy < 0.5 { -1 } else { 1 };
+                                dark(x as i64 + dx, y as i64)
+                                    || dark(x as i64, y as i64 + dy)
+                                    || round <= 0.25
+                            }
+                        };
+                        (filled, style.foreground)
+                    };
+                    if filled {
+                        paint(offset + x * scale + sx, offset + y * scale + sy, color);
+                    }
+                }
+            }
+        }
+    }
+
+    if let (
+        Some((left, top, width, height)),
+        Some(Logo {
+            image: Some(image), ..
+        }),
+    ) = (area, &style.logo)
+    {
+        // the largest size that fits, centred in the cutout
+        let (box_width, box_height) = (width * scale, height * scale);
+        let ratio =
+            (box_width as f32 / image.width as f32).min(box_height as f32 / image.height as f32);
+        let (logo_width, logo_height) = (
+            (image.width as f32 * ratio) as u32,
+            (image.height as f32 * ratio) as u32,
+        );
+        let logo_left = offset + left * scale + (box_width - logo_width) / 2;
+        let logo_top = offset + top * scale + (box_height - logo_height) / 2;
+        for y in 0..logo_height {
+            for x in 0..logo_width {
+                let source = image.get(
+                    ((x as f32 / ratio) as u32).min(image.width - 1),
+                    ((y as f32 / ratio) as u32).min(image.height - 1),
+                );
+                let alpha = source.0[3] as u32;
+                let index =
+                    4 * ((logo_top + y) as usize * size as usize + (logo_left + x) as usize);
+                let under = &mut raster.pixels[index..index + 4];
+                for (channel, &over) in under.iter_mut().zip(&source.0).take(3) {
+                    *channel =
+                        ((over as u32 * alpha + *channel as u32 * (255 - alpha)) / 255) as u8;
+                }
+                under[3] = under[3].max(source.0[3]);
+            }
+        }
+    }
+
+    match decode(&raster) {
+        Ok(text) if text == contents => Ok(raster),
+        _ => Err(Exceptions::illegal_argument_with(
+            "the styled symbol does not decode, try plainer shapes, a smaller logo or a higher error correction level",
+        )),
+    }
+}
+
+/// Encodes `contents` as a QR code at `ec_level` and draws it in `style`.
+pub fn encode_styled_qr(
+    contents: &str,
+    ec_level: ErrorCorrectionLevel,
+    hints: &EncodeHints,
+    style: &QrStyle,
+) -> Result<RgbaRaster> {
+    let code = qrcode_encoder::encode_with_hints(contents, ec_level, hints)?;
+    render_styled_qr(&code, contents, style)
+}
+
+#[cfg(test)]
+mod StyledQrTestCase {
+    use crate::{
+        qrcode::decoder::ErrorCorrectionLevel,
+        render::{Rgba, RgbaRaster},
+        EncodeHints,
+    };
+
+    use super::{encode_styled_qr, FinderShape, Logo, ModuleShape, QrStyle};
+
+    const CONTENTS: &str = "https://example.com/styled-qr-code";
+
+    #[test]
+    fn testShapes() {
+        for (module_shape, finder_shape) in [
+            (ModuleShape::Dot, FinderShape::Circle),
+            (ModuleShape::Rounded, FinderShape::Rounded),
+        ] {
+            let style = QrStyle {
+                module_shape,
+                finder_shape,
+                finder_color: Some(Rgba([0x20, 0x40, 0x80, 0xff])),
+                ..Default::default()
+            };
+            let raster = encode_styled_qr(
+                CONTENTS,
+                ErrorCorrectionLevel::M,
+                &EncodeHints::default(),
+                &style,
+            )
+            .expect("styled symbol decodes");
+            // version 3 is 29 modules, plus the quiet zone, at 10 pixels each
+            assert_eq!(raster.width, 37 * 10);
+            // the centre of the top left eye is dark, the very corner of a round eye is not
+            assert_eq!(raster.get(75, 75), Rgba([0x20, 0x40, 0x80, 0xff]));
+            if finder_shape == FinderShape::Circle {
+                assert_eq!(raster.get(40, 40), Rgba::WHITE);
+            }
+        }
+    }
+
+    #[test]
+    fn testLogo() {
+        let red = RgbaRaster {
+            width: 2,
+            height: 1,
+            dpi: None,
+            pixels: [255, 0, 0, 255].repeat(2),
+        };
+        let style = QrStyle {
+            logo: Some(Logo {
+                width: 9,
+                height: 9,
+                image: Some(red),
+            }),
+            ..Default::default()
+        };
+        let raster = encode_styled_qr(
+            CONTENTS,
+            ErrorCorrectionLevel::H,
+            &EncodeHints::default(),
+            &style,
+        )
+        .expect("logo within the error correction budget");
+        let middle = raster.width / 2;
+        assert_eq!(raster.get(middle, middle), Rgba([255, 0, 0, 255]));
+
+        // level L cannot restore that much
+        assert!(encode_styled_qr(
+            CONTENTS,
+            ErrorCorrectionLevel::L,
+            &EncodeHints::default(),
+            &style
+        )
+        .is_err());
+
+        // a cutout over the timing patterns is refused at any level
+        let style = QrStyle {
+            logo: Some(Logo {
+                width: 29,
+                height: 3,
+                image: None,
+            }),
+            ..Default::default()
+        };
+        assert!(encode_styled_qr(
+            CONTENTS,
+            ErrorCorrectionLevel::H,
+            &EncodeHints::default(),
+            &style
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use svg::node::element::{Description, Path, Rectangle, Text, Title};
+
+use crate::common::{BitMatrix, Result};
+
+use super::{outline_path_data, trace_outlines, ModuleSize, RenderOptions, Rgba};
+
+/// Options that only apply to SVG output.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub struct SvgOptions {
+    /// Width of the document, with its unit, for example `"40mm"`. The drawing is scaled to it
+    /// through the `viewBox`. Taken from the module size of the `RenderOptions` if not given.
+    pub width: Option<String>,
+    /// Height of the document, with its unit.
+    pub height: Option<String>,
+    /// Accessible name of the image.
+    pub title: Option<String>,
+    /// Accessible description of the image.
+    pub description: Option<String>,
+    /// Text printed centred under the symbol.
+    pub text: Option<String>,
+}
+
+fn color(rgba: Rgba) -> (String, Option<String>) {
+    let [r, g, b, a] = rgba.0;
+    let opacity = (a != u8::MAX).then(|| format!("{:.3}", a as f32 / u8::MAX as f32));
+    (format!("#{r:02x}{g:02x}{b:02x}"), opacity)
+}
+
+/**
+ * Renders `matrix` as an SVG document. The dark modules are traced into a single path, so
+ * the document stays small and viewers show no seams between modules. The `viewBox` is in
+ * modules, with the quiet zone of `options` around the matrix.
+ */
+pub fn render_svg(
+    matrix: &BitMatrix,
+    options: &RenderOptions,
+    svg_options: &SvgOptions,
+) -> svg::Document {
+    let quiet_zone = options.quiet_zone;
+    let full_width = matrix.getWidth() + 2 * quiet_zone;
+    let symbol_height = matrix.getHeight() + 2 * quiet_zone;
+    let font_size = (full_width / 8).clamp(2, 10);
+    let full_height = if svg_options.text.is_some() {
+        symbol_height + font_size + font_size / 2
+    } else {
+        symbol_height
+    };
+    let (dark, light) = options.colors();
+
+    let mut document = svg::Document::new()
+        .set("viewBox", (0, 0, full_width, full_height))
+        .set("shape-rendering", "crispEdges");
+
+    let (width, height) = match options.module_size {
+        ModuleSize::Pixels(scale) => (
+            Some((full_width * scale).to_string()),
+            Some((full_height * scale).to_string()),
+        ),
+        ModuleSize::Target { width, height } => (Some(width.to_string()), Some(height.to_string())),
+    };
+    if let Some(width) = svg_options.width.clone().or(width) {
+        document = document.set("width", width);
+    }
+    if let Some(height) = svg_options.height.clone().or(height) {
+        document = document.set("height", height);
+    }
+
+    if svg_options.title.is_some() || svg_options.description.is_some() {
+        document = document.set("role", "img");
+    }
+    if let Some(title) = &svg_options.title {
+        document = document
+            .set("aria-labelledby", "title")
+            .add(Title::new(title.as_str()).set("id", "title"));
+    }
+    if let Some(description) = &svg_options.description {
+        document = document.set("aria-describedby", "desc").add(
+            Description::new()
+                .set("id", "desc")
+                .add(svg::node::Text::new(description.as_str())),
+        );
+    }
+
+    if light.0[3] != 0 {
+        let (fill, opacity) = color(light);
+        let mut background = Rectangle::new()
+            .set("width", full_width)
+            .set("height", full_height)
+            .set("fill", fill);
+        if let Some(opacity) = opacity {
+            background = background.set("fill-opacity", opacity);
+        }
+        document = document.add(background);
+    }
+
+    let (fill, opacity) = color(dark);
+    let outlines = trace_outlines(matrix);
+    if !outlines.is_empty() {
+        let mut path = Path::new()
+            .set("d", outline_path_data(&outlines, (quiet_zone, quiet_zone)))
+            .set("fill", fill.as_str())
+            .set("shape-rendering", "crispEdges");
+        if let Some(opacity) = &opacity {
+            path = path.set("fill-opacity", opacity.as_str());
+        }
+        document = document.add(path);
+    }
+
+    if let Some(text) = &svg_options.text {
+        let mut label = Text::new(text.as_str())
+            .set("x", full_width as f32 / 2.0)
+            .set("y", symbol_height + font_size)
+            .set("font-family", "monospace")
+            .set("font-size", font_size)
+            .set("text-anchor", "middle")
+            .set("fill", fill.as_str());
+        if let Some(opacity) = &opacity {
+            label = label.set("fill-opacity", opacity.as_str());
+        }
+        document = document.add(label);
+    }
+
+    document
+}
+
+/// Saves `matrix` as an SVG file.
+pub fn save_svg_with_options(
+    file_name: &str,
+    matrix: &BitMatrix,
+    options: &RenderOptions,
+    svg_options: &SvgOptions,
+) -> Result<()> {
+    svg::save(file_name, &render_svg(matrix, options, svg_options)).map_err(|err| {
+        crate::Exceptions::illegal_argument_with(format!(
+            "could not save file '{file_name}': {err}"
+        ))
+    })
+}
+
+#[cfg(test)]
+mod SvgTestCase {
+    use crate::{
+        common::BitMatrix,
+        qrcode::QRCodeWriter,
+        render::{ModuleSize, RenderOptions, Rgba},
+        BarcodeFormat, EncodeHintValue, EncodeHints, Writer,
+    };
+
+    use super::{render_svg, SvgOptions};
+
+    #[test]
+    fn testSinglePath() {
+        let matrix = QRCodeWriter
+            .encode_with_hints(
+                &"x".repeat(2000),
+                &BarcodeFormat::QR_CODE,
+                0,
+                0,
+                &EncodeHints::default().with(EncodeHintValue::Margin("0".to_owned())),
+            )
+            .expect("encode");
+        let svg =
+            render_svg(&matrix, &RenderOptions::default(), &SvgOptions::default()).to_string();
+        assert_eq!(svg.matches("<path").count(), 1);
+        assert!(!svg.contains("<rect x="));
+        assert!(svg.contains("shape-rendering=\"crispEdges\""));
+    }
+
+    #[test]
+    fn testDocument() {
+        let mut matrix = BitMatrix::new(3, 3).unwrap();
+        matrix.setRegion(0, 0, 2, 2).unwrap();
+        let options = RenderOptions {
+            foreground: Rgba([0x11, 0x22, 0x33, 0xff]),
+            background: Rgba::TRANSPARENT,
+            module_size: ModuleSize::Pixels(10),
+            quiet_zone: 1,
+            ..Default::default()
+        };
+        let svg_options = SvgOptions {
+            title: Some("Label <1>".to_owned()),
+            description: Some("A test".to_owned()),
+            ..Default::default()
+        };
+        let svg = render_svg(&matrix, &options, &svg_options).to_string();
+        assert!(svg.contains("viewBox=\"0 0 5 5\""));
+        assert!(svg.contains("width=\"50\""));
+        assert!(svg.contains("d=\"M1 1H3V3H1Z\""));
+        assert!(svg.contains("fill=\"#112233\""));
+        assert!(svg.contains("<title id=\"title\">Label &lt;1&gt;</title>"));
+        assert!(svg.contains("<desc id=\"desc\">A test</desc>"));
+        assert!(svg.contains("role=\"img\""));
+        // no background rectangle when it is transparent
+        assert!(!svg.contains("<rect"));
+    }
+
+    #[test]
+    fn testTextAndSize() {
+        let mut matrix = BitMatrix::new(40, 10).unwrap();
+        matrix.setRegion(0, 0, 1, 10).unwrap();
+        let svg_options = SvgOptions {
+            width: Some("40mm".to_owned()),
+            height: Some("12mm".to_owned()),
+            text: Some("1234".to_owned()),
+            ..Default::default()
+        };
+        let svg = render_svg(&matrix, &RenderOptions::default(), &svg_options).to_string();
+        assert!(svg.contains("width=\"40mm\""));
+        assert!(svg.contains("viewBox=\"0 0 40 17\""));
+        assert!(svg.contains("text-anchor=\"middle\" x=\"20\" y=\"15\""));
+        assert!(svg.contains("1234"));
+        assert!(svg.contains("<rect fill=\"#ffffff\" height=\"17\" width=\"40\"/>"));
+    }
+}
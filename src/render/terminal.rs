@@ -0,0 +1,144 @@
+use crate::common::BitMatrix;
+
+const UPPER_HALF: char = '▀';
+const LOWER_HALF: char = '▄';
+const FULL_BLOCK: char = '█';
+
+const ANSI_BLACK_FOREGROUND: &str = "30";
+const ANSI_WHITE_FOREGROUND: &str = "97";
+const ANSI_BLACK_BACKGROUND: &str = "40";
+const ANSI_WHITE_BACKGROUND: &str = "107";
+const ANSI_RESET: &str = "\x1b[0m";
+
+/// Options for drawing a module matrix as text in a terminal.
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct TerminalOptions {
+    /// Quiet zone added around the matrix, in modules, on top of the writer's margin.
+    pub quiet_zone: u32,
+    /// Sets the colours of every character with ANSI escape codes, so the symbol is black on
+    /// white whatever the colours of the terminal are.
+    pub ansi_colors: bool,
+    /// Without ANSI colours, the block characters take the text colour of the terminal. Most
+    /// terminals draw light text on a dark background, so the blocks are printed for the light
+    /// modules. Set this to false for terminals with dark text on a light background.
+    pub dark_background: bool,
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        Self {
+            quiet_zone: 0,
+            ansi_colors: false,
+            dark_background: true,
+        }
+    }
+}
+
+/**
+ * Draws `matrix` with Unicode half block characters, two module rows per line of text, so that
+ * each module is about square in a terminal. Every line ends in `\n`.
+ */
+pub fn render_terminal(matrix: &BitMatrix, options: &TerminalOptions) -> String {
+    let quiet_zone = options.quiet_zone as i64;
+    let width = matrix.getWidth() as i64 + 2 * quiet_zone;
+    let height = matrix.getHeight() as i64 + 2 * quiet_zone;
+    let dark = |x: i64, y: i64| {
+        let (x, y) = (x - quiet_zone, y - quiet_zone);
+        x >= 0
+            && y >= 0
+            && x < matrix.getWidth() as i64
+            && y < matrix.getHeight() as i64
+            && matrix.get(x as u32, y as u32)
+    };
+
+    let mut output = String::new();
+    for y in (0..height).step_by(2) {
+        for x in 0..width {
+            let top = dark(x, y);
+            // an odd last row is completed with the light quiet zone
+            let bottom = y + 1 < height && dark(x, y + 1);
+            if options.ansi_colors {
+                output.push_str(&format!(
+                    "\x1b[{};{}m{UPPER_HALF}",
+                    if top {
+                        ANSI_BLACK_FOREGROUND
+                    } else {
+                        ANSI_WHITE_FOREGROUND
+                    },
+                    if bottom {
+                        ANSI_BLACK_BACKGROUND
+                    } else {
+                        ANSI_WHITE_BACKGROUND
+                    }
+                ));
+                continue;
+            }
+            let (top, bottom) = if options.dark_background {
+                (!top, y + 1 < height && !bottom)
+            } else {
+                (top, bottom)
+            };
+            output.push(match (top, bottom) {
+                (true, true) => FULL_BLOCK,
+                (true, false) => UPPER_HALF,
+                (false, true) => LOWER_HALF,
+                (false, false) => ' ',
+            });
+        }
+        if options.ansi_colors {
+            output.push_str(ANSI_RESET);
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod TerminalTestCase {
+    use crate::common::BitMatrix;
+
+    use super::{render_terminal, TerminalOptions};
+
+    fn matrix() -> BitMatrix {
+        // X.
+        // XX
+        // .X
+        let mut matrix = BitMatrix::new(2, 3).unwrap();
+        matrix.set(0, 0);
+        matrix.set(0, 1);
+        matrix.set(1, 1);
+        matrix.set(1, 2);
+        matrix
+    }
+
+    #[test]
+    fn testLightBackground() {
+        let options = TerminalOptions {
+            dark_background: false,
+            ..Default::default()
+        };
+        assert_eq!(render_terminal(&matrix(), &options), "█▄\n ▀\n");
+    }
+
+    #[test]
+    fn testDarkBackground() {
+        let options = TerminalOptions {
+            quiet_zone: 1,
+            ..Default::default()
+        };
+        // the light modules are drawn, and the lower half of the last line stays blank
+        assert_eq!(render_terminal(&matrix(), &options), "█▀██\n█▄ █\n▀▀▀▀\n");
+    }
+
+    #[test]
+    fn testAnsiColors() {
+        let options = TerminalOptions {
+            ansi_colors: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            render_terminal(&matrix(), &options),
+            "\x1b[30;40m▀\x1b[97;40m▀\x1b[0m\n\x1b[97;107m▀\x1b[30;107m▀\x1b[0m\n"
+        );
+    }
+}
@@ -0,0 +1,414 @@
+use std::{path::Path, str::FromStr};
+
+use crate::{
+    common::{BitMatrix, Result},
+    Exceptions,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::{trace_outlines, GridPoint, RenderOptions, Rgba};
+
+const POINTS_PER_INCH: f32 = 72.0;
+const MILLIMETERS_PER_INCH: f32 = 25.4;
+const MILS_PER_INCH: f32 = 1000.0;
+
+/// A length on paper.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PhysicalLength {
+    Millimeters(f32),
+    /// Thousandths of an inch, the unit X-dimensions are usually given in.
+    Mils(f32),
+    /// PostScript points, 1/72 of an inch.
+    Points(f32),
+}
+
+impl PhysicalLength {
+    pub fn to_inches(&self) -> f32 {
+        match *self {
+            PhysicalLength::Millimeters(mm) => mm / MILLIMETERS_PER_INCH,
+            PhysicalLength::Mils(mils) => mils / MILS_PER_INCH,
+            PhysicalLength::Points(points) => points / POINTS_PER_INCH,
+        }
+    }
+
+    pub fn to_points(&self) -> f32 {
+        self.to_inches() * POINTS_PER_INCH
+    }
+
+    pub fn to_millimeters(&self) -> f32 {
+        self.to_inches() * MILLIMETERS_PER_INCH
+    }
+
+    /// This length in the dots of a printer with a resolution of `dpi`, not rounded.
+    pub fn to_dots(&self, dpi: u32) -> f32 {
+        self.to_inches() * dpi as f32
+    }
+}
+
+impl FromStr for PhysicalLength {
+    type Err = Exceptions;
+
+    /// Parses a number followed by `mm`, `mil` or `pt`, for example `0.33mm` or `13mil`.
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim().to_lowercase();
+        let (number, constructor): (&str, fn(f32) -> PhysicalLength) =
+            if let Some(number) = s.strip_suffix("mm") {
+                (number, PhysicalLength::Millimeters)
+            } else if let Some(number) = s.strip_suffix("mils").or(s.strip_suffix("mil")) {
+                (number, PhysicalLength::Mils)
+            } else if let Some(number) = s.strip_suffix("pt") {
+                (number, PhysicalLength::Points)
+            } else {
+                return Err(Exceptions::illegal_argument_with(format!(
+                    "length needs a unit of mm, mil or pt: {s}"
+                )));
+            };
+        match number.trim().parse::<f32>() {
+            Ok(value) if value.is_finite() && value > 0.0 => Ok(constructor(value)),
+            _ => Err(Exceptions::illegal_argument_with(format!(
+                "not a positive length: {s}"
+            ))),
+        }
+    }
+}
+
+/// The physical size of the modules in vector output.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct PrintOptions {
+    /// Width of one module, the X-dimension of the symbol. The quiet zone of the
+    /// `RenderOptions` is measured in the same modules.
+    pub x_dimension: PhysicalLength,
+    /// Height of one row of the matrix, if it differs from the width. Height modulated postal
+    /// symbols, encoded with a height of 0, have one row per vertical unit of the bars, so this
+    /// sets the height of their tracker.
+    pub y_dimension: Option<PhysicalLength>,
+}
+
+impl Default for PrintOptions {
+    /// One point per module, the size a 72 dpi image of the matrix would be printed at.
+    fn default() -> Self {
+        Self {
+            x_dimension: PhysicalLength::Points(1.0),
+            y_dimension: None,
+        }
+    }
+}
+
+/// The page and the placement of the modules on it, in points, with the origin at the bottom
+/// left as in PostScript and PDF.
+struct PageLayout {
+    width: f32,
+    height: f32,
+    module_width: f32,
+    module_height: f32,
+}
+
+impl PageLayout {
+    fn new(matrix: &BitMatrix, options: &RenderOptions, print_options: &PrintOptions) -> Self {
+        let module_width = print_options.x_dimension.to_points();
+        let module_height = print_options
+            .y_dimension
+            .map(|length| length.to_points())
+            .unwrap_or(module_width);
+        let quiet_zone = options.quiet_zone as f32;
+        Self {
+            width: (matrix.getWidth() as f32 + 2.0 * quiet_zone) * module_width,
+            height: (matrix.getHeight() as f32 + 2.0 * quiet_zone) * module_height,
+            module_width,
+            module_height,
+        }
+    }
+
+    /// Maps a corner of the module grid onto the page.
+    fn place(&self, (x, y): GridPoint, quiet_zone: u32) -> (f32, f32) {
+        (
+            (x + quiet_zone) as f32 * self.module_width,
+            self.height - (y + quiet_zone) as f32 * self.module_height,
+        )
+    }
+}
+
+/// Formats `value` with at most four decimals and no trailing zeros.
+fn number(value: f32) -> String {
+    let formatted = format!("{value:.4}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_owned()
+    } else {
+        trimmed.to_owned()
+    }
+}
+
+fn rgb(color: Rgba) -> String {
+    let [r, g, b, _] = color.0;
+    [r, g, b]
+        .iter()
+        .map(|channel| number(*channel as f32 / u8::MAX as f32))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/**
+ * The drawing shared by PostScript and PDF: a background rectangle unless the background is
+ * transparent, then the traced outlines of the dark modules filled with the nonzero rule.
+ * `operators` names the set colour, move, line, close path, rectangle and fill operators.
+ */
+fn drawing(
+    matrix: &BitMatrix,
+    options: &RenderOptions,
+    layout: &PageLayout,
+    operators: [&str; 6],
+) -> String {
+    let [color, move_to, line_to, close, rectangle, fill] = operators;
+    let (dark, light) = options.colors();
+    let mut drawing = String::new();
+    if light.0[3] != 0 {
+        drawing.push_str(&format!(
+            "{} {color}\n0 0 {} {} {rectangle}\n",
+            rgb(light),
+            number(layout.width),
+            number(layout.height)
+        ));
+        if rectangle == "re" {
+            drawing.push_str(&format!("{fill}\n"));
+        }
+    }
+    let outlines = trace_outlines(matrix);
+    if outlines.is_empty() {
+        return drawing;
+    }
+    drawing.push_str(&format!("{} {color}\n", rgb(dark)));
+    for outline in outlines {
+        for (i, corner) in outline.into_iter().enumerate() {
+            let (x, y) = layout.place(corner, options.quiet_zone);
+            let operator = if i == 0 { move_to } else { line_to };
+            drawing.push_str(&format!("{} {} {operator} ", number(x), number(y)));
+        }
+        drawing.push_str(close);
+        drawing.push('\n');
+    }
+    drawing.push_str(fill);
+    drawing.push('\n');
+    drawing
+}
+
+/**
+ * Renders `matrix` as Encapsulated PostScript, with each module `print_options.x_dimension`
+ * wide. The colours, inversion and quiet zone come from `options`; PostScript has no
+ * transparency, so only a fully transparent background is honoured, by not painting it.
+ */
+pub fn render_eps(
+    matrix: &BitMatrix,
+    options: &RenderOptions,
+    print_options: &PrintOptions,
+) -> String {
+    let layout = PageLayout::new(matrix, options, print_options);
+    format!(
+        "%!PS-Adobe-3.0 EPSF-3.0\n\
+         %%BoundingBox: 0 0 {} {}\n\
+         %%HiResBoundingBox: 0 0 {} {}\n\
+         %%Creator: rxing\n\
+         %%EndComments\n\
+         gsave\n\
+         {}\
+         grestore\n\
+         showpage\n\
+         %%EOF\n",
+        layout.width.ceil() as u32,
+        layout.height.ceil() as u32,
+        number(layout.width),
+        number(layout.height),
+        drawing(
+            matrix,
+            options,
+            &layout,
+            [
+                "setrgbcolor",
+                "moveto",
+                "lineto",
+                "closepath",
+                "rectfill",
+                "fill"
+            ],
+        ),
+    )
+}
+
+/**
+ * Renders `matrix` as a single page PDF document, the page exactly as large as the symbol
+ * with its quiet zone. The options work as for [`render_eps`].
+ */
+pub fn render_pdf(
+    matrix: &BitMatrix,
+    options: &RenderOptions,
+    print_options: &PrintOptions,
+) -> Vec<u8> {
+    let layout = PageLayout::new(matrix, options, print_options);
+    let content = drawing(matrix, options, &layout, ["rg", "m", "l", "h", "re", "f"]);
+
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_owned(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_owned(),
+        format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << >> /Contents 4 0 R >>",
+            number(layout.width),
+            number(layout.height)
+        ),
+        format!(
+            "<< /Length {} >>\nstream\n{content}endstream",
+            content.len()
+        ),
+    ];
+
+    // the high bytes in the comment mark the file as binary for transfer programs
+    let mut pdf = b"%PDF-1.4\n%\xe2\xe3\xcf\xd3\n".to_vec();
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{object}\nendobj\n", i + 1).as_bytes());
+    }
+    let xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{offset:010} 00000 n \n").as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+            objects.len() + 1
+        )
+        .as_bytes(),
+    );
+    pdf
+}
+
+/// Saves `matrix` as a PDF file if `file_name` ends in `.pdf`, or as (Encapsulated)
+/// PostScript if it ends in `.eps` or `.ps`.
+pub fn save_vector(
+    file_name: &str,
+    matrix: &BitMatrix,
+    options: &RenderOptions,
+    print_options: &PrintOptions,
+) -> Result<()> {
+    let extension = Path::new(file_name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let contents = match extension.as_str() {
+        "pdf" => render_pdf(matrix, options, print_options),
+        "eps" | "ps" => render_eps(matrix, options, print_options).into_bytes(),
+        _ => {
+            return Err(Exceptions::illegal_argument_with(format!(
+                "not a vector format: '{file_name}'"
+            )))
+        }
+    };
+    std::fs::write(file_name, contents).map_err(|err| {
+        Exceptions::illegal_argument_with(format!("could not save file '{file_name}': {err}"))
+    })
+}
+
+#[cfg(test)]
+mod VectorTestCase {
+    use crate::{
+        common::BitMatrix,
+        render::{RenderOptions, Rgba},
+    };
+
+    use super::{render_eps, render_pdf, PhysicalLength, PrintOptions};
+
+    fn square() -> BitMatrix {
+        let mut matrix = BitMatrix::new(3, 3).unwrap();
+        matrix.setRegion(0, 0, 2, 2).unwrap();
+        matrix
+    }
+
+    #[test]
+    fn testPhysicalLength() {
+        assert_eq!(
+            "0.33mm".parse::<PhysicalLength>().unwrap(),
+            PhysicalLength::Millimeters(0.33)
+        );
+        assert_eq!(
+            "13 mil".parse::<PhysicalLength>().unwrap(),
+            PhysicalLength::Mils(13.0)
+        );
+        assert!("13".parse::<PhysicalLength>().is_err());
+        assert!("-1pt".parse::<PhysicalLength>().is_err());
+        assert!((PhysicalLength::Millimeters(25.4).to_points() - 72.0).abs() < 1e-4);
+        assert!((PhysicalLength::Mils(500.0).to_points() - 36.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn testEps() {
+        let options = RenderOptions {
+            quiet_zone: 1,
+            ..Default::default()
+        };
+        let print_options = PrintOptions {
+            x_dimension: PhysicalLength::Mils(50.0),
+            y_dimension: Some(PhysicalLength::Points(2.0)),
+        };
+        let eps = render_eps(&square(), &options, &print_options);
+        assert!(eps.starts_with("%!PS-Adobe-3.0 EPSF-3.0\n"));
+        // 5 modules of 3.6 points by 5 rows of 2 points
+        assert!(eps.contains("%%BoundingBox: 0 0 18 10\n"));
+        assert!(eps.contains("1 1 1 setrgbcolor\n0 0 18 10 rectfill\n"));
+        assert!(eps.contains(
+            "0 0 0 setrgbcolor\n3.6 8 moveto 10.8 8 lineto 10.8 4 lineto 3.6 4 lineto closepath\nfill\n"
+        ));
+        assert!(eps.ends_with("%%EOF\n"));
+
+        // a transparent background is left unpainted
+        let options = RenderOptions {
+            background: Rgba::TRANSPARENT,
+            ..options
+        };
+        assert!(!render_eps(&square(), &options, &print_options).contains("rectfill"));
+    }
+
+    #[test]
+    fn testPdf() {
+        let print_options = PrintOptions {
+            x_dimension: PhysicalLength::Millimeters(25.4),
+            y_dimension: None,
+        };
+        let pdf = render_pdf(&square(), &RenderOptions::default(), &print_options);
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.contains("/MediaBox [0 0 216 216]"));
+        assert!(text.contains("0 216 m 144 216 l 144 72 l 0 72 l h\nf\n"));
+
+        // every offset in the cross-reference table points at its object
+        let find = |needle: &str| {
+            pdf.windows(needle.len())
+                .position(|window| window == needle.as_bytes())
+                .unwrap()
+        };
+        let read_number = |start: usize| -> usize {
+            let digits = pdf[start..]
+                .iter()
+                .take_while(|b| b.is_ascii_digit())
+                .count();
+            std::str::from_utf8(&pdf[start..start + digits])
+                .unwrap()
+                .parse()
+                .unwrap()
+        };
+        let xref = read_number(find("startxref\n") + "startxref\n".len());
+        assert!(pdf[xref..].starts_with(b"xref\n0 5\n0000000000 65535 f \n"));
+        for i in 0..4 {
+            let offset = read_number(xref + "xref\n0 5\n".len() + 20 * (i + 1));
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj\n", i + 1).as_bytes()));
+        }
+        let length = read_number(find("/Length ") + "/Length ".len());
+        let stream = find("stream\n") + "stream\n".len();
+        assert!(pdf[stream + length..].starts_with(b"endstream"));
+    }
+}
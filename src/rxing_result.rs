@@ -131,10 +131,33 @@ impl RXingResult {
             RXingResultMetadataType::STRUCTURED_APPEND_SEQUENCE,
             RXingResultMetadataValue::StructuredAppendSequence(res.structuredAppend().index),
         );
+        if res.structuredAppend().index >= 0 {
+            meta_data.insert(
+                RXingResultMetadataType::STRUCTURED_APPEND,
+                RXingResultMetadataValue::StructuredAppend(res.structuredAppend().clone()),
+            );
+        }
         meta_data.insert(
             RXingResultMetadataType::SYMBOLOGY_IDENTIFIER,
             RXingResultMetadataValue::SymbologyIdentifier(res.symbologyIdentifier()),
         );
+        meta_data.insert(
+            RXingResultMetadataType::IS_MIRRORED,
+            RXingResultMetadataValue::IsMirrored(res.isMirrored()),
+        );
+        if let Some(error_correction) = res.errorCorrection() {
+            meta_data.insert(
+                RXingResultMetadataType::ERRORS_CORRECTED,
+                RXingResultMetadataValue::ErrorsCorrected(error_correction),
+            );
+        }
+
+        if let Some(eci) = res.content().first_eci() {
+            meta_data.insert(
+                RXingResultMetadataType::CHARACTER_SET_ECI,
+                RXingResultMetadataValue::CharacterSetEci(eci as u32),
+            );
+        }
 
         new_res.putAllMetadata(meta_data);
 
@@ -18,7 +18,11 @@
 
 use std::sync::Arc;
 
-use crate::pdf417::PDF417RXingResultMetadata;
+use crate::{
+    common::{cpp_essentials::StructuredAppendInfo, BitMatrix},
+    pdf417::PDF417RXingResultMetadata,
+    Point,
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -109,6 +113,10 @@ pub enum RXingResultMetadataType {
      */
     SYMBOLOGY_IDENTIFIER,
 
+    /**
+     * Whether the symbol was read mirrored, as it is when photographed through glass or printed on
+     * transparent film.
+     */
     IS_MIRRORED,
 
     CONTENT_TYPE,
@@ -120,6 +128,55 @@ pub enum RXingResultMetadataType {
 
     // In a filtered context, what was the final read resolution
     FILTERED_RESOLUTION,
+
+    /**
+     * The GS1 element string of the 2D component of a GS1 Composite symbol. The text of the
+     * result holds the data of both the linear symbol and the component.
+     */
+    GS1_COMPOSITE,
+
+    /**
+     * Whether the text of a Code 39 or Code 93 result was decoded from Full ASCII shift pairs,
+     * such as "+A" for "a".
+     */
+    FULL_ASCII,
+
+    /**
+     * The position of a symbol in a structured append sequence, the number of symbols in it and
+     * the identifier they share: the file ID of Data Matrix, the message ID of Aztec and the
+     * parity, in decimal, of QR Code.
+     */
+    STRUCTURED_APPEND,
+
+    /**
+     * How many codewords Reed-Solomon error correction repaired, and how many it could have.
+     * The closer the errors and erasures come to the capacity, the more marginal the read.
+     */
+    ERRORS_CORRECTED,
+
+    /**
+     * For 1D formats, how many of the scanned rows show the same bars and spaces as the row that
+     * was read, that row included.
+     */
+    ROWS_AGREED,
+
+    /**
+     * The estimated size of a module, the narrowest bar or space of a 1D symbol, in pixels of
+     * the image that was decoded.
+     */
+    MODULE_SIZE,
+
+    /**
+     * The character set ECI a symbol declared for its data, as its assignment number, such as 26
+     * for UTF-8. Symbols without an ECI do not have it.
+     */
+    CHARACTER_SET_ECI,
+
+    /**
+     * Whether a GS1 DataBar or GS1-128 symbol flags that a 2D composite component is printed
+     * above it. Linear symbols without the flag do not have it.
+     */
+    GS1_COMPOSITE_LINKAGE,
 }
 
 impl From<String> for RXingResultMetadataType {
@@ -152,6 +209,18 @@ impl From<String> for RXingResultMetadataType {
             "ISINVERTED" => RXingResultMetadataType::IS_INVERTED,
             "FILTERED_CLOSED" => RXingResultMetadataType::FILTERED_CLOSED,
             "FILTERED_RESOLUTION" => RXingResultMetadataType::FILTERED_RESOLUTION,
+            "GS1_COMPOSITE" | "GS1COMPOSITE" => RXingResultMetadataType::GS1_COMPOSITE,
+            "FULL_ASCII" | "FULLASCII" => RXingResultMetadataType::FULL_ASCII,
+            "STRUCTURED_APPEND" | "STRUCTUREDAPPEND" => RXingResultMetadataType::STRUCTURED_APPEND,
+            "ERRORS_CORRECTED" | "ERRORSCORRECTED" => RXingResultMetadataType::ERRORS_CORRECTED,
+            "ROWS_AGREED" | "ROWSAGREED" => RXingResultMetadataType::ROWS_AGREED,
+            "MODULE_SIZE" | "MODULESIZE" => RXingResultMetadataType::MODULE_SIZE,
+            "CHARACTER_SET_ECI" | "CHARACTERSETECI" | "ECI" => {
+                RXingResultMetadataType::CHARACTER_SET_ECI
+            }
+            "GS1_COMPOSITE_LINKAGE" | "GS1COMPOSITELINKAGE" => {
+                RXingResultMetadataType::GS1_COMPOSITE_LINKAGE
+            }
             _ => RXingResultMetadataType::OTHER,
         }
     }
@@ -246,4 +315,305 @@ pub enum RXingResultMetadataValue {
     FilteredClosed(bool),
 
     FilteredResolution((usize, usize)),
+
+    /**
+     * The GS1 element string of the 2D component of a GS1 Composite symbol
+     */
+    Gs1Composite(String),
+
+    /**
+     * Whether the text of a Code 39 or Code 93 result was decoded from Full ASCII shift pairs
+     */
+    FullAscii(bool),
+
+    /**
+     * The position of a symbol in a structured append sequence, the number of symbols in it and
+     * the identifier they share
+     */
+    StructuredAppend(StructuredAppendInfo),
+
+    /**
+     * How many codewords Reed-Solomon error correction repaired, and how many it could have
+     */
+    ErrorsCorrected(ErrorCorrectionStats),
+
+    /**
+     * How many of the scanned rows show the same symbol
+     */
+    RowsAgreed(u32),
+
+    /**
+     * The estimated size of a module in pixels
+     */
+    ModuleSize(EstimatedModuleSize),
+
+    /**
+     * The assignment number of the character set ECI of a symbol
+     */
+    CharacterSetEci(u32),
+
+    /**
+     * Whether a linear symbol flags a 2D composite component above it
+     */
+    Gs1CompositeLinkage(bool),
+}
+
+/**
+ * The work done by Reed-Solomon error correction on a symbol, summed over its blocks.
+ *
+ * Each error costs two error correction codewords and each erasure, a codeword known to be
+ * unreadable, costs one, so a symbol can be corrected as long as
+ * `2 * errors + erasures <= capacity`.
+ */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Default)]
+pub struct ErrorCorrectionStats {
+    /// The number of codewords that were wrong and corrected
+    pub errors: u32,
+    /// The number of codewords that were known to be unreadable and filled in
+    pub erasures: u32,
+    /// The number of error correction codewords in the symbol
+    pub capacity: u32,
+}
+
+impl ErrorCorrectionStats {
+    /// The share of the capacity that was used, from 0 for a clean read to 1 for a symbol that
+    /// could not have taken one more error.
+    pub fn usage(&self) -> f32 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        (2 * self.errors + self.erasures) as f32 / self.capacity as f32
+    }
+}
+
+/**
+ * The estimated size of a module in pixels, as a reader reports it. Compared by its bits so
+ * that it can be held in metadata.
+ */
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EstimatedModuleSize(pub f32);
+
+impl PartialEq for EstimatedModuleSize {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for EstimatedModuleSize {}
+
+impl EstimatedModuleSize {
+    /// Estimates the module size of a symbol `width` by `height` modules wide from its four
+    /// corners, in order around it.
+    pub(crate) fn from_corners(corners: &[Point], width: u32, height: u32) -> Option<Self> {
+        if corners.len() < 4 || width + height == 0 {
+            return None;
+        }
+        let perimeter: f32 = (0..4)
+            .map(|i| corners[i].distance(corners[(i + 1) % 4]))
+            .sum();
+        Some(Self(perimeter / (2 * (width + height)) as f32))
+    }
+
+    /// Estimates the module size of a symbol `width` modules wide that is alone and upright in
+    /// `image`, as it is for a pure barcode.
+    pub(crate) fn from_pure_symbol(image: &BitMatrix, width: u32) -> Option<Self> {
+        let [_, _, pixels, _] = image.getEnclosingRectangle()?;
+        (width > 0).then(|| Self(pixels as f32 / width as f32))
+    }
+}
+
+#[cfg(test)]
+mod DecodeQualityTestCase {
+    use crate::{
+        aztec::AztecReader,
+        common::{test_image::image, BitMatrix},
+        datamatrix::DataMatrixReader,
+        oned::{rss::RSS14Reader, Code128Reader},
+        pdf417::PDF417Reader,
+        qrcode::{cpp_port::QrReader, QRCodeReader},
+        BarcodeFormat, DecodeHintValue, DecodeHints, MultiFormatWriter, RXingResult, Reader,
+        Writer,
+    };
+
+    use super::{
+        ErrorCorrectionStats, EstimatedModuleSize, RXingResultMetadataType,
+        RXingResultMetadataValue,
+    };
+
+    const SCALE: u32 = 4;
+    /// The quiet zone around the symbol, in pixels.
+    const MARGIN: u32 = 10 * SCALE;
+
+    fn encode(format: BarcodeFormat, contents: &str) -> BitMatrix {
+        // 1D symbols are 20 modules high
+        let height = if matches!(format, BarcodeFormat::CODE_128 | BarcodeFormat::RSS_14) {
+            20
+        } else {
+            0
+        };
+        MultiFormatWriter
+            .encode(contents, &format, 0, height)
+            .expect("encode")
+    }
+
+    fn errors_corrected(result: &RXingResult) -> ErrorCorrectionStats {
+        match result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::ERRORS_CORRECTED)
+        {
+            Some(RXingResultMetadataValue::ErrorsCorrected(stats)) => *stats,
+            other => panic!("no error correction metadata: {other:?}"),
+        }
+    }
+
+    fn assert_module_size(result: &RXingResult) {
+        match result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::MODULE_SIZE)
+        {
+            Some(RXingResultMetadataValue::ModuleSize(EstimatedModuleSize(size))) => assert!(
+                (size - SCALE as f32).abs() < 0.5,
+                "module size {size} instead of {SCALE}"
+            ),
+            other => panic!("no module size metadata: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn testCleanRead() {
+        let result = QrReader
+            .decode(&mut image(
+                &encode(BarcodeFormat::QR_CODE, "CLEAN"),
+                SCALE,
+                MARGIN,
+            ))
+            .expect("decode");
+        let stats = errors_corrected(&result);
+        assert_eq!((stats.errors, stats.erasures), (0, 0));
+        assert_eq!(stats.capacity, 7); // version 1, level L
+        assert_eq!(stats.usage(), 0.0);
+        assert_module_size(&result);
+
+        let result = QRCodeReader
+            .decode(&mut image(
+                &encode(BarcodeFormat::QR_CODE, "CLEAN"),
+                SCALE,
+                MARGIN,
+            ))
+            .expect("decode");
+        assert_eq!(errors_corrected(&result).capacity, 7);
+        assert_module_size(&result);
+
+        let pure = DecodeHints::default().with(DecodeHintValue::PureBarcode(true));
+        let result = QRCodeReader
+            .decode_with_hints(
+                &mut image(&encode(BarcodeFormat::QR_CODE, "CLEAN"), SCALE, MARGIN),
+                &pure,
+            )
+            .expect("decode");
+        assert_module_size(&result);
+    }
+
+    #[test]
+    fn testCorrectedErrors() {
+        let mut matrix = encode(BarcodeFormat::DATA_MATRIX, "CORRECTED ERRORS");
+        // two modules of the data region, far apart
+        matrix.flip_coords(3, 3);
+        matrix.flip_coords(matrix.getWidth() - 4, matrix.getHeight() - 4);
+        let result = DataMatrixReader
+            .decode(&mut image(&matrix, SCALE, MARGIN))
+            .expect("decode");
+        assert_eq!(result.getText(), "CORRECTED ERRORS");
+        let stats = errors_corrected(&result);
+        assert_eq!(stats.errors, 2);
+        assert!(stats.usage() > 0.0 && stats.usage() <= 1.0);
+        assert_module_size(&result);
+
+        let result = PDF417Reader
+            .decode(&mut image(
+                &encode(BarcodeFormat::PDF_417, "PDF417"),
+                SCALE,
+                MARGIN,
+            ))
+            .expect("decode");
+        assert_eq!(errors_corrected(&result).errors, 0);
+        assert!(errors_corrected(&result).capacity > 0);
+    }
+
+    fn assert_rows_agreed(result: &RXingResult) {
+        // the symbol is 20 of the 40 modules of the image high, and rows are 1/32 of it apart
+        match result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::ROWS_AGREED)
+        {
+            Some(RXingResultMetadataValue::RowsAgreed(rows)) => {
+                assert!((15..=17).contains(rows), "{rows} rows agreed")
+            }
+            other => panic!("no rows agreed metadata: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn testRowsAgreed() {
+        let result = Code128Reader
+            .decode(&mut image(
+                &encode(BarcodeFormat::CODE_128, "ROWS"),
+                SCALE,
+                MARGIN,
+            ))
+            .expect("decode");
+        assert_rows_agreed(&result);
+        assert_module_size(&result);
+    }
+
+    #[test]
+    fn testRowsAgreedRss14() {
+        // the reader keeps the pairs of earlier rows, which must not make rows outside the
+        // symbol agree
+        let result = RSS14Reader::default()
+            .decode(&mut image(
+                &encode(BarcodeFormat::RSS_14, "0950110153001"),
+                SCALE,
+                MARGIN,
+            ))
+            .expect("decode");
+        assert_rows_agreed(&result);
+        assert_module_size(&result);
+    }
+
+    #[test]
+    fn testMirrored() {
+        let matrix = encode(BarcodeFormat::AZTEC, "MIRRORED");
+        let result = AztecReader
+            .decode(&mut image(&matrix, SCALE, MARGIN))
+            .expect("decode");
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::IS_MIRRORED),
+            Some(&RXingResultMetadataValue::IsMirrored(false))
+        );
+        assert_module_size(&result);
+
+        let mut mirrored = BitMatrix::new(matrix.getWidth(), matrix.getHeight()).expect("matrix");
+        for y in 0..matrix.getHeight() {
+            for x in 0..matrix.getWidth() {
+                if matrix.get(matrix.getWidth() - 1 - x, y) {
+                    mirrored.set(x, y);
+                }
+            }
+        }
+        let result = AztecReader
+            .decode(&mut image(&mirrored, SCALE, MARGIN))
+            .expect("decode");
+        assert_eq!(result.getText(), "MIRRORED");
+        assert_eq!(
+            result
+                .getRXingResultMetadata()
+                .get(&RXingResultMetadataType::IS_MIRRORED),
+            Some(&RXingResultMetadataValue::IsMirrored(true))
+        );
+    }
 }
@@ -0,0 +1,538 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    common::{string_utils, CharacterSet, Eci, Result},
+    BarcodeFormat, DecodeHints, Exceptions, RXingResult, RXingResultMetadataType,
+    RXingResultMetadataValue,
+};
+
+/// Identifies the symbols of one structured append message.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MessageId {
+    pub format: BarcodeFormat,
+    /// The file ID of PDF417 and Data Matrix, the message ID of Aztec, which may be empty, and the
+    /// parity, in decimal, of QR Code.
+    pub id: String,
+}
+
+/// A structured append message put back together from all of its symbols.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructuredAppendMessage {
+    pub id: MessageId,
+    /// The contents of the symbols, in sequence order.
+    pub text: String,
+    /// The data of the symbols, when all of them hold byte data only, joined before decoding.
+    pub bytes: Option<Vec<u8>>,
+    /// The results of the symbols, in sequence order.
+    pub segments: Vec<RXingResult>,
+}
+
+#[derive(Default)]
+struct Segments {
+    count: Option<u32>,
+    parts: BTreeMap<u32, RXingResult>,
+}
+
+/**
+ * Collects the symbols of structured append messages from any number of images, in any order,
+ * and puts each message back together once all of its symbols have been added.
+ *
+ * Results are grouped by format and message ID. QR Code has no message ID, so its symbols are
+ * grouped by the parity of the whole message, which is checked once the message is complete.
+ * When every symbol of a message holds byte data only, the bytes are joined before they are
+ * decoded, so that a character split between two symbols comes out whole. They are decoded with
+ * the charset of [`StructuredAppendCollector::with_charset`], else with the character set ECI of
+ * the first symbol that declares one, and only else with the charset guessed for the message.
+ */
+#[derive(Default)]
+pub struct StructuredAppendCollector {
+    charset: Option<CharacterSet>,
+    messages: Vec<(MessageId, Segments)>,
+}
+
+impl StructuredAppendCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A collector that decodes messages of byte data with `charset`, as an ECI would.
+    pub fn with_charset(charset: CharacterSet) -> Self {
+        Self {
+            charset: Some(charset),
+            ..Default::default()
+        }
+    }
+
+    /**
+     * Adds a decoded symbol.
+     *
+     * @return the message the symbol is part of, or None if it is not part of a structured append
+     *  sequence
+     * @throws FormatException if the symbol gives another number of symbols than the others of
+     *  its message, or holds other contents at the same position
+     */
+    pub fn add(&mut self, result: RXingResult) -> Result<Option<MessageId>> {
+        let Some((id, index, count)) = Self::sequence_of(&result) else {
+            return Ok(None);
+        };
+
+        let position = match self.messages.iter().position(|(other, _)| *other == id) {
+            Some(position) => position,
+            None => {
+                self.messages.push((id.clone(), Segments::default()));
+                self.messages.len() - 1
+            }
+        };
+        let segments = &mut self.messages[position].1;
+
+        if let Some(count) = count {
+            match segments.count {
+                Some(known) if known != count => {
+                    return Err(Exceptions::format_with(format!(
+                        "symbol {} of message {} gives {count} symbols, others {known}",
+                        index + 1,
+                        id.id
+                    )))
+                }
+                _ => segments.count = Some(count),
+            }
+        }
+        if let Some(other) = segments.parts.get(&index) {
+            if other.getText() != result.getText() {
+                return Err(Exceptions::format_with(format!(
+                    "two symbols at position {} of message {}",
+                    index + 1,
+                    id.id
+                )));
+            }
+        }
+        segments.parts.insert(index, result);
+
+        Ok(Some(id))
+    }
+
+    /// The messages symbols have been added for, in the order they were first seen.
+    pub fn messages(&self) -> Vec<&MessageId> {
+        self.messages.iter().map(|(id, _)| id).collect()
+    }
+
+    /// The number of symbols of a message, if one of its symbols gave it.
+    pub fn count(&self, id: &MessageId) -> Option<u32> {
+        self.segments(id)?.count
+    }
+
+    /**
+     * The positions, from 0, of the symbols of a message still missing, or None for an unknown
+     * message. While the number of symbols is unknown, only the gaps before the last symbol added
+     * can be given.
+     */
+    pub fn missing(&self, id: &MessageId) -> Option<Vec<u32>> {
+        let segments = self.segments(id)?;
+        let end = segments
+            .count
+            .unwrap_or_else(|| segments.parts.keys().next_back().map_or(0, |last| last + 1));
+        Some(
+            (0..end)
+                .filter(|index| !segments.parts.contains_key(index))
+                .collect(),
+        )
+    }
+
+    /// Whether all symbols of a message have been added.
+    pub fn is_complete(&self, id: &MessageId) -> bool {
+        self.segments(id).is_some_and(|segments| {
+            segments
+                .count
+                .is_some_and(|count| (0..count).all(|index| segments.parts.contains_key(&index)))
+        })
+    }
+
+    /**
+     * Puts a message back together.
+     *
+     * @throws NotFoundException if the message is unknown or symbols of it are missing
+     * @throws ChecksumException if the parity of a QR Code message is not that of its contents
+     */
+    pub fn message(&self, id: &MessageId) -> Result<StructuredAppendMessage> {
+        let segments = self.segments(id).ok_or_else(|| {
+            Exceptions::not_found_with(format!("no symbols of message {}", id.id))
+        })?;
+        if !self.is_complete(id) {
+            let missing = self.missing(id).unwrap_or_default();
+            return Err(Exceptions::not_found_with(format!(
+                "message {} is missing symbols {:?}{}",
+                id.id,
+                missing.iter().map(|index| index + 1).collect::<Vec<_>>(),
+                if segments.count.is_none() {
+                    " and the last"
+                } else {
+                    ""
+                }
+            )));
+        }
+
+        let parts: Vec<&RXingResult> = segments.parts.values().collect();
+        let bytes: Option<Vec<u8>> = parts
+            .iter()
+            .map(|part| Self::byte_data(part))
+            .collect::<Option<Vec<_>>>()
+            .map(|data| data.concat());
+        let text = match &bytes {
+            Some(bytes) => {
+                let charset = self
+                    .charset
+                    .or_else(|| parts.iter().find_map(|part| Self::eci_charset(part)))
+                    .or_else(|| string_utils::guessCharset(bytes, &DecodeHints::default()))
+                    .unwrap_or(CharacterSet::ISO8859_1);
+                charset.decode_replace(bytes)?
+            }
+            None => parts.iter().map(|part| part.getText()).collect(),
+        };
+
+        if id.format == BarcodeFormat::QR_CODE {
+            let parity: u8 = id
+                .id
+                .parse()
+                .map_err(|_| Exceptions::format_with("QR Code parity is not a byte"))?;
+            // the parity is that of the bytes the message was encoded from
+            let matches = |data: &[u8]| data.iter().fold(0, |acc, b| acc ^ b) == parity;
+            let valid = match &bytes {
+                Some(bytes) => matches(bytes),
+                None => [
+                    CharacterSet::ISO8859_1,
+                    CharacterSet::Shift_JIS,
+                    CharacterSet::UTF8,
+                ]
+                .iter()
+                .filter_map(|charset| charset.encode(&text).ok())
+                .any(|data| matches(&data)),
+            };
+            if !valid {
+                return Err(Exceptions::checksum_with(format!(
+                    "QR Code message does not match its parity {parity}"
+                )));
+            }
+        }
+
+        Ok(StructuredAppendMessage {
+            id: id.clone(),
+            text,
+            bytes,
+            segments: parts.into_iter().cloned().collect(),
+        })
+    }
+
+    /// Removes and returns the messages that are complete and valid. Incomplete and invalid
+    /// messages are kept, for [`StructuredAppendCollector::message`] to report.
+    pub fn take_complete(&mut self) -> Vec<StructuredAppendMessage> {
+        let complete: Vec<StructuredAppendMessage> = self
+            .messages
+            .iter()
+            .filter_map(|(id, _)| self.message(id).ok())
+            .collect();
+        self.messages
+            .retain(|(id, _)| !complete.iter().any(|message| message.id == *id));
+        complete
+    }
+
+    fn segments(&self, id: &MessageId) -> Option<&Segments> {
+        self.messages
+            .iter()
+            .find(|(other, _)| other == id)
+            .map(|(_, segments)| segments)
+    }
+
+    /// The message, position and number of symbols, if known, of a symbol.
+    fn sequence_of(result: &RXingResult) -> Option<(MessageId, u32, Option<u32>)> {
+        let metadata = result.getRXingResultMetadata();
+        let format = *result.getBarcodeFormat();
+        let id = |id: &str| MessageId {
+            format,
+            id: id.to_owned(),
+        };
+
+        if let Some(RXingResultMetadataValue::StructuredAppend(sai)) =
+            metadata.get(&RXingResultMetadataType::STRUCTURED_APPEND)
+        {
+            if sai.index < 0 {
+                return None;
+            }
+            return Some((
+                id(&sai.id),
+                sai.index as u32,
+                (sai.count > 0).then_some(sai.count as u32),
+            ));
+        }
+
+        if let Some(RXingResultMetadataValue::Pdf417ExtraMetadata(pdf417)) =
+            metadata.get(&RXingResultMetadataType::PDF417_EXTRA_METADATA)
+        {
+            let index = pdf417.getSegmentIndex() as u32;
+            let count = if pdf417.getSegmentCount() > 0 {
+                Some(pdf417.getSegmentCount() as u32)
+            } else {
+                pdf417.isLastSegment().then_some(index + 1)
+            };
+            return Some((id(pdf417.getFileId()), index, count));
+        }
+
+        // QR Code results of the Java port give the symbol sequence indicator and the parity
+        if let (
+            Some(RXingResultMetadataValue::StructuredAppendSequence(sequence)),
+            Some(RXingResultMetadataValue::StructuredAppendParity(parity)),
+        ) = (
+            metadata.get(&RXingResultMetadataType::STRUCTURED_APPEND_SEQUENCE),
+            metadata.get(&RXingResultMetadataType::STRUCTURED_APPEND_PARITY),
+        ) {
+            if format == BarcodeFormat::QR_CODE && *sequence >= 0 && *parity >= 0 {
+                return Some((
+                    id(&parity.to_string()),
+                    (*sequence >> 4) as u32,
+                    Some((*sequence & 0x0F) as u32 + 1),
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// The bytes of a symbol that holds byte data only, whatever charset its text was decoded with.
+    fn byte_data(result: &RXingResult) -> Option<Vec<u8>> {
+        let Some(RXingResultMetadataValue::ByteSegments(segments)) = result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::BYTE_SEGMENTS)
+        else {
+            return None;
+        };
+        let data = segments.concat();
+        if data.is_empty() {
+            return None;
+        }
+        let text = result.getText();
+        let decodes_to_text = Self::eci_charset(result)
+            .into_iter()
+            .chain(string_utils::guessCharset(&data, &DecodeHints::default()))
+            .chain([CharacterSet::ISO8859_1, CharacterSet::UTF8])
+            .any(|charset| {
+                charset
+                    .decode_replace(&data)
+                    .is_ok_and(|decoded| decoded == text)
+            });
+        decodes_to_text.then_some(data)
+    }
+
+    /// The charset of the character set ECI of a symbol, if it declares one.
+    fn eci_charset(result: &RXingResult) -> Option<CharacterSet> {
+        match result
+            .getRXingResultMetadata()
+            .get(&RXingResultMetadataType::CHARACTER_SET_ECI)
+        {
+            Some(RXingResultMetadataValue::CharacterSetEci(eci)) => {
+                Some(CharacterSet::from(Eci::from(*eci)))
+                    .filter(|charset| *charset != CharacterSet::Unknown)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod StructuredAppendCollectorTestCase {
+    use std::sync::Arc;
+
+    use crate::{
+        common::CharacterSet,
+        datamatrix::{decoder::decoded_bit_stream_parser, DataMatrixReader},
+        pdf417::PDF417RXingResultMetadata,
+        BarcodeFormat, Exceptions, RXingResult, RXingResultMetadataType, RXingResultMetadataValue,
+    };
+
+    use super::{MessageId, StructuredAppendCollector};
+
+    fn qr_symbol(text: &str, bytes: &[u8], index: i32, count: i32, parity: u8) -> RXingResult {
+        let mut result = RXingResult::new(text, Vec::new(), Vec::new(), BarcodeFormat::QR_CODE);
+        result.putMetadata(
+            RXingResultMetadataType::BYTE_SEGMENTS,
+            RXingResultMetadataValue::ByteSegments(vec![bytes.to_vec()]),
+        );
+        result.putMetadata(
+            RXingResultMetadataType::STRUCTURED_APPEND_SEQUENCE,
+            RXingResultMetadataValue::StructuredAppendSequence(index << 4 | (count - 1)),
+        );
+        result.putMetadata(
+            RXingResultMetadataType::STRUCTURED_APPEND_PARITY,
+            RXingResultMetadataValue::StructuredAppendParity(parity as i32),
+        );
+        result
+    }
+
+    fn pdf417_symbol(text: &str, index: usize, last: bool) -> RXingResult {
+        let mut metadata = PDF417RXingResultMetadata::default();
+        metadata.setFileId("017053".to_owned());
+        metadata.setSegmentIndex(index);
+        metadata.setLastSegment(last);
+        let mut result = RXingResult::new(text, Vec::new(), Vec::new(), BarcodeFormat::PDF_417);
+        result.putMetadata(
+            RXingResultMetadataType::PDF417_EXTRA_METADATA,
+            RXingResultMetadataValue::Pdf417ExtraMetadata(Arc::new(metadata)),
+        );
+        result
+    }
+
+    #[test]
+    fn testQRCharacterSplitBetweenSymbols() {
+        let message = "héllo wörld".as_bytes();
+        let parity = message.iter().fold(0, |acc, b| acc ^ b);
+        let (first, second) = message.split_at(2);
+
+        let mut collector = StructuredAppendCollector::new();
+        // each symbol on its own decodes as ISO-8859-1
+        let id = collector
+            .add(qr_symbol(
+                "\u{a9}llo w\u{c3}\u{b6}rld",
+                second,
+                1,
+                2,
+                parity,
+            ))
+            .unwrap()
+            .unwrap();
+        assert_eq!(BarcodeFormat::QR_CODE, id.format);
+        assert_eq!(parity.to_string(), id.id);
+        assert!(!collector.is_complete(&id));
+        assert_eq!(Some(vec![0]), collector.missing(&id));
+        assert!(matches!(
+            collector.message(&id),
+            Err(Exceptions::NotFoundException(_))
+        ));
+
+        collector
+            .add(qr_symbol("h\u{c3}", first, 0, 2, parity))
+            .unwrap();
+        let complete = collector.take_complete();
+        assert_eq!(1, complete.len());
+        assert_eq!("héllo wörld", complete[0].text);
+        assert_eq!(Some(message.to_vec()), complete[0].bytes);
+        assert_eq!(2, complete[0].segments.len());
+        assert!(collector.messages().is_empty());
+    }
+
+    #[test]
+    fn testQRCharacterSetECI() {
+        let message = "Привет, мир";
+        let data = CharacterSet::Cp1251.encode(message).unwrap();
+        let parity = data.iter().fold(0, |acc, b| acc ^ b);
+        let (first, second) = data.split_at(6);
+
+        // the ECI of the first symbol applies to the symbols after it, which decode on their own
+        // as ISO-8859-1
+        let mut with_eci = qr_symbol("Привет", first, 0, 2, parity);
+        with_eci.putMetadata(
+            RXingResultMetadataType::CHARACTER_SET_ECI,
+            RXingResultMetadataValue::CharacterSetEci(22),
+        );
+        let without_eci = qr_symbol(
+            &CharacterSet::ISO8859_1.decode(second).unwrap(),
+            second,
+            1,
+            2,
+            parity,
+        );
+
+        let mut collector = StructuredAppendCollector::new();
+        let id = collector.add(without_eci.clone()).unwrap().unwrap();
+        collector.add(with_eci).unwrap();
+        assert_eq!(message, collector.message(&id).unwrap().text);
+
+        // without the ECI, the charset is guessed
+        let mut collector = StructuredAppendCollector::new();
+        let id = collector.add(without_eci).unwrap().unwrap();
+        collector
+            .add(qr_symbol(
+                &CharacterSet::ISO8859_1.decode(first).unwrap(),
+                first,
+                0,
+                2,
+                parity,
+            ))
+            .unwrap();
+        assert_ne!(message, collector.message(&id).unwrap().text);
+    }
+
+    #[test]
+    fn testQRParityMismatch() {
+        let mut collector = StructuredAppendCollector::new();
+        let id = collector
+            .add(qr_symbol("AB", b"AB", 0, 2, 0x42))
+            .unwrap()
+            .unwrap();
+        collector.add(qr_symbol("CD", b"CD", 1, 2, 0x42)).unwrap();
+        assert!(collector.is_complete(&id));
+        assert!(matches!(
+            collector.message(&id),
+            Err(Exceptions::ChecksumException(_))
+        ));
+        assert!(collector.take_complete().is_empty());
+        assert_eq!(vec![&id], collector.messages());
+    }
+
+    #[test]
+    fn testConflictingCount() {
+        let mut collector = StructuredAppendCollector::new();
+        collector.add(qr_symbol("A", b"A", 0, 2, 0)).unwrap();
+        assert!(collector.add(qr_symbol("B", b"B", 1, 3, 0)).is_err());
+    }
+
+    #[test]
+    fn testDataMatrix() {
+        // structured append of symbol 2 of 2, then of symbol 1 of 2, file ID 1 2
+        let second =
+            decoded_bit_stream_parser::decode(&[233, 1 << 4 | (17 - 2), 1, 2, b'C' + 1], false)
+                .unwrap();
+        let first =
+            decoded_bit_stream_parser::decode(&[233, 17 - 2, 1, 2, b'A' + 1, b'B' + 1], false)
+                .unwrap();
+
+        let mut collector = StructuredAppendCollector::new();
+        let id = collector
+            .add(DataMatrixReader::build_result(&second, Vec::new(), None))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            MessageId {
+                format: BarcodeFormat::DATA_MATRIX,
+                id: "258".to_owned()
+            },
+            id
+        );
+        assert_eq!(Some(2), collector.count(&id));
+        collector
+            .add(DataMatrixReader::build_result(&first, Vec::new(), None))
+            .unwrap();
+        assert_eq!("ABC", collector.message(&id).unwrap().text);
+    }
+
+    #[test]
+    fn testPDF417() {
+        let mut collector = StructuredAppendCollector::new();
+        assert_eq!(
+            None,
+            collector
+                .add(RXingResult::new(
+                    "plain",
+                    Vec::new(),
+                    Vec::new(),
+                    BarcodeFormat::PDF_417
+                ))
+                .unwrap()
+        );
+        let id = collector
+            .add(pdf417_symbol("world", 2, true))
+            .unwrap()
+            .unwrap();
+        collector.add(pdf417_symbol("hello ", 0, false)).unwrap();
+        assert_eq!(Some(vec![1]), collector.missing(&id));
+        collector.add(pdf417_symbol("big ", 1, false)).unwrap();
+        assert_eq!("hello big world", collector.message(&id).unwrap().text);
+    }
+}
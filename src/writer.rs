@@ -18,7 +18,7 @@
 
 use crate::{
     common::{BitMatrix, Result},
-    BarcodeFormat, EncodeHints,
+    BarcodeFormat, EncodeHints, EncodedSymbol,
 };
 
 /**
@@ -62,4 +62,174 @@ pub trait Writer {
         height: i32,
         hints: &EncodeHints,
     ) -> Result<BitMatrix>;
+
+    /**
+     * Encodes `contents` like `encode_with_hints`, and also returns the module matrix the symbol
+     * was drawn from, where its modules are in the drawn matrix and, for QR Code, Data Matrix,
+     * Aztec and PDF417, what the encoder chose for it.
+     *
+     * The default implementation takes the matrix `encode_with_hints` draws as the module
+     * matrix. Writers that scale, pad or decorate the symbol override it.
+     *
+     * @param contents The contents to encode in the barcode
+     * @param format The barcode format to generate
+     * @param width The preferred width in pixels
+     * @param height The preferred height in pixels
+     * @param hints Additional parameters to supply to the encoder
+     * @return {@link EncodedSymbol} with the drawn matrix and the module matrix
+     * @throws WriterException if contents cannot be encoded legally in a format
+     */
+    fn encode_with_metadata(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        width: i32,
+        height: i32,
+        hints: &EncodeHints,
+    ) -> Result<EncodedSymbol> {
+        let matrix = self.encode_with_hints(contents, format, width, height, hints)?;
+        Ok(EncodedSymbol::unscaled(*format, matrix))
+    }
+
+    /**
+     * Encodes the symbol at exactly one pixel per module, whatever the writer does with the
+     * requested size, surrounded by `quiet_zone` light modules. The `Margin` hint is ignored.
+     *
+     * The symbol is the module matrix of `encode_with_metadata`, so it leaves out what a writer
+     * draws around or stretches the modules by: linear symbols are one module high and only get
+     * the quiet zone on their left and right, ITF-14 has no bearer bars and every PDF417 row is
+     * one module high.
+     *
+     * @param contents The contents to encode in the barcode
+     * @param format The barcode format to generate
+     * @param quiet_zone The quiet zone on each side, in modules
+     * @param hints Additional parameters to supply to the encoder
+     * @return {@link BitMatrix} with one pixel per module
+     * @throws WriterException if contents cannot be encoded legally in a format
+     */
+    fn encode_modules(
+        &self,
+        contents: &str,
+        format: &BarcodeFormat,
+        quiet_zone: u32,
+        hints: &EncodeHints,
+    ) -> Result<BitMatrix> {
+        let symbol = self
+            .encode_with_metadata(contents, format, 0, 0, hints)?
+            .modules;
+        let vertical_quiet_zone = if symbol.getHeight() == 1 {
+            0
+        } else {
+            quiet_zone
+        };
+
+        let mut output = BitMatrix::new(
+            symbol.getWidth() + 2 * quiet_zone,
+            symbol.getHeight() + 2 * vertical_quiet_zone,
+        )?;
+        for y in 0..symbol.getHeight() {
+            for x in 0..symbol.getWidth() {
+                if symbol.get(x, y) {
+                    output.set(x + quiet_zone, y + vertical_quiet_zone);
+                }
+            }
+        }
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod WriterTestCase {
+    use crate::{
+        BarcodeFormat, EncodeHintValue, EncodeHints, MultiFormatWriter, SymbolVersion, Writer,
+    };
+
+    #[test]
+    fn testEncodeModules() {
+        // the margin hint and the size each writer would pad to make no difference
+        let hints = EncodeHints::default().with(EncodeHintValue::Margin("7".to_owned()));
+        for (contents, format, width, height) in [
+            ("HELLO WORLD", BarcodeFormat::QR_CODE, 21, 21),
+            ("123456", BarcodeFormat::DATA_MATRIX, 10, 10),
+            ("Hello", BarcodeFormat::AZTEC, 15, 15),
+            ("12345670", BarcodeFormat::EAN_8, 67, 1),
+            ("01234565", BarcodeFormat::UPC_E, 51, 1),
+            ("5901234123457", BarcodeFormat::EAN_13, 95, 1),
+            ("012345678905", BarcodeFormat::UPC_A, 95, 1),
+            ("ABC", BarcodeFormat::CODE_39, 64, 1),
+            ("ABC", BarcodeFormat::CODE_93, 64, 1),
+            ("ABC", BarcodeFormat::CODE_128, 68, 1),
+            ("A123B", BarcodeFormat::CODABAR, 51, 1),
+            ("ABC", BarcodeFormat::TELEPEN, 95, 1),
+            ("20012345678909", BarcodeFormat::RSS_14, 96, 1),
+            ("123456", BarcodeFormat::ITF, 63, 1),
+            // no bearer bars
+            ("00012345600012", BarcodeFormat::ITF_14, 135, 1),
+            (
+                "2132103200116",
+                BarcodeFormat::DEUTSCHE_POST_LEITCODE,
+                135,
+                1,
+            ),
+            (
+                "56310243031",
+                BarcodeFormat::DEUTSCHE_POST_IDENTCODE,
+                117,
+                1,
+            ),
+            ("12345", BarcodeFormat::POSTNET, 63, 5),
+            ("12345678901", BarcodeFormat::PLANET, 123, 5),
+            ("1139987520", BarcodeFormat::AUSTRALIA_POST, 73, 8),
+        ] {
+            let matrix = MultiFormatWriter
+                .encode_modules(contents, &format, 0, &hints)
+                .expect("encode");
+            assert_eq!(
+                (matrix.getWidth(), matrix.getHeight()),
+                (width, height),
+                "{format}"
+            );
+
+            let padded = MultiFormatWriter
+                .encode_modules(contents, &format, 4, &hints)
+                .expect("encode");
+            let vertical = if height == 1 { 0 } else { 4 };
+            assert_eq!(
+                (padded.getWidth(), padded.getHeight()),
+                (width + 8, height + 2 * vertical),
+                "{format}"
+            );
+            for y in 0..height {
+                for x in 0..width {
+                    assert_eq!(matrix.get(x, y), padded.get(x + 4, y + vertical));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn testEncodeModulesPdf417() {
+        let hints = EncodeHints::default();
+        let metadata = MultiFormatWriter
+            .encode_with_metadata("Hello", &BarcodeFormat::PDF_417, 0, 0, &hints)
+            .expect("encode")
+            .metadata
+            .expect("metadata");
+        let SymbolVersion::Pdf417 { rows, columns } = metadata.version else {
+            panic!("not a PDF417 version: {:?}", metadata.version);
+        };
+
+        let matrix = MultiFormatWriter
+            .encode_modules("Hello", &BarcodeFormat::PDF_417, 2, &hints)
+            .expect("encode");
+        // start and stop patterns, row indicators and data columns of 17 modules, one module
+        // per row, and the quiet zone around them
+        assert_eq!(
+            (matrix.getWidth(), matrix.getHeight()),
+            ((columns + 4) * 17 + 1 + 4, rows + 4)
+        );
+        assert!(!matrix.get(1, 2));
+        assert!(matrix.get(2, 2) && matrix.get(2, rows + 1));
+        assert!(!matrix.get(2, rows + 2));
+    }
 }
@@ -24,12 +24,12 @@ use std::{
 };
 
 use rxing::{
-    common::{CharacterSet, HybridBinarizer, Result},
+    common::{cpp_essentials::StructuredAppendInfo, CharacterSet, HybridBinarizer, Result},
     multi::MultipleBarcodeReader,
     pdf417::PDF417RXingResultMetadata,
     BarcodeFormat, Binarizer, BinaryBitmap, BufferedImageLuminanceSource, DecodeHintType,
-    DecodeHintValue, DecodeHints, RXingResult, RXingResultMetadataType, RXingResultMetadataValue,
-    Reader,
+    DecodeHintValue, DecodeHints, ErrorCorrectionStats, EstimatedModuleSize, RXingResult,
+    RXingResultMetadataType, RXingResultMetadataValue, Reader,
 };
 
 use super::TestRXingResult;
@@ -90,6 +90,7 @@ impl<T: MultipleBarcodeReader + Reader> MultiImageSpanAbstractBlackBoxTestCase<T
             DecodeHintValue::AssumeCode39CheckDigit(v) => {
                 self.hints.AssumeCode39CheckDigit = Some(v)
             }
+            DecodeHintValue::AssumeCode39FullAscii(v) => self.hints.AssumeCode39FullAscii = Some(v),
             DecodeHintValue::AssumeGs1(v) => self.hints.AssumeGs1 = Some(v),
             DecodeHintValue::ReturnCodabarStartEnd(v) => self.hints.ReturnCodabarStartEnd = Some(v),
             DecodeHintValue::NeedResultPointCallback(v) => {
@@ -98,6 +99,13 @@ impl<T: MultipleBarcodeReader + Reader> MultiImageSpanAbstractBlackBoxTestCase<T
             DecodeHintValue::AllowedEanExtensions(v) => self.hints.AllowedEanExtensions = Some(v),
             DecodeHintValue::AlsoInverted(v) => self.hints.AlsoInverted = Some(v),
             DecodeHintValue::TelepenAsNumeric(v) => self.hints.TelepenAsNumeric = Some(v),
+            DecodeHintValue::RegionOfInterest(v) => self.hints.RegionOfInterest = Some(v),
+            DecodeHintValue::SymbolSizeRange(v) => self.hints.SymbolSizeRange = Some(v),
+            DecodeHintValue::ScanLines(v) => self.hints.ScanLines = Some(v),
+            DecodeHintValue::ScanAngles(v) => self.hints.ScanAngles = Some(v),
+            DecodeHintValue::CancellationToken(v) => self.hints.CancellationToken = Some(v),
+            DecodeHintValue::Diagnostics(v) => self.hints.Diagnostics = Some(v),
+            DecodeHintValue::BestEffort(v) => self.hints.BestEffort = Some(v),
             #[cfg(feature = "allow_forced_iso_ied_18004_compliance")]
             DecodeHintValue::QrAssumeSpecConformInput(v) => {
                 self.hints.QrAssumeSpecConformInput = Some(v)
@@ -439,6 +447,30 @@ impl<T: MultipleBarcodeReader + Reader> MultiImageSpanAbstractBlackBoxTestCase<T
                             .collect();
                         RXingResultMetadataValue::FilteredResolution((arr[0], arr[1]))
                     }
+                    RXingResultMetadataType::GS1_COMPOSITE => {
+                        RXingResultMetadataValue::Gs1Composite(v)
+                    }
+                    RXingResultMetadataType::FULL_ASCII => {
+                        RXingResultMetadataValue::FullAscii(v.parse().unwrap())
+                    }
+                    RXingResultMetadataType::STRUCTURED_APPEND => {
+                        RXingResultMetadataValue::StructuredAppend(StructuredAppendInfo::default())
+                    }
+                    RXingResultMetadataType::ERRORS_CORRECTED => {
+                        RXingResultMetadataValue::ErrorsCorrected(ErrorCorrectionStats::default())
+                    }
+                    RXingResultMetadataType::ROWS_AGREED => {
+                        RXingResultMetadataValue::RowsAgreed(v.parse().unwrap_or_default())
+                    }
+                    RXingResultMetadataType::MODULE_SIZE => RXingResultMetadataValue::ModuleSize(
+                        EstimatedModuleSize(v.parse().unwrap_or_default()),
+                    ),
+                    RXingResultMetadataType::CHARACTER_SET_ECI => {
+                        RXingResultMetadataValue::CharacterSetEci(v.parse().unwrap_or_default())
+                    }
+                    RXingResultMetadataType::GS1_COMPOSITE_LINKAGE => {
+                        RXingResultMetadataValue::Gs1CompositeLinkage(v.parse().unwrap_or_default())
+                    }
                 };
                 expected_metadata.insert(new_k, new_v);
             }